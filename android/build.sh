@@ -0,0 +1,16 @@
+#!/bin/sh
+# 安卓版构建，用 cargo-quad-apk（macroquad 官方的打包工具，需要 Android SDK / NDK，最省事的是用它的 docker 镜像）：
+#   docker run --rm -v $(pwd):/root/src -w /root/src notfl3/cargo-apk android/build.sh
+# 产物在 target/android-artifacts/release/apk/。包名、横竖屏、权限见 Cargo.toml 的 [package.metadata.android]。
+# 只带 audio 特性（默认的 tls 也关掉：ring 交叉编译到安卓麻烦，手机上连不了 https 地址）；手柄、脚本、Discord、Steam、语音播报这些手机上用不了。
+set -e
+cd "$(dirname "$0")/.."
+# APK 里的资源路径要和桌面版一样以 assets/ 开头，所以先拷到 android/stage/assets/ 再打包整个 stage，
+# 顺便列一份清单给 storage::preload 逐个取（字体由 load_ttf_font 自己取，不进清单）
+stage=android/stage
+rm -rf "$stage"
+mkdir -p "$stage"
+cp -r assets "$stage"/
+(cd "$stage" && find assets -type f ! -name '*.otf' ! -name manifest.txt | sort > assets/manifest.txt)
+cargo quad-apk build --release --no-default-features --features audio
+echo "已生成 target/android-artifacts/release/apk/"
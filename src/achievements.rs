@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{tr, trf};
+use crate::{xp, Game, PlayMode};
+
+// ===== 成就（assets/achievements.json）=====
+// 每条成就 = 名称 + 触发条件；条件是数据（见 Trigger），局内每步拿当前的 Facts 逐条比对。
+// 解锁后记下时间戳存进存档，不会再次触发。单局类条件只在普通模式里判定，等级类随时判定。
+pub const ACHIEVEMENTS_PATH: &str = "assets/achievements.json";
+const STILL_SPEED: f32 = 5.0; // 低于此横向速度算“没动”
+const UNLOCK_COLOR: Color = Color::new(1.0, 0.75, 0.3, 1.0);
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Trigger {
+    Score { score: i32 },           // 单局得分达到
+    Survive { secs: f32 },          // 单局存活
+    ShieldBlocks { count: u32 },    // 单局用护盾挡下的撞击
+    Still { secs: f32 },            // 单局连续不移动
+    NearMisses { count: u32 },      // 单局擦边
+    Combo { count: u32 },           // 连击倍率
+    Dodged { count: u32 },          // 单局躲过障碍
+    Pickups { count: u32 },         // 单局拾取道具
+    Level { level: u32 },           // 账号等级
+}
+
+// 判定用的当前数据
+struct Facts {
+    in_run: bool, // 正在计入的普通模式局
+    score: i32,
+    elapsed: f32,
+    shield_blocks: u32,
+    still: f32,
+    near_misses: u32,
+    combo: u32,
+    dodged: u32,
+    pickups: u32,
+    level: u32,
+}
+
+impl Trigger {
+    fn met(&self, f: &Facts) -> bool {
+        match *self {
+            Trigger::Level { level } => f.level >= level,
+            _ if !f.in_run => false,
+            Trigger::Score { score } => f.score >= score,
+            Trigger::Survive { secs } => f.elapsed >= secs,
+            Trigger::ShieldBlocks { count } => f.shield_blocks >= count,
+            Trigger::Still { secs } => f.still >= secs,
+            Trigger::NearMisses { count } => f.near_misses >= count,
+            Trigger::Combo { count } => f.combo >= count,
+            Trigger::Dodged { count } => f.dodged >= count,
+            Trigger::Pickups { count } => f.pickups >= count,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AchievementDef {
+    pub key: String,
+    pub name: String,
+    pub desc: String,
+    pub trigger: Trigger,
+}
+
+#[derive(Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct AchievementTable {
+    pub list: Vec<AchievementDef>,
+}
+
+impl AchievementTable {
+    pub fn load(path: &str) -> Self {
+        let Ok(text) = crate::storage::read_asset_string(path) else { return Self::default() };
+        serde_json::from_str(&text).unwrap_or_else(|e| {
+            crate::logfile::warn(&format!("成就表 {} 解析失败：{}", path, e));
+            Self::default()
+        })
+    }
+}
+
+// 存档：成就 key → 解锁时间（Unix 秒）
+pub type Unlocked = BTreeMap<String, u64>;
+
+fn now_secs() -> u64 { macroquad::miniquad::date::now() as u64 }
+
+// 连续不动的计时（局内状态，开局清零）
+pub fn tick_still(game: &mut Game, dt: f32) {
+    game.still = if game.player.vx.abs() < STILL_SPEED { game.still + dt } else { 0.0 };
+}
+
+pub fn check(game: &mut Game) {
+    let f = Facts {
+        in_run: game.play == PlayMode::Standard,
+        score: game.score,
+        elapsed: game.elapsed,
+        shield_blocks: game.stats.shields_used,
+        still: game.still,
+        near_misses: game.stats.near_misses,
+        combo: game.stats.max_combo,
+        dodged: game.dodged,
+        pickups: game.stats.total_pickups(),
+        level: xp::level_of(game.xp).0,
+    };
+    let fresh: Vec<usize> = (0..game.achievement_defs.list.len())
+        .filter(|&i| {
+            let a = &game.achievement_defs.list[i];
+            !game.achievements.contains_key(&a.key) && a.trigger.met(&f)
+        })
+        .collect();
+    for i in fresh {
+        let a = &game.achievement_defs.list[i];
+        game.achievements.insert(a.key.clone(), now_secs());
+        #[cfg(feature = "steam")]
+        if let Some(s) = game.steam.as_mut() { s.achievement(&a.key); }
+        game.banners.show(tr("成就解锁"), trf("{}：{}", &[&tr(&a.name), &tr(&a.desc)]), UNLOCK_COLOR, 2.0);
+    }
+}
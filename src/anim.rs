@@ -0,0 +1,77 @@
+use macroquad::prelude::*;
+use serde::Deserialize;
+
+use crate::sprites::Atlas;
+
+// ===== 帧动画 =====
+// 数据写在贴图集 atlas.json 的 "animations" 里，每条是帧列表 + 帧率 + 是否循环：
+//   "player": { "frames": ["player_0", "player_1", "player_2"], "fps": 8, "looping": true }
+// 动画和贴图同名时，画这个贴图就换成动画的当前帧（见 Atlas::frame）：挡板待机的闪光、道具旋转都这样接上。
+// 一次性的动画（障碍碎裂）是 Anim 组件，由主循环每帧的 Anims::update 推进，放完就删。
+// 计时走真实帧时间、在模拟之外，不进回放；暂停时停住。
+pub const CRUMBLE: &str = "obstacle_crumble";
+const CRUMBLE_MAX: usize = 64; // 无窗口校验时没有更新，最多攒这么多
+
+#[derive(Deserialize)]
+pub struct AnimDef {
+    pub frames: Vec<String>,
+    pub fps: f32,
+    #[serde(default)]
+    pub looping: bool,
+}
+
+impl AnimDef {
+    // 播放了 t 秒时的帧；不循环的停在最后一帧
+    pub fn frame_at(&self, t: f32) -> Option<&str> {
+        let n = self.frames.len();
+        if n == 0 { return None; }
+        let i = (t.max(0.0) * self.fps.max(0.0)) as usize;
+        let i = if self.looping { i % n } else { i.min(n - 1) };
+        Some(&self.frames[i])
+    }
+    fn length(&self) -> f32 { self.frames.len() as f32 / self.fps.max(0.001) }
+}
+
+// 一段正在播的动画
+pub struct Anim {
+    pub name: &'static str,
+    pub t: f32,
+}
+
+impl Anim {
+    pub fn new(name: &'static str) -> Self { Self { name, t: 0.0 } }
+    pub fn done(&self, atlas: &Atlas) -> bool { atlas.anim(self.name).is_none_or(|d| !d.looping && self.t >= d.length()) }
+}
+
+struct Crumble { rect: Rect, anim: Anim }
+
+#[derive(Default)]
+pub struct Anims {
+    clock: f32, // 循环动画共用的时钟
+    crumbles: Vec<Crumble>,
+}
+
+impl Anims {
+    pub fn clock(&self) -> f32 { self.clock }
+
+    // 障碍被打碎 / 炸掉的位置放一段碎裂动画
+    pub fn crumble(&mut self, rect: Rect) {
+        if self.crumbles.len() >= CRUMBLE_MAX { self.crumbles.remove(0); }
+        self.crumbles.push(Crumble { rect, anim: Anim::new(CRUMBLE) });
+    }
+
+    pub fn update(&mut self, dt: f32, atlas: &Atlas) {
+        self.clock += dt;
+        for c in &mut self.crumbles { c.anim.t += dt; }
+        self.crumbles.retain(|c| !c.anim.done(atlas));
+    }
+
+    // 颜色和活着的障碍一样
+    pub fn draw_crumbles(&self, atlas: &Atlas, tint: Color) {
+        let mut batch = atlas.batch();
+        for c in &self.crumbles {
+            if let Some(f) = atlas.anim(c.anim.name).and_then(|d| d.frame_at(c.anim.t)) { batch.push(f, c.rect, tint); }
+        }
+        batch.flush();
+    }
+}
@@ -0,0 +1,39 @@
+use crate::settings::Assist;
+use crate::{steer, widest_gap, Game};
+
+// ===== 辅助移动 =====
+// 给单手或行动不便的玩家：挡板自己动，玩家只用一个键（默认回车，可在改键里换成开关设备发出的键）。
+//   自动往返：在场地两侧之间来回扫，碰到边就掉头；
+//   自动找空隙：朝玩家上方最宽的空隙开（和瞬移找落点用的是同一个 widest_gap）。
+// 按住“定住”键时挡板刹停、原地不动，松开再继续，用来躲一下或守住当前这条道。
+// 辅助排在所有输入源最后：玩家自己给了方向就听玩家的。强度是辅助推杆的力度（0.2–1），
+// 和模拟摇杆一样按比例给方向，录进回放的也就是这个比例。开了辅助的局不进排行榜。
+pub const STRENGTH_MIN: f32 = 0.2;
+pub const STRENGTH_MAX: f32 = 1.0;
+const EDGE: f32 = 40.0;     // 自动往返离边这么近就掉头
+const HOLD_STOP: f32 = 30.0; // 定住时速度降到这以下就松手，免得来回抖
+
+// 作为输入源：-1..=1，不开辅助时给 0
+pub fn axis(game: &Game) -> f32 {
+    let s = &game.settings;
+    if s.assist == Assist::Off { return 0.0; }
+    let p = &game.player;
+    if s.keys.hold.down() {
+        return if p.vx.abs() > HOLD_STOP { -p.vx.signum() } else { 0.0 };
+    }
+    let dir = match s.assist {
+        Assist::Off => 0.0,
+        Assist::Sweep => sweep(game),
+        Assist::Seek => steer(game, widest_gap(game)),
+    };
+    dir * s.assist_strength
+}
+
+// 不记状态：沿着当前速度方向走，到边掉头；停着时朝离得远的那边出发
+fn sweep(game: &Game) -> f32 {
+    let x = game.player.rect().center().x;
+    let vx = game.player.vx;
+    if x < EDGE { return 1.0; }
+    if x > game.field.x - EDGE { return -1.0; }
+    if vx.abs() > HOLD_STOP { vx.signum() } else if x < game.field.x * 0.5 { 1.0 } else { -1.0 }
+}
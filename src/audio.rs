@@ -0,0 +1,248 @@
+use std::cell::{Cell, RefCell};
+
+use macroquad::prelude::KeyCode;
+use macroquad::audio::{load_sound_from_bytes, play_sound, set_sound_volume, PlaySoundParams, Sound};
+
+use crate::i18n::tr;
+use crate::playlist::Playlist;
+use crate::settings::Volume;
+use crate::trace::is_key_pressed;
+use crate::{Game, GameMode, OB_START_SPEED};
+
+// ===== 声音 =====
+// 音效和背景音乐放在 assets/audio/ 下，文件名见下表，ogg 或 wav 都行。缺哪个音效文件就现场合成一个（见 synth.rs）；缺音乐就没有音乐，都不算错。
+// 真正出声要用 --features audio 编译（打开 macroquad 的声音后端，Linux 上要 libasound）；不开时接口照旧，一律静音。
+// 模拟里只把要播的音效记进 game.sfx（同一种一帧只记一次，回放快进也不会叠成一片），每帧画之前由 Audio::flush 播出去。
+//
+// —— 混音 ——
+// 三条声道：音乐、音效（局内）、界面（菜单提示音），各自的音量再乘总音量（设置 → 声音），改了当场生效。
+// 炸弹和撞死的声音响起时音乐自动压低（ducking）：瞬间压下去，再在几百毫秒到一秒多里慢慢回来，重音听得清，叠在一起也不爆音。
+//
+// —— 分层音乐 ——
+// 音乐由几条同样长度的分轨同时循环：底子 music 一直在；打击乐 music_percussion、主旋律 music_lead 随局内的紧张程度淡入。
+// 紧张程度（game.intensity，0–1）由模拟每步算出：落速比开局快了多少、场上有多少障碍，平滑过再用。
+// 暂停和减速时换成闷住的版本 music_muffled（事先做好低通的底子），其余分轨淡出；没有这条就只把底子压低一些。
+// 所有分轨在同一帧一起开始播，之后只调音量，所以一直是对齐的。缺哪条分轨就少哪层。
+//
+// —— 自带音乐 ——
+// 音乐目录里有玩家自己放的曲子时（见 playlist.rs）改放它们，分层音乐不再出声；压低和声道音量照旧，暂停和减速时只是压低一些。
+// [F7] 下一首、[F8] 音乐静音在任何界面都能按。
+//
+// 听觉预警（见 cues.rs）的提示音是开局合成的，不用文件，走音效声道。
+pub const AUDIO_DIR: &str = "assets/audio";
+const EXTENSIONS: [&str; 2] = ["ogg", "wav"];
+const FADE: f32 = 1.5;            // 分轨音量每秒最多变化这么多（满音量为 1）
+const MUFFLE_FALLBACK: f32 = 0.5; // 没有闷住版时底子压低的比例
+const INTENSITY_SMOOTH: f32 = 1.5; // 紧张程度的平滑时间常数（秒）
+const DENSITY_FULL: f32 = 20.0;   // 场上有这么多障碍就算最密
+const SPEED_FULL: f32 = 3.0;      // 落速到开局的这么多倍就算最快
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Layer { Base, Percussion, Lead, Muffled }
+
+const STEMS: [(Layer, &str); 4] = [
+    (Layer::Base, "music"),
+    (Layer::Percussion, "music_percussion"),
+    (Layer::Lead, "music_lead"),
+    (Layer::Muffled, "music_muffled"),
+];
+
+impl Layer {
+    // 这一层在给定紧张程度下的目标音量（未闷住时）：各自在一段区间里从 0 升到 1
+    fn level(self, intensity: f32) -> f32 {
+        let ramp = |lo: f32, hi: f32| ((intensity - lo) / (hi - lo)).clamp(0.0, 1.0);
+        match self {
+            Layer::Base => 1.0,
+            Layer::Percussion => ramp(0.25, 0.45),
+            Layer::Lead => ramp(0.55, 0.8),
+            Layer::Muffled => 0.0,
+        }
+    }
+}
+
+struct Stem {
+    layer: Layer,
+    sound: Sound,
+    level: Cell<f32>,   // 当前音量（淡入淡出中），未乘声道音量
+    applied: Cell<f32>, // 上次实际设下去的音量；负数表示还没开始放
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Channel { Music, Sfx, Ui }
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Sfx { Pickup, Shield, Bomb, Death, MenuMove, MenuSelect }
+
+impl Sfx {
+    fn channel(self) -> Channel {
+        match self {
+            Sfx::MenuMove | Sfx::MenuSelect => Channel::Ui,
+            _ => Channel::Sfx,
+        }
+    }
+
+    // 响起时把音乐压低多少、多久回来：(压低比例, 秒)
+    fn duck(self) -> Option<(f32, f32)> {
+        match self {
+            Sfx::Bomb => Some((0.55, 0.6)),
+            Sfx::Death => Some((0.7, 1.6)),
+            _ => None,
+        }
+    }
+}
+
+// 正在进行的一次压低：depth 随 left / total 线性回到 0
+#[derive(Clone, Copy, Default)]
+struct Duck { depth: f32, left: f32, total: f32 }
+
+impl Duck {
+    fn level(self) -> f32 { if self.total > 0.0 { self.depth * (self.left / self.total).clamp(0.0, 1.0) } else { 0.0 } }
+}
+
+const SFX: [(Sfx, &str); 6] = [
+    (Sfx::Pickup, "pickup"),
+    (Sfx::Shield, "shield"),
+    (Sfx::Bomb, "bomb"),
+    (Sfx::Death, "death"),
+    (Sfx::MenuMove, "menu_move"),
+    (Sfx::MenuSelect, "menu_select"),
+];
+
+pub struct Audio {
+    sfx: Vec<(Sfx, Sound)>,
+    stems: Vec<Stem>,
+    duck: Cell<Duck>,
+    playlist: RefCell<Playlist>,
+    cues: Vec<Sound>,
+}
+
+// 找 assets/audio/<name>.ogg 或 .wav；没有就是 None，有但解不开记一条警告
+async fn load(name: &str) -> Option<Sound> {
+    let dir = std::path::Path::new(AUDIO_DIR);
+    let (path, bytes) = EXTENSIONS.iter().find_map(|ext| {
+        let path = dir.join(format!("{}.{}", name, ext));
+        crate::storage::read_asset(&path).ok().map(|b| (path, b))
+    })?;
+    match load_sound_from_bytes(&bytes).await {
+        Ok(s) => Some(s),
+        Err(e) => {
+            crate::logfile::warn(&format!("声音文件 {} 读不出来，跳过：{:?}", path.display(), e));
+            None
+        }
+    }
+}
+
+impl Audio {
+    pub async fn load() -> Self {
+        let voices = crate::synth::Voices::load(crate::synth::SFXR_PATH);
+        let mut sfx = Vec::new();
+        for (kind, name) in SFX {
+            let sound = match load(name).await {
+                Some(s) => Some(s),
+                None => load_sound_from_bytes(&voices.get(kind).render()).await.ok(),
+            };
+            if let Some(s) = sound { sfx.push((kind, s)); }
+        }
+        let mut stems = Vec::new();
+        for (layer, name) in STEMS {
+            if let Some(sound) = load(name).await { stems.push(Stem { layer, sound, level: Cell::new(0.0), applied: Cell::new(-1.0) }); }
+        }
+        let mut cues = Vec::new();
+        for wav in crate::cues::bank() {
+            // 合成的小样一定解得开；解不开（声音后端有问题）就不要预警了
+            match load_sound_from_bytes(&wav).await {
+                Ok(s) => cues.push(s),
+                Err(_) => { cues.clear(); break; }
+            }
+        }
+        Self { sfx, stems, duck: Cell::new(Duck::default()), playlist: RefCell::new(Playlist::load()), cues }
+    }
+
+    // 每帧调用：播掉本帧记下的音效，音乐没开始就开始循环，音量跟着设置和压低走
+    pub fn flush(&self, game: &mut Game, dt: f32) {
+        let v = game.settings.volume;
+        let mut duck = self.duck.get();
+        duck.left = (duck.left - dt).max(0.0);
+        for kind in std::mem::take(&mut game.sfx) {
+            // 新的压低比正在进行的更深才换掉它
+            if let Some((depth, secs)) = kind.duck().filter(|(d, _)| *d >= duck.level()) {
+                duck = Duck { depth, left: secs, total: secs };
+            }
+            if let Some((_, s)) = self.sfx.iter().find(|(k, _)| *k == kind) {
+                play_sound(s, PlaySoundParams { looped: false, volume: gain(v, kind.channel()) });
+            }
+        }
+        self.duck.set(duck);
+        for cue in std::mem::take(&mut game.cues) {
+            if let Some(s) = self.cues.get(cue.index()) {
+                play_sound(s, PlaySoundParams { looped: false, volume: gain(v, Channel::Sfx) * crate::cues::VOLUME });
+            }
+        }
+        if is_key_pressed(KeyCode::F8) {
+            game.settings.volume.music_muted = !game.settings.volume.music_muted;
+            game.settings.save();
+            game.toast.show(tr(if game.settings.volume.music_muted { "音乐：静音（[F8] 恢复）" } else { "音乐：已开启" }));
+        }
+        let v = game.settings.volume;
+        if self.playlist.borrow().is_empty() { return self.mix_music(game, v, duck, dt); }
+        let mut list = self.playlist.borrow_mut();
+        let vol = gain(v, Channel::Music) * (1.0 - duck.level()) * if muffled(game) { 1.0 - MUFFLE_FALLBACK } else { 1.0 };
+        let changed = if is_key_pressed(KeyCode::F7) { list.next(vol) } else { list.tick(vol, dt) };
+        if let Some(name) = changed { game.toast.show(format!("♪ {}", name)); }
+    }
+
+    fn mix_music(&self, game: &Game, v: Volume, duck: Duck, dt: f32) {
+        let in_run = matches!(game.mode, GameMode::Playing | GameMode::Paused);
+        let intensity = if in_run { game.intensity } else { 0.0 };
+        let muffled = muffled(game);
+        let has_muffled = self.stems.iter().any(|s| s.layer == Layer::Muffled);
+        let out = gain(v, Channel::Music) * (1.0 - duck.level());
+        for s in &self.stems {
+            let target = match (muffled, s.layer) {
+                (false, l) => l.level(intensity),
+                (true, Layer::Muffled) => 1.0,
+                (true, Layer::Base) if !has_muffled => 1.0 - MUFFLE_FALLBACK,
+                (true, _) => 0.0,
+            };
+            let cur = s.level.get();
+            let next = cur + (target - cur).clamp(-FADE * dt, FADE * dt);
+            s.level.set(next);
+            // 音量按 1/100 取整，没变化的分轨不用每帧都去设
+            let vol = (out * next * 100.0).round() / 100.0;
+            let last = s.applied.replace(vol);
+            if last < 0.0 {
+                play_sound(&s.sound, PlaySoundParams { looped: true, volume: vol });
+            } else if last != vol {
+                set_sound_volume(&s.sound, vol);
+            }
+        }
+    }
+}
+
+// 暂停和减速时音乐闷住
+fn muffled(game: &Game) -> bool {
+    game.mode == GameMode::Paused || (game.mode == GameMode::Playing && game.slow.active())
+}
+
+// 模拟每步调用：算紧张程度（落速 + 障碍密度），平滑后存进 game.intensity
+pub fn track_intensity(game: &mut Game, dt: f32) {
+    let speed = ((game.fall_speed / OB_START_SPEED - 1.0) / (SPEED_FULL - 1.0)).clamp(0.0, 1.0);
+    let density = (game.obs.live.len() as f32 / DENSITY_FULL).min(1.0);
+    let target = 0.6 * speed + 0.4 * density;
+    game.intensity += (target - game.intensity) * (1.0 - (-dt / INTENSITY_SMOOTH).exp());
+}
+
+fn gain(v: Volume, channel: Channel) -> f32 {
+    let c = match channel {
+        Channel::Music if v.music_muted => 0.0,
+        Channel::Music => v.music,
+        Channel::Sfx => v.sfx,
+        Channel::Ui => v.ui,
+    };
+    (v.master * c).clamp(0.0, 1.0)
+}
+
+// 记一个本帧要播的音效
+pub fn play(game: &mut Game, kind: Sfx) {
+    if !game.sfx.contains(&kind) { game.sfx.push(kind); }
+}
@@ -0,0 +1,110 @@
+use macroquad::prelude::*;
+
+use crate::i18n::{tr, trf};
+use crate::rng::{Rng, Stream};
+
+// ===== 由种子生成的背景：星空 + 星云 =====
+// 坐标都存成屏幕比例，窗口大小变化时图案不变形。
+const STAR_COUNT: usize = 90;
+const NEBULA_MAX: usize = 4;
+
+const HUES: [(&str, [f32; 3]); 6] = [
+    ("紫色", [0.55, 0.30, 0.85]),
+    ("青色", [0.20, 0.70, 0.80]),
+    ("琥珀", [0.90, 0.60, 0.20]),
+    ("翠绿", [0.25, 0.80, 0.45]),
+    ("绯红", [0.85, 0.25, 0.35]),
+    ("银白", [0.75, 0.78, 0.85]),
+];
+const SHAPES: [&str; 4] = ["星云", "星河", "尘埃", "极光"];
+
+struct Star { x: f32, y: f32, r: f32, phase: f32 }
+struct Blob { x: f32, y: f32, r: f32 }
+
+pub struct Backdrop {
+    hue: &'static str,
+    shape: &'static str,
+    tint: Color,
+    stars: Vec<Star>,
+    blobs: Vec<Blob>,
+}
+
+impl Backdrop {
+    pub fn generate(seed: u64) -> Self {
+        let mut rng = Rng::stream(seed, Stream::Cosmetic);
+        let (hue_name, c) = HUES[rng.index(HUES.len())];
+        let shape = SHAPES[rng.index(SHAPES.len())];
+        let stars = (0..STAR_COUNT)
+            .map(|_| Star { x: rng.next_f32(), y: rng.next_f32(), r: rng.range(0.6, 1.8), phase: rng.range(0.0, std::f32::consts::TAU) })
+            .collect();
+        let n = 1 + rng.index(NEBULA_MAX);
+        let blobs = (0..n)
+            .map(|_| Blob { x: rng.next_f32(), y: rng.range(0.1, 0.9), r: rng.range(0.12, 0.3) })
+            .collect();
+        Self { hue: hue_name, shape, tint: Color::new(c[0], c[1], c[2], 1.0), stars, blobs }
+    }
+
+    // 结算画面上的名字，如“紫色星云”
+    pub fn name(&self) -> String { trf("{}{}", &[&tr(self.hue), &tr(self.shape)]) }
+
+    // stars：星星亮度倍率（白天几乎看不见，见 Sky）
+    pub fn draw(&self, field: Vec2, t: f32, reduced_motion: bool, stars: f32) {
+        let (w, h) = (field.x, field.y);
+        // 星云：几层半透明圆叠出柔和边缘
+        for b in &self.blobs {
+            for k in 0..4 {
+                let r = b.r * w * (1.0 - k as f32 * 0.2);
+                draw_circle(b.x * w, b.y * h, r, Color::new(self.tint.r, self.tint.g, self.tint.b, 0.035));
+            }
+        }
+        for s in &self.stars {
+            let twinkle = if reduced_motion { 0.8 } else { 0.6 + 0.4 * (t * 1.5 + s.phase).sin() };
+            draw_circle(s.x * w, s.y * h, s.r, Color::new(0.9, 0.92, 1.0, 0.5 * twinkle * stars));
+        }
+    }
+}
+
+// ===== 昼夜循环 =====
+// 局内背景按本局用时走 黎明 → 白天 → 黄昏 → 夜晚，DAY_CYCLE 秒一整圈，相邻两段之间平滑过渡。
+// 只看 game.elapsed（模拟状态），回放和原局一模一样。
+// 每段给出：混进背景的天色和比例、星星亮度、障碍描边往黑（负）/ 往白（正）拉多少——天亮时描边压暗，夜里提亮，保证看得清。
+pub const DAY_CYCLE: f32 = 180.0;
+
+struct Phase { sky: [f32; 3], mix: f32, stars: f32, edge: f32 }
+
+const PHASES: [Phase; 4] = [
+    Phase { sky: [0.95, 0.55, 0.45], mix: 0.18, stars: 0.6, edge: 0.0 },   // 黎明
+    Phase { sky: [0.45, 0.65, 0.95], mix: 0.35, stars: 0.1, edge: -0.5 },  // 白天
+    Phase { sky: [0.85, 0.40, 0.30], mix: 0.25, stars: 0.5, edge: -0.1 },  // 黄昏
+    Phase { sky: [0.02, 0.02, 0.08], mix: 0.40, stars: 1.0, edge: 0.3 },   // 夜晚
+];
+
+#[derive(Clone, Copy)]
+pub struct Sky { sky: Color, mix: f32, pub stars: f32, edge: f32 }
+
+impl Sky {
+    // 不参与循环时（局外、高对比度、设置关掉）：背景原样
+    pub const NONE: Sky = Sky { sky: BLACK, mix: 0.0, stars: 1.0, edge: 0.0 };
+
+    pub fn at(elapsed: f32) -> Self {
+        let n = PHASES.len() as f32;
+        let x = (elapsed.max(0.0) / DAY_CYCLE).fract() * n;
+        let (a, b) = (&PHASES[x as usize % PHASES.len()], &PHASES[(x as usize + 1) % PHASES.len()]);
+        let f = x.fract();
+        let k = f * f * (3.0 - 2.0 * f); // smoothstep，段与段之间不打折
+        let lerp = |p: f32, q: f32| p + (q - p) * k;
+        let sky = Color::new(lerp(a.sky[0], b.sky[0]), lerp(a.sky[1], b.sky[1]), lerp(a.sky[2], b.sky[2]), 1.0);
+        Self { sky, mix: lerp(a.mix, b.mix), stars: lerp(a.stars, b.stars), edge: lerp(a.edge, b.edge) }
+    }
+
+    pub fn background(&self, c: Color) -> Color { mix(c, self.sky, self.mix) }
+
+    pub fn outline(&self, c: Color) -> Color {
+        let to = if self.edge < 0.0 { BLACK } else { WHITE };
+        Color { a: c.a, ..mix(c, to, self.edge.abs()) }
+    }
+}
+
+fn mix(a: Color, b: Color, t: f32) -> Color {
+    Color::new(a.r + (b.r - a.r) * t, a.g + (b.g - a.g) * t, a.b + (b.b - a.b) * t, a.a)
+}
@@ -0,0 +1,85 @@
+use std::collections::VecDeque;
+
+use macroquad::prelude::*;
+
+use crate::draw_text_center;
+
+// ===== 屏幕中央的定时横幅 =====
+// 阶段切换、世界事件、分数里程碑等公告都走这里：同一时间只显示一条，其余排队依次出现。
+// 按真实时间计时，淡入淡出；纯外观，不进快照。
+const FADE_IN: f32 = 0.15;
+const FADE_OUT: f32 = 0.5;
+const QUEUE_MAX: usize = 4;
+const BANNER_Y: f32 = 200.0;
+
+struct Banner {
+    title: String,
+    sub: String, // 副标题，可为空
+    color: Color,
+    age: f32,
+    duration: f32,
+}
+
+pub struct Banners {
+    current: Option<Banner>,
+    queue: VecDeque<Banner>,
+}
+
+impl Banners {
+    pub fn new() -> Self { Self { current: None, queue: VecDeque::new() } }
+    pub fn clear(&mut self) { *self = Self::new(); }
+
+    pub fn show(&mut self, title: impl Into<String>, sub: impl Into<String>, color: Color, duration: f32) {
+        if self.queue.len() >= QUEUE_MAX { self.queue.pop_front(); }
+        self.queue.push_back(Banner { title: title.into(), sub: sub.into(), color, age: 0.0, duration });
+    }
+
+    pub fn tick(&mut self, dt: f32) {
+        if let Some(b) = &mut self.current {
+            b.age += dt;
+            if b.age >= b.duration { self.current = None; }
+        }
+        if self.current.is_none() { self.current = self.queue.pop_front(); }
+    }
+
+    // calm：减少动态效果，不淡入淡出
+    pub fn draw(&self, font: &Font, field_w: f32, ts: f32, calm: bool) {
+        let Some(b) = &self.current else { return };
+        let a = if calm { 1.0 } else { (b.age / FADE_IN).min((b.duration - b.age) / FADE_OUT).clamp(0.0, 1.0) };
+        let c = Color::new(b.color.r, b.color.g, b.color.b, a);
+        draw_text_center(font, field_w, &b.title, BANNER_Y, 52.0 * ts, c);
+        if !b.sub.is_empty() {
+            draw_text_center(font, field_w, &b.sub, BANNER_Y + 40.0 * ts, 26.0 * ts, Color::new(0.8, 0.8, 0.8, a));
+        }
+    }
+}
+
+// ===== 底部提示条 =====
+// 截图之类的一行回执：任何界面都画在最上层（主循环里 draw_game 之后），不排队，新的直接顶掉旧的。
+const TOAST_TIME: f32 = 3.0;
+
+#[derive(Default)]
+pub struct Toast(Option<(String, f32)>); // (文字, 剩余秒数)
+
+impl Toast {
+    pub fn show(&mut self, text: impl Into<String>) { self.0 = Some((text.into(), TOAST_TIME)); }
+    #[cfg(feature = "tts")]
+    pub fn text(&self) -> Option<&str> { self.0.as_ref().map(|(t, _)| t.as_str()) }
+
+    pub fn tick(&mut self, dt: f32) {
+        if let Some((_, t)) = &mut self.0 {
+            *t -= dt;
+            if *t <= 0.0 { self.0 = None; }
+        }
+    }
+
+    pub fn draw(&self, font: &Font, field: Vec2, ts: f32) {
+        let Some((text, t)) = &self.0 else { return };
+        let a = (t / FADE_OUT).clamp(0.0, 1.0);
+        let size = 18.0 * ts;
+        let w = measure_text(text, Some(font), size as u16, 1.0).width + 24.0;
+        let (x, y) = ((field.x - w) * 0.5, field.y - 40.0 - size);
+        draw_rectangle(x, y, w, size + 16.0, Color::new(0.0, 0.0, 0.0, 0.7 * a));
+        draw_text_center(font, field.x, text, y + size + 4.0, size, Color::new(1.0, 1.0, 1.0, a));
+    }
+}
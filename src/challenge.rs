@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+
+use crate::difficulty::Difficulty;
+use crate::i18n::{tr, trf};
+use crate::movement::Scheme;
+use crate::mutators::{Mutators, MUTATORS};
+use crate::rng::{Rng, Stream};
+
+// ===== 每日 / 每周挑战 =====
+// 种子与变异组合都只由 UTC 日期决定：同一天（同一周）所有人面对同一串障碍。
+// 每日挑战每天只有一次机会——开局即记入历史，中途退出也算用掉；
+// 每周挑战每周一轮换，次数不限，只保留本周最好成绩。
+pub const HISTORY_MAX: usize = 90;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Challenge { Daily, Weekly }
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DailyResult {
+    pub day: u32,   // 自 1970-01-01 起的天数（UTC）
+    pub score: i32,
+    pub elapsed: f32,
+    pub dodged: u32,
+    pub tag: String, // 当天的变异标签
+}
+
+// 进入固定规则（挑战/教程）前玩家自己的选择，回到菜单时还原
+#[derive(Clone, Copy)]
+pub struct Stash {
+    pub difficulty: Difficulty,
+    pub adaptive: bool,
+    pub mutators: Mutators,
+    pub scheme: Scheme,
+    pub wrap: bool,
+    pub shooting: bool,
+    pub start_lives: u32,
+    pub loadout: i32,
+    pub character: usize,
+}
+
+pub struct ChallengeRun {
+    pub kind: Challenge,
+    pub period: u32, // 每日为天数，每周为周数
+}
+
+// 每周精选的 2~3 个变异，按周轮换
+const fn loadout(tiny: bool, ice: bool, double_spawn: bool, no_powerups: bool, giant: bool) -> Mutators {
+    Mutators { tiny, ice, double_spawn, no_powerups, giant }
+}
+const WEEKLY_LOADOUTS: [Mutators; 6] = [
+    loadout(true, false, true, false, false),
+    loadout(false, true, false, true, false),
+    loadout(false, false, true, false, true),
+    loadout(true, true, false, false, true),
+    loadout(false, true, true, true, false),
+    loadout(true, false, false, true, true),
+];
+
+pub fn today() -> u32 { (macroquad::miniquad::date::now() / 86_400.0) as u32 }
+
+// 1970-01-01 是周四，往前推 3 天对齐到周一
+pub fn this_week() -> u32 { (today() + 3) / 7 }
+fn week_start(week: u32) -> u32 { (week * 7).saturating_sub(3) }
+
+impl Challenge {
+    pub fn label(self) -> &'static str {
+        tr(match self {
+            Challenge::Daily => "每日挑战",
+            Challenge::Weekly => "每周挑战",
+        })
+    }
+    pub fn current(self) -> u32 {
+        match self {
+            Challenge::Daily => today(),
+            Challenge::Weekly => this_week(),
+        }
+    }
+    pub fn single_attempt(self) -> bool { self == Challenge::Daily }
+    pub fn seed(self, period: u32) -> u64 {
+        match self {
+            Challenge::Daily => Rng::stream(period as u64, Stream::Daily).next_u64(),
+            Challenge::Weekly => Rng::stream(period as u64, Stream::Weekly).next_u64(),
+        }
+    }
+    pub fn mutators(self, period: u32) -> Mutators {
+        match self {
+            Challenge::Daily => daily_mutators(period),
+            Challenge::Weekly => WEEKLY_LOADOUTS[period as usize % WEEKLY_LOADOUTS.len()],
+        }
+    }
+    pub fn period_label(self, period: u32) -> String {
+        match self {
+            Challenge::Daily => date_string(period),
+            Challenge::Weekly => trf("{} 起", &[&date_string(week_start(period))]),
+        }
+    }
+}
+
+// 每天 1~2 个变异
+fn daily_mutators(day: u32) -> Mutators {
+    let mut rng = Rng::stream(Challenge::Daily.seed(day), Stream::Daily);
+    let mut m = Mutators::default();
+    let n = 1 + rng.index(2);
+    while MUTATORS.iter().filter(|i| (i.get)(&m)).count() < n {
+        let info = &MUTATORS[rng.index(MUTATORS.len())];
+        if !(info.get)(&m) { (info.toggle)(&mut m); }
+    }
+    m
+}
+
+// 天数 → "YYYY-MM-DD"（公历换算，Howard Hinnant 的 civil_from_days）
+pub fn date_string(day: u32) -> String {
+    let z = day as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + (m <= 2) as i64;
+    format!("{:04}-{:02}-{:02}", y, m, d)
+}
+
+pub fn result_for(history: &[DailyResult], day: u32) -> Option<&DailyResult> {
+    history.iter().find(|r| r.day == day)
+}
@@ -0,0 +1,32 @@
+use macroquad::prelude::Color;
+
+// ===== 可选角色（菜单 [C] 切换）=====
+// 数值全在 CHARACTERS 表里；非标准角色的成绩按规则组合单独记录。
+pub struct CharacterDef {
+    pub key: &'static str,   // 成绩标签
+    pub name: &'static str,
+    pub blurb: &'static str, // 菜单上的一句话介绍
+    pub color: [u8; 3],      // 玩家方块的颜色
+    pub speed_mul: f32,      // 乘在加速度与最高速度上
+    pub extra_shield: u32,   // 开局额外护盾
+    pub dash_cd_mul: f32,    // 乘在冲刺冷却上
+    pub pu_interval_mul: f32, // 乘在道具生成间隔上（越小越多）
+    pub trap_floor: f32,     // 陷阱概率的下限（开局就会出陷阱）
+}
+
+pub const CHARACTERS: [CharacterDef; 4] = [
+    CharacterDef { key: "std", name: "标准", blurb: "没有特长，也没有短板", color: [90, 200, 255],
+        speed_mul: 1.0, extra_shield: 0, dash_cd_mul: 1.0, pu_interval_mul: 1.0, trap_floor: 0.0 },
+    CharacterDef { key: "tank", name: "坦克", blurb: "开局带 1 层护盾，但移动偏慢", color: [150, 170, 190],
+        speed_mul: 0.85, extra_shield: 1, dash_cd_mul: 1.0, pu_interval_mul: 1.0, trap_floor: 0.0 },
+    CharacterDef { key: "sprint", name: "疾风", blurb: "速度更快，冲刺冷却减半", color: [120, 255, 160],
+        speed_mul: 1.15, extra_shield: 0, dash_cd_mul: 0.5, pu_interval_mul: 1.0, trap_floor: 0.0 },
+    CharacterDef { key: "gamble", name: "赌徒", blurb: "道具翻倍，但从一开始就会掉陷阱", color: [255, 200, 80],
+        speed_mul: 1.0, extra_shield: 0, dash_cd_mul: 1.0, pu_interval_mul: 0.5, trap_floor: 0.25 },
+];
+
+impl CharacterDef {
+    pub fn body_color(&self) -> Color { Color::from_rgba(self.color[0], self.color[1], self.color[2], 255) }
+}
+
+pub fn get(index: usize) -> &'static CharacterDef { &CHARACTERS[index.min(CHARACTERS.len() - 1)] }
@@ -0,0 +1,72 @@
+use macroquad::prelude::*;
+
+use crate::missions::{self, Metric};
+use crate::rng::{Rng, Stream};
+use crate::shop::Perk;
+use crate::{coop, player_hitbox, Game, PlayMode};
+
+// ===== 金币 =====
+// 每隔一段时间从顶端掉一枚，碰到就进钱包（与分数无关）。
+// 位置用单独的随机流，不打乱玩法序列；不进快照，倒流后场上的金币保持原样。
+const COIN_R: f32 = 8.0;
+const INTERVAL: (f32, f32) = (2.0, 4.5); // 两枚金币的间隔范围（秒）
+const FALL_MUL: f32 = 0.6;               // 相对障碍下落速度
+const MAGNET_REACH: f32 = 160.0;
+const MAGNET_PULL: f32 = 260.0;          // 磁铁吸引速度（px/s）
+const COIN_COLOR: Color = Color::new(1.0, 0.84, 0.2, 1.0);
+
+pub struct Coins {
+    live: Vec<Vec2>,
+    next_in: f32,
+    pub run: u32, // 本局拾取数
+    rng: Rng,
+}
+
+impl Coins {
+    pub fn new(seed: u64) -> Self {
+        let mut rng = Rng::stream(seed, Stream::Coins);
+        Self { live: Vec::new(), next_in: rng.range(INTERVAL.0, INTERVAL.1), run: 0, rng }
+    }
+    pub fn clear(&mut self) { self.live.clear(); }
+}
+
+// dt：真实步长；ob_dt：障碍用的步长（受减速/冻结影响）
+pub fn update(game: &mut Game, dt: f32, ob_dt: f32) {
+    if game.play == PlayMode::Tutorial { return; }
+    let c = &mut game.coins;
+    c.next_in -= ob_dt;
+    if c.next_in <= 0.0 {
+        c.next_in = c.rng.range(INTERVAL.0, INTERVAL.1);
+        let x = c.rng.range(COIN_R * 2.0, game.field.x - COIN_R * 2.0);
+        c.live.push(vec2(x, -COIN_R));
+    }
+    // 合作局两人都能接，磁铁吸向近的那位
+    let hits: Vec<Rect> = coop::standing(game).into_iter().map(|(_, r)| player_hitbox(r.x, r.w)).collect();
+    let Some(&first) = hits.first() else { return };
+    let magnet = game.unlocks_apply() && game.wallet.has_perk(&game.shop, Perk::CoinMagnet);
+    let fall = game.fall_speed * FALL_MUL * ob_dt;
+    let mut got = 0;
+    game.coins.live.retain_mut(|p| {
+        p.y += fall;
+        let near = hits.iter().min_by(|a, b| a.center().distance(*p).total_cmp(&b.center().distance(*p))).unwrap_or(&first);
+        let to = near.center() - *p;
+        if magnet && to.length() < MAGNET_REACH { *p += to.normalize_or_zero() * MAGNET_PULL * dt; }
+        let cx = p.x.clamp(near.x, near.x + near.w);
+        let cy = p.y.clamp(near.y, near.y + near.h);
+        if p.distance(vec2(cx, cy)) < COIN_R { got += 1; return false; }
+        p.y < game.field.y + COIN_R
+    });
+    if got > 0 {
+        game.coins.run += got;
+        game.wallet.coins += got;
+        missions::bump(game, Metric::Coins, got);
+        game.texts.spawn(first.center() - vec2(0.0, 40.0), &format!("+{} coin", got), COIN_COLOR);
+    }
+}
+
+pub fn draw(game: &Game) {
+    for p in &game.coins.live {
+        draw_circle(p.x, p.y, COIN_R, COIN_COLOR);
+        draw_circle_lines(p.x, p.y, COIN_R * 0.6, 1.5, Color::new(0.8, 0.55, 0.1, 1.0));
+    }
+}
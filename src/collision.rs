@@ -0,0 +1,43 @@
+// ===== 碰撞层与过滤表 =====
+// 每个可碰撞物体属于一个层；两层之间是否发生接触只由下面的 PAIRS 决定，
+// 新交互在这里加一行，而不是在各处写 if。
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Layer(u8);
+
+impl Layer {
+    pub const PLAYER: Layer = Layer(1 << 0);
+    pub const OBSTACLE: Layer = Layer(1 << 1);
+    pub const PICKUP: Layer = Layer(1 << 2);
+    pub const PROJECTILE: Layer = Layer(1 << 3);
+    pub const HAZARD: Layer = Layer(1 << 4);
+    pub const DEFLECTED: Layer = Layer(1 << 5); // 被护盾弹开的障碍
+}
+
+// 会发生接触的层对（对称）
+const PAIRS: [(Layer, Layer); 7] = [
+    (Layer::PLAYER, Layer::OBSTACLE),
+    (Layer::PLAYER, Layer::HAZARD),
+    (Layer::PLAYER, Layer::PICKUP),
+    (Layer::PROJECTILE, Layer::OBSTACLE), // 子弹穿过道具
+    (Layer::PROJECTILE, Layer::HAZARD),
+    (Layer::DEFLECTED, Layer::OBSTACLE), // 弹开的障碍撞别的障碍，不伤玩家
+    (Layer::DEFLECTED, Layer::HAZARD),
+];
+
+// 被弹开的障碍保持 DEFLECTED 层的时长
+pub const DEFLECT_TIME: f32 = 0.3;
+
+// 与该层接触的所有层的掩码
+const fn mask(a: Layer) -> u8 {
+    let mut m = 0;
+    let mut i = 0;
+    while i < PAIRS.len() {
+        let (x, y) = PAIRS[i];
+        if x.0 == a.0 { m |= y.0; }
+        if y.0 == a.0 { m |= x.0; }
+        i += 1;
+    }
+    m
+}
+
+pub fn interacts(a: Layer, b: Layer) -> bool { mask(a) & b.0 != 0 }
@@ -0,0 +1,244 @@
+use std::collections::VecDeque;
+
+use macroquad::prelude::*;
+
+use crate::i18n::{tr, trf};
+use crate::replay::ReplayState;
+use crate::stages::Hazard;
+use crate::textfield::{Event, TextField};
+use crate::trace::is_key_pressed;
+use crate::{field_camera, powerup_sprite, spawn_hazard, view_size, Game, GameMode, PlayMode, ALL_POWERUPS, PLAYER_Y, PU_SIZE};
+
+// ===== 开发者控制台 =====
+// [~] 从顶上拉下来，打一行命令回车执行，比如 spawn ob 3、give shield、set fall_speed 400、seed 1234、timescale 0.5、god on。
+// [↑/↓] 翻之前打过的命令，[~/ESC] 收起。开着时局内时间停住，按键都归控制台。
+// 命令是一张表：名字、用法、执行函数。各系统在自己的模块里放一张 `pub const COMMANDS: &[Command]`，加进 REGISTRY 就能用（见 debug.rs）。
+// 会进最高分 / 排行榜的局（普通、禅、挑战、对战、轮流赛）和看回放时打不开，之前设的无敌、时间倍率在这些局里也不生效；
+// 练习、合作、教程和局外随便用。局内执行过命令，这一局就不再录回放。
+const INPUT_MAX: usize = 80;
+const LOG_MAX: usize = 50;
+const LOG_SHOWN: usize = 10;
+const HISTORY_MAX: usize = 30;
+const LINE_H: f32 = 20.0;
+const TIMESCALE_RANGE: (f32, f32) = (0.1, 4.0);
+
+pub struct Command {
+    pub name: &'static str,
+    pub usage: &'static str, // 参数写法和一句说明，help 里列出来
+    pub run: fn(&mut Game, &[&str]) -> Result<String, String>,
+}
+
+const REGISTRY: &[&[Command]] = &[BUILTIN, crate::debug::COMMANDS];
+
+pub struct Console {
+    field: Option<TextField>, // 打开时的输入行
+    log: VecDeque<String>,
+    history: Vec<String>,
+    recall: Option<usize>, // [↑/↓] 翻到的历史下标
+    god: bool,
+    timescale: f32,
+}
+
+impl Default for Console {
+    fn default() -> Self { Self { field: None, log: VecDeque::new(), history: Vec::new(), recall: None, god: false, timescale: 1.0 } }
+}
+
+impl Console {
+    pub fn is_open(&self) -> bool { self.field.is_some() }
+    fn print(&mut self, line: String) {
+        if self.log.len() == LOG_MAX { self.log.pop_front(); }
+        self.log.push_back(line);
+    }
+}
+
+fn in_run(game: &Game) -> bool { matches!(game.mode, GameMode::Playing | GameMode::Paused | GameMode::Killcam | GameMode::GameOver) }
+
+pub fn allowed(game: &Game) -> bool {
+    !in_run(game) || !(matches!(game.play, PlayMode::Standard | PlayMode::Zen | PlayMode::Versus | PlayMode::Hotseat) || game.replay.watching())
+}
+
+// 正在打字的界面不抢 [~]
+fn typing(game: &Game) -> bool {
+    matches!(game.mode, GameMode::NameEntry | GameMode::Feedback | GameMode::Lobby | GameMode::Hotseat | GameMode::Keybinds | GameMode::Settings)
+        || game.profile_edit.is_some() || game.naming.is_some() || game.seed_entry.is_some()
+}
+
+// 只在允许的局里生效
+pub fn god(game: &Game) -> bool { game.console.god && allowed(game) }
+pub fn time_scale(game: &Game) -> f32 { if allowed(game) { game.console.timescale } else { 1.0 } }
+
+// 每帧最先调用；返回 true 表示这一帧的按键归控制台
+pub fn update(game: &mut Game) -> bool {
+    if !game.console.is_open() {
+        if is_key_pressed(KeyCode::GraveAccent) && allowed(game) && !typing(game) {
+            game.console.field = Some(TextField::new("", INPUT_MAX));
+            game.console.recall = None;
+        }
+        return false;
+    }
+    if is_key_pressed(KeyCode::GraveAccent) || !allowed(game) {
+        game.console.field = None;
+        return true;
+    }
+    let Some(field) = game.console.field.as_mut() else { return false };
+    if !field.picking() {
+        let h = &game.console.history;
+        let step = is_key_pressed(KeyCode::Down) as i32 - is_key_pressed(KeyCode::Up) as i32;
+        if step != 0 && !h.is_empty() {
+            let at = match game.console.recall {
+                None if step < 0 => Some(h.len() - 1),
+                None => None,
+                Some(i) => (i as i32 + step).try_into().ok().filter(|&i: &usize| i < h.len()),
+            };
+            game.console.recall = at;
+            *field = TextField::new(at.map_or("", |i| h[i].as_str()), INPUT_MAX);
+        }
+    }
+    match field.update() {
+        Some(Event::Submit) => {
+            let line = field.text();
+            *field = TextField::new("", INPUT_MAX);
+            execute(game, line.trim());
+        }
+        Some(Event::Cancel) => game.console.field = None,
+        None => {}
+    }
+    true
+}
+
+fn execute(game: &mut Game, line: &str) {
+    if line.is_empty() { return; }
+    let c = &mut game.console;
+    c.print(format!("> {}", line));
+    if c.history.last().map(String::as_str) != Some(line) { c.history.push(line.to_string()); }
+    if c.history.len() > HISTORY_MAX { c.history.remove(0); }
+    c.recall = None;
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let out = match REGISTRY.iter().flat_map(|t| t.iter()).find(|cmd| cmd.name == words[0]) {
+        Some(cmd) => {
+            let out = (cmd.run)(game, &words[1..]);
+            // 局内改过东西，录下的输入回放不出同样的局
+            if out.is_ok() && in_run(game) && matches!(game.replay, ReplayState::Recording(_)) { game.replay = ReplayState::Off; }
+            out
+        }
+        None => Err(trf("没有这个命令：{}（help 看全部）", &[&words[0]])),
+    };
+    let line = match out { Ok(s) => s, Err(e) => format!("! {}", e) };
+    if !line.is_empty() { game.console.print(line); }
+}
+
+// —— 参数 ——
+fn arg<T: std::str::FromStr>(args: &[&str], i: usize) -> Result<T, String> {
+    let s = args.get(i).ok_or_else(|| tr("缺参数").to_string())?;
+    s.parse().map_err(|_| trf("看不懂的参数：{}", &[s]))
+}
+
+pub fn on_off(args: &[&str]) -> Result<bool, String> {
+    match args.first().copied() {
+        Some("on" | "1") => Ok(true),
+        Some("off" | "0") => Ok(false),
+        _ => Err(tr("要写 on 或 off").to_string()),
+    }
+}
+
+fn need_run(game: &Game) -> Result<(), String> {
+    if in_run(game) { Ok(()) } else { Err(tr("只能在局内用").to_string()) }
+}
+
+// —— 内置命令 ——
+const BUILTIN: &[Command] = &[
+    Command { name: "help", usage: "列出所有命令", run: help },
+    Command { name: "clear", usage: "清空控制台", run: |g, _| { g.console.log.clear(); Ok(String::new()) } },
+    Command { name: "spawn", usage: "ob|drifter|dart|slab|well [数量]：在顶上生成危险物", run: spawn },
+    Command { name: "give", usage: "<道具名>：直接拿到一个道具（shield、slow、bomb……）", run: give },
+    Command { name: "set", usage: "<变量> <值>：改局内数值，不带参数列出变量", run: set },
+    Command { name: "seed", usage: "<种子>：用这个种子重开（局外就是下一局）", run: seed },
+    Command { name: "timescale", usage: "<倍率>：局内时间流速，0.1~4", run: timescale },
+    Command { name: "god", usage: "on|off：无敌，撞上只把障碍弹开", run: |g, a| { g.console.god = on_off(a)?; Ok(trf("无敌：{}", &[&tr(if g.console.god { "开" } else { "关" })])) } },
+];
+
+fn help(game: &mut Game, _: &[&str]) -> Result<String, String> {
+    for cmd in REGISTRY.iter().flat_map(|t| t.iter()) { game.console.print(format!("{}  {}", cmd.name, tr(cmd.usage))); }
+    Ok(String::new())
+}
+
+fn spawn(game: &mut Game, args: &[&str]) -> Result<String, String> {
+    need_run(game)?;
+    let name = args.first().copied().unwrap_or("ob");
+    let hazard: Hazard = serde_json::from_value(serde_json::Value::String(if name == "ob" { "block".into() } else { name.into() }))
+        .map_err(|_| trf("没有这种危险物：{}", &[&name]))?;
+    let n: u32 = if args.len() > 1 { arg(args, 1)? } else { 1 };
+    for _ in 0..n.min(100) { spawn_hazard(game, hazard); }
+    Ok(trf("生成了 {} 个 {}", &[&n.min(100), &name]))
+}
+
+// 把道具直接放在挡板上，下一步照常拾取，叠加规则、成就、任务都和真捡到一样
+fn give(game: &mut Game, args: &[&str]) -> Result<String, String> {
+    need_run(game)?;
+    let name: String = arg(args, 0)?;
+    let kind = ALL_POWERUPS.iter().copied().find(|k| powerup_sprite(*k).strip_prefix("powerup_") == Some(name.as_str()))
+        .ok_or_else(|| trf("没有这种道具：{}", &[&name]))?;
+    let x = game.player.x + game.player.w * 0.5 - PU_SIZE * 0.5;
+    game.pus.spawn(x, PLAYER_Y, kind);
+    Ok(trf("给了 {}", &[&tr(kind.label())]))
+}
+
+// 能改的局内数值：(名字, 读, 写)
+type Var = (&'static str, fn(&Game) -> f32, fn(&mut Game, f32));
+const VARS: &[Var] = &[
+    ("fall_speed", |g| g.fall_speed, |g, v| g.fall_speed = v.max(0.0)),
+    ("spawn_interval", |g| g.spawn_interval, |g, v| g.spawn_interval = v.max(0.05)),
+    ("score", |g| g.score as f32, |g, v| g.score = v as i32),
+    ("lives", |g| g.lives as f32, |g, v| g.lives = v.max(1.0) as u32),
+    ("shield", |g| g.shield as f32, |g, v| g.shield = v.max(0.0) as u32),
+    ("ammo", |g| g.ammo as f32, |g, v| g.ammo = v.max(0.0) as u32),
+    ("ult", |g| g.ult, |g, v| g.ult = v.clamp(0.0, 1.0)),
+];
+
+fn set(game: &mut Game, args: &[&str]) -> Result<String, String> {
+    if args.is_empty() {
+        return Ok(VARS.iter().map(|(name, get, _)| format!("{}={}", name, get(game))).collect::<Vec<_>>().join("  "));
+    }
+    need_run(game)?;
+    let (name, _, put) = VARS.iter().find(|v| v.0 == args[0]).ok_or_else(|| trf("没有这个变量：{}", &[&args[0]]))?;
+    put(game, arg(args, 1)?);
+    let get = VARS.iter().find(|v| v.0 == *name).map_or(0.0, |v| (v.1)(game));
+    Ok(format!("{}={}", name, get))
+}
+
+fn seed(game: &mut Game, args: &[&str]) -> Result<String, String> {
+    let seed: u64 = arg(args, 0)?;
+    game.forced_seed = Some(seed);
+    if in_run(game) {
+        game.reset_round();
+        return Ok(trf("用种子 {} 重开了", &[&seed]));
+    }
+    Ok(trf("下一局用种子 {}", &[&seed]))
+}
+
+fn timescale(game: &mut Game, args: &[&str]) -> Result<String, String> {
+    let v: f32 = arg(args, 0)?;
+    game.console.timescale = v.clamp(TIMESCALE_RANGE.0, TIMESCALE_RANGE.1);
+    Ok(trf("时间倍率 {}", &[&game.console.timescale]))
+}
+
+// 画在最上层，盖住场地上面一截
+pub fn draw(font: &Font, game: &Game) {
+    let Some(field) = &game.console.field else { return };
+    let view = view_size(game);
+    set_camera(&field_camera(view, Vec2::ZERO));
+    let size = 16.0 * game.settings.text_scale();
+    let h = LINE_H * (LOG_SHOWN as f32 + 2.5);
+    draw_rectangle(0.0, 0.0, view.x, h, Color::new(0.02, 0.03, 0.06, 0.92));
+    draw_line(0.0, h, view.x, h, 2.0, DARKGRAY);
+    let params = TextParams { font: Some(font), font_size: size as u16, color: LIGHTGRAY, ..Default::default() };
+    let shown = game.console.log.iter().skip(game.console.log.len().saturating_sub(LOG_SHOWN));
+    for (i, line) in shown.enumerate() {
+        let color = if line.starts_with('!') { ORANGE } else if line.starts_with('>') { GRAY } else { LIGHTGRAY };
+        draw_text_ex(line, 10.0, LINE_H * (i as f32 + 1.0), TextParams { color, ..params.clone() });
+    }
+    let y = h - LINE_H * 0.6;
+    field.draw_left(font, 10.0, y, size, "] ", WHITE);
+    field.draw_picker(font, view.x * 0.5, h + LINE_H * 1.2, size);
+    set_default_camera();
+}
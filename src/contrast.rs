@@ -0,0 +1,38 @@
+use macroquad::prelude::*;
+
+use crate::{coop, gravity, view_size, visible_obstacles, Game, PLAYER_Y};
+
+// ===== 高对比描边 =====
+// 无障碍里的“高对比描边”不换掉主题（“高对比度”是整套换成纯色），而是在当前主题上再叠一层：
+// 背景层压暗，挡板、障碍、陷阱道具和黑洞核心描粗白边（白边下面垫一圈黑，亮色主题上也分得清），挡板所在高度画一条亮底线。
+// 给低视力玩家和阳光下的笔记本屏幕用；只是画面，和模拟无关。
+const DIM: f32 = 0.45;   // 背景压暗的不透明度
+const EDGE: f32 = 3.0;   // 白边粗细
+const UNDER: f32 = 6.0;  // 垫在白边下面的黑边粗细
+
+fn on(game: &Game) -> bool { game.settings.contrast_outlines }
+
+// 画完背景层之后调用：整片压暗
+pub fn dim(game: &Game) {
+    if !on(game) { return; }
+    let view = view_size(game);
+    draw_rectangle(-view.x, -view.y, view.x * 3.0, view.y * 3.0, Color::new(0.0, 0.0, 0.0, DIM));
+}
+
+fn outline(r: Rect) {
+    draw_rectangle_lines(r.x - 1.0, r.y - 1.0, r.w + 2.0, r.h + 2.0, UNDER, BLACK);
+    draw_rectangle_lines(r.x - 1.0, r.y - 1.0, r.w + 2.0, r.h + 2.0, EDGE, WHITE);
+}
+
+// 场地上的东西画完之后调用（粒子和飘字之前）
+pub fn draw(game: &Game) {
+    if !on(game) { return; }
+    draw_line(0.0, PLAYER_Y, game.field.x, PLAYER_Y, 2.0, Color::new(1.0, 1.0, 1.0, 0.85));
+    for o in visible_obstacles(game) { outline(o.rect); }
+    for p in game.pus.live.iter().filter(|p| p.kind.is_trap()) { outline(p.rect); }
+    for c in gravity::cores(game) {
+        draw_circle_lines(c.x, c.y, gravity::CORE_RADIUS + 2.0, UNDER, BLACK);
+        draw_circle_lines(c.x, c.y, gravity::CORE_RADIUS + 2.0, EDGE, WHITE);
+    }
+    for (_, p) in coop::players(game) { outline(p.rect()); }
+}
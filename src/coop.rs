@@ -0,0 +1,160 @@
+use macroquad::prelude::*;
+
+use crate::feel::Feel;
+use crate::i18n::trf;
+use crate::movement::Mover;
+use crate::trace::{is_key_down, is_key_pressed};
+use crate::{draw_text_center, gravity, Game, MoveInput, Physics, PlayMode, Player};
+
+// ===== 本地双人合作 =====
+// 同屏两块挡板：1P 用 A/D（左 Shift 冲刺），2P 用方向键（右 Shift 冲刺；同屏对决也用这套键位），分数、护盾、命数两人共用。
+// 被撞的一方倒地：不能动、不判碰撞；队友在 REVIVE_TIME 秒内擦边 REVIVE_GRAZES 次就能把他救起来。
+// 超时没救起、或有人倒地时队友也被撞，本局结束。合作局和联机对战一样不计最高分、不录回放。
+// 玩家编号：0 是 game.player，1 是 partner。
+pub const REVIVE_GRAZES: u32 = 10;
+pub const REVIVE_TIME: f32 = 8.0;
+const REVIVE_INVULN: f32 = 1.0; // 倒地 / 救起后两人共用的无敌时间
+const P2_COLOR: Color = Color::new(1.0, 0.62, 0.2, 1.0);
+const DOWN_COLOR: Color = Color::new(0.45, 0.45, 0.5, 0.7);
+
+pub struct Pad { left: KeyCode, right: KeyCode, dash: KeyCode }
+
+pub const PADS: [Pad; 2] = [
+    Pad { left: KeyCode::A, right: KeyCode::D, dash: KeyCode::LeftShift },
+    Pad { left: KeyCode::Left, right: KeyCode::Right, dash: KeyCode::RightShift },
+];
+
+impl Pad {
+    pub fn input(&self) -> MoveInput {
+        let dir = is_key_down(self.right) as i32 - is_key_down(self.left) as i32;
+        MoveInput { dir: dir as f32, dash: is_key_pressed(self.dash), push: 0.0 }
+    }
+}
+
+pub struct Downed {
+    pub who: usize,
+    pub left: f32,   // 剩余救援时间
+    pub grazes: u32, // 队友已擦边次数
+}
+
+pub struct Coop {
+    pub partner: Player,
+    mover: Mover, // 2P 自己的车道状态
+    pub downed: Option<Downed>,
+}
+
+// 菜单 [J]
+pub fn start(game: &mut Game) {
+    game.play = PlayMode::Coop;
+    game.reset_round();
+}
+
+// 开局（reset_round 末尾）：两人分站场地三分之一处
+pub fn setup(game: &mut Game) {
+    if game.play != PlayMode::Coop { game.coop = None; return; }
+    let w = game.field.x;
+    let mut partner = game.player.clone();
+    let mut mover = Mover::new(game.mover.scheme, game.mover.wrap);
+    mover.warp(&mut partner, w * 2.0 / 3.0, w);
+    game.mover.warp(&mut game.player, w / 3.0, w);
+    game.coop = Some(Coop { partner, mover, downed: None });
+}
+
+fn down(game: &Game, who: usize) -> bool { game.coop.as_ref().is_some_and(|c| c.downed.as_ref().is_some_and(|d| d.who == who)) }
+
+// 所有玩家（单人局只有 0 号）
+pub fn players(game: &Game) -> Vec<(usize, &Player)> {
+    let mut v = vec![(0, &game.player)];
+    if let Some(c) = &game.coop { v.push((1, &c.partner)); }
+    v
+}
+
+// 还站着的玩家的外框：碰撞、擦边、拾取都只看这些
+pub fn standing(game: &Game) -> Vec<(usize, Rect)> {
+    players(game).into_iter().filter(|(who, _)| !down(game, *who)).map(|(who, p)| (who, p.rect())).collect()
+}
+
+// 合作局的移动：各读各的键，倒地的原地不动；颠倒、风、引力照常作用在两人身上
+pub fn step(game: &mut Game, phys: &Physics, acc_mul: f32, dt: f32) {
+    let fw = game.field.x;
+    for (who, pad) in PADS.iter().enumerate() {
+        let mut input = pad.input();
+        if game.reverse > 0.0 { input.dir = -input.dir; }
+        let Some(c) = &game.coop else { return };
+        let r = if who == 0 { game.player.rect() } else { c.partner.rect() };
+        input.push = game.wind.push_at(r) + gravity::push_on(game, r);
+        if down(game, who) { input = MoveInput { dir: 0.0, dash: false, push: 0.0 }; }
+        let Some(c) = &mut game.coop else { return };
+        if who == 0 {
+            game.mover.step(&mut game.player, phys, input, acc_mul, dt, fw);
+        } else {
+            c.mover.step(&mut c.partner, phys, input, acc_mul, dt, fw);
+        }
+        if down(game, who) { player_mut(game, who).vx = 0.0; }
+    }
+}
+
+fn player_mut(game: &mut Game, who: usize) -> &mut Player {
+    match &mut game.coop {
+        Some(c) if who == 1 => &mut c.partner,
+        _ => &mut game.player,
+    }
+}
+
+// 被撞时还能不能倒地（已经有人倒着就不行了）
+pub fn can_go_down(game: &Game) -> bool { game.coop.as_ref().is_some_and(|c| c.downed.is_none()) }
+
+pub fn go_down(game: &mut Game, who: usize, feel: &Feel) {
+    let Some(c) = &mut game.coop else { return };
+    c.downed = Some(Downed { who, left: REVIVE_TIME, grazes: 0 });
+    let at = players(game)[who].1.rect().center();
+    game.effects.burst(feel, at, DOWN_COLOR, 20);
+    game.invuln = game.invuln.max(REVIVE_INVULN);
+    game.shake.add(crate::shake::Jolt::Down);
+    game.banners.show(trf("{}P 倒下了！", &[&(who + 1)]), trf("队友擦边 {} 次救起", &[&REVIVE_GRAZES]), RED, 1.5);
+}
+
+// 每步推进救援：grazes 是本步每人的擦边数。超时没救起时返回倒地者的外框（本局结束）
+pub fn tick(game: &mut Game, dt: f32, grazes: [u32; 2], feel: &Feel) -> Option<Rect> {
+    let d = game.coop.as_mut()?.downed.as_mut()?;
+    d.grazes += grazes[1 - d.who];
+    d.left -= dt;
+    let who = d.who;
+    if d.grazes >= REVIVE_GRAZES {
+        game.coop.as_mut()?.downed = None;
+        let at = players(game)[who].1.rect().center();
+        game.effects.burst(feel, at, LIME, 24);
+        game.invuln = game.invuln.max(REVIVE_INVULN);
+        game.banners.show(trf("{}P 救起来了！", &[&(who + 1)]), "", LIME, 1.2);
+        return None;
+    }
+    (d.left <= 0.0).then(|| players(game)[who].1.rect())
+}
+
+// 挡板颜色：2P 固定橙色，倒地的发灰
+pub fn tint(game: &Game, who: usize, body: Color) -> Color {
+    if down(game, who) { DOWN_COLOR } else if who == 1 { P2_COLOR } else { body }
+}
+
+// HUD：挡板上方标出 1P / 2P，有人倒地时屏幕中间是救援进度
+pub fn draw_hud(font: &Font, game: &Game, ts: f32) {
+    let Some(c) = &game.coop else { return };
+    for (who, p) in players(game) {
+        let r = p.rect();
+        let color = tint(game, who, WHITE);
+        draw_text_ex(&format!("{}P", who + 1), r.x + r.w * 0.5 - 9.0 * ts, r.y - 8.0, TextParams { font: Some(font), font_size: (16.0 * ts) as u16, color, ..Default::default() });
+    }
+    let Some(d) = &c.downed else { return };
+    let txt = trf("救援 {}P：{}P 擦边 {}/{}   {:.1}s", &[&(d.who + 1), &(2 - d.who), &d.grazes, &REVIVE_GRAZES, &d.left.max(0.0)]);
+    draw_text_center(font, game.field.x, &txt, game.field.y * 0.5, 24.0 * ts, if d.left < 3.0 { RED } else { ORANGE });
+    // 倒地者头顶一圈倒计时
+    let r = players(game)[d.who].1.rect();
+    let frac = d.left / REVIVE_TIME;
+    let (cx, cy) = (r.x + r.w * 0.5, r.y - 28.0 * ts);
+    draw_circle_lines(cx, cy, 10.0 * ts, 2.0, Color::new(1.0, 1.0, 1.0, 0.2));
+    let n = 24;
+    for i in 0..(frac * n as f32).ceil() as i32 {
+        let a = -std::f32::consts::FRAC_PI_2 + i as f32 / n as f32 * std::f32::consts::TAU;
+        draw_circle(cx + a.cos() * 10.0 * ts, cy + a.sin() * 10.0 * ts, 1.6, LIME);
+    }
+}
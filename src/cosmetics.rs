@@ -0,0 +1,123 @@
+use macroquad::prelude::Color;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{tr, trf};
+use crate::palette::Palette;
+use crate::stats::StatBests;
+
+// ===== 外观（assets/cosmetics.json）=====
+// 玩家皮肤与配色主题，全部由数据定义；解锁条件可以是免费、商店购买或达成某项成绩。
+// 颜色留空的字段沿用默认（皮肤用角色原色，主题的障碍和背景用当前阶段的配色，其余见 palette.rs）。
+pub const COSMETICS_PATH: &str = "assets/cosmetics.json";
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Requirement {
+    Free,
+    Shop,                        // 在商店里用金币买（商品 key 与外观 key 相同）
+    Score { score: i32 },        // 任一难度最高分达到
+    Dodged { count: u32 },       // 单局躲过障碍数达到
+    NearMisses { count: u32 },   // 单局擦边次数达到
+    Combo { count: u32 },        // 单局最高连击达到
+    Level { level: u32 },        // 账号等级达到
+}
+
+// 判断解锁需要的玩家进度
+pub struct Progress<'a> {
+    pub level: u32,
+    pub best_score: i32,
+    pub stat_bests: &'a StatBests,
+    pub owned: &'a [String], // 商店已购的 key
+}
+
+impl Requirement {
+    pub fn met(&self, key: &str, p: &Progress) -> bool {
+        match *self {
+            Requirement::Free => true,
+            Requirement::Shop => p.owned.iter().any(|k| k == key),
+            Requirement::Score { score } => p.best_score >= score,
+            Requirement::Dodged { count } => p.stat_bests.dodged >= count,
+            Requirement::NearMisses { count } => p.stat_bests.near_misses >= count,
+            Requirement::Combo { count } => p.stat_bests.max_combo >= count,
+            Requirement::Level { level } => p.level >= level,
+        }
+    }
+    pub fn label(&self) -> String {
+        match *self {
+            Requirement::Free => tr("免费").to_string(),
+            Requirement::Shop => tr("商店购买").to_string(),
+            Requirement::Score { score } => trf("最高分达到 {}", &[&score]),
+            Requirement::Dodged { count } => trf("单局躲过 {} 个障碍", &[&count]),
+            Requirement::NearMisses { count } => trf("单局擦边 {} 次", &[&count]),
+            Requirement::Combo { count } => trf("连击达到 x{}", &[&count]),
+            Requirement::Level { level } => trf("Lv {} 解锁", &[&level]),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Skin {
+    pub key: String,
+    pub name: String,
+    #[serde(default)]
+    pub body: Option<[u8; 3]>, // None：角色原色
+    pub stripe: [u8; 3],       // 顶部高光条
+    pub glow: [u8; 3],         // 护盾外圈
+    pub unlock: Requirement,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Theme {
+    pub key: String,
+    pub name: String,
+    #[serde(default)]
+    pub obstacle: Option<[u8; 3]>,   // None：当前阶段的障碍色
+    #[serde(default)]
+    pub background: Option<[u8; 3]>, // None：当前阶段的背景色
+    #[serde(default)]
+    pub threat: Option<[[u8; 3]; 3]>, // 慢 / 普通 / 快三档障碍色；None：从障碍色推出来（见 threat.rs）
+    #[serde(default)]
+    pub palette: Palette,             // 其余各处的颜色（见 palette.rs）
+    pub unlock: Requirement,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Cosmetics {
+    pub skins: Vec<Skin>,
+    pub themes: Vec<Theme>,
+}
+
+impl Default for Cosmetics {
+    // 找不到配置时只有默认外观，保持原本的配色
+    fn default() -> Self {
+        Self {
+            skins: vec![Skin { key: "default".into(), name: "默认".into(), body: None, stripe: [200, 245, 255], glow: [120, 220, 255], unlock: Requirement::Free }],
+            themes: vec![Theme { key: "stage".into(), name: "随阶段变化".into(), obstacle: None, background: None, threat: None, palette: Palette::default(), unlock: Requirement::Free }],
+        }
+    }
+}
+
+impl Cosmetics {
+    pub fn load(path: &str) -> Self {
+        let c: Self = crate::storage::read_asset_string(path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+        if c.skins.is_empty() || c.themes.is_empty() { Self::default() } else { c }
+    }
+    // 找不到（或被删掉）的 key 退回第一项
+    pub fn skin(&self, key: &str) -> &Skin { self.skins.iter().find(|s| s.key == key).unwrap_or(&self.skins[0]) }
+    pub fn theme(&self, key: &str) -> &Theme { self.themes.iter().find(|t| t.key == key).unwrap_or(&self.themes[0]) }
+    pub fn is_skin(&self, key: &str) -> bool { self.skins.iter().any(|s| s.key == key) }
+}
+
+// 当前选用的外观（存档）
+#[derive(Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Look {
+    pub skin: String,
+    pub theme: String,
+}
+
+pub fn rgb(c: [u8; 3]) -> Color { Color::from_rgba(c[0], c[1], c[2], 255) }
@@ -0,0 +1,71 @@
+use std::f32::consts::{FRAC_PI_4, TAU};
+
+use crate::synth::RATE;
+use crate::threat::Tier;
+use crate::{Game, PLAYER_Y};
+
+// ===== 听觉预警 =====
+// 障碍进入挡板上方的“通道”（横向和挡板重叠或很近、再过不久就落到挡板高度）时响一声短促的提示音，不看画面也能听出危险从哪来。
+// 左右声道按障碍在挡板左边还是右边分配，音高按危险等级（见 threat.rs）：慢的低、快的高。
+// 声音后端不能实时调声像和音高，所以开局时直接合成一组立体声小样（5 个方位 × 3 档音高），播时挑最接近的一个。
+// 无障碍设置里开关，默认关。每个障碍进通道只响一次，离开再进来才会再响；只是声音，不影响模拟和回放。
+const LENGTH: f32 = 0.11;       // 一声多长（秒）
+const ATTACK: f32 = 0.006;
+const PANS: [f32; 5] = [-1.0, -0.5, 0.0, 0.5, 1.0];
+const MARGIN: f32 = 40.0;       // 挡板两侧各放宽这么多算进通道
+const LOOKAHEAD: f32 = 1.4;     // 预计这么多秒内落到挡板高度才提示
+pub const VOLUME: f32 = 0.45;   // 提示音相对音效声道的音量
+const MAX_PER_FRAME: usize = 3; // 一帧最多响几声（回放快进时不至于一片嘈杂）
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Cue { pan: usize, tier: Tier }
+
+impl Cue {
+    // 在合成好的小样里的下标
+    pub fn index(self) -> usize { self.pan * Tier::ALL.len() + self.tier as usize }
+}
+
+fn pitch(tier: Tier) -> f32 {
+    match tier {
+        Tier::Slow => 392.0,
+        Tier::Normal => 523.0,
+        Tier::Fast => 784.0,
+    }
+}
+
+// 一声提示音：正弦波，起音很快，之后指数衰减；声像用等功率分配
+fn synth(pan: f32, freq: f32) -> Vec<u8> {
+    let n = (LENGTH * RATE as f32) as u32;
+    let angle = (pan + 1.0) * FRAC_PI_4;
+    let (l, r) = (angle.cos(), angle.sin());
+    crate::synth::wav((0..n).map(|i| {
+        let t = i as f32 / RATE as f32;
+        let s = (TAU * freq * t).sin() * (t / ATTACK).min(1.0) * (-t * 40.0).exp() * 0.8;
+        (s * l, s * r)
+    }))
+}
+
+// 全部小样，顺序同 Cue::index
+pub fn bank() -> Vec<Vec<u8>> {
+    PANS.iter().flat_map(|&p| Tier::ALL.map(|t| synth(p, pitch(t)))).collect()
+}
+
+// 模拟每步调用：找出刚进通道的障碍，记进 game.cues
+pub fn scan(game: &mut Game) {
+    if !game.settings.audio_cues { return; }
+    let player = game.player.rect();
+    let (lo, hi) = (player.x - MARGIN, player.right() + MARGIN);
+    let center = player.center().x;
+    for o in &mut game.obs.live {
+        let eta = (PLAYER_Y - o.rect.bottom()) / o.vy;
+        let inside = o.rect.right() > lo && o.rect.x < hi && o.vy > 0.0 && (0.0..LOOKAHEAD).contains(&eta);
+        if inside && !o.cued && game.cues.len() < MAX_PER_FRAME {
+            let reach = player.w * 0.5 + MARGIN + o.rect.w * 0.5;
+            let pan = ((o.rect.center().x - center) / reach).clamp(-1.0, 1.0);
+            let pan = ((pan + 1.0) * 0.5 * (PANS.len() - 1) as f32).round() as usize;
+            let cue = Cue { pan, tier: Tier::of(o.vy, game.fall_speed) };
+            if !game.cues.contains(&cue) { game.cues.push(cue); }
+        }
+        o.cued = inside;
+    }
+}
@@ -0,0 +1,105 @@
+use macroquad::prelude::*;
+
+use crate::console::Command;
+use crate::i18n::{tr, trf};
+use crate::trace::is_key_pressed;
+use crate::{field_camera, inset_hitbox, view_size, wrap_ghost, Game, GameMode};
+
+// ===== 调试浮层 =====
+// [F3] 随时开关，左上角列出帧率、这一帧跑了几个固定步、各对象池的活 / 闲数量、下落速度、生成间隔、生效中的效果和本局种子。
+// 开着时 [F5] 再把所有命中盒描出来：障碍红、玩家绿（按当前宽容度收过的那个）、道具青、子弹黄。
+// 命中盒按场地坐标画，不跟震屏走，看到的就是碰撞真正用的位置。查平衡和“明明没碰到”一类反馈用。
+const PANEL_X: f32 = 10.0;
+const PANEL_Y: f32 = 90.0;
+const LINE_H: f32 = 18.0;
+
+#[derive(Default)]
+pub struct Debug {
+    on: bool,
+    hitboxes: bool,
+    steps: u32, // 这一帧跑的固定步数
+}
+
+impl Debug {
+    pub fn set_steps(&mut self, n: u32) { self.steps = n; }
+}
+
+// 控制台命令（见 console.rs）
+pub const COMMANDS: &[Command] = &[
+    Command { name: "overlay", usage: "on|off：调试浮层，同 [F3]", run: |g, a| { g.debug.on = crate::console::on_off(a)?; Ok(String::new()) } },
+    Command { name: "hitboxes", usage: "on|off：描出命中盒，同浮层里的 [F5]", run: |g, a| {
+        g.debug.hitboxes = crate::console::on_off(a)?;
+        g.debug.on |= g.debug.hitboxes;
+        Ok(String::new())
+    } },
+];
+
+pub fn input(game: &mut Game) {
+    let d = &mut game.debug;
+    if is_key_pressed(KeyCode::F3) { d.on = !d.on; }
+    if d.on && is_key_pressed(KeyCode::F5) { d.hitboxes = !d.hitboxes; }
+}
+
+fn in_run(game: &Game) -> bool { matches!(game.mode, GameMode::Playing | GameMode::Paused | GameMode::Killcam | GameMode::GameOver) }
+
+// 生效中的效果，带剩余秒数
+fn effects(game: &Game) -> Vec<String> {
+    let mut v = Vec::new();
+    if game.shield > 0 { v.push(format!("{} ×{}", tr("护盾"), game.shield)); }
+    let timed = [
+        ("减速", game.slow.remaining),
+        ("倍率", game.mult.remaining),
+        ("冻结", game.freeze.remaining),
+        ("颠倒", game.reverse),
+        ("加速", game.haste),
+        ("黑暗", game.blackout),
+        ("超载", game.overdrive),
+        ("无敌", game.invuln),
+    ];
+    for (name, left) in timed {
+        if left > 0.0 { v.push(format!("{} {:.1}s", tr(name), left)); }
+    }
+    if game.teleport { v.push(tr("瞬移").to_string()); }
+    if game.rewind { v.push(tr("倒流").to_string()); }
+    if game.stored_bomb { v.push(tr("炸弹").to_string()); }
+    v
+}
+
+pub fn draw(font: &Font, game: &Game) {
+    let d = &game.debug;
+    if !d.on { return; }
+    set_camera(&field_camera(view_size(game), Vec2::ZERO));
+    if d.hitboxes && in_run(game) { draw_hitboxes(game); }
+    let (obs, pus, shots, particles) = (
+        (game.obs.live.len(), game.obs.dead.len()),
+        (game.pus.live.len(), game.pus.dead.len()),
+        game.shots.counts(),
+        game.effects.particles.counts(),
+    );
+    let active = effects(game);
+    let lines = [
+        trf("FPS {}   固定步 {}/帧", &[&get_fps(), &d.steps]),
+        trf("障碍 {}/{}   道具 {}/{}   子弹 {}/{}   粒子 {}/{}（活/闲）", &[&obs.0, &obs.1, &pus.0, &pus.1, &shots.0, &shots.1, &particles.0, &particles.1]),
+        trf("下落速度 {:.0}   生成间隔 {:.2}s", &[&game.fall_speed, &game.spawn_interval]),
+        trf("生效中：{}", &[&if active.is_empty() { tr("无").to_string() } else { active.join("  ") }]),
+        trf("种子 {}", &[&game.seed]),
+        trf("[F5] 命中盒：{}   [F3] 关闭", &[&tr(if d.hitboxes { "开" } else { "关" })]),
+    ];
+    let size = (15.0 * game.settings.text_scale()) as u16;
+    let w = lines.iter().map(|l| measure_text(l, Some(font), size, 1.0).width).fold(0.0, f32::max);
+    draw_rectangle(PANEL_X - 6.0, PANEL_Y - LINE_H, w + 12.0, LINE_H * lines.len() as f32 + 8.0, Color::new(0.0, 0.0, 0.0, 0.6));
+    for (i, l) in lines.iter().enumerate() {
+        draw_text_ex(l, PANEL_X, PANEL_Y + i as f32 * LINE_H, TextParams { font: Some(font), font_size: size, color: LIME, ..Default::default() });
+    }
+    set_default_camera();
+}
+
+fn draw_hitboxes(game: &Game) {
+    let line = |r: Rect, c: Color| draw_rectangle_lines(r.x, r.y, r.w, r.h, 1.5, c);
+    for o in &game.obs.live { line(o.rect, RED); }
+    for p in &game.pus.live { line(p.rect, SKYBLUE); }
+    for s in &game.shots.live { line(s.rect, YELLOW); }
+    let hit = inset_hitbox(game.player.x, game.player.w, game.forgiveness().0);
+    line(hit, GREEN);
+    if let Some(g) = wrap_ghost(hit, game.field.x) { line(g, GREEN); }
+}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+// ===== 难度预设 =====
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+pub const DIFFICULTIES: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+pub struct Tuning {
+    pub acc_mul: f32,         // 乘在 OB_ACC_PER_SEC 上
+    pub spawn_mul: f32,       // 乘在障碍生成间隔上（越小越密）
+    pub pu_interval_mul: f32, // 乘在道具生成间隔上
+    pub start_shield: u32,
+    pub bomb_radius: f32,     // 炸弹冲击波的最大半径
+    pub classic_bomb: bool,   // 旧版炸弹：直接清空全屏
+}
+
+impl Difficulty {
+    pub fn label(self) -> &'static str {
+        crate::i18n::tr(match self {
+            Difficulty::Easy => "简单",
+            Difficulty::Normal => "普通",
+            Difficulty::Hard => "困难",
+        })
+    }
+    // 存档里用的稳定键名
+    pub fn key(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+        }
+    }
+    pub fn tuning(self) -> Tuning {
+        match self {
+            Difficulty::Easy => Tuning { acc_mul: 0.6, spawn_mul: 1.3, pu_interval_mul: 0.75, start_shield: 1, bomb_radius: 260.0, classic_bomb: false },
+            Difficulty::Normal => Tuning { acc_mul: 1.0, spawn_mul: 1.0, pu_interval_mul: 1.0, start_shield: 0, bomb_radius: 200.0, classic_bomb: false },
+            Difficulty::Hard => Tuning { acc_mul: 1.5, spawn_mul: 0.75, pu_interval_mul: 1.4, start_shield: 0, bomb_radius: 160.0, classic_bomb: false },
+        }
+    }
+    pub fn cycle(self, step: i32) -> Self {
+        let i = DIFFICULTIES.iter().position(|d| *d == self).unwrap_or(1) as i32;
+        DIFFICULTIES[(i + step).rem_euclid(DIFFICULTIES.len() as i32) as usize]
+    }
+}
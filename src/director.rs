@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+// ===== 自适应难度（可选，不计入排行）=====
+// 以“每 10 秒擦边次数”为目标：擦边太少且未被击中就收紧生成间隔，
+// 擦边过多或连续早死就放宽。输出一个乘在生成间隔上的倍率。
+const WINDOW: f32 = 10.0;
+const TARGET_NEAR_MISSES: f32 = 3.0;
+const EARLY_DEATH: f32 = 15.0;
+const MUL_MIN: f32 = 0.6;
+const MUL_MAX: f32 = 1.6;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Director {
+    pub enabled: bool,
+    pub spawn_mul: f32,
+    early_deaths: u32,
+    window_time: f32,
+    window_misses: u32,
+    window_hit: bool,
+}
+
+impl Director {
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled, spawn_mul: 1.0, early_deaths: 0, window_time: 0.0, window_misses: 0, window_hit: false }
+    }
+
+    pub fn start_run(&mut self) {
+        self.window_time = 0.0;
+        self.window_misses = 0;
+        self.window_hit = false;
+    }
+
+    fn adjust(&mut self, factor: f32) {
+        self.spawn_mul = (self.spawn_mul * factor).clamp(MUL_MIN, MUL_MAX);
+    }
+
+    pub fn tick(&mut self, dt: f32, near_misses: u32, got_hit: bool) {
+        if !self.enabled { return; }
+        self.window_time += dt;
+        self.window_misses += near_misses;
+        self.window_hit |= got_hit;
+        if self.window_time < WINDOW { return; }
+        let rate = self.window_misses as f32 * WINDOW / self.window_time;
+        if rate > TARGET_NEAR_MISSES * 1.5 || self.window_hit {
+            self.adjust(1.05);
+        } else if rate < TARGET_NEAR_MISSES {
+            self.adjust(0.95);
+        }
+        self.start_run();
+    }
+
+    // 连续两次以上早死才放宽，避免一次失误就大幅降低难度
+    pub fn end_run(&mut self, elapsed: f32) {
+        if !self.enabled { return; }
+        if elapsed < EARLY_DEATH {
+            self.early_deaths += 1;
+            if self.early_deaths >= 2 { self.adjust(1.1); }
+        } else {
+            self.early_deaths = 0;
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use std::cell::Cell;
+
+use macroquad::prelude::*;
+
+use crate::i18n::tr;
+use crate::settings::{Settings, WindowPlace};
+use crate::trace::{alt_enter, get_frame_time, screen_height, screen_width};
+use crate::Game;
+
+// ===== 窗口：大小、位置与全屏 =====
+// 窗口化时每帧把大小和位置记进 settings.window（退出时随设置写盘），下次启动按它开窗、摆回原处，再按 settings.fullscreen 进全屏。
+// 先摆位置再全屏，全屏就落在上次那块显示器上（miniquad 没有显示器列表，只能靠位置）。
+// 全屏是无边框的：铺满当前显示器，不改分辨率，切起来快。[ALT+ENTER] 随时切换，设置里的“全屏”也走这里。
+// 场地不跟着变：局外场地随新窗口大小走；局内场地锁定，field_camera 按比例缩放留黑边（竖屏场地本来就是固定的），照常可按 [Y] 改用新大小。
+const SIZE_MIN: (f32, f32) = (320.0, 240.0);
+const SIZE_MAX: (f32, f32) = (7680.0, 4320.0);
+// 切换全屏后窗口要过几帧才回到原来的大小，这段时间不记
+const SETTLE_SECS: f32 = 0.5;
+
+thread_local! {
+    static SETTLE: Cell<f32> = const { Cell::new(SETTLE_SECS) };
+}
+
+// 开窗时的大小（窗口配置在开窗前就要，这时游戏还没起来，设置由 main 先读好）
+pub fn initial_size(settings: &Settings) -> (i32, i32) {
+    let (w, h) = settings.window.size;
+    (w.clamp(SIZE_MIN.0, SIZE_MAX.0) as i32, h.clamp(SIZE_MIN.1, SIZE_MAX.1) as i32)
+}
+
+// 开窗后调用一次
+pub fn restore(settings: &Settings) {
+    if let Some((x, y)) = settings.window.pos { macroquad::miniquad::window::set_window_position(x, y); }
+    if settings.fullscreen { set_fullscreen(true); }
+}
+
+pub fn set(game: &mut Game, on: bool) {
+    game.settings.fullscreen = on;
+    set_fullscreen(on);
+    SETTLE.set(SETTLE_SECS);
+}
+
+// 每帧：[ALT+ENTER] 切全屏，窗口化时记下大小和位置
+pub fn update(game: &mut Game) {
+    if alt_enter() {
+        set(game, !game.settings.fullscreen);
+        game.settings.save();
+        game.toast.show(tr(if game.settings.fullscreen { "全屏" } else { "窗口" }));
+    }
+    let settle = (SETTLE.get() - get_frame_time()).max(0.0);
+    SETTLE.set(settle);
+    if game.settings.fullscreen || settle > 0.0 || cfg!(any(target_arch = "wasm32", target_os = "android")) { return; }
+    game.settings.window = WindowPlace { size: (screen_width(), screen_height()), pos: position() };
+}
+
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn position() -> Option<(u32, u32)> { Some(macroquad::miniquad::window::get_window_position()) }
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+fn position() -> Option<(u32, u32)> { None }
@@ -0,0 +1,189 @@
+use macroquad::prelude::*;
+
+use crate::coop::PADS;
+use crate::i18n::{tr, trf};
+use crate::rng::{Rng, Stream};
+use crate::trace::is_key_pressed;
+use crate::versus::FIELD;
+use crate::{
+    difficulty_curve, draw_text_center, field_camera, player_hitbox, rects_overlap, step_player, Game, GameMode, ObstaclePool, Physics,
+    Player, OB_ACC_PER_SEC, OB_MAX_SIZE, OB_MIN_SIZE, OB_START_SPEED, PLAYER_H, PLAYER_W, PLAYER_Y, SPAWN_BASE_INTERVAL,
+};
+
+// ===== 同屏对决 =====
+// 一个窗口左右各一块场地（各自一台相机），1P 用 A/D 在左边、2P 用方向键在右边，键位同双人合作。
+// 两边的障碍来自同一个种子的两份随机流，生成时刻只看各自的存活时间，所以序列完全一样；
+// 谁先被撞谁输掉这一回合，同一步一起倒下算平局重打。五局三胜，回合之间显示比分。
+// 不用默认难度和变异，不计任何记录。
+const WINS_NEEDED: u32 = 3;
+const COUNTDOWN: f32 = 1.5;
+const BETWEEN_TIME: f32 = 2.5;
+const SIDE_COLORS: [Color; 2] = [SKYBLUE, Color::new(1.0, 0.62, 0.2, 1.0)];
+
+// 一位玩家的半边：挡板、障碍、随机流和计时都各管各的
+struct Side {
+    player: Player,
+    obs: ObstaclePool,
+    rng: Rng,
+    spawn_timer: f32,
+    elapsed: f32,
+    dodged: u32,
+    down: bool,
+}
+
+impl Side {
+    fn new(seed: u64, phys: &Physics) -> Self {
+        let mut player = Player::new(FIELD.0 * 0.5 - PLAYER_W * 0.5, PLAYER_W);
+        player.acc = phys.acc;
+        player.speed_max = phys.speed_max;
+        Self { player, obs: ObstaclePool::new(), rng: Rng::stream(seed, Stream::Duel), spawn_timer: 0.0, elapsed: 0.0, dodged: 0, down: false }
+    }
+
+    // 一个固定步：移动、生成、下落、碰撞；被撞返回 true
+    fn step(&mut self, phys: &Physics, who: usize, dt: f32) -> bool {
+        step_player(&mut self.player, phys, PADS[who].input(), 1.0, dt, FIELD.0, false);
+        let curve = difficulty_curve(self.elapsed, OB_START_SPEED, SPAWN_BASE_INTERVAL, OB_ACC_PER_SEC);
+        self.elapsed += dt;
+        self.spawn_timer += dt;
+        if self.spawn_timer >= curve.spawn {
+            self.spawn_timer = 0.0;
+            let size = self.rng.range(OB_MIN_SIZE, OB_MAX_SIZE);
+            let x = self.rng.range(0.0, FIELD.0 - size);
+            self.obs.spawn(Rect::new(x, -size - 10.0, size, size), 0.0, curve.fall);
+        }
+        self.dodged += self.obs.update_and_sweep(FIELD.0, FIELD.1, PLAYER_Y, dt);
+        let hit = player_hitbox(self.player.x, self.player.w);
+        self.obs.live.iter().any(|o| rects_overlap(o.rect, hit))
+    }
+}
+
+enum Phase {
+    Countdown(f32),
+    Playing,
+    Between { left: f32, winner: Option<usize> }, // None 为平局
+    Over(usize),
+}
+
+pub struct Duel {
+    sides: [Side; 2],
+    seed: u64,
+    round: u32,
+    wins: [u32; 2],
+    phase: Phase,
+}
+
+impl Duel {
+    fn new(seed: u64, phys: &Physics) -> Self {
+        Self { sides: [Side::new(seed, phys), Side::new(seed, phys)], seed, round: 1, wins: [0; 2], phase: Phase::Countdown(COUNTDOWN) }
+    }
+
+    // 每回合换一个种子（由开局种子推出来，两边仍然相同）
+    fn next_round(&mut self, phys: &Physics) {
+        self.round += 1;
+        let seed = self.seed.wrapping_add(self.round as u64);
+        self.sides = [Side::new(seed, phys), Side::new(seed, phys)];
+        self.phase = Phase::Countdown(COUNTDOWN);
+    }
+}
+
+// 菜单 [X]
+pub fn enter(game: &mut Game) {
+    game.duel = Some(Duel::new(crate::trace::now_ms(), &game.phys));
+    game.mode = GameMode::Duel;
+}
+
+// 固定步
+pub fn update(game: &mut Game, dt: f32) {
+    let phys = game.phys;
+    let Some(d) = &mut game.duel else { return };
+    match &mut d.phase {
+        Phase::Countdown(t) => {
+            *t -= dt;
+            if *t <= 0.0 { d.phase = Phase::Playing; }
+        }
+        Phase::Playing => {
+            let hit = [d.sides[0].step(&phys, 0, dt), d.sides[1].step(&phys, 1, dt)];
+            for (side, h) in d.sides.iter_mut().zip(hit) { side.down = h; }
+            let winner = match hit {
+                [false, false] => return,
+                [true, true] => None,
+                [false, true] => Some(0),
+                [true, false] => Some(1),
+            };
+            if let Some(w) = winner { d.wins[w] += 1; }
+            d.phase = match winner {
+                Some(w) if d.wins[w] >= WINS_NEEDED => Phase::Over(w),
+                _ => Phase::Between { left: BETWEEN_TIME, winner },
+            };
+        }
+        Phase::Between { left, .. } => {
+            *left -= dt;
+            if *left <= 0.0 { d.next_round(&phys); }
+        }
+        Phase::Over(_) => {}
+    }
+}
+
+// 逐帧：[ESC] 回菜单，比赛结束后 [R] 再来一场
+pub fn ui(game: &mut Game) {
+    if is_key_pressed(KeyCode::Escape) {
+        game.duel = None;
+        game.mode = GameMode::Menu;
+        return;
+    }
+    let over = game.duel.as_ref().is_some_and(|d| matches!(d.phase, Phase::Over(_)));
+    if over && is_key_pressed(KeyCode::R) { enter(game); }
+}
+
+fn draw_side(font: &Font, side: &Side, who: usize, ts: f32) {
+    let (w, h) = FIELD;
+    for o in &side.obs.live {
+        draw_rectangle(o.rect.x, o.rect.y, o.rect.w, o.rect.h, Color::new(0.85, 0.35, 0.35, 0.9));
+    }
+    let r = side.player.rect();
+    draw_rectangle(r.x, PLAYER_Y, r.w, PLAYER_H, if side.down { GRAY } else { SIDE_COLORS[who] });
+    draw_rectangle_lines(0.0, 0.0, w, h, 2.0, Color::new(1.0, 1.0, 1.0, 0.25));
+    let keys = if who == 0 { "A / D" } else { "← / →" };
+    draw_text_center(font, w, &format!("{}P  [{}]", who + 1, keys), 28.0, 20.0 * ts, SIDE_COLORS[who]);
+    let t = side.elapsed;
+    draw_text_center(font, w, &trf("{:.1}s   躲过 {}", &[&t, &side.dodged]), 52.0, 16.0 * ts, LIGHTGRAY);
+}
+
+// 两台相机各画一边，比分和横幅画在整屏上
+pub fn draw(font: &Font, game: &Game) {
+    let Some(d) = &game.duel else { return };
+    let ts = game.settings.text_scale();
+    let view = vec2(FIELD.0 * 2.0, FIELD.1);
+    for (who, side) in d.sides.iter().enumerate() {
+        set_camera(&field_camera(view, vec2(-FIELD.0 * who as f32, 0.0)));
+        draw_side(font, side, who, ts);
+    }
+    set_camera(&field_camera(view, Vec2::ZERO));
+    let score = format!("1P  {} : {}  2P", d.wins[0], d.wins[1]);
+    draw_text_center(font, view.x, &score, 90.0, 26.0 * ts, WHITE);
+    match &d.phase {
+        Phase::Countdown(t) => {
+            draw_text_center(font, view.x, &trf("第 {} 回合", &[&d.round]), view.y * 0.4, 40.0 * ts, WHITE);
+            draw_text_center(font, view.x, &format!("{}", t.ceil() as i32), view.y * 0.4 + 50.0, 34.0 * ts, GOLD);
+        }
+        Phase::Playing => {}
+        Phase::Between { winner, .. } => {
+            draw_rectangle(0.0, view.y * 0.4 - 50.0, view.x, 90.0, Color::new(0.0, 0.0, 0.0, 0.6));
+            let (txt, color) = match winner {
+                Some(w) => (trf("{}P 拿下第 {} 回合", &[&(w + 1), &d.round]), SIDE_COLORS[*w]),
+                None => (trf("第 {} 回合平局，重打", &[&d.round]), LIGHTGRAY),
+            };
+            draw_text_center(font, view.x, &txt, view.y * 0.4, 34.0 * ts, color);
+            draw_text_center(font, view.x, &score, view.y * 0.4 + 32.0, 24.0 * ts, WHITE);
+        }
+        Phase::Over(w) => {
+            draw_rectangle(0.0, view.y * 0.4 - 50.0, view.x, 130.0, Color::new(0.0, 0.0, 0.0, 0.7));
+            draw_text_center(font, view.x, &trf("{}P 获胜！", &[&(w + 1)]), view.y * 0.4, 44.0 * ts, SIDE_COLORS[*w]);
+            draw_text_center(font, view.x, &score, view.y * 0.4 + 36.0, 24.0 * ts, WHITE);
+            draw_text_center(font, view.x, tr("[R] 再来一场   [ESC] 菜单"), view.y * 0.4 + 68.0, 22.0 * ts, ORANGE);
+        }
+    }
+    if !matches!(d.phase, Phase::Over(_)) {
+        draw_text_center(font, view.x, tr("五局三胜   [ESC] 退出"), view.y - 12.0, 16.0 * ts, GRAY);
+    }
+}
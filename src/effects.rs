@@ -0,0 +1,93 @@
+use macroquad::prelude::*;
+
+use crate::feel::Feel;
+use crate::particles::{Burst, Particles, SPARK};
+
+// ===== 打击感效果：顿帧、慢动作、粒子、拖影 =====
+// 强度全部来自 Feel；时间相关的计时器按真实时间走，不受自身的时间缩放影响。
+// 粒子见 particles.rs，用外观随机流，不会打乱玩法的随机序列。
+// 顿帧不是跳帧：固定步照常跑，只是步长乘上 HIT_STOP_SCALE，几乎停住；时长按事件轻重给，限制在 40–80ms
+pub const HIT_STOP_SHIELD: f32 = 0.05;
+pub const HIT_STOP_BOMB: f32 = 0.08;
+const HIT_STOP_SCALE: f32 = 0.05;
+const SLOW_MO_TIME: f32 = 0.25;
+const SLOW_MO_SCALE: f32 = 0.4;
+// 拖影：环形缓冲存最近的挡板位置，按速度决定画多长（Feel.trail 是全速时的长度），冲刺 / 超载时翻倍、更不透明
+const TRAIL_CAP: usize = 48;
+const TRAIL_ALPHA: f32 = 0.25;
+const TRAIL_BOOST_ALPHA: f32 = 0.45;
+
+struct Trail {
+    ring: [Rect; TRAIL_CAP],
+    head: usize,  // 最新一个的位置
+    filled: usize,
+    len: f32,     // 当前画的长度，朝目标长度平滑变化
+    boost: bool,
+}
+
+impl Trail {
+    fn new() -> Self { Self { ring: [Rect::default(); TRAIL_CAP], head: 0, filled: 0, len: 0.0, boost: false } }
+
+    // 第 i 新的位置（0 是本步）
+    fn get(&self, i: usize) -> Rect { self.ring[(self.head + TRAIL_CAP - i) % TRAIL_CAP] }
+}
+
+pub struct Effects {
+    hit_stop: f32,
+    slow_mo: f32,
+    pub particles: Particles,
+    trail: Trail,
+}
+
+impl Effects {
+    pub fn new(seed: u64) -> Self {
+        Self { hit_stop: 0.0, slow_mo: 0.0, particles: Particles::new(seed), trail: Trail::new() }
+    }
+
+    // 乘在模拟步长上的时间倍率
+    pub fn time_scale(&self) -> f32 {
+        if self.hit_stop > 0.0 { HIT_STOP_SCALE } else if self.slow_mo > 0.0 { SLOW_MO_SCALE } else { 1.0 }
+    }
+
+    pub fn hit_stop(&mut self, feel: &Feel, secs: f32) {
+        if feel.hit_stop { self.hit_stop = self.hit_stop.max(secs.clamp(0.04, 0.08)); }
+    }
+    pub fn slow_mo(&mut self, feel: &Feel) {
+        if feel.slow_mo { self.slow_mo = SLOW_MO_TIME; }
+    }
+
+    // 一簇通用火花
+    pub fn burst(&mut self, feel: &Feel, at: Vec2, color: Color, base: usize) {
+        self.emit(feel, at, &SPARK.tint(color).times(base));
+    }
+    pub fn emit(&mut self, feel: &Feel, at: Vec2, b: &Burst) {
+        self.particles.emit(at, b, feel.particles);
+    }
+
+    // real_dt：真实帧时间；player：本步玩家矩形；speed：横向速度占最高速度的比例；boost：冲刺或超载中
+    pub fn tick(&mut self, feel: &Feel, real_dt: f32, player: Rect, speed: f32, boost: bool) {
+        self.hit_stop = (self.hit_stop - real_dt).max(0.0);
+        self.slow_mo = (self.slow_mo - real_dt).max(0.0);
+        self.particles.update(real_dt);
+        let t = &mut self.trail;
+        t.head = (t.head + 1) % TRAIL_CAP;
+        t.ring[t.head] = player;
+        t.filled = (t.filled + 1).min(TRAIL_CAP);
+        t.boost = boost;
+        let target = feel.trail as f32 * speed.clamp(0.0, 1.0) * if boost { 2.0 } else { 1.0 };
+        t.len += (target - t.len) * (real_dt * 12.0).min(1.0);
+    }
+
+    pub fn draw_trail(&self, color: Color) {
+        let t = &self.trail;
+        let n = (t.len.round() as usize).min(t.filled).min(TRAIL_CAP - 1);
+        let peak = if t.boost { TRAIL_BOOST_ALPHA } else { TRAIL_ALPHA };
+        for i in 1..=n {
+            let r = t.get(i);
+            let a = peak * (1.0 - i as f32 / (n + 1) as f32);
+            draw_rectangle(r.x, r.y, r.w, r.h, Color::new(color.r, color.g, color.b, a));
+        }
+    }
+
+    pub fn draw_particles(&self) { self.particles.draw(); }
+}
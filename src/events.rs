@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+
+// ===== 随机世界事件：调度 + 效果查询 =====
+const FIRST_EVENT_AFTER: f32 = 20.0;   // 开局多久后才可能出现事件
+const EVENT_GAP_MIN: f32 = 25.0;
+const EVENT_GAP_MAX: f32 = 45.0;
+const GOLDEN_RAIN_EVERY: f32 = 0.35;   // 金色雨中每隔多久掉一个道具
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum WorldEventKind { MeteorShower, Headwind, GoldenRain }
+
+pub struct EventDef {
+    pub kind: WorldEventKind,
+    pub name: &'static str,
+    pub weight: f32,
+    pub duration: f32,
+}
+
+pub const EVENT_TABLE: [EventDef; 3] = [
+    EventDef { kind: WorldEventKind::MeteorShower, name: "流星雨", weight: 3.0, duration: 3.0 },
+    EventDef { kind: WorldEventKind::Headwind, name: "逆风", weight: 2.0, duration: 5.0 },
+    EventDef { kind: WorldEventKind::GoldenRain, name: "金色雨", weight: 1.0, duration: 2.0 },
+];
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ActiveEvent {
+    #[serde(with = "def_index")]
+    pub def: &'static EventDef,
+    pub remaining: f32,
+    pulse: f32,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EventScheduler {
+    next_in: f32,
+    pub active: Option<ActiveEvent>,
+}
+
+impl EventScheduler {
+    pub fn new() -> Self { Self { next_in: FIRST_EVENT_AFTER, active: None } }
+    pub fn reset(&mut self) { *self = Self::new(); }
+
+    // roll_gap / roll_pick 为 0~1 随机数；返回 true 表示本步需要掉落一个“金色雨”道具
+    pub fn tick(&mut self, dt: f32, roll_gap: f32, roll_pick: f32) -> bool {
+        if let Some(ev) = &mut self.active {
+            ev.remaining -= dt;
+            if ev.remaining <= 0.0 {
+                self.active = None;
+            } else if ev.def.kind == WorldEventKind::GoldenRain {
+                ev.pulse -= dt;
+                if ev.pulse <= 0.0 {
+                    ev.pulse += GOLDEN_RAIN_EVERY;
+                    return true;
+                }
+            }
+            return false;
+        }
+        self.next_in -= dt;
+        if self.next_in <= 0.0 {
+            self.next_in = EVENT_GAP_MIN + roll_gap * (EVENT_GAP_MAX - EVENT_GAP_MIN);
+            let def = pick(roll_pick);
+            self.active = Some(ActiveEvent { def, remaining: def.duration, pulse: 0.0 });
+        }
+        false
+    }
+
+    // 立刻开始某个事件（顶掉正在进行的），之后的调度照旧
+    #[cfg(feature = "twitch")]
+    pub fn force(&mut self, kind: WorldEventKind) {
+        let def = EVENT_TABLE.iter().find(|e| e.kind == kind).unwrap_or(&EVENT_TABLE[0]);
+        self.active = Some(ActiveEvent { def, remaining: def.duration, pulse: 0.0 });
+    }
+
+    fn is(&self, kind: WorldEventKind) -> bool {
+        self.active.as_ref().is_some_and(|e| e.def.kind == kind)
+    }
+    pub fn fall_mul(&self) -> f32 { if self.is(WorldEventKind::MeteorShower) { 1.5 } else { 1.0 } }
+    pub fn spawn_mul(&self) -> f32 { if self.is(WorldEventKind::MeteorShower) { 0.5 } else { 1.0 } }
+    pub fn acc_mul(&self) -> f32 { if self.is(WorldEventKind::Headwind) { 0.5 } else { 1.0 } }
+}
+
+// 挂起存档里事件定义只记在表中的序号
+mod def_index {
+    use super::{EventDef, EVENT_TABLE};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(def: &&'static EventDef, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_u64(EVENT_TABLE.iter().position(|e| e.kind == def.kind).unwrap_or(0) as u64)
+    }
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<&'static EventDef, D::Error> {
+        let i = u64::deserialize(d)? as usize;
+        Ok(&EVENT_TABLE[i.min(EVENT_TABLE.len() - 1)])
+    }
+}
+
+// 按权重挑选事件
+fn pick(roll: f32) -> &'static EventDef {
+    let total: f32 = EVENT_TABLE.iter().map(|e| e.weight).sum();
+    let mut t = roll * total;
+    for e in &EVENT_TABLE {
+        t -= e.weight;
+        if t <= 0.0 { return e; }
+    }
+    &EVENT_TABLE[EVENT_TABLE.len() - 1]
+}
@@ -0,0 +1,94 @@
+use std::fmt::Write as _;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::history::HistoryEntry;
+use crate::stats::{DeathRecord, Lifetime, StatBests};
+use crate::{challenge, ALL_POWERUPS};
+
+// ===== 导出对局与平衡数据 =====
+// 给玩家和策划拿去外部工具分析节奏：一份完整 JSON，外加三张 CSV（每局历史 / 生涯汇总 / 死亡记录）。
+// base 不带扩展名，写出 base.json、base-runs.csv、base-lifetime.csv、base-deaths.csv。
+pub const EXPORT_DIR: &str = "exports";
+
+pub struct ExportData<'a> {
+    pub history: &'a [HistoryEntry],
+    pub lifetime: &'a Lifetime,
+    pub bests: &'a StatBests,
+    pub deaths: &'a [DeathRecord],
+}
+
+#[derive(Serialize)]
+struct Dump<'a> {
+    history: &'a [HistoryEntry],
+    lifetime: &'a Lifetime,
+    bests: &'a StatBests,
+    deaths: &'a [DeathRecord],
+}
+
+// 字段里有逗号、引号或换行时按 CSV 规则加引号
+fn field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) { format!("\"{}\"", s.replace('"', "\"\"")) } else { s.to_string() }
+}
+
+fn runs_csv(history: &[HistoryEntry]) -> String {
+    let mut out = String::from("id,date,mode,seed,score,duration,cause,replay\n");
+    for e in history {
+        let _ = writeln!(out, "{},{},{},{},{},{:.2},{},{}", e.id, challenge::date_string(e.day), field(&e.mode), e.seed, e.score, e.duration, field(&e.cause), e.replay);
+    }
+    out
+}
+
+fn lifetime_csv(lt: &Lifetime, bests: &StatBests) -> String {
+    let mut out = String::from("stat,value\n");
+    let _ = writeln!(out, "playtime,{:.1}", lt.playtime);
+    let _ = writeln!(out, "runs,{}", lt.runs);
+    let _ = writeln!(out, "total_score,{}", lt.total_score);
+    let _ = writeln!(out, "deaths,{}", lt.deaths);
+    let _ = writeln!(out, "longest_run,{:.2}", lt.longest_run);
+    let _ = writeln!(out, "best_elapsed,{:.2}", bests.elapsed);
+    let _ = writeln!(out, "best_dodged,{}", bests.dodged);
+    let _ = writeln!(out, "best_near_misses,{}", bests.near_misses);
+    let _ = writeln!(out, "best_combo,{}", bests.max_combo);
+    for k in ALL_POWERUPS {
+        let _ = writeln!(out, "pickups_{},{}", field(k.label()), lt.pickups[k as usize]);
+    }
+    out
+}
+
+fn deaths_csv(deaths: &[DeathRecord]) -> String {
+    let mut out = String::from("x,elapsed,on_screen,killer_x,killer_w\n");
+    for d in deaths {
+        let _ = writeln!(out, "{:.3},{:.2},{},{:.3},{:.3}", d.x, d.elapsed, d.on_screen, d.killer_x, d.killer_w);
+    }
+    out
+}
+
+// 写出全部文件，返回写了哪些
+pub fn write(base: &str, data: &ExportData) -> std::io::Result<Vec<String>> {
+    // 给了扩展名就去掉，统一按 base 命名
+    let p = Path::new(base);
+    let base = if p.extension().is_some() { p.with_extension("").to_string_lossy().into_owned() } else { base.to_string() };
+    if let Some(dir) = Path::new(&base).parent().filter(|d| !d.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+    let dump = Dump { history: data.history, lifetime: data.lifetime, bests: data.bests, deaths: data.deaths };
+    let files = [
+        (format!("{}.json", base), serde_json::to_string_pretty(&dump).unwrap()),
+        (format!("{}-runs.csv", base), runs_csv(data.history)),
+        (format!("{}-lifetime.csv", base), lifetime_csv(data.lifetime, data.bests)),
+        (format!("{}-deaths.csv", base), deaths_csv(data.deaths)),
+    ];
+    let mut written = Vec::new();
+    for (path, text) in files {
+        std::fs::write(&path, text)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+// 统计界面里导出：按当前时间命名放进数据目录的 exports/
+pub fn default_base() -> String {
+    crate::paths::data(EXPORT_DIR).join(format!("dodge-{}", macroquad::miniquad::date::now() as u64)).to_string_lossy().into_owned()
+}
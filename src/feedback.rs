@@ -0,0 +1,98 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use macroquad::prelude::Image;
+
+use crate::i18n::tr;
+use crate::logfile::LOG_PATH;
+use crate::{http, paths, storage};
+use crate::settings::Settings;
+
+// ===== 反馈/问题报告：打包成 zip，本地保存或 POST 到配置的地址 =====
+pub const FEEDBACK_DIR: &str = "feedback";
+pub const ENDPOINT_ENV: &str = "DODGE_FEEDBACK_URL";
+pub const MAX_TEXT: usize = 500;
+
+pub struct FeedbackForm {
+    pub text: String,
+    pub with_screenshot: bool,
+    pub upload: bool,
+    pub screenshot: Option<Image>,
+    pub status: Option<String>,
+}
+
+impl FeedbackForm {
+    pub fn new() -> Self {
+        Self { text: String::new(), with_screenshot: true, upload: false, screenshot: None, status: None }
+    }
+}
+
+pub fn endpoint() -> Option<String> {
+    std::env::var(ENDPOINT_ENV).ok().filter(|s| !s.trim().is_empty())
+}
+
+pub struct Report<'a> {
+    pub text: &'a str,
+    pub settings: &'a Settings,
+    pub seed: u64,
+    pub difficulty: &'a str,
+    pub score: i32,
+    pub elapsed: f32,
+    pub screenshot: Option<&'a Image>,
+}
+
+fn png_bytes(img: &Image) -> Option<Vec<u8>> {
+    let dir = paths::data(FEEDBACK_DIR);
+    std::fs::create_dir_all(&dir).ok()?;
+    let tmp = dir.join(".shot.png");
+    img.export_png(&tmp.to_string_lossy());
+    let bytes = std::fs::read(&tmp).ok();
+    let _ = std::fs::remove_file(&tmp);
+    bytes
+}
+
+pub fn build_zip(r: &Report) -> zip::result::ZipResult<Vec<u8>> {
+    let mut zw = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let opts = zip::write::SimpleFileOptions::default();
+
+    zw.start_file("report.txt", opts)?;
+    writeln!(zw, "version: {}", env!("CARGO_PKG_VERSION"))?;
+    writeln!(zw, "seed: {}", r.seed)?;
+    writeln!(zw, "difficulty: {}", r.difficulty)?;
+    writeln!(zw, "score: {}", r.score)?;
+    writeln!(zw, "elapsed: {:.1}s", r.elapsed)?;
+    writeln!(zw)?;
+    writeln!(zw, "{}", r.text)?;
+
+    zw.start_file("settings.json", opts)?;
+    zw.write_all(serde_json::to_string_pretty(r.settings).unwrap_or_default().as_bytes())?;
+
+    if let Ok(log) = storage::read(paths::data(LOG_PATH)) {
+        zw.start_file("dodge.log", opts)?;
+        zw.write_all(&log)?;
+    }
+    if let Some(png) = r.screenshot.and_then(png_bytes) {
+        zw.start_file("screenshot.png", opts)?;
+        zw.write_all(&png)?;
+    }
+    Ok(zw.finish()?.into_inner())
+}
+
+pub fn save_local(bytes: &[u8]) -> std::io::Result<PathBuf> {
+    let dir = paths::data(FEEDBACK_DIR);
+    std::fs::create_dir_all(&dir)?;
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let path = dir.join(format!("report-{}.zip", secs));
+    std::fs::write(&path, bytes)?;
+    Ok(path)
+}
+
+// 包里有日志和设置，和成绩一样只经 https 上传（本机测试服除外）
+pub fn post(url: &str, bytes: &[u8]) -> Result<(), String> {
+    if !http::secure(url) { return Err(tr("反馈只经 https:// 上传").to_string()); }
+    let (status, _) = http::send("POST", url, Some(("application/zip", bytes)))?;
+    if (200..300).contains(&status) { Ok(()) } else { Err(format!("HTTP {}", status)) }
+}
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::tr;
+
+// ===== 手感设置（震屏、顿帧、慢动作、粒子、拖影、镜头倾斜）=====
+// 所有“打击感”相关的开关集中在这里，由 effects 模块读取。
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Feel {
+    pub shake: f32,       // 震屏强度倍率
+    pub hit_stop: bool,   // 护盾挡下、炸弹引爆时顿帧（减少动态效果时关闭）
+    pub slow_mo: bool,    // 擦边时短暂慢动作
+    pub particles: f32,   // 粒子密度倍率
+    pub trail: usize,     // 全速时的拖影长度（采样数）
+    pub camera_lean: f32, // 镜头随移动方向偏移的最大像素
+}
+
+impl Default for Feel {
+    fn default() -> Self { FeelPreset::Default.feel() }
+}
+
+impl Feel {
+    // 减少动态效果时统一关掉剧烈的画面运动，粒子减半（见 motion.rs）
+    pub fn calmed(self, calm: bool) -> Self {
+        if !calm { return self; }
+        Self { shake: 0.0, hit_stop: false, slow_mo: false, camera_lean: 0.0, particles: self.particles.min(0.5), ..self }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum FeelPreset { Minimal, Default, Juicy }
+
+pub const FEEL_PRESETS: [FeelPreset; 3] = [FeelPreset::Minimal, FeelPreset::Default, FeelPreset::Juicy];
+
+impl FeelPreset {
+    pub fn label(self) -> &'static str {
+        tr(match self {
+            FeelPreset::Minimal => "克制",
+            FeelPreset::Default => "默认",
+            FeelPreset::Juicy => "爽快",
+        })
+    }
+    pub fn feel(self) -> Feel {
+        match self {
+            FeelPreset::Minimal => Feel { shake: 0.3, hit_stop: false, slow_mo: false, particles: 0.0, trail: 0, camera_lean: 0.0 },
+            FeelPreset::Default => Feel { shake: 1.0, hit_stop: true, slow_mo: false, particles: 1.0, trail: 6, camera_lean: 6.0 },
+            FeelPreset::Juicy => Feel { shake: 1.6, hit_stop: true, slow_mo: true, particles: 2.0, trail: 14, camera_lean: 14.0 },
+        }
+    }
+}
+
+// —— 面板条目：名称、显示值、左右微调 ——
+pub struct FeelItem {
+    pub name: &'static str,
+    pub show: fn(&Feel) -> String,
+    pub nudge: fn(&mut Feel, i32),
+}
+
+fn on(b: bool) -> String { (if b { tr("开") } else { tr("关") }).to_string() }
+
+pub const FEEL_ITEMS: [FeelItem; 6] = [
+    FeelItem { name: "震屏强度", show: |f| format!("{:.0}%", f.shake * 100.0), nudge: |f, d| f.shake = (f.shake + d as f32 * 0.1).clamp(0.0, 2.0) },
+    FeelItem { name: "顿帧", show: |f| on(f.hit_stop), nudge: |f, _| f.hit_stop = !f.hit_stop },
+    FeelItem { name: "擦边慢动作", show: |f| on(f.slow_mo), nudge: |f, _| f.slow_mo = !f.slow_mo },
+    FeelItem { name: "粒子密度", show: |f| format!("{:.0}%", f.particles * 100.0), nudge: |f, d| f.particles = (f.particles + d as f32 * 0.25).clamp(0.0, 2.0) },
+    FeelItem { name: "拖影长度", show: |f| f.trail.to_string(), nudge: |f, d| f.trail = (f.trail as i32 + d * 2).clamp(0, 20) as usize },
+    FeelItem { name: "镜头倾斜", show: |f| format!("{:.0}px", f.camera_lean), nudge: |f, d| f.camera_lean = (f.camera_lean + d as f32 * 2.0).clamp(0.0, 20.0) },
+];
@@ -0,0 +1,59 @@
+use macroquad::prelude::*;
+
+// ===== 飘字（加分提示等）=====
+// 在事件发生处生成一行文字，上升并在 LIFE 秒内淡出。
+// 和障碍一样用 live/dead 两个列表复用，字符串缓冲也一起复用；纯外观，按真实时间走。
+const LIFE: f32 = 0.8;
+const RISE: f32 = 60.0; // 上升速度（px/s）
+const LIVE_MAX: usize = 48;
+
+pub struct FloatingText {
+    pos: Vec2,
+    age: f32,
+    text: String,
+    color: Color,
+}
+
+pub struct FloatingTextPool {
+    live: Vec<FloatingText>,
+    dead: Vec<FloatingText>,
+}
+
+impl FloatingTextPool {
+    pub fn new() -> Self { Self { live: Vec::new(), dead: Vec::new() } }
+    pub fn clear(&mut self) { self.dead.append(&mut self.live); }
+
+    pub fn spawn(&mut self, at: Vec2, text: &str, color: Color) {
+        if self.live.len() >= LIVE_MAX { return; }
+        let mut t = self.dead.pop().unwrap_or(FloatingText { pos: at, age: 0.0, text: String::new(), color });
+        t.pos = at;
+        t.age = 0.0;
+        t.color = color;
+        t.text.clear();
+        t.text.push_str(text);
+        self.live.push(t);
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        let mut i = 0;
+        while i < self.live.len() {
+            let t = &mut self.live[i];
+            t.age += dt;
+            t.pos.y -= RISE * dt;
+            if t.age >= LIFE {
+                let dead = self.live.swap_remove(i);
+                self.dead.push(dead);
+            } else {
+                i += 1;
+            }
+        }
+    }
+
+    pub fn draw(&self, font: &Font, size: f32) {
+        for t in &self.live {
+            let a = (1.0 - t.age / LIFE).clamp(0.0, 1.0);
+            let w = measure_text(&t.text, Some(font), size as u16, 1.0).width;
+            draw_text_ex(&t.text, t.pos.x - w * 0.5, t.pos.y, TextParams { font: Some(font), font_size: size as u16, color: Color::new(t.color.r, t.color.g, t.color.b, a), ..Default::default() });
+        }
+    }
+}
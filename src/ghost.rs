@@ -0,0 +1,179 @@
+use std::path::PathBuf;
+
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::characters::CHARACTERS;
+use crate::i18n::{tr, trf};
+use crate::leaderboard::TopTable;
+use crate::replay::{Replay, ReplayState, REPLAY_DIR};
+use crate::{paths, storage, transfer, Game, PlayMode, PLAYER_H, PLAYER_Y};
+
+// ===== 最佳纪录的幽灵 =====
+// 录回放时顺带每 TRACK_EVERY 步记一次玩家中心（按场地宽度归一化），得到一条预先算好的位置轨迹，
+// 不用再并行跑一份模拟。某个规则组合刷新最高分时，这一局的回放另存为该组合的最佳回放；
+// 之后同一组合开局就读它的轨迹，在相同步数的位置画一个半透明的自己。
+// 别人的幽灵：排行榜上的成绩连同回放打成 .drg（或 DRG1: 分享串），赛跑时沿用对方的种子和规则。
+pub const TRACK_EVERY: u32 = 4;
+const BEST_DIR: &str = "best"; // replays/best/<最高分表的键>.json
+
+pub struct Ghost {
+    track: Vec<f32>,
+    label: Option<String>, // 别人的幽灵：头顶标出名字和分数
+}
+
+fn best_path(key: &str) -> PathBuf { paths::profile(REPLAY_DIR).join(BEST_DIR).join(format!("{}.json", key.replace('/', "_"))) }
+
+pub fn save_best(key: &str, replay: &Replay) {
+    let path = best_path(key);
+    if let Err(e) = storage::write(&path, serde_json::to_string(replay).unwrap()) {
+        crate::logfile::warn(&format!("最佳回放写入失败：{}", e));
+    }
+}
+
+// 只有会记最高分的局才有幽灵：普通模式、非挑战、未开自适应、不在看回放
+fn eligible(game: &Game) -> bool {
+    game.settings.ghost && game.play == PlayMode::Standard && game.challenge.is_none() && !game.director.enabled && !game.replay.watching()
+}
+
+// 开局时载入当前规则组合的最佳轨迹
+pub fn start(game: &mut Game) {
+    game.run_steps = 0;
+    game.ghost = if eligible(game) {
+        storage::read_to_string(best_path(&game.best_key())).ok()
+            .and_then(|s| serde_json::from_str::<Replay>(&s).ok())
+            .filter(|r| !r.track.is_empty())
+            .map(|r| Ghost { track: r.track, label: None })
+    } else {
+        None
+    };
+    if let Some(f) = &game.race {
+        game.ghost = Some(Ghost { track: f.replay.track.clone(), label: Some(format!("{} {}", f.name, f.score)) });
+    }
+}
+
+// 每个固定步：录制时记轨迹，然后步数加一
+pub fn tick(game: &mut Game) {
+    if let ReplayState::Recording(r) = &mut game.replay {
+        if game.run_steps.is_multiple_of(TRACK_EVERY) { r.track.push((game.player.x + game.player.w * 0.5) / game.field.x); }
+    }
+    game.run_steps += 1;
+}
+
+// 当前步数对应的中心（归一化）；轨迹走完（那一局在这之前结束）就没有了
+fn position(g: &Ghost, step: u32) -> Option<f32> {
+    let i = (step / TRACK_EVERY) as usize;
+    let a = *g.track.get(i)?;
+    let Some(&b) = g.track.get(i + 1) else { return Some(a) };
+    // 穿墙时两点跨过边缘，不插值
+    if (b - a).abs() > 0.5 { return Some(a); }
+    Some(a + (b - a) * (step % TRACK_EVERY) as f32 / TRACK_EVERY as f32)
+}
+
+pub fn draw(game: &Game, font: &Font) {
+    let Some(g) = &game.ghost else { return };
+    let Some(c) = position(g, game.run_steps) else { return };
+    let w = game.player.w;
+    let x = c * game.field.x - w * 0.5;
+    // 自己的幽灵偏白，别人的偏橙
+    let (r, gr, b) = if g.label.is_some() { (1.0, 0.7, 0.35) } else { (0.85, 0.9, 1.0) };
+    draw_rectangle(x, PLAYER_Y, w, PLAYER_H, Color::new(r, gr, b, 0.28));
+    draw_rectangle_lines(x, PLAYER_Y, w, PLAYER_H, 1.5, Color::new(r, gr, b, 0.55));
+    if let Some(label) = &g.label {
+        let size = (16.0 * game.settings.text_scale()) as u16;
+        let tw = measure_text(label, Some(font), size, 1.0).width;
+        draw_text_ex(label, x + w * 0.5 - tw * 0.5, PLAYER_Y - 8.0, TextParams { font: Some(font), font_size: size, color: Color::new(r, gr, b, 0.8), ..Default::default() });
+    }
+}
+
+// —— 可分享的幽灵文件 ——
+// 压缩包里一份 JSON：格式版本、程序版本、名字、分数和整局回放（输入 + 轨迹）。
+// 模拟逻辑随版本变化，播放前要求格式和程序版本都一致，种子和回放头一致。
+pub const GHOST_VERSION: u32 = 1;
+pub const GHOST_PREFIX: &str = "DRG1:";
+pub const GHOST_DIR: &str = "ghosts"; // 档案目录下，前十名各自的幽灵：ghosts/<id>.drg
+const ENTRY: &str = "ghost.json";
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GhostFile {
+    pub version: u32,
+    pub game: String,
+    pub name: String,
+    pub score: i32,
+    pub seed: u64,
+    pub replay: Replay,
+}
+
+impl GhostFile {
+    pub fn new(name: &str, score: i32, replay: &Replay) -> Self {
+        Self { version: GHOST_VERSION, game: env!("CARGO_PKG_VERSION").to_string(), name: name.to_string(), score, seed: replay.header.seed, replay: replay.clone() }
+    }
+
+    pub fn encode(&self) -> Result<Vec<u8>, String> { transfer::zip_one(ENTRY, &serde_json::to_string(self).unwrap()) }
+
+    // 解出来并校验，不合格的不给播
+    pub fn decode(bytes: &[u8]) -> Result<Self, String> {
+        let text = transfer::unzip_one(ENTRY, bytes).ok_or(tr("不是幽灵文件"))?;
+        let f: Self = serde_json::from_str(&text).map_err(|_| tr("幽灵文件损坏").to_string())?;
+        f.validate()?;
+        Ok(f)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.version != GHOST_VERSION { return Err(trf("幽灵格式版本 {} 不支持", &[&self.version])); }
+        if self.game != env!("CARGO_PKG_VERSION") { return Err(trf("幽灵来自版本 {}，和当前版本不通用", &[&self.game])); }
+        let h = &self.replay.header;
+        if self.seed != h.seed { return Err(tr("种子和回放不一致").to_string()); }
+        if let Some((kind, period)) = h.challenge {
+            if kind.seed(period) != h.seed { return Err(tr("挑战种子不符").to_string()); }
+        }
+        if self.replay.track.is_empty() || h.character >= CHARACTERS.len() { return Err(tr("幽灵文件损坏").to_string()); }
+        Ok(())
+    }
+
+    pub fn share_code(&self) -> Result<String, String> { Ok(format!("{}{}", GHOST_PREFIX, transfer::base64(&self.encode()?))) }
+    pub fn from_share(text: &str) -> Result<Self, String> {
+        let bytes = text.trim().strip_prefix(GHOST_PREFIX).and_then(transfer::unbase64).ok_or(tr("剪贴板里不是幽灵分享串"))?;
+        Self::decode(&bytes)
+    }
+}
+
+fn file_path(id: u64) -> PathBuf { paths::profile(GHOST_DIR).join(format!("{}.drg", id)) }
+
+pub fn store(id: u64, f: &GhostFile) {
+    match f.encode() {
+        Ok(bytes) => if let Err(e) = storage::write(file_path(id), bytes) { crate::logfile::warn(&format!("幽灵写入失败：{}", e)); },
+        Err(e) => crate::logfile::warn(&format!("幽灵打包失败：{}", e)),
+    }
+}
+
+pub fn load(id: u64) -> Result<GhostFile, String> {
+    GhostFile::decode(&storage::read(file_path(id)).map_err(|_| tr("这条成绩没有幽灵").to_string())?)
+}
+
+// 挤出前十名的成绩，幽灵文件一起删掉
+pub fn prune(top: &TopTable) {
+    for f in storage::list(paths::profile(GHOST_DIR)) {
+        let id = f.file_stem().and_then(|s| s.to_str()).and_then(|s| s.parse::<u64>().ok());
+        if id.is_some_and(|id| !top.entries().iter().any(|e| e.ghost == Some(id))) { let _ = storage::remove_file(&f); }
+    }
+}
+
+// 和别人的幽灵赛跑：沿用对方的种子和规则（自己的选择先收起来，回菜单时还原）
+pub fn race(game: &mut Game, f: GhostFile) {
+    let h = &f.replay.header;
+    game.use_fixed_rules();
+    game.difficulty = h.difficulty;
+    game.director.enabled = h.adaptive;
+    game.mutators = h.mutators;
+    game.mover.scheme = h.scheme;
+    game.mover.wrap = h.wrap;
+    game.shooting = h.shooting;
+    game.start_lives = h.start_lives;
+    game.loadout = h.loadout;
+    game.character = h.character;
+    game.forced_seed = Some(h.seed);
+    game.play = PlayMode::Standard;
+    game.race = Some(f);
+    game.reset_round();
+}
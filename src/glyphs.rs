@@ -0,0 +1,91 @@
+use macroquad::prelude::*;
+
+use crate::PowerUpKind;
+
+// ===== 道具图标 =====
+// 无障碍里打开“道具图标”后，每个道具中间再画一个简单的记号，分辨道具不用只靠颜色（色弱、高对比度下尤其有用）。
+// 全是基本图形拼的，不依赖贴图；颜色按道具底色的亮度取黑或白，深浅底上都看得清。
+const LINE: f32 = 2.0;
+
+// 底色亮就用深色记号，反之用白色
+fn ink(bg: Color) -> Color {
+    let luma = 0.299 * bg.r + 0.587 * bg.g + 0.114 * bg.b;
+    if luma > 0.55 { Color::new(0.05, 0.05, 0.08, 0.9) } else { Color::new(1.0, 1.0, 1.0, 0.95) }
+}
+
+// rect 是道具的范围，bg 是道具底色
+pub fn draw(kind: PowerUpKind, rect: Rect, bg: Color) {
+    let c = ink(bg);
+    let o = rect.center();
+    let r = rect.w.min(rect.h) * 0.28;
+    let tri = |a: Vec2, b: Vec2, d: Vec2| draw_triangle(o + a * r, o + b * r, o + d * r, c);
+    let line = |a: Vec2, b: Vec2| draw_line(o.x + a.x * r, o.y + a.y * r, o.x + b.x * r, o.y + b.y * r, LINE, c);
+    match kind {
+        // 盾牌：上平下尖
+        PowerUpKind::Shield => {
+            line(vec2(-0.8, -0.8), vec2(0.8, -0.8));
+            line(vec2(-0.8, -0.8), vec2(-0.8, 0.1));
+            line(vec2(0.8, -0.8), vec2(0.8, 0.1));
+            line(vec2(-0.8, 0.1), vec2(0.0, 1.0));
+            line(vec2(0.8, 0.1), vec2(0.0, 1.0));
+        }
+        // 沙漏
+        PowerUpKind::Slow => {
+            tri(vec2(-0.7, -0.9), vec2(0.7, -0.9), vec2(0.0, 0.0));
+            tri(vec2(-0.7, 0.9), vec2(0.7, 0.9), vec2(0.0, 0.0));
+        }
+        // 炸弹：圆球加引信
+        PowerUpKind::Bomb => {
+            draw_circle(o.x - r * 0.15, o.y + r * 0.2, r * 0.7, c);
+            line(vec2(0.3, -0.4), vec2(0.8, -0.9));
+        }
+        PowerUpKind::Heart => {
+            draw_circle(o.x - r * 0.4, o.y - r * 0.25, r * 0.45, c);
+            draw_circle(o.x + r * 0.4, o.y - r * 0.25, r * 0.45, c);
+            tri(vec2(-0.85, -0.1), vec2(0.85, -0.1), vec2(0.0, 0.9));
+        }
+        // 传送：两个圈
+        PowerUpKind::Teleport => {
+            draw_circle_lines(o.x - r * 0.45, o.y, r * 0.45, LINE, c);
+            draw_circle_lines(o.x + r * 0.45, o.y, r * 0.45, LINE, c);
+        }
+        // 弹药：三发竖条
+        PowerUpKind::Ammo => {
+            for x in [-0.6, 0.0, 0.6] { line(vec2(x, -0.8), vec2(x, 0.8)); }
+        }
+        // 倒带：两个朝左的三角
+        PowerUpKind::Rewind => {
+            tri(vec2(0.0, -0.7), vec2(0.0, 0.7), vec2(-0.9, 0.0));
+            tri(vec2(0.9, -0.7), vec2(0.9, 0.7), vec2(0.0, 0.0));
+        }
+        PowerUpKind::Multiplier => {
+            line(vec2(-0.7, -0.7), vec2(0.7, 0.7));
+            line(vec2(-0.7, 0.7), vec2(0.7, -0.7));
+        }
+        // 雪花：三条交叉线
+        PowerUpKind::Freeze => {
+            for a in [0.0f32, 60.0, 120.0] {
+                let d = Vec2::from_angle(a.to_radians()) * 0.9;
+                line(-d, d);
+            }
+        }
+        // 反向：左右双箭头
+        PowerUpKind::Reverse => {
+            line(vec2(-0.9, 0.0), vec2(0.9, 0.0));
+            tri(vec2(-0.9, 0.0), vec2(-0.4, -0.45), vec2(-0.4, 0.45));
+            tri(vec2(0.9, 0.0), vec2(0.4, -0.45), vec2(0.4, 0.45));
+        }
+        // 加速：两个朝下的尖角
+        PowerUpKind::Haste => {
+            for y in [-0.6, 0.1] {
+                line(vec2(-0.7, y), vec2(0.0, y + 0.6));
+                line(vec2(0.7, y), vec2(0.0, y + 0.6));
+            }
+        }
+        // 熄灯：实心圆外一圈
+        PowerUpKind::Blackout => {
+            draw_circle(o.x, o.y, r * 0.5, c);
+            draw_circle_lines(o.x, o.y, r * 0.95, LINE, c);
+        }
+    }
+}
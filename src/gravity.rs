@@ -0,0 +1,104 @@
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{Game, Obstacle};
+
+// ===== 引力井（黑洞）=====
+// 稀有危险：缓慢下落的黑洞，按平方反比把附近的障碍和玩家往自己身上拉，6 秒后消散。
+// 障碍受力改变速度，落进核心就被吞掉（不算躲过）；玩家只受横向分量，叠加在移动积分的 push 上。
+pub const WELL_CHANCE: f32 = 0.12; // 抽到该危险类型时真正生成黑洞的概率（否则换成普通方块）
+const WELL_LIFE: f32 = 6.0;
+const WELL_FALL: f32 = 45.0;
+const FADE_TIME: f32 = 0.6;        // 出现 / 消散的渐变
+pub const CORE_RADIUS: f32 = 16.0;     // 障碍中心进入此半径即被吞掉
+const REACH: f32 = 320.0;          // 引力作用半径
+const STRENGTH: f32 = 1.4e7;       // 加速度 = STRENGTH / 距离²
+const MIN_DIST: f32 = 50.0;        // 距离下限，避免贴近时加速度爆炸
+const PLAYER_SCALE: f32 = 0.6;     // 玩家受力打个折，保证能挣脱
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct Well {
+    x: f32,
+    y: f32,
+    age: f32,
+}
+
+impl Well {
+    fn center(&self) -> Vec2 { vec2(self.x, self.y) }
+    // 出现与消散时引力跟着渐变
+    fn strength(&self) -> f32 { (self.age / FADE_TIME).min((WELL_LIFE - self.age) / FADE_TIME).clamp(0.0, 1.0) }
+    fn accel(&self, p: Vec2) -> Vec2 {
+        let d = self.center() - p;
+        let dist = d.length();
+        if !(1e-3..=REACH).contains(&dist) { return Vec2::ZERO; }
+        d / dist * (STRENGTH / dist.max(MIN_DIST).powi(2)) * self.strength()
+    }
+}
+
+// 同一时间只会有一个黑洞；roll 为 0~1 随机数，返回是否生成
+pub fn try_spawn(game: &mut Game, roll: f32, x_roll: f32) -> bool {
+    if !game.wells.is_empty() || roll >= WELL_CHANCE { return false; }
+    let margin = REACH * 0.3;
+    let x = margin + x_roll * (game.field.x - margin * 2.0).max(0.0);
+    game.wells.push(Well { x, y: game.field.y * 0.12, age: 0.0 });
+    true
+}
+
+// 玩家受到的横向加速度
+pub fn push_on(game: &Game, r: Rect) -> f32 {
+    game.wells.iter().map(|w| w.accel(r.center()).x).sum::<f32>() * PLAYER_SCALE
+}
+
+// 按世界步长推进：下落、计时、拉扯障碍、吞掉落进核心的障碍
+pub fn update(game: &mut Game, dt: f32) {
+    for w in &mut game.wells {
+        w.y += WELL_FALL * dt;
+        w.age += dt;
+    }
+    game.wells.retain(|w| w.age < WELL_LIFE);
+    if game.wells.is_empty() { return; }
+    let wells = &game.wells;
+    for o in &mut game.obs.live {
+        let a: Vec2 = wells.iter().map(|w| w.accel(o.rect.center())).sum();
+        o.vx += a.x * dt;
+        o.vy += a.y * dt;
+    }
+    let swallowed = |o: &Obstacle| wells.iter().any(|w| w.center().distance(o.rect.center()) < CORE_RADIUS);
+    let mut i = 0;
+    while i < game.obs.live.len() {
+        if swallowed(&game.obs.live[i]) {
+            let dead = game.obs.live.swap_remove(i);
+            game.obs.dead.push(dead);
+        } else {
+            i += 1;
+        }
+    }
+}
+
+// 正在显现的黑洞的核心位置（高对比描边用）
+pub fn cores(game: &Game) -> impl Iterator<Item = Vec2> + '_ {
+    game.wells.iter().filter(|w| w.strength() > 0.0).map(Well::center)
+}
+
+// 暗色核心 + 旋转的吸积臂
+pub fn draw(game: &Game) {
+    let spin = if crate::motion::calm(game) { 0.0 } else { 1.0 };
+    for w in &game.wells {
+        let s = w.strength();
+        let c = w.center();
+        draw_circle_lines(c.x, c.y, REACH * 0.45, 1.0, Color::new(0.6, 0.4, 1.0, 0.12 * s));
+        for arm in 0..4 {
+            let base = arm as f32 * std::f32::consts::FRAC_PI_2 + w.age * 3.0 * spin;
+            let mut prev = c;
+            for k in 1..=12 {
+                let t = k as f32 / 12.0;
+                let ang = base + t * 2.4;
+                let p = c + vec2(ang.cos(), ang.sin()) * (CORE_RADIUS + t * 70.0);
+                draw_line(prev.x, prev.y, p.x, p.y, 2.0, Color::new(0.7, 0.5, 1.0, (1.0 - t) * 0.8 * s));
+                prev = p;
+            }
+        }
+        draw_circle(c.x, c.y, CORE_RADIUS + 4.0, Color::new(0.45, 0.25, 0.8, 0.6 * s));
+        draw_circle(c.x, c.y, CORE_RADIUS, Color::new(0.02, 0.0, 0.05, s));
+    }
+}
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{paths, replay, storage};
+
+// ===== 对局历史（history.jsonl，和存档放在一起）=====
+// 每局结束追加一行 JSON；超过 HISTORY_MAX 条时重写文件只留最近的，被挤掉的局连同回放文件一起删掉。
+pub const HISTORY_PATH: &str = "history.jsonl";
+const HISTORY_MAX: usize = 100;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub id: u64,       // 结束时间（毫秒），同时是回放文件名
+    pub day: u32,
+    pub mode: String,  // 模式 / 难度 / 挑战的显示名
+    pub seed: u64,
+    pub score: i32,
+    pub duration: f32,
+    pub cause: String, // 死因
+    #[serde(default)]
+    pub replay: bool,  // 有回放可看
+}
+
+pub fn load() -> Vec<HistoryEntry> {
+    let Ok(text) = storage::read_to_string(paths::profile(HISTORY_PATH)) else { return Vec::new() };
+    text.lines().filter_map(|l| serde_json::from_str(l).ok()).collect()
+}
+
+// 追加一条；list 是内存里的副本，一并更新
+pub fn append(list: &mut Vec<HistoryEntry>, entry: HistoryEntry) {
+    list.push(entry);
+    if list.len() > HISTORY_MAX {
+        let cut = list.len() - HISTORY_MAX;
+        for old in list.drain(..cut) {
+            if old.replay { replay::remove(old.id); }
+        }
+        let text: String = list.iter().map(|e| serde_json::to_string(e).unwrap() + "\n").collect();
+        let _ = storage::write(paths::profile(HISTORY_PATH), text);
+        return;
+    }
+    let line = serde_json::to_string(list.last().unwrap()).unwrap();
+    let _ = storage::append(paths::profile(HISTORY_PATH), line + "\n");
+}
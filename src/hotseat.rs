@@ -0,0 +1,170 @@
+use macroquad::prelude::*;
+
+use crate::i18n::{tr, trf};
+use crate::rivals::NAME_MAX;
+use crate::trace::{get_char_pressed, is_key_pressed};
+use crate::{draw_text_center, Game, GameMode, PlayMode};
+
+// ===== 轮流赛（一台电脑传着玩）=====
+// 先报名 2~8 人，之后按报名顺序每人打一局：同一个种子、固定规则（同挑战），所以大家躲的是同一串障碍。
+// 每局结束把键盘交给下一位，中间是实时排名；全部打完后最终排名，第一名夺冠。
+// 分数一样时活得久的在前。轮流赛的成绩不进本档案的任何记录（打的不一定是档案主人）。
+pub const MIN_PLAYERS: usize = 2;
+pub const MAX_PLAYERS: usize = 8;
+
+pub struct Entrant {
+    pub name: String,
+    pub result: Option<(i32, f32)>, // (分数, 存活时间)，还没上场为 None
+}
+
+#[derive(Default)]
+pub struct Hotseat {
+    entrants: Vec<Entrant>,
+    typing: String,
+    seed: Option<u64>, // 开赛后才有；报名阶段为 None
+    turn: usize,       // 下一个上场的（等于人数时比赛结束）
+    msg: Option<String>,
+}
+
+impl Hotseat {
+    fn finished(&self) -> bool { self.seed.is_some() && self.turn >= self.entrants.len() }
+
+    // 排名：打完的按分数、再按存活时间，没打的排在后面
+    fn standings(&self) -> Vec<&Entrant> {
+        let mut v: Vec<&Entrant> = self.entrants.iter().collect();
+        v.sort_by(|a, b| {
+            let key = |e: &Entrant| e.result.map_or((i32::MIN, 0.0), |r| r);
+            let (ka, kb) = (key(a), key(b));
+            kb.0.cmp(&ka.0).then(kb.1.total_cmp(&ka.1))
+        });
+        v
+    }
+}
+
+// 菜单 [U]
+pub fn enter(game: &mut Game) {
+    while get_char_pressed().is_some() {} // 丢弃积压的字符
+    game.hotseat = Hotseat::default();
+    game.mode = GameMode::Hotseat;
+}
+
+fn start_turn(game: &mut Game) {
+    let Some(seed) = game.hotseat.seed else { return };
+    game.use_fixed_rules();
+    game.play = PlayMode::Hotseat;
+    game.forced_seed = Some(seed);
+    game.reset_round();
+}
+
+// 一局结束（或暂停时放弃）：记下成绩，回到排名界面
+pub fn finish_turn(game: &mut Game) {
+    let h = &mut game.hotseat;
+    if let Some(e) = h.entrants.get_mut(h.turn) { e.result = Some((game.score, game.elapsed)); }
+    h.turn += 1;
+    game.mode = GameMode::Hotseat;
+}
+
+// 结束画面：[SPACE] / [ENTER] 交出键盘
+pub fn game_over_input(game: &mut Game) {
+    if is_key_pressed(KeyCode::Space) || is_key_pressed(KeyCode::Enter) { finish_turn(game); }
+}
+
+fn quit(game: &mut Game) {
+    game.hotseat = Hotseat::default();
+    game.leave_run(); // 还原玩家自己的规则
+    game.mode = GameMode::Menu;
+}
+
+// 逐帧：报名 / 排名界面
+pub fn update(game: &mut Game) {
+    let h = &mut game.hotseat;
+    if h.seed.is_none() {
+        while let Some(c) = get_char_pressed() {
+            if !c.is_control() && h.typing.chars().count() < NAME_MAX { h.typing.push(c); }
+        }
+        if is_key_pressed(KeyCode::Backspace) && h.typing.pop().is_none() { h.entrants.pop(); }
+        if is_key_pressed(KeyCode::Escape) { return quit(game); }
+        if !is_key_pressed(KeyCode::Enter) { return; }
+        let name = h.typing.trim().to_string();
+        h.msg = None;
+        if name.is_empty() {
+            // 空行回车：开赛
+            if h.entrants.len() < MIN_PLAYERS { h.msg = Some(trf("至少 {} 人", &[&MIN_PLAYERS])); return; }
+            h.seed = Some(crate::trace::now_ms());
+            h.turn = 0;
+        } else if h.entrants.len() >= MAX_PLAYERS {
+            h.msg = Some(trf("最多 {} 人", &[&MAX_PLAYERS]));
+        } else if h.entrants.iter().any(|e| e.name == name) {
+            h.msg = Some(tr("名字重复了").to_string());
+        } else {
+            h.entrants.push(Entrant { name, result: None });
+            h.typing.clear();
+        }
+        return;
+    }
+    if is_key_pressed(KeyCode::Escape) { return quit(game); }
+    if !h.finished() {
+        if is_key_pressed(KeyCode::Space) || is_key_pressed(KeyCode::Enter) { start_turn(game); }
+        return;
+    }
+    // 再来一场：同一批人、换个种子
+    if is_key_pressed(KeyCode::R) {
+        for e in &mut h.entrants { e.result = None; }
+        h.seed = Some(crate::trace::now_ms());
+        h.turn = 0;
+    }
+}
+
+fn draw_table(font: &Font, game: &Game, y0: f32, ts: f32) {
+    let h = &game.hotseat;
+    for (i, e) in h.standings().iter().enumerate() {
+        let (row, color) = match e.result {
+            Some((score, t)) => {
+                let color = if i == 0 && h.finished() { GOLD } else { WHITE };
+                (trf("{}. {:<12}  {:>6} 分   {:>5.1}s", &[&(i + 1), &e.name, &score, &t]), color)
+            }
+            None => (trf("{}. {:<12}  未上场", &[&(i + 1), &e.name]), GRAY),
+        };
+        draw_text_center(font, game.field.x, &row, y0 + i as f32 * 30.0 * ts, 22.0 * ts, color);
+    }
+}
+
+pub fn draw(font: &Font, game: &Game) {
+    let ts = game.settings.text_scale();
+    let w = game.field.x;
+    let h = &game.hotseat;
+    draw_text_center(font, w, tr("轮流赛"), 80.0, 40.0 * ts, SKYBLUE);
+    if h.seed.is_none() {
+        draw_text_center(font, w, &trf("报名 {}~{} 人，按顺序每人一局，大家打同一个种子", &[&MIN_PLAYERS, &MAX_PLAYERS]), 120.0, 20.0 * ts, LIGHTGRAY);
+        for (i, e) in h.entrants.iter().enumerate() {
+            draw_text_center(font, w, &format!("{}. {}", i + 1, e.name), 170.0 + i as f32 * 28.0 * ts, 22.0 * ts, WHITE);
+        }
+        let y = 170.0 + h.entrants.len() as f32 * 28.0 * ts + 20.0;
+        if h.entrants.len() < MAX_PLAYERS {
+            draw_text_center(font, w, &trf("名字：{}_", &[&h.typing]), y, 24.0 * ts, GOLD);
+        }
+        if let Some(m) = &h.msg { draw_text_center(font, w, m, y + 34.0 * ts, 20.0 * ts, ORANGE); }
+        draw_text_center(font, w, tr("[ENTER] 加入   空行 [ENTER] 开赛   [BACKSPACE] 删除   [ESC] 返回"), game.field.y - 40.0, 20.0 * ts, ORANGE);
+        return;
+    }
+    draw_table(font, game, 170.0, ts);
+    let y = 170.0 + h.entrants.len() as f32 * 30.0 * ts + 30.0;
+    if let Some(next) = h.entrants.get(h.turn) {
+        draw_text_center(font, w, &trf("下一位：{}（第 {}/{} 局）", &[&next.name, &(h.turn + 1), &h.entrants.len()]), y, 28.0 * ts, LIME);
+        draw_text_center(font, w, tr("把键盘交给下一位，按 [SPACE] 开始   [ESC] 结束比赛"), game.field.y - 40.0, 20.0 * ts, ORANGE);
+    } else {
+        let champ = h.standings()[0];
+        draw_text_center(font, w, &trf("🏆 冠军：{}", &[&champ.name]), y, 34.0 * ts, GOLD);
+        draw_text_center(font, w, tr("[R] 同一批人再来一场   [ESC] 回菜单"), game.field.y - 40.0, 20.0 * ts, ORANGE);
+    }
+}
+
+// 结束画面上的这一局成绩
+pub fn draw_over(font: &Font, game: &Game) {
+    let ts = game.settings.text_scale();
+    let h = &game.hotseat;
+    let Some(e) = h.entrants.get(h.turn) else { return };
+    draw_text_center(font, game.field.x, &trf("{}：{} 分", &[&e.name, &game.score]), 300.0, 30.0 * ts, WHITE);
+    let next = if h.turn + 1 < h.entrants.len() { tr("[SPACE] 交给下一位") } else { tr("[SPACE] 看最终排名") };
+    draw_text_center(font, game.field.x, next, 370.0, 24.0 * ts, ORANGE);
+}
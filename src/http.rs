@@ -0,0 +1,112 @@
+use std::io::{ErrorKind, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+use crate::i18n::tr;
+
+// ===== 极简 HTTP 客户端 =====
+// 云同步、在线排行榜和反馈上报共用：短超时、一次一个连接。
+// https 走 rustls 和内置的根证书（tls 特性，默认打开）；没编进 TLS 的版本只认 http://。
+// 地址里带 user:pass@ 的 Basic 认证只在 https 上发，明文 http 直接拒绝，免得密码裸奔。
+
+// 正文是 JSON 的请求
+pub fn request(method: &str, url: &str, body: Option<&[u8]>) -> Result<(u16, Vec<u8>), String> {
+    send(method, url, body.map(|b| ("application/json", b)))
+}
+
+// 正文带上自己的 Content-Type；返回状态码和正文，支持 Content-Length 和 chunked
+pub fn send(method: &str, url: &str, body: Option<(&str, &[u8])>) -> Result<(u16, Vec<u8>), String> {
+    let (tls, rest) = match (url.strip_prefix("https://"), url.strip_prefix("http://")) {
+        (Some(rest), _) => (true, rest),
+        (None, Some(rest)) => (false, rest),
+        _ => return Err(tr("仅支持 http:// 和 https:// 地址").to_string()),
+    };
+    let (authority, path) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, "/"),
+    };
+    let (auth, host_port) = match authority.rsplit_once('@') {
+        Some((cred, host)) => (Some(crate::transfer::base64(cred.as_bytes())), host),
+        None => (None, authority),
+    };
+    if auth.is_some() && !tls { return Err(tr("带用户名密码的地址必须用 https://").to_string()); }
+    let addr_str = if host_port.contains(':') { host_port.to_string() } else { format!("{}:{}", host_port, if tls { 443 } else { 80 }) };
+    let addr = addr_str.to_socket_addrs().map_err(|e| e.to_string())?.next().ok_or(tr("无法解析地址"))?;
+    let timeout = Duration::from_secs(5);
+    let stream = TcpStream::connect_timeout(&addr, timeout).map_err(|e| e.to_string())?;
+    let _ = stream.set_read_timeout(Some(timeout));
+    let _ = stream.set_write_timeout(Some(timeout));
+    let mut head = format!("{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n", method, path, host_port);
+    if let Some(a) = auth { head.push_str(&format!("Authorization: Basic {}\r\n", a)); }
+    if let Some((kind, b)) = body { head.push_str(&format!("Content-Type: {}\r\nContent-Length: {}\r\n", kind, b.len())); }
+    head.push_str("\r\n");
+    let resp = if tls {
+        let host = host_port.rsplit_once(':').map_or(host_port, |(h, _)| h);
+        exchange(tls_stream(host, stream)?, &head, body.map(|(_, b)| b))?
+    } else {
+        exchange(stream, &head, body.map(|(_, b)| b))?
+    };
+    let split = resp.windows(4).position(|w| w == b"\r\n\r\n").ok_or(tr("响应不完整"))?;
+    let header = String::from_utf8_lossy(&resp[..split]).to_ascii_lowercase();
+    let status = header.split_whitespace().nth(1).and_then(|s| s.parse().ok()).ok_or(tr("响应不完整"))?;
+    let raw = &resp[split + 4..];
+    let body = if header.contains("transfer-encoding: chunked") { dechunk(raw) } else { raw.to_vec() };
+    Ok((status, body))
+}
+
+// 写请求、读到对方关连接为止
+fn exchange(mut stream: impl Read + Write, head: &str, body: Option<&[u8]>) -> Result<Vec<u8>, String> {
+    stream.write_all(head.as_bytes()).map_err(|e| e.to_string())?;
+    if let Some(b) = body { stream.write_all(b).map_err(|e| e.to_string())?; }
+    stream.flush().map_err(|e| e.to_string())?;
+    let mut resp = Vec::new();
+    match stream.read_to_end(&mut resp) {
+        Ok(_) => {}
+        // 不少服务器不发 close_notify 就断开；已经收到东西就算读完
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof && !resp.is_empty() => {}
+        Err(e) => return Err(e.to_string()),
+    }
+    Ok(resp)
+}
+
+#[cfg(feature = "tls")]
+fn tls_stream(host: &str, stream: TcpStream) -> Result<rustls::StreamOwned<rustls::ClientConnection, TcpStream>, String> {
+    use std::sync::{Arc, OnceLock};
+    static CONFIG: OnceLock<Arc<rustls::ClientConfig>> = OnceLock::new();
+    let config = CONFIG.get_or_init(|| {
+        let roots = rustls::RootCertStore { roots: webpki_roots::TLS_SERVER_ROOTS.to_vec() };
+        let config = rustls::ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .expect("ring 支持默认的 TLS 版本")
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        Arc::new(config)
+    });
+    let name = rustls::pki_types::ServerName::try_from(host.to_string()).map_err(|e| e.to_string())?;
+    let conn = rustls::ClientConnection::new(config.clone(), name).map_err(|e| e.to_string())?;
+    Ok(rustls::StreamOwned::new(conn, stream))
+}
+
+#[cfg(not(feature = "tls"))]
+fn tls_stream(_: &str, _: TcpStream) -> Result<TcpStream, String> {
+    Err(tr("这个版本没有编进 TLS，不支持 https:// 地址").to_string())
+}
+
+// 成绩、回放、反馈包这些只往 https 发；本机 localhost / 127.0.0.1 上的测试服除外
+pub fn secure(url: &str) -> bool {
+    let local = ["http://localhost", "http://127.0.0.1"].iter().any(|p| url.strip_prefix(p).is_some_and(|r| r.is_empty() || r.starts_with([':', '/'])));
+    url.starts_with("https://") || local
+}
+
+fn dechunk(mut raw: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    while let Some(eol) = raw.windows(2).position(|w| w == b"\r\n") {
+        let size = usize::from_str_radix(String::from_utf8_lossy(&raw[..eol]).split(';').next().unwrap_or("").trim(), 16).unwrap_or(0);
+        if size == 0 { break; }
+        let start = eol + 2;
+        let end = (start + size).min(raw.len());
+        out.extend_from_slice(&raw[start..end]);
+        raw = &raw[(end + 2).min(raw.len())..];
+    }
+    out
+}
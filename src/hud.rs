@@ -0,0 +1,67 @@
+use macroquad::prelude::*;
+
+use crate::Game;
+
+// ===== HUD 布局 =====
+// 局内 HUD 不直接写像素坐标，而是“锚点 + 偏移”：偏移按 800×600 的设计尺寸写，乘上缩放倍率后从锚点朝场地内侧量。
+// 缩放 = 窗口相对设计尺寸的大小（宽高取小的，窄窗口按宽算）× 设置里的 HUD 缩放 × 大字号，字号和间距一起放大。
+// 这样 4K 下 HUD 不会缩成一小撮，窄窗口里左右两组也不会撞到一起。
+const DESIGN: Vec2 = Vec2::new(800.0, 600.0);
+pub const BAR_H: f32 = 46.0; // 顶栏高度
+pub const UI_SCALE_MIN: f32 = 0.75;
+pub const UI_SCALE_MAX: f32 = 1.5;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Anchor { TopLeft, Top, TopRight, BottomLeft, Bottom, BottomRight }
+
+pub struct Hud {
+    pub s: f32, // 缩放倍率
+    field: Vec2,
+}
+
+impl Hud {
+    pub fn new(game: &Game) -> Self {
+        let auto = (game.field.x / DESIGN.x).min(game.field.y / DESIGN.y).clamp(0.6, 4.0);
+        let user = game.settings.ui_scale.clamp(UI_SCALE_MIN, UI_SCALE_MAX);
+        Self { s: auto * user * game.settings.text_scale(), field: game.field }
+    }
+
+    pub fn px(&self, v: f32) -> f32 { v * self.s }
+
+    // 同样的锚点，整体再缩放 k 倍（精简 HUD 的小图标）
+    pub fn scaled(&self, k: f32) -> Self { Self { s: self.s * k, field: self.field } }
+
+    // 锚点 a 往场地内侧偏 off；居中锚点的 x 向右为正
+    pub fn at(&self, a: Anchor, off: Vec2) -> Vec2 {
+        let (dx, dy, f) = (off.x * self.s, off.y * self.s, self.field);
+        match a {
+            Anchor::TopLeft => vec2(dx, dy),
+            Anchor::Top => vec2(f.x * 0.5 + dx, dy),
+            Anchor::TopRight => vec2(f.x - dx, dy),
+            Anchor::BottomLeft => vec2(dx, f.y - dy),
+            Anchor::Bottom => vec2(f.x * 0.5 + dx, f.y - dy),
+            Anchor::BottomRight => vec2(f.x - dx, f.y - dy),
+        }
+    }
+
+    // 文字（off.y 是基线）：靠右的锚点右对齐，居中的锚点居中
+    pub fn text(&self, font: &Font, s: &str, a: Anchor, off: Vec2, size: f32, color: Color) {
+        let size = self.px(size) as u16;
+        let p = self.at(a, off);
+        let w = measure_text(s, Some(font), size, 1.0).width;
+        let x = match a {
+            Anchor::TopRight | Anchor::BottomRight => p.x - w,
+            Anchor::Top | Anchor::Bottom => p.x - w * 0.5,
+            Anchor::TopLeft | Anchor::BottomLeft => p.x,
+        };
+        draw_text_ex(s, x, p.y, TextParams { font: Some(font), font_size: size, color, ..Default::default() });
+    }
+
+    // 细条：左上角在锚点往内 off 处（靠右的锚点量的是条的左端），大小 size，填 fill 比例
+    pub fn meter(&self, a: Anchor, off: Vec2, size: Vec2, fill: f32, color: Color) {
+        let p = self.at(a, off);
+        let (w, h) = (self.px(size.x), self.px(size.y));
+        draw_rectangle(p.x, p.y, w, h, Color::new(1.0, 1.0, 1.0, 0.15));
+        draw_rectangle(p.x, p.y, w * fill.clamp(0.0, 1.0), h, color);
+    }
+}
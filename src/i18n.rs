@@ -0,0 +1,113 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt::{Display, Write};
+
+use crate::settings::Lang;
+
+// ===== 界面文字多语言 =====
+// 源码里直接写简体中文，原文本身就是查表的键：tr("开始游戏")。简体中文是原文，不用表；
+// 其他语言各一张扁平的 { 原文: 译文 } 表，放在 assets/lang/<代码>.json。表里没有的键原样显示原文，漏翻的地方一眼就能看出来。
+// 少数原来就写成英文的 HUD 标签（SCORE、COMBO 之类）也当键，别的语言需要时可以翻。
+// 带数字的句子用 trf：原文里的 {} 依次换成参数，译文里可以写 {0} {1} 调整次序。
+// 当前语言是全局的（界面只在主线程画），设置里一换，下一帧起全部文字跟着换，不用重启。
+// 数据文件里的名字（成就、外观、商品……）显示时也过一遍 tr，想翻的话把原文加进表里就行。
+pub const LANG_DIR: &str = "assets/lang";
+
+type Bundle = HashMap<String, String>;
+
+thread_local! {
+    // 当前语言的表；None 时显示原文
+    static ACTIVE: Cell<Option<&'static Bundle>> = const { Cell::new(None) };
+    // 读过的表留着，来回切换不重复读盘
+    static LOADED: RefCell<Vec<(Lang, &'static Bundle)>> = const { RefCell::new(Vec::new()) };
+}
+
+fn load(lang: Lang) -> &'static Bundle {
+    let path = format!("{}/{}.json", LANG_DIR, lang.code());
+    let bundle = match crate::storage::read_asset_string(&path) {
+        Ok(text) => serde_json::from_str(&text).unwrap_or_else(|e| {
+            crate::logfile::warn(&format!("语言文件 {} 解析失败：{}", path, e));
+            Bundle::new()
+        }),
+        Err(_) => {
+            crate::logfile::warn(&format!("找不到语言文件 {}，显示原文", path));
+            Bundle::new()
+        }
+    };
+    Box::leak(Box::new(bundle))
+}
+
+// 启动、换档案和设置里改语言时调用
+pub fn set(lang: Lang) {
+    if lang == Lang::ZhCn { return ACTIVE.set(None); }
+    let bundle = LOADED.with_borrow_mut(|loaded| {
+        if let Some((_, b)) = loaded.iter().find(|(l, _)| *l == lang) { return *b; }
+        let b = load(lang);
+        loaded.push((lang, b));
+        b
+    });
+    ACTIVE.set(Some(bundle));
+}
+
+pub fn tr(key: &str) -> &str {
+    match ACTIVE.get().and_then(|b| b.get(key)) {
+        Some(t) => t,
+        None => key,
+    }
+}
+
+// 查表后像 format! 一样把参数填进去：{} 依次取参数，{N} 取第 N 个；冒号后面支持 + 号、< > 对齐、宽度和 .小数位，
+// 所以原来的 format!("躲过 {:>4}", n) 直接改成 trf("躲过 {:>4}", &[&n])，键还是原来那串
+pub fn trf(key: &str, args: &[&dyn Display]) -> String {
+    let mut out = String::new();
+    let (mut rest, mut next) = (tr(key), 0);
+    while let Some(i) = rest.find('{') {
+        out.push_str(&rest[..i]);
+        let Some(j) = rest[i..].find('}').map(|j| i + j) else {
+            out.push_str(&rest[i..]);
+            return out;
+        };
+        let (at, spec) = rest[i + 1..j].split_once(':').unwrap_or((&rest[i + 1..j], ""));
+        let at = if at.is_empty() { next += 1; Some(next - 1) } else { at.parse().ok() };
+        match (at.and_then(|k| args.get(k)), Spec::parse(spec)) {
+            (Some(a), Some(s)) => s.write(&mut out, *a),
+            _ => out.push_str(&rest[i..=j]),
+        }
+        rest = &rest[j + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+// {:+>6.1} 这类格式说明里支持的部分；宽度前写 0 就用 0 补齐（{:02} 的时钟）
+struct Spec { plus: bool, left: bool, zero: bool, width: usize, precision: Option<usize> }
+
+impl Spec {
+    fn parse(s: &str) -> Option<Self> {
+        let (plus, s) = s.strip_prefix('+').map_or((false, s), |r| (true, r));
+        let (left, s) = match s.as_bytes().first() {
+            Some(b'<') => (true, &s[1..]),
+            Some(b'>') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        let (width, precision) = s.split_once('.').map_or((s, None), |(w, p)| (w, Some(p)));
+        Some(Self {
+            plus,
+            left,
+            zero: width.len() > 1 && width.starts_with('0'),
+            width: if width.is_empty() { 0 } else { width.parse().ok()? },
+            precision: match precision { Some(p) => Some(p.parse().ok()?), None => None },
+        })
+    }
+
+    fn write(&self, out: &mut String, a: &dyn Display) {
+        let s = match (self.plus, self.precision) {
+            (true, Some(p)) => format!("{:+.*}", p, a),
+            (true, None) => format!("{:+}", a),
+            (false, Some(p)) => format!("{:.*}", p, a),
+            (false, None) => a.to_string(),
+        };
+        let pad = (if self.zero { "0" } else { " " }).repeat(self.width.saturating_sub(s.chars().count()));
+        let _ = if self.left { write!(out, "{}{}", s, pad) } else { write!(out, "{}{}", pad, s) };
+    }
+}
@@ -0,0 +1,41 @@
+use serde_json::Value;
+
+// ===== 存档校验 =====
+// 写盘时对整份存档（含前十名和挑战排行）算一个带密钥的校验值放进 "sig" 字段，读档时重算比对。
+// 对不上的存档照常可玩，只是标记为改动过：自己的成绩不再进挑战排行、也不能生成分享码。
+// 密钥就在程序里，挡得住手改文件，挡不住有心人；要的只是让“随手改分”不值得。
+// 校验对象是去掉 sig 后的紧凑 JSON（serde_json 的对象键有序，写读两边得到同一串）。
+const KEY: &[u8] = b"dodge-rush/save-sig/1";
+pub const SIGNED_SINCE: u32 = 1; // 从这个存档版本起必须带签名（v1、v2 同一批发布，没有不带签名的 v1 文件）
+// v0 之后才有的键：没签名的文件里出现它们，就是把新存档改回旧版本号绕过校验
+const POST_V0_KEYS: [&str; 4] = ["records", "top", "rivals", "tampered"];
+
+// FNV-1a 64，前后各混入一次密钥
+fn keyed_hash(data: &[u8]) -> u64 {
+    let mut h = 0xcbf2_9ce4_8422_2325u64;
+    for b in KEY.iter().chain(data).chain(KEY) {
+        h ^= *b as u64;
+        h = h.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    h
+}
+
+fn sign(v: &Value) -> String { format!("{:016x}", keyed_hash(v.to_string().as_bytes())) }
+
+pub fn seal(v: &mut Value) {
+    let Some(obj) = v.as_object_mut() else { return };
+    obj.remove("sig");
+    let sig = sign(v);
+    if let Some(obj) = v.as_object_mut() { obj.insert("sig".into(), Value::String(sig)); }
+}
+
+// 取下 sig 并校验；没有签名的老存档（SIGNED_SINCE 之前的版本、且不含新键）视为完好
+pub fn check(v: &mut Value) -> bool {
+    let Some(obj) = v.as_object_mut() else { return false };
+    let version = obj.get("version").and_then(Value::as_u64).unwrap_or(0) as u32;
+    match obj.remove("sig") {
+        Some(Value::String(sig)) => sig == sign(v),
+        Some(_) => false,
+        None => version < SIGNED_SINCE && !POST_V0_KEYS.iter().any(|k| obj.contains_key(*k)),
+    }
+}
@@ -0,0 +1,94 @@
+use macroquad::prelude::*;
+
+use crate::i18n::{tr, trf};
+use crate::settings::{key_name, Action, Keys, ACTIONS, KEY_NAMES};
+use crate::trace::is_key_pressed;
+use crate::{draw_text_center, Game, GameMode};
+
+// ===== 改键 =====
+// 设置“操作”页里进来（那里只改主键）。每个动作一个主键、一个备用键：[↑/↓] 选动作，[←/→] 选主键 / 备用，[ENTER] 之后按下的下一个键绑上去。
+// 已经绑在别的动作上的键不收，提示冲突；捕获时 [ESC] 取消。主键不能清空，备用键用 [BACKSPACE] 清掉。
+// 只认 KEY_NAMES 里有名字的键（settings.json 里写的是键名），改完当场写盘。
+
+#[derive(Default)]
+pub struct Rebind {
+    cursor: usize,
+    slot: usize, // 0 主键，1 备用
+    capturing: bool,
+    msg: Option<String>,
+}
+
+pub fn enter(game: &mut Game) {
+    game.rebind = Rebind::default();
+    game.mode = GameMode::Keybinds;
+}
+
+// 捕获时本帧按下的键（只认有名字的）
+pub fn captured() -> Option<KeyCode> {
+    KEY_NAMES.iter().map(|(_, k)| *k).find(|k| is_key_pressed(*k))
+}
+
+// 把 k 绑到 action 的第 slot 个键上；和别的动作冲突时不改，返回提示
+pub fn bind(keys: &mut Keys, action: Action, slot: usize, k: KeyCode) -> Result<(), String> {
+    if let Some(other) = keys.owner(k).filter(|a| *a != action) {
+        return Err(trf("{} 已经绑在「{}」上，先给那边换个键", &[&key_name(k), &other.label()]));
+    }
+    let b = keys.get_mut(action);
+    b.0[slot] = Some(k);
+    if b.0[0] == b.0[1] { b.0[1] = None; } // 主键和备用是同一个键
+    Ok(())
+}
+
+pub fn update(game: &mut Game) {
+    let r = &mut game.rebind;
+    if r.capturing {
+        if is_key_pressed(KeyCode::Escape) { r.capturing = false; return; }
+        let Some(k) = captured() else { return };
+        r.capturing = false;
+        r.msg = bind(&mut game.settings.keys, ACTIONS[r.cursor], r.slot, k).err();
+        return game.settings.save();
+    }
+    let n = ACTIONS.len();
+    if is_key_pressed(KeyCode::Up) { r.cursor = (r.cursor + n - 1) % n; }
+    if is_key_pressed(KeyCode::Down) { r.cursor = (r.cursor + 1) % n; }
+    if is_key_pressed(KeyCode::Left) || is_key_pressed(KeyCode::Right) { r.slot = 1 - r.slot; }
+    if is_key_pressed(KeyCode::Enter) {
+        r.capturing = true;
+        r.msg = None;
+    }
+    if is_key_pressed(KeyCode::Backspace) {
+        if r.slot == 0 {
+            r.msg = Some(tr("主键不能清空").to_string());
+        } else {
+            game.settings.keys.get_mut(ACTIONS[r.cursor]).0[1] = None;
+            game.settings.save();
+        }
+    }
+    if is_key_pressed(KeyCode::Delete) {
+        game.settings.keys = Keys::default();
+        game.settings.save();
+        game.rebind.msg = Some(tr("已恢复默认键位").to_string());
+    }
+    if is_key_pressed(KeyCode::Escape) { game.mode = GameMode::Settings; }
+}
+
+pub fn draw(font: &Font, game: &Game) {
+    let ts = game.settings.text_scale();
+    let w = game.field.x;
+    let r = &game.rebind;
+    draw_text_center(font, w, tr("改键"), 80.0, 40.0 * ts, SKYBLUE);
+    draw_text_center(font, w, tr("动作          主键        备用"), 130.0, 20.0 * ts, GRAY);
+    for (i, a) in ACTIONS.iter().enumerate() {
+        let b = game.settings.keys.get(*a);
+        let slot = |s: usize| {
+            let name = b.0[s].map_or("—", key_name);
+            if i != r.cursor || s != r.slot { format!(" {:<8} ", name) } else if r.capturing { tr("[ 按键… ]").to_string() } else { format!("[{:<8}]", name) }
+        };
+        let row = format!("{:<8}  {}  {}", a.label(), slot(0), slot(1));
+        draw_text_center(font, w, &row, 170.0 + i as f32 * 32.0 * ts, 24.0 * ts, if i == r.cursor { GOLD } else { WHITE });
+    }
+    let y = 170.0 + ACTIONS.len() as f32 * 32.0 * ts + 20.0;
+    if let Some(m) = &r.msg { draw_text_center(font, w, m, y, 20.0 * ts, ORANGE); }
+    let hint = if r.capturing { tr("按下新的按键   [ESC] 取消") } else { tr("[↑/↓] 选择   [←/→] 主键 / 备用   [ENTER] 改键   [BACKSPACE] 清除备用   [DEL] 恢复默认   [ESC] 返回") };
+    draw_text_center(font, w, hint, game.field.y - 40.0, 18.0 * ts, ORANGE);
+}
@@ -0,0 +1,55 @@
+use macroquad::prelude::*;
+
+use crate::trace::is_key_pressed;
+use crate::shake::Offset;
+use crate::{field_camera, replay, trail_boost, trail_speed, view_size, Game, GameMode, PlayMode};
+
+// ===== 死亡镜头 =====
+// 撞上之后先不进结算：1.2 秒里镜头缓缓推向撞击点，碎片和飘字按 0.2 倍速慢放，然后才是游戏结束画面。
+// 只是镜头：结算（纪录、存档、统计）在进镜头之前已经做完，模拟状态也不再动，续关和回放校验不受影响。
+// 减少动态效果、联机对战（对面在等结果）和无窗口校验时直接跳过；[SPACE] / [ENTER] / [ESC] 也能跳过。
+pub const DURATION: f32 = 1.2;
+const TIME_SCALE: f32 = 0.2;
+const ZOOM: f32 = 1.8; // 推到最近时的放大倍数
+
+#[derive(Clone, Copy, Default)]
+pub struct Killcam {
+    at: Vec2, // 撞击点（场地坐标）
+    t: f32,
+}
+
+// game_over 里调用：放镜头，或者直接进结算
+pub fn start(game: &mut Game, at: Vec2) {
+    let skip = crate::motion::calm(game) || game.play == PlayMode::Versus || replay::headless(game);
+    game.killcam = Killcam { at, t: 0.0 };
+    game.mode = if skip { GameMode::GameOver } else { GameMode::Killcam };
+}
+
+pub fn update(game: &mut Game, dt: f32) {
+    game.killcam.t += dt;
+    let slow = dt * TIME_SCALE;
+    let feel = game.feel_now();
+    game.effects.tick(&feel, slow, game.player.rect(), trail_speed(game), trail_boost(game));
+    game.texts.update(slow);
+    let skip = [KeyCode::Space, KeyCode::Enter, KeyCode::Escape].into_iter().any(is_key_pressed);
+    if skip || game.killcam.t >= DURATION { game.mode = GameMode::GameOver; }
+}
+
+// 镜头推进的进度：先快后慢
+fn eased(k: &Killcam) -> f32 {
+    let p = (k.t / DURATION).clamp(0.0, 1.0);
+    1.0 - (1.0 - p).powi(3)
+}
+
+// 在场地相机的基础上把中心挪向撞击点、放大；中心夹在场地内，推近了也不会露出场地外面
+pub fn camera(game: &Game, shake: Offset) -> Camera2D {
+    let view = view_size(game);
+    let e = eased(&game.killcam);
+    let z = 1.0 + (ZOOM - 1.0) * e;
+    let half = view * 0.5 / z;
+    let center = view * 0.5 + (game.killcam.at - view * 0.5) * e;
+    let mut cam = field_camera(view, Vec2::ZERO);
+    cam.target = center.clamp(half, view - half);
+    cam.zoom *= z;
+    shake.camera(cam)
+}
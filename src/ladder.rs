@@ -0,0 +1,146 @@
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{tr, trf};
+use crate::trace::is_key_pressed;
+use crate::versus::Outcome;
+use crate::{challenge, draw_text_center, Game, GameMode};
+
+// ===== 联机排位 =====
+// 按对手分开记：对每个朋友有一个自己的 Elo 分（从 START 起），外加胜负和垃圾往来。
+// 两个人的分互为镜像（对方眼里自己的分 = 2×START − 我的分），所以只存我这边。
+// 作废的局不计。记在存档里，跟着档案走。
+const START: f32 = 1000.0;
+const K: f32 = 32.0;
+const HISTORY_MAX: usize = 20; // 每个对手只留最近这么多场
+const ROWS: usize = 8;          // 界面上列出的对手数
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum MatchResult { Win, Lose, Draw }
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Match {
+    pub day: u32,
+    pub result: MatchResult,
+    pub sent: u32, // 送出的垃圾
+    pub got: u32,  // 收到的垃圾
+    pub delta: i32,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Opponent {
+    pub name: String,
+    pub rating: f32,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub sent: u32,
+    pub got: u32,
+    pub history: Vec<Match>, // 新的在前
+}
+
+impl Opponent {
+    pub fn played(&self) -> u32 { self.wins + self.losses + self.draws }
+}
+
+#[derive(Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Ladder {
+    pub opponents: Vec<Opponent>,
+}
+
+// 我对镜像对手的期望得分
+fn expected(rating: f32) -> f32 {
+    let theirs = 2.0 * START - rating;
+    1.0 / (1.0 + 10f32.powf((theirs - rating) / 400.0))
+}
+
+impl Ladder {
+    pub fn get(&self, name: &str) -> Option<&Opponent> { self.opponents.iter().find(|o| o.name == name) }
+
+    // 记一场，返回我的分变了多少；作废的局返回 None
+    pub fn record(&mut self, name: &str, outcome: &Outcome, sent: u32, got: u32) -> Option<i32> {
+        let (result, score) = match outcome {
+            Outcome::Win => (MatchResult::Win, 1.0),
+            Outcome::Lose => (MatchResult::Lose, 0.0),
+            Outcome::Draw => (MatchResult::Draw, 0.5),
+            Outcome::Void(_) => return None,
+        };
+        let i = match self.opponents.iter().position(|o| o.name == name) {
+            Some(i) => i,
+            None => {
+                self.opponents.push(Opponent { name: name.to_string(), rating: START, wins: 0, losses: 0, draws: 0, sent: 0, got: 0, history: Vec::new() });
+                self.opponents.len() - 1
+            }
+        };
+        let o = &mut self.opponents[i];
+        let before = o.rating.round() as i32;
+        o.rating += K * (score - expected(o.rating));
+        let delta = o.rating.round() as i32 - before;
+        match result {
+            MatchResult::Win => o.wins += 1,
+            MatchResult::Lose => o.losses += 1,
+            MatchResult::Draw => o.draws += 1,
+        }
+        o.sent += sent;
+        o.got += got;
+        o.history.insert(0, Match { day: challenge::today(), result, sent, got, delta });
+        o.history.truncate(HISTORY_MAX);
+        Some(delta)
+    }
+
+    // 排位界面的顺序：打得多的在前
+    fn sorted(&self) -> Vec<&Opponent> {
+        let mut v: Vec<&Opponent> = self.opponents.iter().collect();
+        v.sort_by_key(|o| std::cmp::Reverse(o.played()));
+        v
+    }
+}
+
+// 联机房间 [R] 进来；[↑/↓] 选对手，[ESC] 回房间
+pub fn update(game: &mut Game) {
+    let n = game.ladder.opponents.len().min(ROWS);
+    if n > 0 {
+        let step = is_key_pressed(KeyCode::Down) as i32 - is_key_pressed(KeyCode::Up) as i32;
+        game.ladder_cursor = (game.ladder_cursor as i32 + step).rem_euclid(n as i32) as usize;
+    }
+    if is_key_pressed(KeyCode::Escape) { game.mode = GameMode::Lobby; }
+}
+
+fn result_label(r: MatchResult) -> (&'static str, Color) {
+    match r {
+        MatchResult::Win => (tr("胜"), GOLD),
+        MatchResult::Lose => (tr("负"), RED),
+        MatchResult::Draw => (tr("平"), LIGHTGRAY),
+    }
+}
+
+pub fn draw(font: &Font, game: &Game) {
+    let ts = game.settings.text_scale();
+    let w = game.field.x;
+    draw_text_center(font, w, tr("排位记录"), 80.0, 40.0 * ts, SKYBLUE);
+    draw_text_center(font, w, tr("每个对手单独计分，从 1000 起；赢强过自己的对手涨得多"), 116.0, 18.0 * ts, LIGHTGRAY);
+    let list = game.ladder.sorted();
+    if list.is_empty() {
+        draw_text_center(font, w, tr("还没打过联机对战"), 240.0, 24.0 * ts, GRAY);
+        draw_text_center(font, w, tr("[ESC] 返回"), game.field.y - 40.0, 20.0 * ts, ORANGE);
+        return;
+    }
+    let row_h = 28.0 * ts;
+    let cursor = game.ladder_cursor.min(list.len() - 1);
+    for (i, o) in list.iter().take(ROWS).enumerate() {
+        let y = 160.0 + i as f32 * row_h;
+        let row = trf("{:<12}  {:>4}   {}胜 {}负 {}平   垃圾 送{} / 收{}", &[&o.name, &(o.rating.round() as i32), &o.wins, &o.losses, &o.draws, &o.sent, &o.got]);
+        draw_text_center(font, w, &row, y, 20.0 * ts, if i == cursor { GOLD } else { WHITE });
+    }
+    // 选中对手的最近几场
+    let o = list[cursor];
+    let y0 = 160.0 + list.len().min(ROWS) as f32 * row_h + 20.0;
+    draw_text_center(font, w, &trf("和 {} 的最近对局", &[&o.name]), y0, 20.0 * ts, SKYBLUE);
+    for (i, m) in o.history.iter().take(5).enumerate() {
+        let (label, color) = result_label(m.result);
+        let line = trf("{}  {}  {:+}   送{} 收{}", &[&challenge::date_string(m.day), &label, &m.delta, &m.sent, &m.got]);
+        draw_text_center(font, w, &line, y0 + (i + 1) as f32 * 24.0 * ts, 18.0 * ts, color);
+    }
+    draw_text_center(font, w, tr("[↑/↓] 选择   [ESC] 返回"), game.field.y - 20.0, 20.0 * ts, ORANGE);
+}
@@ -0,0 +1,69 @@
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::rivals::NAME_MAX;
+use crate::textfield::{Event, TextField};
+use crate::trace::is_key_pressed;
+
+// ===== 本地前十名 =====
+// 跨模式的一张总榜：普通模式（含挑战）未续关、未开自适应的局，分数够进前十就在结束后输入名字。
+// 名字可以直接打字（文本框见 textfield.rs），也可以像街机一样拼：↑/↓ 改光标前的字符，光标在末尾时 → 加一位。
+pub const TOP_N: usize = 10;
+const ARCADE_CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct TopEntry {
+    pub name: String,
+    pub score: i32,
+    pub day: u32,
+    pub mode: String, // 模式 / 难度 / 挑战的显示名
+    #[serde(default)]
+    pub ghost: Option<u64>, // 这一局的幽灵文件（见 ghost.rs）
+    #[serde(default)]
+    pub elapsed: f32, // 存活秒数；旧存档里没有，记为 0
+}
+
+#[derive(Clone, Serialize, Deserialize, Default)]
+#[serde(transparent)]
+pub struct TopTable(Vec<TopEntry>); // 按分数从高到低
+
+impl TopTable {
+    pub fn entries(&self) -> &[TopEntry] { &self.0 }
+    pub fn qualifies(&self, score: i32) -> bool {
+        score > 0 && (self.0.len() < TOP_N || self.0.last().is_some_and(|e| score > e.score))
+    }
+    // 插入并返回名次（从 0 起）；同分排在旧纪录后面
+    pub fn insert(&mut self, e: TopEntry) -> usize {
+        let i = self.0.iter().position(|o| e.score > o.score).unwrap_or(self.0.len());
+        self.0.insert(i, e);
+        self.0.truncate(TOP_N);
+        i
+    }
+    // 导入档案：别处的成绩够格就并进来（完全相同的一条不重复）
+    pub fn merge(&mut self, other: &TopTable) {
+        for e in &other.0 {
+            if !self.0.contains(e) && self.qualifies(e.score) { self.insert(e.clone()); }
+        }
+    }
+}
+
+// 结束后输入名字；retry 为真时确认后直接再来一局，否则去本局总结
+pub struct NameEntry {
+    pub field: TextField,
+    pub retry: bool,
+}
+
+impl NameEntry {
+    pub fn new(name: &str, retry: bool) -> Self { Self { field: TextField::new(name, NAME_MAX), retry } }
+
+    // 返回 true 表示确认
+    pub fn input(&mut self) -> bool {
+        let grow = self.field.at_end() && !self.field.picking() && is_key_pressed(KeyCode::Right);
+        let submit = matches!(self.field.update(), Some(Event::Submit));
+        if self.field.picking() { return false; }
+        if grow { self.field.insert('A'); }
+        let step = is_key_pressed(KeyCode::Up) as i32 - is_key_pressed(KeyCode::Down) as i32;
+        if step != 0 { self.field.cycle(ARCADE_CHARS, step); }
+        submit && !self.field.is_blank()
+    }
+}
@@ -0,0 +1,17 @@
+// ===== 运行日志：同时输出到 stderr 和 dodge.log =====
+// 网页版没有 stderr，改打到浏览器控制台；也不往 localStorage 里记，免得日志挤占存档的空间
+pub const LOG_PATH: &str = "dodge.log";
+
+pub fn warn(msg: &str) {
+    eprintln!("{}", msg);
+    #[cfg(target_arch = "wasm32")]
+    macroquad::miniquad::warn!("{}", msg);
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let secs = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let _ = crate::storage::append(crate::paths::data(LOG_PATH), format!("[{}] {}\n", secs, msg));
+    }
+}
@@ -1,12 +1,163 @@
 use macroquad::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+
+mod achievements;
+mod anim;
+mod assist;
+mod audio;
+mod background;
+mod banner;
+mod challenge;
+mod characters;
+mod coins;
+mod collision;
+mod console;
+mod contrast;
+mod coop;
+mod cues;
+mod cosmetics;
+mod debug;
+mod difficulty;
+mod director;
+mod display;
+mod duel;
+mod effects;
+mod events;
+mod export;
+mod feedback;
+mod feel;
+mod floating;
+mod ghost;
+mod glyphs;
+mod gravity;
+mod history;
+mod hotseat;
+mod http;
+mod hud;
+mod i18n;
+mod integrity;
+mod keybinds;
+mod killcam;
+mod ladder;
+mod leaderboard;
+mod logfile;
+mod migrate;
+mod missions;
+mod motion;
+mod movement;
+mod mutators;
+mod online;
+mod options;
+mod overlay;
+mod palette;
+mod particles;
+#[cfg(feature = "gamepad")]
+mod pad;
+mod paths;
+mod physics;
+mod playlist;
+mod postfx;
+mod powerups;
+mod practice;
+#[cfg(feature = "discord")]
+mod presence;
+mod profiles;
+mod rating;
+mod records;
+mod replay;
+mod rivals;
+mod rng;
+#[cfg(feature = "scripting")]
+mod scripting;
+mod sandbox;
+mod screenshot;
+mod settings;
+mod shake;
+mod shooting;
+mod shop;
+#[cfg(feature = "tts")]
+mod speech;
+mod spectate;
+mod sprites;
+mod stages;
+mod stats;
+mod status;
+#[cfg(feature = "steam")]
+mod steam;
+mod storage;
+mod suspend;
+mod sync;
+mod synth;
+mod textfield;
+mod touch;
+mod trace;
+mod transfer;
+mod threat;
+mod tutorial;
+#[cfg(feature = "twitch")]
+mod twitch;
+mod versus;
+mod waves;
+mod widget;
+mod wind;
+mod xp;
+use achievements::{AchievementTable, Unlocked};
+use audio::Sfx;
+use background::{Backdrop, Sky};
+use banner::{Banners, Toast};
+use coins::Coins;
+use collision::Layer;
+use cosmetics::{Cosmetics, Look, Progress, Skin, Theme};
+use challenge::{Challenge, ChallengeRun, DailyResult};
+use characters::{CharacterDef, CHARACTERS};
+use difficulty::Difficulty;
+use director::Director;
+use anim::Anims;
+use effects::Effects;
+use events::EventScheduler;
+use feedback::FeedbackForm;
+use feel::{Feel, FEEL_ITEMS, FEEL_PRESETS};
+use floating::FloatingTextPool;
+use history::HistoryEntry;
+use hud::Hud;
+use i18n::{tr, trf};
+use ladder::Ladder;
+use leaderboard::{NameEntry, TopEntry, TopTable};
+use missions::{Metric, MissionBoard};
+use movement::{Mover, Scheme};
+use mutators::{Mutators, MUTATORS};
+use physics::Physics;
+use palette::{Paint, Palette};
+use powerups::{EffectSlot, PowerUpTuning};
+use shake::{Jolt, Shake, ShakeTuning};
+use practice::{Practice, PracticePanel};
+use replay::{Replay, ReplayState, StepInput};
+use records::{BestEntry, Records};
+use profiles::{ProfileEdit, Profiles};
+use rivals::Rivals;
+use sandbox::Sandbox;
+use settings::{HudMode, Lang, Playfield, Settings};
+use shooting::{Armor, ProjectilePool};
+use shop::{Catalog, Perk, Unlock, Wallet};
+use stages::{Hazard, StageTable};
+use textfield::{Event, TextField};
+use stats::{DeathLog, DeathRecord, Lifetime, NewBests, RunStats, StatBests, HEAT_BINS};
+use trace::{get_char_pressed, get_frame_time, is_key_pressed, mouse_position, screen_height, screen_width};
+use tutorial::Tutorial;
+use waves::{PendingSpawn, SpawnKind, WavePattern, WaveRunner};
+use widget::{Item, ListMenu};
+use wind::Wind;
+use xp::Reward;
 
 // ===== 窗口配置 =====
-fn window_conf() -> Conf {
+// 窗口大小沿用上次的（见 display.rs）
+fn window_conf(settings: &Settings) -> Conf {
+    let (window_width, window_height) = display::initial_size(settings);
     Conf {
-        window_title: "Dodge Rush + PowerUps (No SFX)".to_string(),
-        window_width: 800,
-        window_height: 600,
+        window_title: if cfg!(feature = "audio") { "Dodge Rush + PowerUps" } else { "Dodge Rush + PowerUps (No SFX)" }.to_string(),
+        window_width,
+        window_height,
         high_dpi: true,
         ..Default::default()
     }
@@ -19,13 +170,64 @@ const PLAYER_Y: f32 = 560.0;
 const PLAYER_SPEED_MAX: f32 = 520.0;
 const PLAYER_ACC: f32 = 2400.0;     // 加速度
 const PLAYER_DECAY: f32 = 0.0008;   // 指数衰减（松手后减速）
+// 开局体型：每档宽度与速度一起增减（窄 = 慢，宽 = 快）
+const LOADOUT_STEPS: i32 = 2;
+const LOADOUT_W_STEP: f32 = 0.15;
+const LOADOUT_SPEED_STEP: f32 = 0.12;
+const SEED_DIGITS: usize = 19;       // 手动输入的种子最多几位（19 位以内一定装得进 u64）
 
 const OB_MIN_SIZE: f32 = 22.0;
 const OB_MAX_SIZE: f32 = 60.0;
 const OB_START_SPEED: f32 = 140.0;
+const SPAWN_LEAD: f32 = 0.5;           // 障碍从生成到进入场地的时间（来袭预警）
 const OB_ACC_PER_SEC: f32 = 18.0;
 const SPAWN_BASE_INTERVAL: f32 = 0.9;
 const SPAWN_MIN_INTERVAL: f32 = 0.25;
+const RAMP_TIME: f32 = 25.0;          // 每轮爬坡时长
+const BREATHER_TIME: f32 = 5.0;       // 每轮喘息时长
+const PEAK_CARRY: f32 = 0.8;          // 下一轮从上轮峰值的多少起步
+const BREATHER_SPAWN_MUL: f32 = 2.2;  // 喘息期生成间隔倍率
+const BREATHER_FALL_MUL: f32 = 0.85;  // 喘息期下落速度倍率
+const NEAR_MISS_MARGIN: f32 = 18.0;   // 擦边判定：命中盒外扩的距离
+const NEAR_MISS_BONUS: i32 = 2;       // 每次擦边的加分
+const COMBO_WINDOW: f32 = 4.0;        // 连击窗口：这么久没有擦边或拾取就断
+const COMBO_MAX: u32 = 5;             // 连击倍率上限（只乘在存活计分上）
+const COMBO_COLOR: Color = Color::new(1.0, 0.55, 0.85, 1.0);
+const GRAZE_FOR_BOMB: u32 = 8;        // 攒满一颗手动炸弹需要的擦边次数
+// 大招：存活时间和拾取道具充能，满了按 [Q] 进入超载——世界变慢、玩家不受影响、穿过障碍
+const ULT_PER_SEC: f32 = 1.0 / 45.0;
+const ULT_PER_PICKUP: f32 = 0.15;
+const OVERDRIVE_TIME: f32 = 3.0;
+const OVERDRIVE_SCALE: f32 = 0.4;
+// 时光倒流：持有充能时每隔一小段世界时间拍快照，只保留最近几秒
+const REWIND_SAMPLE: f32 = 0.1;
+const REWIND_KEEP: f32 = 3.0;
+const REWIND_BACK: f32 = 2.0;
+const MULT_DURATION: f32 = 12.0;       // x2 倍率持续时间
+const MULT_COLOR: Color = Color::new(0.3, 1.0, 0.85, 1.0);
+const FREEZE_DURATION: f32 = 3.0;     // 冻结：障碍停住（仍然致命）、生成暂停
+const FREEZE_COLOR: Color = Color::new(0.65, 0.9, 1.0, 1.0);
+// 陷阱道具：外观仿照正常道具，拾取后是惩罚；出现概率随难度曲线爬升
+const TRAP_MAX_CHANCE: f32 = 0.3;
+const TRAP_RAMP_TIME: f32 = 150.0;   // 曲线时间到这里达到最大概率
+const REVERSE_DURATION: f32 = 5.0;   // 左右颠倒
+const HASTE_DURATION: f32 = 5.0;     // 障碍加速
+const HASTE_MUL: f32 = 1.4;
+const BLACKOUT_DURATION: f32 = 4.0;  // 只看得见玩家附近的障碍
+const BLACKOUT_RADIUS: f32 = 150.0;
+const BLAST_TIME: f32 = 0.4;         // 炸弹冲击波扩散到最大半径的时间
+const BLAST_POINTS: i32 = 2;         // 冲击波每炸掉一个障碍的加分
+const DODGE_BONUS: i32 = 1;           // 每躲过一个障碍的加分
+const STAGE_FADE_TIME: f32 = 1.0;     // 阶段切换的背景过渡时长
+const STAGE_BANNER_TIME: f32 = 1.6;
+const EVENT_BANNER_TIME: f32 = 2.0;
+// —— 分数里程碑 —— 
+const MILESTONE_EVERY: i32 = 100;
+const MILESTONE_BANNER_TIME: f32 = 1.2;
+const MILESTONE_PU_WITHIN: f32 = 3.0; // 里程碑后这么久之内必出一个道具
+const MILESTONE_PULSE_TIME: f32 = 0.8; // 边框光晕（缓慢淡出，不闪烁）
+const XP_FILL_TIME: f32 = 1.5;        // 结算界面经验条涨满的时间
+const WAVE_CHANCE: f32 = 0.12;        // 每次生成时改为触发阵型的概率
 
 const FIXED_DT: f32 = 1.0 / 120.0;  // 固定物理步：120Hz
 
@@ -35,16 +237,95 @@ const PU_FALL_SPEED: f32 = 120.0;
 const PU_SIZE: f32 = 28.0;
 const SLOW_DURATION: f32 = 6.0;       // 减速持续时间
 const SLOW_FACTOR: f32 = 0.5;         // 减速倍率
+const MAX_LIVES: u32 = 3;
+const LIFE_INVULN_TIME: f32 = 1.5;    // 掉命后的无敌时间
+const SHIELD_INVULN_TIME: f32 = 1.0;  // 护盾挡下一击后的无敌时间（防止紧接着被第二块撞死）
+const LIFE_CLEAR_RADIUS: f32 = 160.0; // 掉命时清掉玩家附近的障碍
+const CONTINUE_INVULN_TIME: f32 = 2.0; // 续关后的无敌时间
+const RESUME_BEAT: f32 = 0.5;          // 取消暂停后的倒数：每个数字停留的时间
+const RESUME_COUNTDOWN: f32 = RESUME_BEAT * 3.0;
 
 // ===== 模式 =====
 #[derive(Clone, Copy, PartialEq)]
-enum GameMode { Menu, Playing, Paused, GameOver }
+enum GameMode { Menu, PreRun, Playing, Paused, Killcam, GameOver, Summary, Stats, Shop, Customize, Missions, Achievements, History, Challenges, NameEntry, Leaderboard, Profiles, SyncConflict, Settings, FeelPanel, Feedback, Sandbox, Lobby, Spectate, Ranked, Duel, Hotseat, Keybinds }
 
-// ===== 数据结构 =====
-struct Player { x: f32, vx: f32 }
+// 本局玩法：禅模式不计分、不会结束，难度停在 ZEN_PLATEAU 秒的水平；
+// 练习模式的生成参数由面板实时决定；教程由脚本序列驱动；联机对战见 versus.rs，双人合作见 coop.rs，轮流赛见 hotseat.rs
+#[derive(Clone, Copy, PartialEq)]
+enum PlayMode { Standard, Zen, Practice, Tutorial, Versus, Coop, Hotseat }
+
+const ZEN_PLATEAU: f32 = 20.0;
 
+// 炸弹冲击波：圆环从引爆点向外扩，碰到的障碍都会被炸掉
 #[derive(Clone, Copy)]
-struct Obstacle { rect: Rect, vy: f32 }
+struct Blast { center: Vec2, age: f32, radius: f32 }
+
+// ===== 数据结构 =====
+#[derive(Clone, Serialize, Deserialize)]
+struct Player {
+    x: f32,
+    w: f32,
+    vx: f32,
+    dash_timer: f32,
+    dash_cd: f32,
+    // 本局的加速度与最高速度（由手感参数和开局体型得出）
+    #[serde(default = "default_acc")]
+    acc: f32,
+    #[serde(default = "default_speed_max")]
+    speed_max: f32,
+}
+fn default_acc() -> f32 { PLAYER_ACC }
+fn default_speed_max() -> f32 { PLAYER_SPEED_MAX }
+impl Player {
+    fn new(x: f32, w: f32) -> Self { Self { x, w, vx: 0.0, dash_timer: 0.0, dash_cd: 0.0, acc: PLAYER_ACC, speed_max: PLAYER_SPEED_MAX } }
+    fn rect(&self) -> Rect { Rect::new(self.x, PLAYER_Y, self.w, PLAYER_H) }
+}
+
+// 一步的移动输入；push 为外界施加的横向加速度（阵风）
+#[derive(Clone, Copy, Default)]
+struct MoveInput { dir: f32, dash: bool, push: f32 }
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct Obstacle {
+    #[serde(with = "suspend::RectDef")]
+    rect: Rect,
+    vx: f32,
+    vy: f32,
+    passed_band: bool,
+    grazed: bool,
+    deflect: f32,
+    #[serde(default)]
+    armor: Armor, // 只在开启射击时会出现非普通障碍
+    #[serde(skip)]
+    cued: bool,   // 听觉预警已经响过（见 cues.rs）
+}
+
+impl Obstacle {
+    fn layer(&self) -> Layer { if self.deflect > 0.0 { Layer::DEFLECTED } else { Layer::OBSTACLE } }
+    // 被弹回：向上飞并短暂进入 DEFLECTED 层
+    fn bounce_back(&mut self) {
+        self.vy = -self.vy.abs() * DEFLECT_BOUNCE;
+        self.deflect = collision::DEFLECT_TIME;
+    }
+}
+
+const DEFLECT_BOUNCE: f32 = 0.7; // 护盾弹开时保留的速度比例
+
+// 障碍离场时的出口方向
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum ExitEdge { Bottom, Left, Right, Top }
+
+const EXIT_MARGIN: f32 = 5.0;
+
+// 判断障碍是否已经离开场地；向上运动时才检查顶部，避免误判刚生成的障碍
+fn exit_edge(o: &Obstacle, field_w: f32, field_h: f32) -> Option<ExitEdge> {
+    let r = o.rect;
+    if r.y > field_h + EXIT_MARGIN { Some(ExitEdge::Bottom) }
+    else if r.x + r.w < -EXIT_MARGIN { Some(ExitEdge::Left) }
+    else if r.x > field_w + EXIT_MARGIN { Some(ExitEdge::Right) }
+    else if o.vy < 0.0 && r.y + r.h < -EXIT_MARGIN { Some(ExitEdge::Top) }
+    else { None }
+}
 
 struct ObstaclePool {
     live: Vec<Obstacle>,
@@ -52,26 +333,68 @@ struct ObstaclePool {
 }
 impl ObstaclePool {
     fn new() -> Self { Self { live: Vec::new(), dead: Vec::new() } }
-    fn spawn(&mut self, rect: Rect, vy: f32) {
+    fn spawn(&mut self, rect: Rect, vx: f32, vy: f32) {
         if let Some(mut o) = self.dead.pop() {
-            o.rect = rect; o.vy = vy;
+            o.rect = rect; o.vx = vx; o.vy = vy; o.passed_band = false; o.grazed = false; o.deflect = 0.0; o.armor = Armor::Plain;
             self.live.push(o);
         } else {
-            self.live.push(Obstacle { rect, vy });
+            self.live.push(Obstacle { rect, vx, vy, passed_band: false, grazed: false, deflect: 0.0, armor: Armor::Plain, cued: false });
         }
     }
-    fn update_and_sweep(&mut self, screen_h: f32, dt: f32) {
+    // 移动并回收离场障碍；返回“躲过”的数量：
+    // 只要障碍曾到达玩家所在高度（band_top），从任何边离场都算躲过
+    fn update_and_sweep(&mut self, field_w: f32, field_h: f32, band_top: f32, dt: f32) -> u32 {
+        let mut dodged = 0;
         let mut i = 0;
         while i < self.live.len() {
             let o = &mut self.live[i];
+            o.rect.x += o.vx * dt;
             o.rect.y += o.vy * dt;
-            if o.rect.y > screen_h + 5.0 {
+            o.deflect = (o.deflect - dt).max(0.0);
+            if o.rect.y + o.rect.h >= band_top { o.passed_band = true; }
+            if exit_edge(o, field_w, field_h).is_some() {
+                if o.passed_band { dodged += 1; }
                 let dead = self.live.swap_remove(i);
                 self.dead.push(dead);
             } else {
                 i += 1;
             }
         }
+        dodged
+    }
+    // 擦边：进入命中盒外扩区域但没碰到命中盒，每个障碍只算一次
+    fn count_near_misses(&mut self, hit: Rect) -> u32 {
+        let near = Rect::new(hit.x - NEAR_MISS_MARGIN, hit.y - NEAR_MISS_MARGIN, hit.w + NEAR_MISS_MARGIN * 2.0, hit.h + NEAR_MISS_MARGIN * 2.0);
+        let mut n = 0;
+        for o in &mut self.live {
+            if !collision::interacts(Layer::PLAYER, o.layer()) { continue; }
+            if !o.grazed && rects_overlap(o.rect, near) && !rects_overlap(o.rect, hit) {
+                o.grazed = true;
+                n += 1;
+            }
+        }
+        n
+    }
+    // 弹开的障碍与其它障碍相撞：两者一起销毁，返回被撞掉的数量
+    fn resolve_deflected(&mut self) -> u32 {
+        let mut n = 0;
+        let mut i = 0;
+        while i < self.live.len() {
+            let a = &self.live[i];
+            let hit = self.live.iter().position(|b| collision::interacts(a.layer(), b.layer()) && rects_overlap(a.rect, b.rect));
+            if let Some(j) = hit {
+                let (hi, lo) = if i > j { (i, j) } else { (j, i) };
+                let x = self.live.swap_remove(hi);
+                let y = self.live.swap_remove(lo);
+                self.dead.push(x);
+                self.dead.push(y);
+                n += 1;
+                i = 0;
+            } else {
+                i += 1;
+            }
+        }
+        n
     }
     fn clear_all(&mut self) {
         while let Some(dead) = self.live.pop() { self.dead.push(dead); }
@@ -79,10 +402,43 @@ impl ObstaclePool {
 }
 
 // —— 道具 —— 
-#[derive(Clone, Copy)]
-enum PowerUpKind { Shield, Slow, Bomb }
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+enum PowerUpKind {
+    Shield, Slow, Bomb, Heart, Teleport, Ammo, Rewind, Multiplier, Freeze,
+    // 陷阱
+    Reverse, Haste, Blackout,
+}
+
+const PU_KINDS: usize = 12;
+const ALL_POWERUPS: [PowerUpKind; PU_KINDS] = [
+    PowerUpKind::Shield, PowerUpKind::Slow, PowerUpKind::Bomb, PowerUpKind::Heart, PowerUpKind::Teleport, PowerUpKind::Ammo,
+    PowerUpKind::Rewind, PowerUpKind::Multiplier, PowerUpKind::Freeze, PowerUpKind::Reverse, PowerUpKind::Haste, PowerUpKind::Blackout,
+];
+
+impl PowerUpKind {
+    fn label(self) -> &'static str {
+        match self {
+            PowerUpKind::Shield => "护盾",
+            PowerUpKind::Slow => "减速",
+            PowerUpKind::Bomb => "炸弹",
+            PowerUpKind::Heart => "红心",
+            PowerUpKind::Teleport => "瞬移",
+            PowerUpKind::Ammo => "弹药",
+            PowerUpKind::Rewind => "倒流",
+            PowerUpKind::Multiplier => "倍率",
+            PowerUpKind::Freeze => "冻结",
+            PowerUpKind::Reverse => "颠倒",
+            PowerUpKind::Haste => "加速",
+            PowerUpKind::Blackout => "黑暗",
+        }
+    }
+    fn is_trap(self) -> bool { matches!(self, PowerUpKind::Reverse | PowerUpKind::Haste | PowerUpKind::Blackout) }
+    fn is_defensive(self) -> bool { matches!(self, PowerUpKind::Shield | PowerUpKind::Heart | PowerUpKind::Bomb) }
+}
 
+#[derive(Clone, Serialize, Deserialize)]
 struct PowerUp {
+    #[serde(with = "suspend::RectDef")]
     rect: Rect,
     vy: f32,
     kind: PowerUpKind,
@@ -135,340 +491,4074 @@ impl PowerUpPool {
 }
 
 #[derive(Serialize, Deserialize, Default)]
-struct Save { best: i32 }
+struct Save {
+    #[serde(default)]
+    version: u32,              // 存档格式版本，见 migrate.rs
+    #[serde(default)]
+    records: Records,          // “模式/难度/规则标签” → 最高分
+    #[serde(default, skip_serializing)]
+    difficulty: Difficulty,    // 旧版本存档里的难度，只读（现在记在 settings.json）
+    #[serde(default)]
+    adaptive: bool,
+    #[serde(default)]
+    mutators: Mutators,        // 上次选择的变异规则
+    #[serde(default)]
+    daily: Vec<DailyResult>,   // 每日挑战历史
+    #[serde(default)]
+    weekly: BTreeMap<u32, i32>, // 周数 → 每周挑战最好成绩
+    #[serde(default)]
+    feel: Feel,
+    #[serde(default)]
+    practice: Practice,
+    #[serde(default)]
+    scheme: Scheme,
+    #[serde(default)]
+    wrap: bool,
+    #[serde(default)]
+    shooting: bool,
+    #[serde(default)]
+    tutorial_done: bool,
+    #[serde(default = "one_life")]
+    start_lives: u32,
+    #[serde(default)]
+    loadout: i32,
+    #[serde(default)]
+    character: usize,
+    #[serde(default)]
+    stat_bests: StatBests,
+    #[serde(default)]
+    deaths: DeathLog,
+    #[serde(default)]
+    lifetime: Lifetime,
+    #[serde(default)]
+    wallet: Wallet,
+    #[serde(default)]
+    look: Look,
+    #[serde(default)]
+    xp: u32,                   // 账号累计经验（等级由它推出）
+    #[serde(default)]
+    missions: MissionBoard,
+    #[serde(default)]
+    achievements: Unlocked,
+    #[serde(default, skip_serializing)]
+    settings: Option<Settings>, // 旧版本存档里的设置，只读（现在存 settings.json）
+    #[serde(default)]
+    rivals: Rivals,            // 挑战的本地排行（含导入的朋友成绩）
+    #[serde(default)]
+    ladder: Ladder,            // 联机对战的排位（按对手记）
+    #[serde(default)]
+    tampered: bool,            // 校验对不上过（一旦标记就一直保留）
+    #[serde(flatten)]
+    extra: serde_json::Map<String, serde_json::Value>, // 不认识的字段（更新版本写的）原样保留
+    #[serde(skip)]
+    warning: Option<String>,   // 读档时的问题（从备份恢复等），菜单上提示一次
+    #[serde(default)]
+    top: TopTable,             // 本地前十名
+    #[serde(default)]
+    player_name: String,
+    #[serde(default)]
+    saved_at: u64,             // 写盘时间（Unix 秒），云同步对账用
+}
 
 struct Resources {
     font: Font,
+    content: Content,
+    overlay: overlay::Overlay,
+    sprites: sprites::Atlas,
+    postfx: postfx::PostFx,
+    audio: audio::Audio,
+}
+
+// 模拟要用的数据（不依赖窗口，无窗口校验回放时也要载入，见 replay::verify）
+struct Content {
+    waves: Vec<WavePattern>, // 按评估难度从易到难
+    stages: StageTable,
+    #[cfg(feature = "scripting")]
+    scripts: scripting::ScriptHost,
+}
+
+impl Content {
+    fn load() -> Self {
+        Self {
+            waves: {
+                let mut waves = waves::load_waves(waves::WAVES_DIR);
+                rating::sort_by_difficulty(&mut waves);
+                waves
+            },
+            stages: StageTable::load(stages::STAGES_PATH),
+            #[cfg(feature = "scripting")]
+            scripts: scripting::ScriptHost::load(scripting::SCRIPTS_DIR),
+        }
+    }
 }
 
 struct Game {
     mode: GameMode,
     player: Player,
+    mover: Mover,              // 移动方式（自由 / 车道）
+    phys: Physics,             // 手感参数
+    pu_tuning: PowerUpTuning,  // 道具调参（重复拾取规则）
     obs: ObstaclePool,
     pus: PowerUpPool,
     time_tick: f32,            // 计分步进
+    run_steps: u32,            // 本局已走的固定步数（幽灵按它对齐）
+    ghost: Option<ghost::Ghost>, // 当前规则组合最佳一局的轨迹
+    elapsed: f32,              // 本局已进行时间
     score: i32,
-    best_score: i32,
+    dodged: u32,               // 本局躲过的障碍数
+    records: Records,          // 各模式 / 难度 / 规则组合的最高分
+    stats: RunStats,           // 本局统计
+    stat_bests: StatBests,     // 各项统计的历史最佳
+    new_bests: NewBests,       // 本局刷新了哪些最佳（结算界面高亮）
+    deaths: DeathLog,          // 跨局的死亡位置记录
+    lifetime: Lifetime,        // 生涯累计统计
+    stats_page: usize,         // 统计界面当前页（0 生涯 / 1 死亡位置）
+    export_msg: Option<String>, // 统计界面导出的结果提示
+    shop: Catalog,             // 商店目录（assets/shop.json）
+    wallet: Wallet,            // 金币与已购解锁
+    shop_cursor: usize,
+    coins: Coins,              // 场上的金币
+    cosmetics: Cosmetics,      // 皮肤与主题（assets/cosmetics.json）
+    look: Look,                // 选用的皮肤与主题
+    look_cursor: usize,
+    xp: u32,                   // 账号累计经验
+    xp_gain: u32,              // 上一局获得的经验（结算界面动画）
+    xp_anim: f32,              // 结算界面经验条动画已进行的时间
+    missions: MissionBoard,    // 今日任务
+    achievement_defs: AchievementTable, // 成就表（assets/achievements.json）
+    achievements: Unlocked,    // 已解锁的成就 → 解锁时间
+    ach_scroll: usize,         // 成就界面滚动位置
+    still: f32,                // 连续不移动的时间（成就判定用）
+    rivals: Rivals,
+    ladder: Ladder,
+    ladder_cursor: usize,
+    profiles: Profiles,
+    profile_cursor: usize,
+    profile_edit: Option<ProfileEdit>, // 正在新建 / 改名
+    profile_delete: Option<usize>,     // 再按一次 [DEL] 才真的删
+    profile_msg: Option<String>,       // 导出 / 导入的结果提示
+    cloud: Option<sync::CloudSync>,    // 配了云同步时的远端（见 sync.rs）
+    save_warning: Option<String>, // 读档出过问题，菜单上提示到离开菜单为止
+    save_version: u32,         // 读进来的存档版本（比当前新时原样写回）
+    save_extra: serde_json::Map<String, serde_json::Value>,
+    tampered: bool,            // 存档被改动过：成绩不进挑战排行
+    top: TopTable,
+    name_entry: Option<NameEntry>, // 进了前十，正在输入名字
+    top_rank: Option<usize>,   // 本局在前十里的名次（总结和排行榜里高亮）
+    top_cursor: usize,
+    top_msg: Option<String>,   // 排行榜界面复制/导入幽灵的结果提示
+    top_global: bool,          // 排行榜界面看的是全球榜
+    online: online::Online,    // 全球排行榜的后台请求（见 online.rs）
+    versus: versus::Versus,    // 联机对战的房间和连接
+    #[cfg(feature = "twitch")]
+    twitch: twitch::Twitch,    // 直播聊天投票（见 twitch.rs）
+    #[cfg(feature = "discord")]
+    presence: presence::Presence, // Discord 个人状态
+    #[cfg(feature = "steam")]
+    steam: Option<steam::Steam>, // Steam 在运行时才有（见 steam.rs）
+    #[cfg(feature = "tts")]
+    speech: speech::Speech,      // 朗读菜单（见 speech.rs）
+    spectate: spectate::Spectate, // 局域网观战：直播发送端和观战接收端
+    coop: Option<coop::Coop>,  // 双人合作局的 2P 和救援状态
+    duel: Option<duel::Duel>,  // 同屏对决（左右两块场地）
+    hotseat: hotseat::Hotseat, // 轮流赛的报名和排名
+    rebind: keybinds::Rebind,  // 改键界面
+    main_menu: ListMenu,       // 主菜单
+    play_list: ListMenu,       // 主菜单里“开始游戏”展开的玩法列表
+    play_menu: bool,           // 玩法列表展开着
+    quitting: bool,            // 主菜单选了退出：下一帧照关窗口处理
+    pause_menu: ListMenu,
+    confirm_quit: Option<ListMenu>, // 暂停时“离开这一局？”的确认框
+    idle: f32,                 // 局内连续没有移动输入的时间（自动暂停用）
+    resume_in: f32,            // 取消暂停 / 续关后的倒数，数完之前场面定住
+    auto_paused: Option<&'static str>, // 这次暂停是自动的：原因
+    options: options::Options, // 设置界面
+    debug: debug::Debug,       // [F3] 调试浮层
+    console: console::Console, // [~] 开发者控制台
+    player_name: String,       // 分享码和排行榜的默认名字
+    challenge_tab: Challenge,  // 挑战界面当前选中的是每日还是每周
+    rival_msg: Option<String>, // 挑战界面复制/导入的结果提示
+    naming: Option<TextField>, // 正在输入挑战署名
+    replay: ReplayState,       // 本局录制中 / 正在观看的回放
+    history: Vec<HistoryEntry>, // 最近的对局（history.jsonl）
+    history_cursor: usize,     // 历史界面选中的行（0 为最新）
+    pending_log: Option<HistoryEntry>, // 已结束、等确定不续关后写入历史的一局
+    forced_seed: Option<u64>,  // 从历史里按原种子重开，或开局设置里指定的种子
+    seed_entry: Option<TextField>, // 开局设置里正在输入种子
+    race: Option<ghost::GhostFile>, // 正在和这个幽灵赛跑（重开也继续，回菜单结束）
+    difficulty: Difficulty,
+    director: Director,        // 自适应难度
+    mutators: Mutators,        // 本局变异规则
+    play: PlayMode,
+    practice: Practice,        // 练习模式参数
+    practice_panel: PracticePanel,
+    tutorial: Tutorial,
+    tutorial_done: bool,
+    challenge: Option<ChallengeRun>, // 正在进行的每日/每周挑战
+    stash: Option<challenge::Stash>,  // 固定规则期间收起的玩家选择
+    daily_history: Vec<DailyResult>,
+    weekly_bests: BTreeMap<u32, i32>,
+    feel: Feel,                // 手感设置
+    feel_cursor: usize,
+    effects: Effects,
+    anims: Anims,              // 帧动画的时钟和一次性动画（见 anim.rs）
     spawn_timer: f32,
     spawn_interval: f32,
+    breather: bool,            // 处于难度曲线的喘息期
     fall_speed: f32,
-    shake: f32,                // 相机震动强度
+    shake: Shake,              // 相机震动（创伤值）
     // —— 道具状态 ——
     shield: u32,               // 护盾层数
-    slow_timer: f32,           // 减速剩余时间
+    lives: u32,                // 剩余命数（含当前这条）
+    start_lives: u32,          // 开局命数（开局设置，默认 1）
+    loadout: i32,              // 开局体型（-LOADOUT_STEPS ~ LOADOUT_STEPS，0 为标准）
+    character: usize,          // 所选角色（CHARACTERS 下标，0 为标准）
+    invuln: f32,               // 掉命 / 护盾破碎后的无敌剩余时间（期间闪烁、不判碰撞）
+    continued: bool,           // 本局已用掉续关机会
+    crowd: bool,               // 本局被直播观众投票干预过（不计入最高分）
+    teleport: bool,            // 存着一次瞬移
+    graze: u32,                // 擦边计量（攒满存一颗炸弹）
+    stored_bomb: bool,         // 存着的手动炸弹，[B] 引爆
+    ult: f32,                  // 大招充能 0~1
+    overdrive: f32,            // 超载剩余时间（真实时间）
+    overlap: u32,              // 连续压着障碍的步数（命中宽限用）
+    rewind: bool,              // 存着一次时光倒流
+    mult: EffectSlot,          // 得分倍率
+    combo: u32,                // 连击倍率 1~COMBO_MAX
+    combo_timer: f32,          // 连击窗口剩余时间
+    freeze: EffectSlot,        // 冻结
+    reverse: f32,              // 陷阱：左右颠倒剩余时间
+    haste: f32,                // 陷阱：障碍加速剩余时间
+    blackout: f32,             // 陷阱：黑暗剩余时间
+    blast: Option<Blast>,      // 正在扩散的炸弹冲击波
+    rewind_buf: VecDeque<(f32, suspend::Snapshot)>, // (elapsed, 快照)，旧的在前
+    rewind_tick: f32,
+    shooting: bool,            // 射击规则（开局设置）
+    ammo: u32,
+    fire_cd: f32,
+    shots: ProjectilePool,
+    mouse_aim: bool,           // 最近用鼠标瞄准（否则瞬移到最大空隙）
+    last_mouse: Vec2,
+    suspended: bool,           // 有挂起的一局可以恢复
+    slow: EffectSlot,          // 减速
     pu_spawn_timer: f32,       // 道具生成计时器
+    pity: f32,                 // 距上次出现防御类道具的时间
+    wave: WaveRunner,          // 正在进行的阵型
+    wave_due: Vec<PendingSpawn>,
+    events: EventScheduler,    // 随机世界事件
+    wind: Wind,                // 阵风区
+    wells: Vec<gravity::Well>, // 引力井（黑洞）
+    stage: usize,              // 当前阶段（从 0 开始）
+    hazards: Vec<Hazard>,      // 当前阶段已解锁的危险类型
+    stage_fade: f32,           // 阶段切换过渡：1 → 0
+    banners: Banners,          // 屏幕中央的公告横幅
+    texts: FloatingTextPool,   // 加分飘字
+    next_milestone: i32,       // 下一个分数里程碑
+    milestone_pu: Option<f32>, // 里程碑奖励道具的倒计时
+    milestone_pulse: f32,      // 里程碑边框光晕 1 → 0
+    prev_bg: Color,            // 过渡起点的背景色
+    settings: Settings,
+    field: Vec2,               // 逻辑场地尺寸（开局时锁定）
+    field_changed: bool,       // 局内窗口尺寸变了，等待确认
+    seed: u64,                 // 本局随机种子
+    backdrop: Backdrop,        // 由种子生成的背景
+    feedback: FeedbackForm,
+    capture_pending: bool,     // 本帧绘制后截图并打开反馈表单
+    shot: Option<screenshot::Shot>, // 本帧绘制后存一张截图（[F12]）
+    killcam: killcam::Killcam, // 死亡镜头的撞击点和进度
+    toast: Toast,              // 底部一行提示，任何界面都画
+    sfx: Vec<audio::Sfx>,      // 本帧要播的音效，画之前由 Audio::flush 播掉
+    cues: Vec<cues::Cue>,      // 本帧要播的听觉预警，同上
+    intensity: f32,            // 局内紧张程度（0–1），分层音乐用，见 audio.rs
+    sandbox: Sandbox,
 }
 
 impl Game {
-    fn new(best: i32) -> Self {
+    fn new(save: Save) -> Self {
+        let shop = Catalog::load(shop::SHOP_PATH);
+        let max_lives = MAX_LIVES + save.wallet.extra_lives(&shop);
+        // 存档里选着、但等级还没到的角色 / 变异规则不生效
+        let level = xp::level_of(save.xp).0;
+        let character = save.character.min(CHARACTERS.len() - 1);
+        let character = if xp::unlocked(Reward::Character(CHARACTERS[character].key), level) { character } else { 0 };
+        let mut mutators = save.mutators;
+        for m in &MUTATORS {
+            if (m.get)(&mutators) && !xp::unlocked(Reward::Mutator(m.key), level) { (m.toggle)(&mut mutators); }
+        }
+        let settings = Settings::load_or_import(save.settings, save.difficulty);
+        let (menu_entry, play_entry) = settings.as_ref().map_or((0, 0), |s| (s.menu_entry, s.play_entry));
+        i18n::set(settings.as_ref().map_or(Lang::ZhCn, |s| s.lang));
         Self {
-            mode: GameMode::Menu,
-            player: Player { x: 0.0, vx: 0.0 },
+            mode: if settings.is_some() { GameMode::Menu } else { GameMode::Settings },
+            player: Player::new(0.0, PLAYER_W),
+            phys: Physics::load(physics::PHYSICS_PATH),
+            pu_tuning: PowerUpTuning::load(powerups::POWERUPS_PATH),
             obs: ObstaclePool::new(),
             pus: PowerUpPool::new(),
             time_tick: 0.0,
+            run_steps: 0,
+            ghost: None,
+            elapsed: 0.0,
             score: 0,
-            best_score: best,
+            dodged: 0,
+            records: save.records,
+            stats: RunStats::default(),
+            stat_bests: save.stat_bests,
+            new_bests: NewBests::default(),
+            deaths: save.deaths,
+            lifetime: save.lifetime,
+            stats_page: 0,
+            export_msg: None,
+            shop,
+            wallet: save.wallet,
+            shop_cursor: 0,
+            coins: Coins::new(0),
+            cosmetics: Cosmetics::load(cosmetics::COSMETICS_PATH),
+            look: save.look,
+            look_cursor: 0,
+            xp: save.xp,
+            xp_gain: 0,
+            xp_anim: 0.0,
+            missions: {
+                let mut m = save.missions;
+                m.refresh(challenge::today());
+                m
+            },
+            achievement_defs: AchievementTable::load(achievements::ACHIEVEMENTS_PATH),
+            achievements: save.achievements,
+            rivals: save.rivals,
+            ladder: save.ladder,
+            ladder_cursor: 0,
+            profiles: Profiles::load(),
+            profile_cursor: 0,
+            profile_edit: None,
+            profile_delete: None,
+            profile_msg: None,
+            cloud: None,
+            save_warning: save.warning,
+            save_version: save.version,
+            save_extra: save.extra,
+            tampered: save.tampered,
+            top: save.top,
+            name_entry: None,
+            top_rank: None,
+            top_cursor: 0,
+            top_msg: None,
+            top_global: false,
+            online: online::Online::new(),
+            versus: versus::Versus::default(),
+            #[cfg(feature = "twitch")]
+            twitch: twitch::Twitch::default(),
+            #[cfg(feature = "discord")]
+            presence: presence::Presence::default(),
+            #[cfg(feature = "steam")]
+            steam: None,
+            #[cfg(feature = "tts")]
+            speech: speech::Speech::default(),
+            spectate: spectate::Spectate::default(),
+            coop: None,
+            duel: None,
+            hotseat: hotseat::Hotseat::default(),
+            rebind: keybinds::Rebind::default(),
+            main_menu: ListMenu::at(menu_entry),
+            play_list: ListMenu::at(play_entry),
+            play_menu: false,
+            quitting: false,
+            pause_menu: ListMenu::default(),
+            confirm_quit: None,
+            idle: 0.0,
+            resume_in: 0.0,
+            auto_paused: None,
+            options: options::Options::at(options::TAB_A11Y), // 首次启动直接打开无障碍页
+            debug: debug::Debug::default(),
+            console: console::Console::default(),
+            player_name: if save.player_name.is_empty() { "玩家".to_string() } else { save.player_name },
+            challenge_tab: Challenge::Daily,
+            rival_msg: None,
+            naming: None,
+            ach_scroll: 0,
+            still: 0.0,
+            replay: ReplayState::Off,
+            history: history::load(),
+            history_cursor: 0,
+            pending_log: None,
+            forced_seed: None,
+            seed_entry: None,
+            race: None,
+            difficulty: settings.as_ref().map(|s| s.difficulty).unwrap_or_default(),
+            director: Director::new(save.adaptive),
+            mutators,
+            play: PlayMode::Standard,
+            practice: save.practice,
+            mover: Mover::new(save.scheme, save.wrap),
+            practice_panel: PracticePanel::new(),
+            tutorial: Tutorial::new(),
+            tutorial_done: save.tutorial_done,
+            lives: 1,
+            start_lives: save.start_lives.clamp(1, max_lives),
+            loadout: save.loadout.clamp(-LOADOUT_STEPS, LOADOUT_STEPS),
+            character,
+            invuln: 0.0,
+            continued: false,
+            crowd: false,
+            teleport: false,
+            graze: 0,
+            stored_bomb: false,
+            ult: 0.0,
+            overdrive: 0.0,
+            overlap: 0,
+            rewind: false,
+            mult: EffectSlot::default(),
+            combo: 1,
+            combo_timer: 0.0,
+            freeze: EffectSlot::default(),
+            reverse: 0.0,
+            haste: 0.0,
+            blackout: 0.0,
+            blast: None,
+            rewind_buf: VecDeque::new(),
+            rewind_tick: 0.0,
+            shooting: save.shooting,
+            ammo: 0,
+            fire_cd: 0.0,
+            shots: ProjectilePool::new(),
+            mouse_aim: false,
+            last_mouse: Vec2::ZERO,
+            suspended: suspend::exists(),
+            challenge: None,
+            stash: None,
+            daily_history: save.daily,
+            weekly_bests: save.weekly,
+            feel: save.feel,
+            feel_cursor: 0,
+            effects: Effects::new(0),
+            anims: Anims::default(),
             spawn_timer: 0.0,
             spawn_interval: SPAWN_BASE_INTERVAL,
+            breather: false,
             fall_speed: OB_START_SPEED,
-            shake: 0.0,
+            shake: Shake::new(ShakeTuning::load(shake::SHAKE_PATH)),
             shield: 0,
-            slow_timer: 0.0,
+            slow: EffectSlot::default(),
             pu_spawn_timer: 0.0,
+            pity: 0.0,
+            wave: WaveRunner::new(),
+            wave_due: Vec::new(),
+            events: EventScheduler::new(),
+            wind: Wind::new(),
+            wells: Vec::new(),
+            stage: 0,
+            hazards: vec![Hazard::Block],
+            stage_fade: 0.0,
+            banners: Banners::new(),
+            texts: FloatingTextPool::new(),
+            next_milestone: MILESTONE_EVERY,
+            milestone_pu: None,
+            milestone_pulse: 0.0,
+            prev_bg: BLACK,
+            settings: settings.unwrap_or_default(),
+            field: vec2(800.0, 600.0),
+            field_changed: false,
+            seed: 0,
+            backdrop: Backdrop::generate(0),
+            feedback: FeedbackForm::new(),
+            capture_pending: false,
+            shot: None,
+            killcam: killcam::Killcam::default(),
+            toast: Toast::default(),
+            sfx: Vec::new(),
+            cues: Vec::new(),
+            intensity: 0.0,
+            sandbox: Sandbox::new(),
+        }
+    }
+    fn character(&self) -> &'static CharacterDef { characters::get(self.character) }
+    // 世界时间倍率：超载期间除玩家移动以外的一切都变慢
+    fn world_time_scale(&self) -> f32 { if self.overdrive > 0.0 { OVERDRIVE_SCALE } else { 1.0 } }
+    // 变异或车道模式：成绩按规则组合单独记录
+    fn modified(&self) -> bool { self.mutators.any() || self.mover.scheme != Scheme::Free || self.mover.wrap || self.shooting || self.start_lives > 1 || self.loadout != 0 || self.character != 0 }
+    fn rules_tag(&self) -> String {
+        let mut parts: Vec<String> = Vec::new();
+        if self.mutators.any() { parts.push(self.mutators.tag()); }
+        if self.mover.scheme == Scheme::Lanes { parts.push("lanes".to_string()); }
+        if self.mover.wrap { parts.push("wrap".to_string()); }
+        if self.shooting { parts.push("shoot".to_string()); }
+        if self.start_lives > 1 { parts.push(format!("lives{}", self.start_lives)); }
+        if self.loadout != 0 { parts.push(format!("size{:+}", self.loadout)); }
+        if self.character != 0 { parts.push(self.character().key.to_string()); }
+        parts.join("+")
+    }
+    fn rules_labels(&self) -> String {
+        let mut parts: Vec<String> = Vec::new();
+        if self.mutators.any() { parts.push(self.mutators.labels()); }
+        if self.mover.scheme == Scheme::Lanes { parts.push(Scheme::Lanes.label().to_string()); }
+        if self.mover.wrap { parts.push(tr("穿墙").to_string()); }
+        if self.shooting { parts.push(tr("射击").to_string()); }
+        if self.start_lives > 1 { parts.push(trf("{} 条命", &[&self.start_lives])); }
+        if self.loadout != 0 { parts.push(trf("体型 {}", &[&loadout_label(self.loadout)])); }
+        if self.character != 0 { parts.push(trf("角色 {}", &[&tr(self.character().name)])); }
+        parts.join(tr("、"))
+    }
+    // 难度曲线与阶段推进用的时间：禅模式封顶
+    fn curve_time(&self) -> f32 {
+        match self.play {
+            PlayMode::Standard | PlayMode::Versus | PlayMode::Coop | PlayMode::Hotseat => self.elapsed,
+            PlayMode::Zen => self.elapsed.min(ZEN_PLATEAU),
+            PlayMode::Practice | PlayMode::Tutorial => 0.0,
+        }
+    }
+    // 普通方块的尺寸范围（练习模式可调）
+    fn size_range(&self) -> (f32, f32) {
+        if self.play == PlayMode::Practice { (self.practice.size_min, self.practice.size_max) } else { (OB_MIN_SIZE, OB_MAX_SIZE) }
+    }
+    // 碰撞只把障碍弹开：禅模式、教程，或开了无敌的练习模式（控制台的 god 也算）
+    fn harmless(&self) -> bool {
+        matches!(self.play, PlayMode::Zen | PlayMode::Tutorial) || (self.play == PlayMode::Practice && self.practice.invincible) || console::god(self)
+    }
+    // 实际生效的手感（已考虑“减少动态效果”）
+    fn feel_now(&self) -> Feel { self.feel.calmed(motion::calm(self)) }
+    // 最高分表的键：模式 / 难度 / 规则标签（未改规则时为空）
+    fn best_key(&self) -> String {
+        let mode = if self.play == PlayMode::Zen { "zen" } else { "standard" };
+        records::key(mode, self.difficulty, if self.modified() { self.rules_tag() } else { String::new() }.as_str())
+    }
+    // 当前规则对应的最高分：每日挑战取历史最好，每周挑战取本周最好，其余查最高分表
+    fn best(&self) -> i32 {
+        match &self.challenge {
+            Some(c) if c.kind == Challenge::Daily => self.daily_history.iter().map(|r| r.score).max().unwrap_or(0),
+            Some(c) => self.weekly_bests.get(&c.period).copied().unwrap_or(0),
+            None => self.records.score(&self.best_key()),
+        }
+    }
+    // 只记普通模式和禅模式；自适应难度的成绩不记录；挑战写进各自的历史
+    // 续关之后的成绩不再记录（结束时的分数已经记过一次）
+    fn record_best(&mut self) {
+        if !matches!(self.play, PlayMode::Standard | PlayMode::Zen) || self.continued || self.crowd { return; }
+        if let Some(c) = &self.challenge {
+            match c.kind {
+                Challenge::Daily => {
+                    if let Some(r) = self.daily_history.iter_mut().find(|r| r.day == c.period) {
+                        r.score = self.score;
+                        r.elapsed = self.elapsed;
+                        r.dodged = self.dodged;
+                    }
+                    #[cfg(feature = "steam")]
+                    if let Some(s) = self.steam.as_mut().filter(|_| !self.tampered) { s.daily_score(c.period, self.score); }
+                }
+                Challenge::Weekly => {
+                    let e = self.weekly_bests.entry(c.period).or_insert(0);
+                    *e = (*e).max(self.score);
+                }
+            }
+            if !self.tampered {
+                let e = rivals::Entry { name: self.player_name.clone(), score: self.score, elapsed: self.elapsed, mine: true };
+                self.rivals.add(c.kind, c.period, e);
+            }
+        } else if !self.director.enabled {
+            let key = self.best_key();
+            // 刷新纪录的这一局存为最佳回放，之后同规则开局给幽灵用
+            if let ReplayState::Recording(r) = &self.replay {
+                if self.score > self.records.score(&key) { ghost::save_best(&key, r); }
+            }
+            let e = BestEntry { score: self.score, day: challenge::today(), seed: self.seed };
+            self.records.record(key, e);
+            #[cfg(feature = "steam")]
+            if let Some(s) = self.steam.as_mut().filter(|_| !self.tampered) { s.best_score(self.records.top_plain()); }
         }
     }
+    // 挑战和教程用固定规则：普通难度、关闭自适应、无变异、自由移动不穿墙、不能射击；
+    // 玩家自己的选择先收起来，回到菜单时还原
+    fn use_fixed_rules(&mut self) {
+        if self.stash.is_none() {
+            self.stash = Some(challenge::Stash {
+                difficulty: self.difficulty,
+                adaptive: self.director.enabled,
+                mutators: self.mutators,
+                scheme: self.mover.scheme,
+                wrap: self.mover.wrap,
+                shooting: self.shooting,
+                start_lives: self.start_lives,
+                loadout: self.loadout,
+                character: self.character,
+            });
+        }
+        self.difficulty = Difficulty::Normal;
+        self.mover.scheme = Scheme::Free;
+        self.mover.wrap = false;
+        self.shooting = false;
+        self.director.enabled = false;
+        self.mutators = Mutators::default();
+        self.start_lives = 1;
+        self.loadout = 0;
+        self.character = 0;
+    }
+    // 挑战：种子和变异取自当前日期/周
+    fn start_challenge(&mut self, kind: Challenge) {
+        let period = kind.current();
+        if kind == Challenge::Daily && challenge::result_for(&self.daily_history, period).is_some() { return; }
+        self.use_fixed_rules();
+        self.mutators = kind.mutators(period);
+        self.challenge = Some(ChallengeRun { kind, period });
+        self.play = PlayMode::Standard;
+        if kind == Challenge::Daily {
+            self.daily_history.push(DailyResult { day: period, score: 0, elapsed: 0.0, dodged: 0, tag: self.mutators.tag() });
+            if self.daily_history.len() > challenge::HISTORY_MAX { self.daily_history.remove(0); }
+        }
+        save_game(self);
+        self.reset_round();
+    }
+    // 能不能进前十：普通模式（含挑战）、未续关、未开自适应、全速、无辅助移动、判定不比标准宽松
+    fn top_eligible(&self) -> bool {
+        self.play == PlayMode::Standard && !self.continued && !self.crowd && !self.director.enabled && !self.settings.slowed() && !self.settings.assisted() && !self.lenient()
+            && !self.replay.watching() && self.top.qualifies(self.score)
+    }
+    // 命中宽容度（两侧收多少像素, 压着几步才算撞上）；挑战和对战类玩法固定用标准值。设置里按 60 帧算，模拟步是 120Hz
+    fn forgiveness(&self) -> (f32, u32) {
+        if self.challenge.is_some() || matches!(self.play, PlayMode::Versus | PlayMode::Hotseat) { return (settings::HITBOX_INSET as f32, 0); }
+        (self.settings.hitbox_inset as f32, (self.settings.hit_grace as f32 / 60.0 / FIXED_DT).round() as u32)
+    }
+    fn lenient(&self) -> bool {
+        let (inset, grace) = self.forgiveness();
+        inset > settings::HITBOX_INSET as f32 || grace > 0
+    }
+    // 离开结束画面：进了前十先输入名字
+    fn end_over(&mut self, retry: bool) {
+        if self.top_eligible() {
+            self.name_entry = Some(NameEntry::new(&self.player_name, retry));
+            self.mode = GameMode::NameEntry;
+        } else if retry {
+            self.reset_round();
+        } else {
+            self.mode = GameMode::Summary;
+        }
+    }
+    fn open_challenges(&mut self, tab: Challenge) {
+        self.challenge_tab = tab;
+        self.rival_msg = None;
+        self.naming = None;
+        self.mode = GameMode::Challenges;
+    }
+    fn start_tutorial(&mut self) {
+        self.use_fixed_rules();
+        self.play = PlayMode::Tutorial;
+        self.reset_round();
+    }
+    // 每局一次，只在普通模式的非挑战局里提供
+    fn can_continue(&self) -> bool { !self.continued && self.challenge.is_none() && self.play == PlayMode::Standard && !self.replay.watching() }
+    // 续关：分数、时间、难度进度原样保留，清屏并给一段无敌时间
+    fn continue_run(&mut self) {
+        self.continued = true;
+        self.pending_log = None;
+        replay::on_continue(self);
+        self.obs.clear_all();
+        self.wells.clear();
+        self.coins.clear();
+        self.wave.clear();
+        self.invuln = CONTINUE_INVULN_TIME;
+        self.resume();
+    }
+    // 回到局内：先倒数 3-2-1 让玩家看清场面（看回放时不用）
+    fn resume(&mut self) {
+        self.mode = GameMode::Playing;
+        if !self.replay.watching() { self.resume_in = RESUME_COUNTDOWN; }
+    }
+    // 结算：填入时间/躲避数、并入生涯统计、发经验，并刷新各项历史最佳（只记普通模式、未续关的局）
+    fn finish_stats(&mut self, prev_best: i32) {
+        self.stats.elapsed = self.elapsed;
+        self.stats.dodged = self.dodged;
+        self.stats.deaths += 1;
+        if !matches!(self.play, PlayMode::Tutorial | PlayMode::Hotseat) { self.lifetime.add(&self.stats, self.score); }
+        self.new_bests = NewBests::default();
+        // 经验：普通模式的每一局都给（含挑战、续关）
+        self.xp_anim = 0.0;
+        self.xp_gain = if self.play == PlayMode::Standard { xp::run_xp(self.score, &self.stats) } else { 0 };
+        self.xp += self.xp_gain;
+        if self.play != PlayMode::Standard || self.continued { return; }
+        self.new_bests = self.stat_bests.record(&self.stats);
+        self.new_bests.score = self.best() > prev_best;
+    }
+    // —— 外观：选中的皮肤/主题如果不再满足解锁条件（比如配置改了）就退回第一项 ——
+    fn progress(&self) -> Progress<'_> {
+        Progress { level: self.level(), best_score: self.records.top_plain(), stat_bests: &self.stat_bests, owned: &self.wallet.owned }
+    }
+    fn level(&self) -> u32 { xp::level_of(self.xp).0 }
+    fn skin(&self) -> &Skin {
+        let s = self.cosmetics.skin(&self.look.skin);
+        if s.unlock.met(&s.key, &self.progress()) { s } else { &self.cosmetics.skins[0] }
+    }
+    fn theme(&self) -> &Theme {
+        let t = self.cosmetics.theme(&self.look.theme);
+        if t.unlock.met(&t.key, &self.progress()) { t } else { &self.cosmetics.themes[0] }
+    }
+    fn palette(&self) -> &Palette { &self.theme().palette }
+    // 商店解锁的天赋和命数上限只在玩家自己的规则下生效（挑战/教程不受影响）
+    fn unlocks_apply(&self) -> bool { self.stash.is_none() }
+    fn max_lives(&self) -> u32 { MAX_LIVES + if self.unlocks_apply() { self.wallet.extra_lives(&self.shop) } else { 0 } }
+    // 一局真正结束（不再续关）时写入历史；录了回放就一并存下
+    fn flush_history(&mut self) {
+        if let Some(mut e) = self.pending_log.take() {
+            if let ReplayState::Recording(r) = &self.replay { e.replay = r.save(e.id).is_ok(); }
+            history::append(&mut self.history, e);
+        }
+        // 正在看的回放留着：开局要从它取种子和规则
+        if !self.replay.watching() { self.replay = ReplayState::Off; }
+    }
+    // 导出 CSV / JSON 用的数据视图
+    fn export_data(&self) -> export::ExportData<'_> {
+        export::ExportData { history: &self.history, lifetime: &self.lifetime, bests: &self.stat_bests, deaths: &self.deaths.deaths }
+    }
+    // 看完回放：丢掉回放期间的一切，从存档重新载入
+    fn reload_profile(&mut self) {
+        let (field, cloud) = (self.field, self.cloud.take());
+        #[cfg(feature = "twitch")]
+        let twitch = std::mem::take(&mut self.twitch);
+        #[cfg(feature = "steam")]
+        let steam = self.steam.take();
+        *self = Game::new(load_save());
+        self.field = field;
+        self.cloud = cloud;
+        #[cfg(feature = "twitch")]
+        { self.twitch = twitch; }
+        #[cfg(feature = "steam")]
+        { self.steam = steam; }
+    }
+    // 慢速局在后面注明速度，辅助移动、宽松判定的局也注明
+    fn mode_label(&self) -> String {
+        let mut label = self.base_mode_label();
+        if self.settings.slowed() { label = trf("{}·{}%速", &[&label, &(self.settings.speed_scale * 100.0).round()]); }
+        if self.settings.assisted() { label += tr("·辅助"); }
+        if self.lenient() { label += tr("·宽判"); }
+        label
+    }
+    fn base_mode_label(&self) -> String {
+        if let Some(c) = &self.challenge { return c.kind.label().to_string(); }
+        match self.play {
+            PlayMode::Standard if self.modified() => trf("{}·变异", &[&self.difficulty.label()]),
+            PlayMode::Standard => self.difficulty.label().to_string(),
+            PlayMode::Zen => tr("禅模式").to_string(),
+            PlayMode::Practice => tr("练习").to_string(),
+            PlayMode::Tutorial => tr("教程").to_string(),
+            PlayMode::Versus => tr("联机对战").to_string(),
+            PlayMode::Coop => tr("双人合作").to_string(),
+            PlayMode::Hotseat => tr("轮流赛").to_string(),
+        }
+    }
+    // 还能不能原地重开（每日挑战只有一次机会）
+    fn can_retry(&self) -> bool { self.challenge.as_ref().is_none_or(|c| !c.kind.single_attempt()) && !self.replay.watching() && !matches!(self.play, PlayMode::Versus | PlayMode::Hotseat) }
+    // 离开本局回到菜单：结束挑战并还原玩家原来的选择
+    fn leave_run(&mut self) {
+        if self.replay.watching() { self.reload_profile(); return; }
+        // 禅模式不会结束，离开时记一次成绩
+        if self.play == PlayMode::Zen { self.record_best(); }
+        self.flush_history();
+        self.forced_seed = None;
+        self.race = None;
+        self.challenge = None;
+        if let Some(st) = self.stash.take() {
+            self.difficulty = st.difficulty;
+            self.director.enabled = st.adaptive;
+            self.mutators = st.mutators;
+            self.mover.scheme = st.scheme;
+            self.mover.wrap = st.wrap;
+            self.shooting = st.shooting;
+            self.start_lives = st.start_lives;
+            self.loadout = st.loadout;
+            self.character = st.character;
+        }
+        save_game(self);
+    }
+
     fn reset_round(&mut self) {
-        self.player.x = screen_width() * 0.5 - PLAYER_W * 0.5;
-        self.player.vx = 0.0;
+        self.flush_history();
+        self.top_rank = None;
+        // 观看回放时种子和场地尺寸取自回放
+        self.seed = match (self.replay.header(), &self.challenge) {
+            (Some(h), _) => h.seed,
+            (None, Some(c)) => c.kind.seed(c.period),
+            (None, None) => self.forced_seed.unwrap_or_else(trace::now_ms),
+        };
+        rand::srand(self.seed);
+        self.backdrop = Backdrop::generate(self.seed);
+        self.effects = Effects::new(self.seed);
+        self.anims = Anims::default();
+        if replay::headless(self) { self.effects.particles.disable(); }
+        self.coins = Coins::new(self.seed);
+        self.missions.start_run();
+        self.still = 0.0;
+        self.resume_in = 0.0;
+        self.field = match self.replay.header() {
+            Some(h) => vec2(h.field.0, h.field.1),
+            None if self.play == PlayMode::Versus => vec2(versus::FIELD.0, versus::FIELD.1),
+            None => target_field(self),
+        };
+        self.field_changed = false;
+        let (w_mul, speed_mul) = loadout_muls(self.loadout);
+        let w = PLAYER_W * self.mutators.player_scale() * w_mul;
+        self.player = Player::new(self.field.x * 0.5 - w * 0.5, w);
+        let speed_mul = speed_mul * self.character().speed_mul;
+        self.player.acc = self.phys.acc * speed_mul;
+        self.player.speed_max = self.phys.speed_max * speed_mul;
+        self.mover.start(&mut self.player, self.field.x);
+        coop::setup(self);
+        self.tutorial = Tutorial::new();
         self.obs.live.clear(); self.obs.dead.clear();
         self.pus.live.clear(); self.pus.dead.clear();
         self.time_tick = 0.0;
+        self.elapsed = 0.0;
         self.score = 0;
+        self.dodged = 0;
         self.spawn_timer = 0.0;
         self.spawn_interval = SPAWN_BASE_INTERVAL;
+        self.breather = false;
         self.fall_speed = OB_START_SPEED;
-        self.shake = 0.0;
-        self.shield = 0;
-        self.slow_timer = 0.0;
+        self.intensity = 0.0;
+        self.shake.clear();
+        self.shield = self.difficulty.tuning().start_shield + self.character().extra_shield;
+        if self.unlocks_apply() && self.wallet.has_perk(&self.shop, Perk::StartShield) { self.shield += 1; }
+        self.lives = self.start_lives;
+        self.invuln = 0.0;
+        self.continued = false;
+        self.crowd = false;
+        self.teleport = false;
+        self.graze = 0;
+        self.stored_bomb = false;
+        self.ult = 0.0;
+        self.overdrive = 0.0;
+        self.overlap = 0;
+        self.rewind = false;
+        self.mult.clear();
+        self.combo = 1;
+        self.combo_timer = 0.0;
+        self.freeze.clear();
+        self.reverse = 0.0;
+        self.haste = 0.0;
+        self.blackout = 0.0;
+        self.blast = None;
+        self.rewind_buf.clear();
+        self.rewind_tick = 0.0;
+        self.ammo = if self.shooting { shooting::START_AMMO } else { 0 };
+        self.fire_cd = 0.0;
+        self.shots.clear();
+        self.slow.clear();
         self.pu_spawn_timer = 0.0;
+        self.pity = 0.0;
+        self.wave.clear();
+        self.events.reset();
+        self.wind.reset();
+        self.wells.clear();
+        self.director.start_run();
+        self.stage = 0;
+        self.hazards.clear();
+        self.hazards.push(Hazard::Block);
+        self.stage_fade = 0.0;
+        self.banners.clear();
+        self.stats = RunStats::default();
+        self.new_bests = NewBests::default();
+        self.texts.clear();
+        self.next_milestone = MILESTONE_EVERY;
+        self.milestone_pu = None;
+        self.milestone_pulse = 0.0;
         self.mode = GameMode::Playing;
+        replay::start(self);
+        ghost::start(self);
     }
 }
 
 // ===== 工具函数 =====
+// 历史记录里的死因：按撞上的障碍外形粗分
+fn death_cause(o: &Obstacle) -> &'static str {
+    if o.armor == Armor::Armored { tr("装甲障碍") }
+    else if o.rect.w > o.rect.h * 1.8 { tr("宽板") }
+    else if o.vx.abs() > 1.0 { tr("斜飞障碍") }
+    else if o.rect.w < 30.0 { tr("飞镖") }
+    else { tr("方块") }
+}
+
 fn rects_overlap(a: Rect, b: Rect) -> bool {
     a.x < b.x + b.w && a.x + a.w > b.x && a.y < b.y + b.h && a.y + a.h > b.y
 }
 
-fn input_axis() -> f32 {
-    let mut dir = 0.0;
-    if is_key_down(KeyCode::Left) || is_key_down(KeyCode::A) { dir -= 1.0; }
-    if is_key_down(KeyCode::Right) || is_key_down(KeyCode::D) { dir += 1.0; }
-    dir
+// 穿墙时玩家跨过右边缘，左侧露出的那一截（没跨边缘时为 None）
+fn wrap_ghost(r: Rect, field_w: f32) -> Option<Rect> {
+    (r.x + r.w > field_w).then(|| Rect::new(r.x - field_w, r.y, r.w, r.h))
 }
 
-fn difficulty_curve(elapsed: f32, fall_base: f32, spawn_base: f32) -> (f32, f32) {
-    let fall = fall_base + elapsed * OB_ACC_PER_SEC;
-    let spawn = (spawn_base - elapsed * 0.02).max(SPAWN_MIN_INTERVAL);
-    (fall, spawn)
+// 碰撞用的瘦身命中盒（两侧各收 6 像素，迷你玩家按比例收）
+fn player_hitbox(x: f32, w: f32) -> Rect { inset_hitbox(x, w, settings::HITBOX_INSET as f32) }
+
+// 按玩家选的宽容度收（见 Game::forgiveness）
+fn inset_hitbox(x: f32, w: f32, inset: f32) -> Rect {
+    let inset = inset * w / PLAYER_W;
+    Rect::new(x + inset, PLAYER_Y, w - inset * 2.0, PLAYER_H)
 }
 
-fn save_best(best: i32) {
-    let _ = std::fs::write("save.json", serde_json::to_string(&Save { best }).unwrap());
+// wrap：穿墙，出左边从右边进来（x 始终落在 [0, field_w)）
+fn step_player(p: &mut Player, phys: &Physics, input: MoveInput, acc_mul: f32, dt: f32, field_w: f32, wrap: bool) {
+    p.dash_cd = (p.dash_cd - dt).max(0.0);
+    // 冲刺：朝输入方向瞬间提速，期间不受最高速度限制
+    if input.dash && p.dash_cd <= 0.0 && input.dir != 0.0 {
+        p.vx = input.dir.signum() * phys.dash_speed;
+        p.dash_timer = phys.dash_time;
+        p.dash_cd = phys.dash_cooldown;
+    }
+    if p.dash_timer > 0.0 {
+        p.dash_timer = (p.dash_timer - dt).max(0.0);
+    } else {
+        if input.dir.abs() > 0.0 {
+            p.vx += input.dir * p.acc * acc_mul * dt;
+        } else {
+            p.vx *= (1.0 - phys.decay).powf(dt * 1000.0);
+        }
+        p.vx += input.push * dt;
+        // 摇杆推不满时最高速度也按比例降低，方便微调位置
+        let cap = p.speed_max * if input.dir != 0.0 { input.dir.abs().min(1.0) } else { 1.0 };
+        p.vx = p.vx.clamp(-cap, cap);
+    }
+    if wrap {
+        p.x = (p.x + p.vx * dt).rem_euclid(field_w);
+        return;
+    }
+    p.x = (p.x + p.vx * dt).clamp(0.0, field_w - p.w);
+    if p.x <= 0.0 || p.x >= field_w - p.w { p.dash_timer = 0.0; }
 }
 
-fn load_best() -> i32 {
-    std::fs::read_to_string("save.json")
-        .ok()
-        .and_then(|s| serde_json::from_str::<Save>(&s).ok())
-        .map(|v| v.best)
-        .unwrap_or(0)
+// 开局体型 → (宽度倍率, 速度与加速度倍率)
+fn loadout_muls(loadout: i32) -> (f32, f32) {
+    (1.0 + loadout as f32 * LOADOUT_W_STEP, 1.0 + loadout as f32 * LOADOUT_SPEED_STEP)
 }
 
-// ===== 逻辑：固定时间步更新 =====
-fn update_game(game: &mut Game, dt: f32, _res: &Resources) {
-    match game.mode {
-        GameMode::Menu => {
-            if is_key_pressed(KeyCode::Space) { game.reset_round(); }
-        }
-        GameMode::Playing => {
-            // —— 移动：加速度+限速+衰减 —— 
-            let dir = input_axis();
-            if dir.abs() > 0.0 {
-                game.player.vx += dir * PLAYER_ACC * dt;
-            } else {
-                game.player.vx *= (1.0 - PLAYER_DECAY).powf(dt * 1000.0);
-            }
-            game.player.vx = game.player.vx.clamp(-PLAYER_SPEED_MAX, PLAYER_SPEED_MAX);
-            game.player.x = (game.player.x + game.player.vx * dt)
-                .clamp(0.0, screen_width() - PLAYER_W);
+fn loadout_label(loadout: i32) -> String {
+    match loadout {
+        0 => tr("标准").to_string(),
+        n if n < 0 => trf("窄{}", &[&(-n)]),
+        n => trf("宽{}", &[&n]),
+    }
+}
 
-            // —— 减速效果衰减 —— 
-            if game.slow_timer > 0.0 {
-                game.slow_timer = (game.slow_timer - dt).max(0.0);
-            }
-            let slow_mul = if game.slow_timer > 0.0 { SLOW_FACTOR } else { 1.0 };
-
-            // —— 难度递增 —— 
-            let elapsed = macroquad::time::get_time() as f32;
-            let (fall_spd, spawn_itv) = difficulty_curve(elapsed, OB_START_SPEED, game.spawn_interval);
-            game.fall_speed = fall_spd * slow_mul;
-            game.spawn_interval = (spawn_itv / slow_mul).max(SPAWN_MIN_INTERVAL);
-
-            // —— 生成障碍 —— 
-            game.spawn_timer += dt;
-            if game.spawn_timer >= game.spawn_interval {
-                game.spawn_timer = 0.0;
-                let size = rand::gen_range(OB_MIN_SIZE, OB_MAX_SIZE);
-                let x = rand::gen_range(0.0, screen_width() - size);
-                let y = -size - 10.0;
-                let vy = game.fall_speed * rand::gen_range(0.9, 1.3);
-                game.obs.spawn(Rect::new(x, y, size, size), vy);
-            }
-
-            // —— 生成道具（随机一种） —— 
-            game.pu_spawn_timer += dt;
-            if game.pu_spawn_timer >= PU_SPAWN_INTERVAL {
-                game.pu_spawn_timer = 0.0;
-                if rand::gen_range(0.0, 1.0) < 0.30 {
-                    let x = rand::gen_range(PU_SIZE, screen_width() - PU_SIZE);
-                    let kind = match rand::gen_range(0, 3) {
-                        0 => PowerUpKind::Shield,
-                        1 => PowerUpKind::Slow,
-                        _ => PowerUpKind::Bomb,
-                    };
-                    game.pus.spawn(x, -PU_SIZE - 8.0, kind);
-                }
-            }
+// 开启射击时四分之一的道具换成弹药；越往后越可能是陷阱
+fn random_powerup(game: &Game) -> PowerUpKind {
+    let trap_chance = ((game.curve_time() / TRAP_RAMP_TIME).min(1.0) * TRAP_MAX_CHANCE).max(game.character().trap_floor);
+    if rand::gen_range(0.0, 1.0) < trap_chance {
+        return match rand::gen_range(0, 3) {
+            0 => PowerUpKind::Reverse,
+            1 => PowerUpKind::Haste,
+            _ => PowerUpKind::Blackout,
+        };
+    }
+    if game.shooting && rand::gen_range(0, 4) == 0 { return PowerUpKind::Ammo; }
+    roll_table(game.pu_tuning.spawn.weights(game.difficulty, game.curve_time()))
+}
 
-            // —— 更新障碍 & 道具 —— 
-            game.obs.update_and_sweep(screen_height(), dt);
-            game.pus.update_and_sweep(screen_height(), dt);
+// 按掉落表（assets/powerups.json）加权抽一种
+fn roll_table(weights: &[(PowerUpKind, u32)]) -> PowerUpKind {
+    let total = powerups::total_weight(weights);
+    powerups::pick(weights, if total == 0 { 0 } else { rand::gen_range(0, total) })
+}
 
-            // —— 计分 —— 
-            game.time_tick += dt;
-            while game.time_tick >= 0.4 {
-                game.time_tick -= 0.4;
-                game.score += 1;
-            }
+// 移动方向的输入源：依次问，第一个给出方向的说了算。手柄按键在 trace.rs 里已经并进键盘，
+// 摇杆的模拟量排在最前，推得不满时不会被它翻译出的方向键盖掉；辅助移动垫底
+const INPUT_SOURCES: [fn(&Game) -> f32; 5] = [stick_axis, keyboard_axis, touch::axis, mouse_axis, assist::axis];
+const STEER_DEADZONE: f32 = 6.0; // 挡板中心离目标这么近就不再动
 
-            // —— 拾取道具 —— 
-            let pbox = Rect::new(game.player.x, PLAYER_Y, PLAYER_W, PLAYER_H);
-            if let Some(kind) = game.pus.pick_at(pbox) {
-                match kind {
-                    PowerUpKind::Shield => { game.shield = (game.shield + 1).min(3); }
-                    PowerUpKind::Slow   => { game.slow_timer = SLOW_DURATION; }
-                    PowerUpKind::Bomb   => { game.obs.clear_all(); game.shake = 6.0; }
-                }
-            }
+fn keyboard_axis(game: &Game) -> f32 {
+    let keys = &game.settings.keys;
+    let mut dir = 0.0;
+    if keys.left.down() { dir -= 1.0; }
+    if keys.right.down() { dir += 1.0; }
+    dir
+}
 
-            // —— 碰撞（护盾可抵消；命中盒瘦身） —— 
-            let mut hit = Rect::new(game.player.x, PLAYER_Y, PLAYER_W, PLAYER_H);
-            hit.x += 6.0; hit.w -= 12.0;
+// 左摇杆：按死区和响应曲线换算成 -1..=1 的小数
+fn stick_axis(game: &Game) -> f32 { game.settings.stick.apply(trace::stick_x()) }
 
-            let mut collided_index: Option<usize> = None;
-            for (i, o) in game.obs.live.iter().enumerate() {
-                if rects_overlap(o.rect, hit) { collided_index = Some(i); break; }
-            }
-            if let Some(i) = collided_index {
-                if game.shield > 0 {
-                    // 护盾抵消一次：移除该障碍、护盾-1、轻微震屏
-                    let dead = game.obs.live.swap_remove(i);
-                    game.obs.dead.push(dead);
-                    game.shield -= 1;
-                    game.shake = game.shake.max(4.0);
-                } else {
-                    // 游戏结束
-                    game.best_score = game.best_score.max(game.score);
-                    save_best(game.best_score);
-                    game.mode = GameMode::GameOver;
-                    game.shake = 10.0;
-                }
-            }
+// 鼠标操控：设置里打开、且鼠标比方向键后动过时，朝光标开过去
+fn mouse_axis(game: &Game) -> f32 {
+    if !game.settings.mouse || !game.mouse_aim { return 0.0; }
+    steer(game, field_camera(view_size(game), Vec2::ZERO).screen_to_world(game.last_mouse).x)
+}
 
-            if is_key_pressed(KeyCode::P) { game.mode = GameMode::Paused; }
-        }
-        GameMode::Paused => {
-            if is_key_pressed(KeyCode::P) { game.mode = GameMode::Playing; }
-            if is_key_pressed(KeyCode::R) { game.reset_round(); }
-            if is_key_pressed(KeyCode::Escape) { game.mode = GameMode::Menu; }
-        }
-        GameMode::GameOver => {
-            if is_key_pressed(KeyCode::R) { game.reset_round(); }
-            if is_key_pressed(KeyCode::Escape) { game.mode = GameMode::Menu; }
-        }
+// 朝场地上的某个 x 开过去：只给方向，加速度和限速照常（不是瞬移），快到时反向刹车免得冲过头
+fn steer(game: &Game, target: f32) -> f32 {
+    let p = &game.player;
+    let dx = target - p.rect().center().x;
+    if dx.abs() < STEER_DEADZONE { return 0.0; }
+    let brake = p.vx * p.vx / (2.0 * p.acc); // 全力反向刹停要走的距离
+    if p.vx * dx > 0.0 && brake >= dx.abs() { -dx.signum() } else { dx.signum() }
+}
+
+fn input_axis(game: &Game) -> f32 { INPUT_SOURCES.iter().map(|f| f(game)).find(|d| *d != 0.0).unwrap_or(0.0) }
+
+// 本步的键盘/鼠标输入（录制回放时逐步记下）
+fn read_step_input(game: &mut Game) -> StepInput {
+    let keys = game.settings.keys;
+    // 鼠标最近动过（比方向键晚）：瞬移去鼠标处，开了鼠标操控时挡板也跟着光标；否则瞬移去玩家高度上最大的空隙
+    let mouse: Vec2 = mouse_position().into();
+    if mouse != game.last_mouse { game.mouse_aim = true; game.last_mouse = mouse; }
+    if keyboard_axis(game) != 0.0 { game.mouse_aim = false; }
+    let mv = move_input(game);
+    let teleport = game.teleport && is_key_pressed(teleport_key(game));
+    // 触屏第二指点按：有炸弹放炸弹，否则开超载
+    let tap = touch::second_tap();
+    StepInput {
+        dir: (mv.dir > 0.0) as i8 - (mv.dir < 0.0) as i8,
+        analog: (mv.dir.abs() < 1.0 && mv.dir != 0.0).then(|| (mv.dir.abs() * 100.0).round().max(1.0) as u8),
+        dash: mv.dash,
+        teleport,
+        aim: (teleport && game.mouse_aim).then(|| field_camera(view_size(game), Vec2::ZERO).screen_to_world(mouse).x),
+        overdrive: keys.overdrive.pressed() || (tap && !game.stored_bomb),
+        bomb: keys.bomb.pressed() || (tap && game.stored_bomb),
+        fire: is_key_pressed(KeyCode::Space),
     }
+}
 
-    // 震动衰减
-    if game.shake > 0.0 {
-        game.shake = (game.shake - 60.0 * dt).max(0.0);
+fn move_input(game: &Game) -> MoveInput {
+    MoveInput {
+        dir: input_axis(game),
+        dash: game.settings.keys.dash.pressed(),
+        push: 0.0,
     }
 }
 
-// ===== 绘制 =====
-fn draw_text_center(font: &Font, text: &str, y: f32, size: f32, color: Color) {
-    let dim = measure_text(text, Some(font), size as u16, 1.0);
-    let x = screen_width() * 0.5 - dim.width * 0.5;
-    draw_text_ex(text, x, y, TextParams { font: Some(font), font_size: size as u16, color, ..Default::default() });
+// 所有加分的唯一入口：倍率在这里统一相乘，叠加新的倍率来源时只改 score_mul
+// 叠加规则下每层多 x1（按 falloff 递减后取整，至少 x2）
+fn score_mul(game: &Game) -> i32 {
+    if !game.mult.active() { return 1; }
+    1 + (game.mult.strength(game.pu_tuning.stacking.multiplier).round() as i32).max(1)
+}
+fn award_points(game: &mut Game, base: i32) -> i32 {
+    let pts = base * score_mul(game);
+    game.score += pts;
+    pts
+}
+// 加分并在 at 处飘出 "+N"
+fn award_points_at(game: &mut Game, base: i32, at: Vec2, color: Color) {
+    let pts = award_points(game, base);
+    if pts > 0 { game.texts.spawn(at, &format!("+{}", pts), color); }
 }
 
-fn draw_hud(font: &Font, game: &Game) {
-    draw_rectangle(0.0, 0.0, screen_width(), 46.0, Color::from_rgba(20, 24, 32, 220));
-    draw_text_ex(&format!("SCORE: {:>4}", game.score), 16.0, 30.0, TextParams { font: Some(font), font_size: 28, color: YELLOW, ..Default::default() });
-    draw_text_ex(&format!("BEST:  {:>4}", game.best_score), 190.0, 30.0, TextParams { font: Some(font), font_size: 28, color: GOLD, ..Default::default() });
+// 擦边或拾取：连击 +1 并重新开始计时
+fn bump_combo(game: &mut Game) {
+    game.combo = (game.combo + 1).min(COMBO_MAX);
+    game.combo_timer = COMBO_WINDOW;
+    game.stats.max_combo = game.stats.max_combo.max(game.combo);
+}
+fn break_combo(game: &mut Game) {
+    game.combo = 1;
+    game.combo_timer = 0.0;
+}
 
-    // 道具状态提示
-    let slow_txt = if game.slow_timer > 0.0 { format!("SLOW:{:.1}s", game.slow_timer) } else { "SLOW:OFF".to_string() };
-    let shield_txt = format!("SHIELD:{}", game.shield);
-    draw_text_ex(&shield_txt, screen_width() - 300.0, 30.0, TextParams { font: Some(font), font_size: 22, color: SKYBLUE, ..Default::default() });
-    draw_text_ex(&slow_txt,   screen_width() - 170.0, 30.0, TextParams { font: Some(font), font_size: 22, color: LIME, ..Default::default() });
+// 回到 REWIND_BACK 秒前最近的快照（不够久就用最早的一张）
+fn rewind_world(game: &mut Game) {
+    let target = game.elapsed - REWIND_BACK;
+    let i = game.rewind_buf.iter().rposition(|(t, _)| *t <= target).unwrap_or(0);
+    let snap = game.rewind_buf.drain(..).nth(i);
+    if let Some((_, s)) = snap { suspend::restore_state(game, s); }
+    game.rewind = false;
+    game.rewind_tick = 0.0;
 }
 
-fn draw_player(game: &Game) {
-    let r = Rect::new(game.player.x, PLAYER_Y, PLAYER_W, PLAYER_H);
-    draw_rectangle(r.x, r.y, r.w, r.h, Color::from_rgba(90, 200, 255, 255));
-    draw_rectangle(r.x + 10.0, r.y + 4.0, r.w - 20.0, 3.0, Color::from_rgba(200, 245, 255, 255));
-    // 若有护盾，画一圈外发光
-    if game.shield > 0 {
-        draw_rectangle_lines(r.x - 4.0, r.y - 4.0, r.w + 8.0, r.h + 8.0, 2.0, Color::from_rgba(120, 220, 255, 220));
+// 引爆炸弹（拾取炸弹和擦边攒的手动炸弹共用）：以玩家为中心放出冲击波；
+// 难度参数里的 classic_bomb 保留旧版的全屏清除
+fn detonate_bomb(game: &mut Game, feel: &Feel) {
+    game.shake.add(Jolt::Bomb);
+    audio::play(game, Sfx::Bomb);
+    game.effects.hit_stop(feel, effects::HIT_STOP_BOMB);
+    rumble(game);
+    let tuning = game.difficulty.tuning();
+    if tuning.classic_bomb {
+        for o in &game.obs.live {
+            game.effects.emit(feel, o.rect.center(), &particles::BLAST);
+            game.anims.crumble(o.rect);
+        }
+        game.obs.clear_all();
+        return;
     }
+    game.blast = Some(Blast { center: game.player.rect().center(), age: 0.0, radius: tuning.bomb_radius });
 }
 
-fn draw_obstacles(game: &Game) {
-    for o in &game.obs.live {
-        draw_rectangle(o.rect.x, o.rect.y, o.rect.w, o.rect.h, Color::from_rgba(255, 100, 100, 230));
-        draw_rectangle_lines(o.rect.x, o.rect.y, o.rect.w, o.rect.h, 2.0, Color::from_rgba(255, 180, 180, 240));
-    }
+fn circle_hits_rect(c: Vec2, r: f32, rect: Rect) -> bool {
+    let nearest = vec2(c.x.clamp(rect.x, rect.x + rect.w), c.y.clamp(rect.y, rect.y + rect.h));
+    nearest.distance(c) <= r
 }
 
-fn draw_powerups(game: &Game) {
-    for p in &game.pus.live {
-        match p.kind {
-            PowerUpKind::Shield => {
-                draw_circle(p.rect.x + p.rect.w/2.0, p.rect.y + p.rect.h/2.0, p.rect.w*0.45, SKYBLUE);
-            }
-            PowerUpKind::Slow => {
-                draw_circle(p.rect.x + p.rect.w/2.0, p.rect.y + p.rect.h/2.0, p.rect.w*0.45, LIME);
-            }
-            PowerUpKind::Bomb => {
-                draw_circle(p.rect.x + p.rect.w/2.0, p.rect.y + p.rect.h/2.0, p.rect.w*0.45, ORANGE);
-            }
+// 冲击波扩散一步，炸掉圆环已经扫到的障碍
+fn update_blast(game: &mut Game, feel: &Feel, dt: f32) {
+    let Some(b) = &mut game.blast else { return };
+    b.age += dt;
+    let (center, reach, done) = (b.center, b.radius * (b.age / BLAST_TIME).min(1.0), b.age >= BLAST_TIME);
+    let mut i = 0;
+    while i < game.obs.live.len() {
+        if circle_hits_rect(center, reach, game.obs.live[i].rect) {
+            let dead = game.obs.live.swap_remove(i);
+            game.effects.emit(feel, dead.rect.center(), &particles::BLAST);
+            game.anims.crumble(dead.rect);
+            award_points_at(game, BLAST_POINTS, dead.rect.center(), ORANGE);
+            game.obs.dead.push(dead);
+        } else {
+            i += 1;
         }
-        draw_rectangle_lines(p.rect.x, p.rect.y, p.rect.w, p.rect.h, 1.5, WHITE);
     }
+    if done { game.blast = None; }
 }
 
-fn draw_game(game: &Game, res: &Resources) {
-    // 简单相机震动偏移
-    let ox = if game.shake > 0.0 { rand::gen_range(-game.shake, game.shake) } else { 0.0 };
-    let oy = if game.shake > 0.0 { rand::gen_range(-game.shake, game.shake) } else { 0.0 };
+// 开启射击后空格用来开火，瞬移改到 [E]
+fn teleport_key(game: &Game) -> KeyCode { if game.shooting { KeyCode::E } else { KeyCode::Space } }
 
-    set_camera(&Camera2D {
-    target: vec2(screen_width() / 2.0 + ox, screen_height() / 2.0 + oy),
-    zoom: vec2(2.0 / screen_width(),  2.0 / screen_height()), // <-- 去掉负号，保持 y 向下
-    ..Default::default()
-});
+// 玩家上方一段高度内没有障碍的最宽横向区间，返回其中心
+const GAP_LOOKAHEAD: f32 = 220.0;
+fn widest_gap(game: &Game) -> f32 {
+    let top = PLAYER_Y - GAP_LOOKAHEAD;
+    let mut spans: Vec<(f32, f32)> = game.obs.live.iter()
+        .filter(|o| o.rect.y + o.rect.h > top && o.rect.y < PLAYER_Y + PLAYER_H)
+        .map(|o| (o.rect.x, o.rect.x + o.rect.w))
+        .collect();
+    spans.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let (mut best, mut best_w, mut cursor) = (game.field.x * 0.5, 0.0, 0.0f32);
+    for (l, r) in spans.into_iter().chain(std::iter::once((game.field.x, game.field.x))) {
+        if l - cursor > best_w { best_w = l - cursor; best = (cursor + l) * 0.5; }
+        cursor = cursor.max(r);
+    }
+    best
+}
 
+// 波浪形难度：每个周期先爬坡 RAMP_TIME 秒，再喘息 BREATHER_TIME 秒；
+// 下个周期从略低于上次峰值处起步，峰值逐轮抬高
+struct Curve { fall: f32, spawn: f32, breather: bool }
+
+fn difficulty_curve(elapsed: f32, fall_base: f32, spawn_base: f32, acc_per_sec: f32) -> Curve {
+    let cycle_len = RAMP_TIME + BREATHER_TIME;
+    let cycle = (elapsed / cycle_len).floor();
+    let t = elapsed - cycle * cycle_len;
+    let breather = t >= RAMP_TIME;
+    let e = cycle * RAMP_TIME * PEAK_CARRY + t.min(RAMP_TIME);
+    let fall = fall_base + e * acc_per_sec;
+    let spawn = (spawn_base - e * 0.02).max(SPAWN_MIN_INTERVAL);
+    if breather {
+        Curve { fall: fall * BREATHER_FALL_MUL, spawn: spawn * BREATHER_SPAWN_MUL, breather }
+    } else {
+        Curve { fall, spawn, breather }
+    }
+}
 
-    clear_background(Color::from_rgba(14, 17, 22, 255));
+fn one_life() -> u32 { 1 }
 
-    match game.mode {
-        GameMode::Menu => {
-            draw_text_center(&res.font, "Dodge Rush", 140.0, 62.0, SKYBLUE);
-            draw_text_center(&res.font, "左右移动躲避方块，收集道具增强能力", 200.0, 24.0, LIGHTGRAY);
-            draw_text_center(&res.font, "按 [SPACE] 开始", 300.0, 28.0, WHITE);
+fn save_game(game: &Game) {
+    if game.replay.watching() { return; }
+    // 固定规则期间存的是玩家自己的选择，而不是挑战/教程强制的规则
+    let own = game.stash.unwrap_or(challenge::Stash {
+        difficulty: game.difficulty,
+        adaptive: game.director.enabled,
+        mutators: game.mutators,
+        scheme: game.mover.scheme,
+        wrap: game.mover.wrap,
+        shooting: game.shooting,
+        start_lives: game.start_lives,
+        loadout: game.loadout,
+        character: game.character,
+    });
+    let save = Save {
+        version: game.save_version.max(migrate::SAVE_VERSION),
+        records: game.records.clone(),
+        difficulty: Difficulty::default(),
+        adaptive: own.adaptive,
+        mutators: own.mutators,
+        daily: game.daily_history.clone(),
+        weekly: game.weekly_bests.clone(),
+        feel: game.feel,
+        practice: game.practice,
+        scheme: own.scheme,
+        wrap: own.wrap,
+        shooting: own.shooting,
+        tutorial_done: game.tutorial_done,
+        start_lives: own.start_lives,
+        loadout: own.loadout,
+        character: own.character,
+        stat_bests: game.stat_bests,
+        deaths: game.deaths.clone(),
+        lifetime: game.lifetime,
+        wallet: game.wallet.clone(),
+        look: game.look.clone(),
+        xp: game.xp,
+        missions: game.missions.clone(),
+        achievements: game.achievements.clone(),
+        settings: None,
+        rivals: game.rivals.clone(),
+        ladder: game.ladder.clone(),
+        top: game.top.clone(),
+        tampered: game.tampered,
+        extra: game.save_extra.clone(),
+        warning: None,
+        player_name: game.player_name.clone(),
+        saved_at: macroquad::miniquad::date::now() as u64,
+    };
+    if let Err(e) = write_save(&paths::profile(SAVE_PATH), &save) {
+        logfile::warn(&format!("存档写入失败：{}", e));
+    }
+}
+
+// 签名后原子写盘
+fn write_save(path: &std::path::Path, save: &Save) -> std::io::Result<()> {
+    let mut v = serde_json::to_value(save).unwrap();
+    integrity::seal(&mut v);
+    paths::write_atomic(path, &v.to_string())
+}
+
+const SAVE_PATH: &str = "save.json";
+
+// 先校验签名（按文件原本的版本），再逐步迁移、反序列化
+fn parse_save(text: &str) -> Option<Save> {
+    let mut v: serde_json::Value = serde_json::from_str(text).ok()?;
+    let intact = integrity::check(&mut v);
+    let from = migrate::upgrade(&mut v);
+    if from > migrate::SAVE_VERSION { logfile::warn(&format!("存档版本 {} 比本程序新（{}），按能识别的部分读取", from, migrate::SAVE_VERSION)); }
+    let mut save: Save = serde_json::from_value(v).ok()?;
+    if !intact && !save.tampered {
+        let msg = "存档校验不符：之后的成绩不计入挑战排行";
+        logfile::warn(msg);
+        save.warning = Some(msg.to_string());
+        save.tampered = true;
+    }
+    Some(save)
+}
+
+// 正式存档读不出来就用 .bak，并把提示带给菜单（不中断启动）
+fn load_save() -> Save {
+    let path = paths::profile(SAVE_PATH);
+    let parse = |p: &std::path::Path| storage::read_to_string(p).ok().and_then(|s| parse_save(&s));
+    if let Some(s) = parse(&path) { return s; }
+    let bak = paths::backup_of(&path);
+    if !storage::exists(&path) && !storage::exists(&bak) { return Save::default(); }
+    paths::keep_corrupt(&path);
+    let (mut save, msg) = match parse(&bak) {
+        Some(s) => (s, "存档损坏，已从备份恢复"),
+        None => (Save::default(), "存档和备份都无法读取，已从头开始"),
+    };
+    logfile::warn(msg);
+    save.warning = Some(msg.to_string());
+    save
+}
+
+// 随机生成一个指定类型的危险物
+fn spawn_hazard(game: &mut Game, hazard: Hazard) {
+    let vy = game.fall_speed * rand::gen_range(0.9, 1.3);
+    let (lo, hi) = game.size_range();
+    let (w, h, vx, vy) = match hazard {
+        Hazard::Block => {
+            let size = rand::gen_range(lo, hi);
+            (size, size, 0.0, vy)
         }
-        GameMode::Playing => {
-            draw_hud(&res.font, game);
-            draw_player(game);
-            draw_obstacles(game);
-            draw_powerups(game);
+        Hazard::Drifter => {
+            let size = rand::gen_range(lo, (hi * 0.8).max(lo));
+            let vx = rand::gen_range(60.0, 120.0) * if rand::gen_range(0, 2) == 0 { -1.0 } else { 1.0 };
+            (size, size, vx, vy)
         }
-        GameMode::Paused => {
-            draw_hud(&res.font, game);
-            draw_player(game);
-            draw_obstacles(game);
-            draw_powerups(game);
-            draw_text_center(&res.font, "已暂停 [P]继续 / [R]重开 / [ESC]菜单", 300.0, 28.0, YELLOW);
+        Hazard::Dart => (14.0, 22.0, 0.0, vy * 1.8),
+        Hazard::Slab => (rand::gen_range(140.0, 220.0), 18.0, 0.0, vy * 0.8),
+        // 黑洞很稀有：多数时候换成一个普通方块
+        Hazard::Well => {
+            if !gravity::try_spawn(game, rand::gen_range(0.0, 1.0), rand::gen_range(0.0, 1.0)) { spawn_hazard(game, Hazard::Block); }
+            return;
         }
-        GameMode::GameOver => {
-            draw_hud(&res.font, game);
-            draw_player(game);
-            draw_obstacles(game);
-            draw_powerups(game);
-            draw_text_center(&res.font, "💥 游戏结束!", 250.0, 44.0, RED);
-            draw_text_center(&res.font, &format!("得分：{}   最高：{}", game.score, game.best_score), 300.0, 28.0, WHITE);
-            draw_text_center(&res.font, "[R] 再来一局   [ESC] 返回菜单", 350.0, 24.0, ORANGE);
+    };
+    let scale = game.mutators.obstacle_scale();
+    let (w, h) = (w * scale, h * scale);
+    let x = lane_align(game, rand::gen_range(0.0, (game.field.x - w).max(0.0)), w);
+    game.obs.spawn(Rect::new(x, spawn_y(h, vy), w, h), vx, vy);
+}
+
+// 障碍生成在场地上方，离上沿还有 SPAWN_LEAD 秒的路：这段时间里上沿画来袭预警（见 draw_spawn_warnings）。
+// 预警只是画不画的区别，关掉它障碍也一样提前生成，所以关不关都公平
+fn spawn_y(h: f32, vy: f32) -> f32 { -h - 10.0 - vy * SPAWN_LEAD }
+
+// 车道模式下把左边缘为 x、宽 w 的物体挪到最近的车道中央
+fn lane_align(game: &Game, x: f32, w: f32) -> f32 {
+    if game.mover.scheme != Scheme::Lanes { return x; }
+    (movement::snap_to_lane(x + w * 0.5, game.field.x) - w * 0.5).clamp(0.0, (game.field.x - w).max(0.0))
+}
+
+// 所有道具生成都走这里，便于“没有道具”变异统一拦截
+fn spawn_powerup(game: &mut Game, x: f32, kind: PowerUpKind) {
+    if game.mutators.no_powerups { return; }
+    if kind.is_defensive() { game.pity = 0.0; }
+    let x = lane_align(game, x, PU_SIZE);
+    game.pus.spawn(x, -PU_SIZE - 8.0, kind);
+}
+
+// 阵型/脚本共用：在屏幕顶端生成一个障碍或道具（x 为像素）
+fn spawn_kind(game: &mut Game, kind: SpawnKind, x: f32, size: f32, speed: f32, vx: f32) {
+    let x = x.clamp(0.0, (game.field.x - size).max(0.0));
+    match kind {
+        SpawnKind::Block => {
+            let vy = game.fall_speed * speed;
+            let size = size * game.mutators.obstacle_scale();
+            let x = lane_align(game, x.min((game.field.x - size).max(0.0)), size);
+            game.obs.spawn(Rect::new(x, spawn_y(size, vy), size, size), vx, vy);
+            if game.shooting {
+                let armor = shooting::roll_armor(rand::gen_range(0.0, 1.0));
+                if let Some(o) = game.obs.live.last_mut() { o.armor = armor; }
+            }
         }
+        SpawnKind::Shield => spawn_powerup(game, x, PowerUpKind::Shield),
+        SpawnKind::Slow => spawn_powerup(game, x, PowerUpKind::Slow),
+        SpawnKind::Bomb => spawn_powerup(game, x, PowerUpKind::Bomb),
+        SpawnKind::Heart => spawn_powerup(game, x, PowerUpKind::Heart),
+        SpawnKind::Teleport => spawn_powerup(game, x, PowerUpKind::Teleport),
+        SpawnKind::Ammo => spawn_powerup(game, x, PowerUpKind::Ammo),
+        SpawnKind::Rewind => spawn_powerup(game, x, PowerUpKind::Rewind),
+        SpawnKind::Multiplier => spawn_powerup(game, x, PowerUpKind::Multiplier),
+        SpawnKind::Freeze => spawn_powerup(game, x, PowerUpKind::Freeze),
+        SpawnKind::Reverse => spawn_powerup(game, x, PowerUpKind::Reverse),
+        SpawnKind::Haste => spawn_powerup(game, x, PowerUpKind::Haste),
+        SpawnKind::Blackout => spawn_powerup(game, x, PowerUpKind::Blackout),
     }
-
-    set_default_camera();
 }
 
-// ===== 主循环（固定物理步 + 渲染分离）=====
-#[macroquad::main(window_conf)]
-async fn main() {
-    // 字体
-    let font = load_ttf_font("assets/NotoSansCJKsc-Regular.otf")
-        .await
-        .expect("无法加载中文字体：assets/NotoSansCJKsc-Regular.otf");
+// 难度曲线、世界事件、阶段推进与各类生成（教程模式由脚本序列代替）
+fn update_spawning(game: &mut Game, res: &Content, dt: f32, slow_mul: f32) {
+    // —— 难度递增 —— 
+    let tuning = game.difficulty.tuning();
+    let curve = if game.play == PlayMode::Practice {
+        Curve { fall: game.practice.fall_speed, spawn: game.practice.spawn_interval, breather: false }
+    } else {
+        difficulty_curve(game.curve_time(), OB_START_SPEED, SPAWN_BASE_INTERVAL, OB_ACC_PER_SEC * tuning.acc_mul)
+    };
+    // 减速在障碍积分时统一生效（见 Playing），这里只拉长生成间隔保持密度
+    game.fall_speed = curve.fall;
+    game.spawn_interval = if game.play == PlayMode::Practice { curve.spawn / slow_mul } else { (curve.spawn / slow_mul).max(SPAWN_MIN_INTERVAL) };
+    // 进入喘息期：必出一个道具，方便攒着用
+    if curve.breather && !game.breather {
+        let x = rand::gen_range(PU_SIZE, game.field.x - PU_SIZE);
+        spawn_powerup(game, x, random_powerup(game));
+    }
+    game.breather = curve.breather;
+    game.elapsed += dt;
 
-    let res = Resources { font };
-    let best = load_best();
-    let mut game = Game::new(best);
-    game.player.x = screen_width() * 0.5 - PLAYER_W * 0.5;
+    // —— 世界事件 —— 
+    let had_event = game.events.active.is_some();
+    if game.events.tick(dt, rand::gen_range(0.0, 1.0), rand::gen_range(0.0, 1.0)) {
+        let x = rand::gen_range(PU_SIZE, game.field.x - PU_SIZE);
+        spawn_powerup(game, x, random_powerup(game));
+    }
+    if let (false, Some(ev)) = (had_event, &game.events.active) {
+        game.banners.show(trf("【{}】", &[&tr(ev.def.name)]), "", Color::new(1.0, 0.65, 0.1, 1.0), EVENT_BANNER_TIME);
+    }
+    game.fall_speed *= game.events.fall_mul();
+    game.wind.tick(dt, game.field.x, || rand::gen_range(0.0, 1.0));
 
-    let mut acc = 0.0f32;
+    // —— 阶段推进 —— 
+    let stage = res.stages.stage_at(game.curve_time());
+    if stage != game.stage {
+        game.prev_bg = res.stages.def(game.stage).bg_color();
+        game.stage = stage;
+        game.stage_fade = 1.0;
+        res.stages.hazards(stage, &mut game.hazards);
+        game.banners.show(trf("STAGE {}", &[&(stage + 1)]), tr(&res.stages.def(stage).name), WHITE, STAGE_BANNER_TIME);
+    }
+    game.stage_fade = (game.stage_fade - dt / STAGE_FADE_TIME).max(0.0);
+    let stage_def = res.stages.def(game.stage);
+    game.fall_speed *= stage_def.fall_mul;
 
-    loop {
-        let dt = get_frame_time();
-        acc += dt;
-        while acc >= FIXED_DT {
-            update_game(&mut game, FIXED_DT, &res);
-            acc -= FIXED_DT;
+    // —— 脚本钩子 —— 
+    #[cfg(feature = "scripting")]
+    for cmd in res.scripts.tick(game.elapsed, game.player.x, game.field.x) {
+        match cmd {
+            scripting::ScriptCmd::Obstacle { x, size, speed, vx } => spawn_kind(game, SpawnKind::Block, x, size, speed, vx),
+            scripting::ScriptCmd::PowerUp { x, kind } => spawn_kind(game, kind, x, PU_SIZE, 1.0, 0.0),
+            scripting::ScriptCmd::FallSpeed(v) => game.fall_speed = v.max(0.0),
         }
-        draw_game(&game, &res);
-        next_frame().await;
+    }
+
+    // —— 生成障碍（阵型进行中暂停随机生成） —— 
+    if !game.wave.active() { game.spawn_timer += dt; }
+    let adaptive_mul = if game.director.enabled && game.play == PlayMode::Standard { game.director.spawn_mul } else { 1.0 };
+    let spawn_mul = tuning.spawn_mul * adaptive_mul * game.mutators.spawn_mul() * game.events.spawn_mul() * stage_def.spawn_mul;
+    if game.spawn_timer >= game.spawn_interval * spawn_mul {
+        game.spawn_timer = 0.0;
+        let wave = if rand::gen_range(0.0, 1.0) < WAVE_CHANCE {
+            waves::pick_wave(waves::unlocked(&res.waves, game.elapsed), rand::gen_range(0.0, 1.0))
+        } else {
+            None
+        };
+        if let Some(w) = wave {
+            game.wave.start(w);
+        } else {
+            let hazard = game.hazards[rand::gen_range(0, game.hazards.len())];
+            spawn_hazard(game, hazard);
+        }
+    }
+
+    // —— 阵型到期的生成点 —— 
+    game.wave.tick(dt, &mut game.wave_due);
+    let mut due = std::mem::take(&mut game.wave_due);
+    for s in due.drain(..) {
+        spawn_kind(game, s.spawn, s.x * game.field.x, s.size, s.speed, s.vx);
+    }
+    game.wave_due = due;
+
+    // —— 生成道具（随机一种） —— 
+    game.pu_spawn_timer += dt;
+    let pu_interval = if game.play == PlayMode::Practice { game.practice.pu_interval } else { PU_SPAWN_INTERVAL * tuning.pu_interval_mul * game.character().pu_interval_mul };
+    if game.pu_spawn_timer >= pu_interval {
+        game.pu_spawn_timer = 0.0;
+        if rand::gen_range(0.0, 1.0) < stage_def.powerup_chance {
+            let x = rand::gen_range(PU_SIZE, game.field.x - PU_SIZE);
+            spawn_powerup(game, x, random_powerup(game));
+        }
+    }
+    // 保底：太久没见到防御类道具就补一个
+    game.pity += dt;
+    if game.pity >= game.pu_tuning.spawn.pity_time && !game.mutators.no_powerups {
+        let x = rand::gen_range(PU_SIZE, game.field.x - PU_SIZE);
+        let kind = roll_table(&game.pu_tuning.spawn.pity);
+        spawn_powerup(game, x, kind);
+        game.pity = 0.0;
+    }
+}
+
+// ===== 逐帧输入：菜单类界面（不随物理步重复触发）=====
+// 菜单类界面里移动光标、确认的提示音（局内和对战画面不响）
+fn menu_sounds(game: &mut Game) {
+    if matches!(game.mode, GameMode::Playing | GameMode::Killcam | GameMode::Duel | GameMode::Sandbox | GameMode::Spectate) { return; }
+    if [KeyCode::Up, KeyCode::Down, KeyCode::Left, KeyCode::Right].into_iter().any(is_key_pressed) { audio::play(game, Sfx::MenuMove); }
+    if is_key_pressed(KeyCode::Enter) { audio::play(game, Sfx::MenuSelect); }
+}
+
+fn handle_frame_input(game: &mut Game) {
+    if console::update(game) { return; }
+    screenshot::poll(game);
+    menu_sounds(game);
+    debug::input(game);
+    // 局内 [F4] 轮换 HUD 显示方式
+    if matches!(game.mode, GameMode::Playing | GameMode::Paused | GameMode::GameOver) && is_key_pressed(KeyCode::F4) {
+        game.settings.hud = game.settings.hud.cycle(1);
+        game.settings.save();
+        game.banners.show(trf("HUD：{}", &[&game.settings.hud.label()]), tr("[F4] 切换"), WHITE, 1.2);
+    }
+    match game.mode {
+        GameMode::Menu => {
+            game.missions.refresh(challenge::today());
+            update_main_menu(game);
+            for m in &MENU_MODES {
+                if m.key.is_some_and(is_key_pressed) { (m.start)(game); }
+            }
+            if is_key_pressed(KeyCode::D) { game.open_challenges(Challenge::Daily); }
+            if is_key_pressed(KeyCode::W) { game.open_challenges(Challenge::Weekly); }
+            if is_key_pressed(KeyCode::N) {
+                game.versus.msg = None;
+                game.mode = GameMode::Lobby;
+            }
+            if is_key_pressed(KeyCode::V) { spectate::enter(game); }
+            if is_key_pressed(KeyCode::R) && game.suspended { suspend::resume(game); }
+            if is_key_pressed(KeyCode::A) { options::enter(game, options::TAB_A11Y); }
+            if is_key_pressed(KeyCode::F) { game.mode = GameMode::FeelPanel; }
+            if is_key_pressed(KeyCode::S) { open_stats(game); }
+            if is_key_pressed(KeyCode::B) { game.mode = GameMode::Shop; }
+            if is_key_pressed(KeyCode::K) { game.mode = GameMode::Customize; }
+            if is_key_pressed(KeyCode::M) { game.mode = GameMode::Missions; }
+            if is_key_pressed(KeyCode::E) { game.mode = GameMode::Achievements; }
+            if is_key_pressed(KeyCode::P) {
+                game.profile_cursor = game.profiles.current();
+                game.profile_edit = None;
+                game.profile_delete = None;
+                game.profile_msg = None;
+                game.mode = GameMode::Profiles;
+            }
+            if is_key_pressed(KeyCode::O) { open_leaderboard(game); }
+            if is_key_pressed(KeyCode::L) {
+                game.history_cursor = 0;
+                game.mode = GameMode::History;
+            }
+            if cfg!(debug_assertions) && is_key_pressed(KeyCode::F9) { sandbox::enter(game); }
+            if is_key_pressed(KeyCode::G) {
+                game.director.enabled = !game.director.enabled;
+                save_game(game);
+            }
+            if is_key_pressed(KeyCode::C) {
+                // 跳过等级还没到的角色（标准角色总是可选）
+                let level = game.level();
+                game.character = (1..=CHARACTERS.len()).map(|d| (game.character + d) % CHARACTERS.len())
+                    .find(|&i| xp::unlocked(Reward::Character(CHARACTERS[i].key), level))
+                    .unwrap_or(0);
+                save_game(game);
+            }
+            let step = is_key_pressed(KeyCode::Right) as i32 - is_key_pressed(KeyCode::Left) as i32;
+            if step != 0 {
+                game.difficulty = game.difficulty.cycle(step);
+                game.settings.difficulty = game.difficulty;
+                game.settings.save();
+            }
+            if game.mode != GameMode::Menu { game.save_warning = None; }
+        }
+        GameMode::PreRun if game.seed_entry.is_some() => update_seed_entry(game),
+        GameMode::PreRun => {
+            let keys = [KeyCode::Key1, KeyCode::Key2, KeyCode::Key3, KeyCode::Key4, KeyCode::Key5];
+            let level = game.level();
+            for (k, m) in keys.iter().zip(MUTATORS.iter()) {
+                if is_key_pressed(*k) && xp::unlocked(Reward::Mutator(m.key), level) { (m.toggle)(&mut game.mutators); }
+            }
+            if is_key_pressed(KeyCode::Backspace) { game.mutators = Mutators::default(); }
+            let step = is_key_pressed(KeyCode::Equal) as i32 - is_key_pressed(KeyCode::Minus) as i32;
+            if step != 0 { game.start_lives = (game.start_lives as i32 + step).clamp(1, game.max_lives() as i32) as u32; }
+            let step = is_key_pressed(KeyCode::RightBracket) as i32 - is_key_pressed(KeyCode::LeftBracket) as i32;
+            game.loadout = (game.loadout + step).clamp(-LOADOUT_STEPS, LOADOUT_STEPS);
+            if is_key_pressed(KeyCode::L) {
+                game.mover.scheme = if game.mover.scheme == Scheme::Free { Scheme::Lanes } else { Scheme::Free };
+            }
+            if is_key_pressed(KeyCode::X) { game.mover.wrap = !game.mover.wrap; }
+            if is_key_pressed(KeyCode::S) { game.shooting = !game.shooting; }
+            if is_key_pressed(KeyCode::G) {
+                game.settings.ghost = !game.settings.ghost;
+                game.settings.save();
+            }
+            if is_key_pressed(KeyCode::B) {
+                game.settings.broadcast = !game.settings.broadcast;
+                game.settings.save();
+            }
+            if is_key_pressed(KeyCode::E) {
+                let seed = game.forced_seed.map(|s| s.to_string()).unwrap_or_default();
+                game.seed_entry = Some(TextField::digits(&seed, SEED_DIGITS));
+            }
+            if is_key_pressed(KeyCode::Space) || is_key_pressed(KeyCode::Enter) {
+                save_game(game);
+                game.play = PlayMode::Standard;
+                game.reset_round();
+            }
+            if is_key_pressed(KeyCode::Escape) {
+                game.forced_seed = None;
+                game.mode = GameMode::Menu;
+            }
+        }
+        GameMode::FeelPanel => {
+            let n = FEEL_ITEMS.len();
+            if is_key_pressed(KeyCode::Up) { game.feel_cursor = (game.feel_cursor + n - 1) % n; }
+            if is_key_pressed(KeyCode::Down) { game.feel_cursor = (game.feel_cursor + 1) % n; }
+            let step = is_key_pressed(KeyCode::Right) as i32 - is_key_pressed(KeyCode::Left) as i32;
+            if step != 0 { (FEEL_ITEMS[game.feel_cursor].nudge)(&mut game.feel, step); }
+            let keys = [KeyCode::Key1, KeyCode::Key2, KeyCode::Key3];
+            for (k, p) in keys.iter().zip(FEEL_PRESETS.iter()) {
+                if is_key_pressed(*k) { game.feel = p.feel(); }
+            }
+            if is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::Escape) {
+                save_game(game);
+                game.mode = GameMode::Menu;
+            }
+        }
+        GameMode::Settings => options::update(game),
+        GameMode::Playing | GameMode::Paused if game.field_changed && !game.replay.watching() && is_key_pressed(KeyCode::Y) => {
+            apply_field_change(game);
+        }
+        GameMode::Paused if is_key_pressed(KeyCode::F) => {
+            game.feedback = FeedbackForm::new();
+            game.capture_pending = true;
+            while get_char_pressed().is_some() {} // 丢弃游戏中积压的字符
+        }
+        GameMode::Paused => update_pause_menu(game),
+        GameMode::Playing if game.play == PlayMode::Practice => practice::ui(game),
+        GameMode::Feedback => update_feedback_form(game),
+        GameMode::Challenges => update_challenges(game),
+        GameMode::Profiles => update_profiles(game),
+        GameMode::SyncConflict => update_sync_conflict(game),
+        GameMode::Stats => {
+            if is_key_pressed(KeyCode::Left) || is_key_pressed(KeyCode::Right) { game.stats_page = 1 - game.stats_page; }
+            if is_key_pressed(KeyCode::X) {
+                let base = export::default_base();
+                game.export_msg = Some(match export::write(&base, &game.export_data()) {
+                    Ok(_) => trf("已导出到 {}.json / *.csv", &[&base]),
+                    Err(e) => trf("导出失败：{}", &[&e]),
+                });
+            }
+            if is_key_pressed(KeyCode::Escape) || is_key_pressed(KeyCode::Enter) { game.mode = GameMode::Menu; }
+        }
+        GameMode::Shop => {
+            let n = game.shop.items.len().max(1);
+            if is_key_pressed(KeyCode::Up) { game.shop_cursor = (game.shop_cursor + n - 1) % n; }
+            if is_key_pressed(KeyCode::Down) { game.shop_cursor = (game.shop_cursor + 1) % n; }
+            if is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::Space) {
+                if let Some(item) = game.shop.items.get(game.shop_cursor) {
+                    // 买下的外观直接换上
+                    if game.wallet.buy(item) {
+                        if matches!(item.unlock, Unlock::Cosmetic) {
+                            let slot = if game.cosmetics.is_skin(&item.key) { &mut game.look.skin } else { &mut game.look.theme };
+                            *slot = item.key.clone();
+                        }
+                        save_game(game);
+                    }
+                }
+            }
+            if is_key_pressed(KeyCode::Escape) { game.mode = GameMode::Menu; }
+        }
+        GameMode::Customize => {
+            // 皮肤在前、主题在后排成一列
+            let n = game.cosmetics.skins.len() + game.cosmetics.themes.len();
+            if is_key_pressed(KeyCode::Up) { game.look_cursor = (game.look_cursor + n - 1) % n; }
+            if is_key_pressed(KeyCode::Down) { game.look_cursor = (game.look_cursor + 1) % n; }
+            if is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::Space) {
+                let ns = game.cosmetics.skins.len();
+                let (key, unlock) = match game.look_cursor.checked_sub(ns) {
+                    None => { let s = &game.cosmetics.skins[game.look_cursor]; (s.key.clone(), s.unlock) }
+                    Some(i) => { let t = &game.cosmetics.themes[i]; (t.key.clone(), t.unlock) }
+                };
+                if unlock.met(&key, &game.progress()) {
+                    if game.look_cursor < ns { game.look.skin = key; } else { game.look.theme = key; }
+                    save_game(game);
+                }
+            }
+            if is_key_pressed(KeyCode::Escape) && !options::back_from_looks(game) { game.mode = GameMode::Menu; }
+        }
+        GameMode::Achievements => {
+            let n = game.achievement_defs.list.len();
+            if is_key_pressed(KeyCode::Up) { game.ach_scroll = game.ach_scroll.saturating_sub(1); }
+            if is_key_pressed(KeyCode::Down) { game.ach_scroll = (game.ach_scroll + 1).min(n.saturating_sub(1)); }
+            if is_key_pressed(KeyCode::Escape) || is_key_pressed(KeyCode::Enter) { game.mode = GameMode::Menu; }
+        }
+        GameMode::History => {
+            let n = game.history.len();
+            if is_key_pressed(KeyCode::Up) { game.history_cursor = game.history_cursor.saturating_sub(1); }
+            if is_key_pressed(KeyCode::Down) { game.history_cursor = (game.history_cursor + 1).min(n.saturating_sub(1)); }
+            // 最新的在最上面
+            let picked = game.history.iter().rev().nth(game.history_cursor).cloned();
+            if let Some(e) = picked {
+                if is_key_pressed(KeyCode::Enter) {
+                    game.forced_seed = Some(e.seed);
+                    game.play = PlayMode::Standard;
+                    game.reset_round();
+                } else if is_key_pressed(KeyCode::V) && e.replay {
+                    if let Some(r) = Replay::load(e.id) { replay::watch(game, r); }
+                }
+            }
+            if is_key_pressed(KeyCode::Escape) { game.mode = GameMode::Menu; }
+        }
+        GameMode::Missions if is_key_pressed(KeyCode::Escape) || is_key_pressed(KeyCode::Enter) => game.mode = GameMode::Menu,
+        // 名字输入：guard 里顺带处理按键，确认时才进分支
+        GameMode::NameEntry if game.name_entry.as_mut().is_some_and(|e| e.input()) => {
+            let Some(entry) = game.name_entry.take() else { return };
+            let mut e = TopEntry { name: entry.field.text().trim().to_string(), score: game.score, day: challenge::today(), mode: game.mode_label(), ghost: None, elapsed: game.elapsed };
+            // 连同回放存一份幽灵，排行榜上可以和它赛跑、分享给别人
+            if let ReplayState::Recording(r) = &game.replay {
+                let id = (macroquad::miniquad::date::now() * 1000.0) as u64;
+                ghost::store(id, &ghost::GhostFile::new(&e.name, e.score, r));
+                e.ghost = Some(id);
+            }
+            game.player_name = e.name.clone();
+            game.top_rank = Some(game.top.insert(e));
+            ghost::prune(&game.top);
+            save_game(game);
+            if entry.retry { game.reset_round(); } else { game.mode = GameMode::Summary; }
+        }
+        GameMode::Leaderboard => update_leaderboard(game),
+        GameMode::Lobby => versus::update_lobby(game),
+        GameMode::Spectate => spectate::update(game),
+        GameMode::Ranked => ladder::update(game),
+        GameMode::Summary => {
+            if game.settings.keys.restart.pressed() && game.can_retry() { game.reset_round(); }
+            if is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::Escape) {
+                game.leave_run();
+                game.mode = GameMode::Menu;
+            }
+        }
+        GameMode::Sandbox => sandbox::ui(game),
+        GameMode::Duel => duel::ui(game),
+        GameMode::Hotseat => hotseat::update(game),
+        GameMode::Keybinds => keybinds::update(game),
+        _ => {}
+    }
+}
+
+// 排行榜：[↑/↓] 选择，[ENTER] 和这条成绩的幽灵赛跑，[C] 复制幽灵分享串，[V] 从剪贴板导入幽灵（也可以拖入 .drg 文件）
+// [TAB] 切换本地 / 全球榜，[U] 开关全球排行榜，[R] 刷新全球榜
+fn update_leaderboard(game: &mut Game) {
+    if is_key_pressed(KeyCode::U) {
+        game.settings.online = !game.settings.online;
+        game.settings.save();
+        if game.settings.online && game.top_global { fetch_global(game); }
+    }
+    if is_key_pressed(KeyCode::Tab) {
+        game.top_global = !game.top_global;
+        game.top_cursor = 0;
+        game.top_msg = None;
+        if game.top_global && game.settings.online { fetch_global(game); }
+    }
+    if game.top_global && game.settings.online && is_key_pressed(KeyCode::R) { fetch_global(game); }
+    let n = global_board(game).map_or(game.top.entries().len(), |b| b.entries.len());
+    if n > 0 {
+        if is_key_pressed(KeyCode::Up) { game.top_cursor = (game.top_cursor + n - 1) % n; }
+        if is_key_pressed(KeyCode::Down) { game.top_cursor = (game.top_cursor + 1) % n; }
+    }
+    // 全球榜的幽灵在后台下载，下好了再开跑
+    match game.online.ghost.take() {
+        Some(Ok(f)) => return ghost::race(game, f),
+        Some(Err(e)) => game.top_msg = Some(e),
+        None => {}
+    }
+    if let Some(b) = global_board(game) {
+        if is_key_pressed(KeyCode::Enter) {
+            let id = b.entries.get(game.top_cursor).map(|e| e.ghost.clone());
+            match id {
+                Some(Some(id)) => {
+                    game.online.fetch_ghost(&id);
+                    game.top_msg = Some(tr("正在下载幽灵…").to_string());
+                }
+                Some(None) => game.top_msg = Some(tr("这条成绩没有幽灵").to_string()),
+                None => {}
+            }
+        }
+        if is_key_pressed(KeyCode::Escape) { game.mode = GameMode::Menu; }
+        return;
+    }
+    let picked = |game: &Game| game.top.entries().get(game.top_cursor).map(|e| e.ghost.ok_or_else(|| tr("这条成绩没有幽灵").to_string()).and_then(ghost::load));
+    if is_key_pressed(KeyCode::Enter) {
+        match picked(game) {
+            Some(Ok(f)) => return ghost::race(game, f),
+            Some(Err(e)) => game.top_msg = Some(e),
+            None => {}
+        }
+    }
+    if is_key_pressed(KeyCode::C) {
+        game.top_msg = match picked(game).map(|r| r.and_then(|f| f.share_code())) {
+            Some(Ok(code)) => {
+                macroquad::miniquad::window::clipboard_set(&code);
+                Some(tr("幽灵分享串已复制到剪贴板").to_string())
+            }
+            Some(Err(e)) => Some(e),
+            None => None,
+        };
+    }
+    let incoming = if is_key_pressed(KeyCode::V) {
+        Some(ghost::GhostFile::from_share(&macroquad::miniquad::window::clipboard_get().unwrap_or_default()))
+    } else {
+        get_dropped_files().into_iter().find_map(|f| f.bytes).map(|b| ghost::GhostFile::decode(&b))
+    };
+    match incoming {
+        Some(Ok(f)) => return ghost::race(game, f),
+        Some(Err(e)) => game.top_msg = Some(e),
+        None => {}
+    }
+    if is_key_pressed(KeyCode::Escape) { game.mode = GameMode::Menu; }
+}
+
+// 按菜单上当前的规则组合拉全球榜
+fn fetch_global(game: &mut Game) {
+    let (key, name) = (game.best_key(), game.player_name.clone());
+    game.online.fetch(&key, &name);
+}
+
+// 全球榜拉到了才用它；没开、还在加载或离线时都退回本地前十名
+fn global_board(game: &Game) -> Option<&online::Board> {
+    if !game.top_global || !game.settings.online || game.online.status != online::Status::Ready { return None; }
+    game.online.board.as_ref()
+}
+
+// 开局设置里的种子输入：确定后这一轮都用它开局，留空就回到随机种子
+fn update_seed_entry(game: &mut Game) {
+    let Some(field) = &mut game.seed_entry else { return };
+    match field.update() {
+        Some(Event::Submit) => {
+            game.forced_seed = field.text().parse().ok();
+            game.seed_entry = None;
+        }
+        Some(Event::Cancel) => game.seed_entry = None,
+        None => {}
+    }
+}
+
+// 档案界面：[↑/↓] 选择，[ENTER] 切换，[N] 新建，[R] 改名，[DEL] 删除（按两次），[ESC] 返回
+fn update_profiles(game: &mut Game) {
+    if let Some(edit) = &mut game.profile_edit {
+        let ev = edit.field.update();
+        let done = matches!(ev, Some(Event::Submit)) && !edit.field.is_blank();
+        let (index, name) = (edit.index, edit.field.text().trim().to_string());
+        if done {
+            match index {
+                Some(i) => game.profiles.rename(i, &name),
+                None => if let Some(i) = game.profiles.create(&name) { game.profile_cursor = i; },
+            }
+        }
+        if done || matches!(ev, Some(Event::Cancel)) { game.profile_edit = None; }
+        return;
+    }
+    let n = game.profiles.list.len();
+    if is_key_pressed(KeyCode::Up) { game.profile_cursor = (game.profile_cursor + n - 1) % n; game.profile_delete = None; }
+    if is_key_pressed(KeyCode::Down) { game.profile_cursor = (game.profile_cursor + 1) % n; game.profile_delete = None; }
+    let i = game.profile_cursor;
+    if is_key_pressed(KeyCode::Enter) && i != game.profiles.current() {
+        // 先把当前档案存好，再换目录整个重新读档
+        save_game(game);
+        if let Some(c) = game.cloud.as_mut() { c.push(); }
+        game.profiles.activate(i);
+        game.reload_profile();
+        sync_profile(game);
+        return;
+    }
+    if is_key_pressed(KeyCode::N) && !game.profiles.full() {
+        game.profile_edit = Some(ProfileEdit { index: None, field: TextField::new("", profiles::NAME_MAX) });
+    }
+    if is_key_pressed(KeyCode::R) {
+        game.profile_edit = Some(ProfileEdit { index: Some(i), field: TextField::new(&game.profiles.list[i].name, profiles::NAME_MAX) });
+    }
+    if is_key_pressed(KeyCode::X) {
+        if i == game.profiles.current() { save_game(game); }
+        let p = &game.profiles.list[i];
+        game.profile_msg = Some(match transfer::export(&p.id, &p.name, &game.profiles.save_path(i)) {
+            Ok((path, code)) => {
+                macroquad::miniquad::window::clipboard_set(&code);
+                trf("已导出到 {}，分享串已复制到剪贴板", &[&path.display()])
+            }
+            Err(e) => trf("导出失败：{}", &[&e]),
+        });
+    }
+    // 导入：剪贴板里的分享串，或者直接把 .drp 文件拖进窗口
+    if is_key_pressed(KeyCode::V) {
+        let text = macroquad::miniquad::window::clipboard_get().unwrap_or_default();
+        game.profile_msg = Some(match transfer::decode_share(&text) {
+            Some(bytes) => import_profile(game, i, &bytes),
+            None => tr("剪贴板里不是档案分享串").to_string(),
+        });
+    }
+    if let Some(bytes) = get_dropped_files().into_iter().find_map(|f| f.bytes) {
+        game.profile_msg = Some(import_profile(game, i, &bytes));
+    }
+    if is_key_pressed(KeyCode::Delete) {
+        if game.profile_delete == Some(i) {
+            if game.profiles.delete(i) { game.profile_cursor = game.profiles.current(); }
+            game.profile_delete = None;
+        } else if i != game.profiles.current() {
+            game.profile_delete = Some(i);
+        }
+    }
+    if is_key_pressed(KeyCode::Escape) { game.mode = GameMode::Menu; }
+}
+
+// 挑战界面：[←/→] 每日/每周，[ENTER] 开始，[C] 复制自己的分享码，[V] 从剪贴板导入，[N] 改名
+fn update_challenges(game: &mut Game) {
+    if let Some(field) = &mut game.naming {
+        match field.update() {
+            Some(Event::Submit) => {
+                let name = field.text().trim().to_string();
+                game.player_name = if name.is_empty() { "玩家".to_string() } else { name };
+                game.naming = None;
+                save_game(game);
+            }
+            Some(Event::Cancel) => game.naming = None,
+            None => {}
+        }
+        return;
+    }
+    let kind = game.challenge_tab;
+    let period = kind.current();
+    if is_key_pressed(KeyCode::Left) || is_key_pressed(KeyCode::Right) {
+        game.challenge_tab = if kind == Challenge::Daily { Challenge::Weekly } else { Challenge::Daily };
+        game.rival_msg = None;
+    }
+    if is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::Space) { game.start_challenge(kind); }
+    if is_key_pressed(KeyCode::C) {
+        game.rival_msg = Some(match game.rivals.my_best(kind, period).filter(|_| !game.tampered) {
+            Some(e) => {
+                macroquad::miniquad::window::clipboard_set(&rivals::share_code(kind, period, e));
+                tr("分享码已复制到剪贴板").to_string()
+            }
+            None if game.tampered => tr("存档被改动过，不能生成分享码").to_string(),
+            None => tr("本期还没有成绩可以分享").to_string(),
+        });
+    }
+    if is_key_pressed(KeyCode::V) {
+        let code = macroquad::miniquad::window::clipboard_get().unwrap_or_default();
+        game.rival_msg = Some(match rivals::parse_code(&code) {
+            Some((k, p, e)) => {
+                let txt = if p == k.current() { trf("已导入 {} 的 {} 分", &[&e.name, &e.score]) } else { trf("已导入 {} 的成绩（{} {}）", &[&e.name, &k.label(), &k.period_label(p)]) };
+                game.rivals.add(k, p, e);
+                save_game(game);
+                txt
+            }
+            None => tr("剪贴板里不是有效的分享码").to_string(),
+        });
+    }
+    if is_key_pressed(KeyCode::N) { game.naming = Some(TextField::new(&game.player_name, rivals::NAME_MAX)); }
+    if is_key_pressed(KeyCode::Escape) { game.mode = GameMode::Menu; }
+}
+
+fn update_feedback_form(game: &mut Game) {
+    let form = &mut game.feedback;
+    while let Some(c) = get_char_pressed() {
+        if !c.is_control() && form.text.chars().count() < feedback::MAX_TEXT { form.text.push(c); }
+    }
+    if is_key_pressed(KeyCode::Backspace) { form.text.pop(); }
+    if is_key_pressed(KeyCode::F1) { form.with_screenshot = !form.with_screenshot; }
+    if is_key_pressed(KeyCode::F2) && feedback::endpoint().is_some() { form.upload = !form.upload; }
+    if is_key_pressed(KeyCode::Escape) {
+        game.mode = GameMode::Paused;
+        return;
+    }
+    if is_key_pressed(KeyCode::Enter) {
+        let report = feedback::Report {
+            text: &form.text,
+            settings: &game.settings,
+            seed: game.seed,
+            difficulty: game.difficulty.label(),
+            score: game.score,
+            elapsed: game.elapsed,
+            screenshot: if form.with_screenshot { form.screenshot.as_ref() } else { None },
+        };
+        form.status = Some(match feedback::build_zip(&report) {
+            Err(e) => trf("打包失败：{}", &[&e]),
+            Ok(bytes) => match (form.upload, feedback::endpoint()) {
+                (true, Some(url)) => match feedback::post(&url, &bytes) {
+                    Ok(()) => tr("已发送，谢谢！").to_string(),
+                    Err(e) => trf("发送失败：{}", &[&e]),
+                },
+                _ => match feedback::save_local(&bytes) {
+                    Ok(path) => trf("已保存到 {}", &[&path.display()]),
+                    Err(e) => trf("保存失败：{}", &[&e]),
+                },
+            },
+        });
+    }
+}
+
+// ===== 逻辑：固定时间步更新 =====
+fn update_game(game: &mut Game, dt: f32, res: &Content) {
+    match game.mode {
+        GameMode::Menu | GameMode::PreRun | GameMode::Stats | GameMode::Shop | GameMode::Customize | GameMode::Missions | GameMode::Achievements | GameMode::History | GameMode::Challenges | GameMode::NameEntry | GameMode::Leaderboard | GameMode::Profiles | GameMode::SyncConflict | GameMode::Settings | GameMode::FeelPanel | GameMode::Feedback | GameMode::Lobby | GameMode::Spectate | GameMode::Ranked | GameMode::Hotseat | GameMode::Keybinds => {}
+        GameMode::Summary => game.xp_anim += dt,
+        GameMode::Sandbox => sandbox::update(game, dt),
+        GameMode::Duel => duel::update(game, dt),
+        GameMode::Playing if game.resume_in > 0.0 => {
+            // 倒数中：模拟不走（也不读这一步的输入），只能再暂停
+            game.resume_in -= dt;
+            if pause_pressed(game) { enter_pause(game); }
+        }
+        GameMode::Playing => {
+            // 按键只在这里读一次，之后全用 inp（观看回放时来自回放）
+            let inp = replay::step_input(game, read_step_input);
+            ghost::tick(game);
+            let feel = game.feel_now();
+            game.effects.tick(&feel, dt, game.player.rect(), trail_speed(game), trail_boost(game));
+            game.banners.tick(dt);
+            game.texts.update(dt);
+            let dt = dt * game.settings.speed_scale * game.effects.time_scale() * console::time_scale(game);
+            // —— 移动：加速度+限速+衰减 —— 
+            // 冰面：无摩擦、加速减半；角色可缩短冲刺冷却
+            let phys = Physics { dash_cooldown: game.phys.dash_cooldown * game.character().dash_cd_mul, ..game.phys };
+            let phys = if game.mutators.ice { Physics { decay: 0.0, ..phys } } else { phys };
+            let acc_mul = game.events.acc_mul() * if game.mutators.ice { mutators::ICE_ACC_MUL } else { 1.0 };
+            let mut input = MoveInput { dir: inp.dir as f32 * inp.analog.map_or(1.0, |a| a as f32 / 100.0), dash: inp.dash, push: 0.0 };
+            if game.reverse > 0.0 { input.dir = -input.dir; }
+            input.push = game.wind.push_at(game.player.rect()) + gravity::push_on(game, game.player.rect());
+            if game.play == PlayMode::Coop {
+                coop::step(game, &phys, acc_mul, dt);
+            } else {
+                game.mover.step(&mut game.player, &phys, input, acc_mul, dt, game.field.x);
+            }
+
+            // —— 瞬移：鼠标瞄准的去鼠标处，否则去玩家高度上最大的空隙 ——
+            if game.teleport && inp.teleport {
+                game.teleport = false;
+                let from = game.player.rect().center();
+                let x = inp.aim.unwrap_or_else(|| widest_gap(game));
+                game.mover.warp(&mut game.player, x, game.field.x);
+                game.effects.burst(&feel, from, VIOLET, 14);
+                game.effects.burst(&feel, game.player.rect().center(), VIOLET, 14);
+            }
+
+            // —— 大招：满充能按 [Q] 超载 —— 
+            game.overdrive = (game.overdrive - dt).max(0.0);
+            if game.ult >= 1.0 && inp.overdrive {
+                game.ult = 0.0;
+                game.overdrive = OVERDRIVE_TIME;
+                game.effects.burst(&feel, game.player.rect().center(), SKYBLUE, 24);
+            }
+            // 以下全部按世界时间推进（玩家移动已经在上面用真实步长走完）
+            let dt = dt * game.world_time_scale();
+            if game.overdrive <= 0.0 { game.ult = (game.ult + ULT_PER_SEC * dt).min(1.0); }
+
+            // —— 冻结：障碍与生成器停住；减速计时也暂停，解冻后剩余的减速照常生效 —— 
+            let frozen = game.freeze.active();
+            game.freeze.tick(dt);
+            // 陷阱计时
+            game.reverse = (game.reverse - dt).max(0.0);
+            let haste_mul = if game.haste > 0.0 { HASTE_MUL } else { 1.0 };
+            game.haste = (game.haste - dt).max(0.0);
+            game.blackout = (game.blackout - dt).max(0.0);
+
+            // —— 减速效果衰减 —— 
+            if !frozen { game.slow.tick(dt); }
+            // 叠加时每层再按 falloff 递减地乘一次 SLOW_FACTOR
+            let slow_mul = SLOW_FACTOR.powf(game.slow.strength(game.pu_tuning.stacking.slow));
+
+            if game.play == PlayMode::Tutorial {
+                tutorial::tick(game, dt);
+                if game.tutorial.finished() {
+                    game.tutorial_done = true;
+                    game.leave_run();
+                    game.mode = GameMode::Menu;
+                    return;
+                }
+            } else if !frozen {
+                update_spawning(game, res, dt, slow_mul);
+            }
+
+            // —— 更新障碍 & 道具 —— 
+            // 减速 / 加速作用在积分步长上，已经在场上的障碍也会跟着变慢、恢复
+            let ob_dt = if frozen { 0.0 } else { dt * haste_mul * slow_mul };
+            gravity::update(game, ob_dt);
+            let dodged = game.obs.update_and_sweep(game.field.x, game.field.y, PLAYER_Y, ob_dt) + game.obs.resolve_deflected();
+            audio::track_intensity(game, dt);
+            cues::scan(game);
+            game.dodged += dodged;
+            award_points(game, dodged as i32 * DODGE_BONUS);
+            missions::bump(game, Metric::Dodged, dodged);
+            game.pus.update_and_sweep(game.field.y, dt);
+            for p in &mut game.pus.live {
+                p.rect.x = (p.rect.x + game.wind.drift_at(p.rect) * dt).clamp(0.0, game.field.x - p.rect.w);
+            }
+            coins::update(game, dt, ob_dt);
+
+            if game.shooting {
+                if inp.fire { shooting::fire(game); }
+                shooting::update(game, &feel, dt);
+            }
+
+            update_blast(game, &feel, dt);
+
+            // —— 倒流快照：只在持有充能时拍（拍照会重新播种随机数，不持有时序列不受影响） ——
+            if game.rewind {
+                game.rewind_tick -= dt;
+                if game.rewind_tick <= 0.0 {
+                    game.rewind_tick = REWIND_SAMPLE;
+                    let snap = suspend::capture(game);
+                    game.rewind_buf.push_back((game.elapsed, snap));
+                    while game.rewind_buf.front().is_some_and(|(t, _)| game.elapsed - t > REWIND_KEEP) { game.rewind_buf.pop_front(); }
+                }
+            }
+
+            // —— 计分（全部经 award_points 结算倍率；连击只乘在存活计分上） —— 
+            game.mult.tick(dt);
+            game.combo_timer -= dt;
+            if game.combo_timer <= 0.0 { break_combo(game); }
+            missions::tick(game, dt);
+            achievements::tick_still(game, dt);
+            achievements::check(game);
+            game.time_tick += dt;
+            while game.time_tick >= 0.4 {
+                game.time_tick -= 0.4;
+                award_points(game, game.combo as i32);
+            }
+
+            // —— 拾取道具 —— 
+            let mut picked = None;
+            for (_, pbox) in coop::standing(game) {
+                picked = game.pus.pick_at(pbox).or_else(|| wrap_ghost(pbox, game.field.x).and_then(|g| game.pus.pick_at(g))).map(|k| (k, pbox));
+                if picked.is_some() { break; }
+            }
+            if let Some((kind, pbox)) = picked {
+                audio::play(game, Sfx::Pickup);
+                game.effects.emit(&feel, pbox.center(), &particles::PICKUP.tint(powerup_color(game, kind)));
+                bump_combo(game);
+                game.stats.picked(kind);
+                missions::on_pickup(game, kind == PowerUpKind::Shield);
+                if game.play == PlayMode::Tutorial { game.tutorial.on_pickup(kind); }
+                if game.overdrive <= 0.0 && !kind.is_trap() { game.ult = (game.ult + ULT_PER_PICKUP).min(1.0); }
+                // 重复拾取按 assets/powerups.json 的规则处理，转换成的分数照常吃倍率
+                let rules = game.pu_tuning.stacking;
+                let converted = match kind {
+                    PowerUpKind::Shield => {
+                        let (n, pts) = powerups::add_count(game.shield, rules.shield, powerups::SHIELD_MAX);
+                        game.shield = n;
+                        pts
+                    }
+                    PowerUpKind::Slow => game.slow.apply(rules.slow, SLOW_DURATION),
+                    PowerUpKind::Multiplier => game.mult.apply(rules.multiplier, MULT_DURATION),
+                    PowerUpKind::Freeze => game.freeze.apply(rules.freeze, FREEZE_DURATION),
+                    _ => 0,
+                };
+                award_points_at(game, converted, pbox.center() - vec2(0.0, 20.0), powerup_color(game, kind));
+                match kind {
+                    PowerUpKind::Shield | PowerUpKind::Slow | PowerUpKind::Multiplier | PowerUpKind::Freeze => {}
+                    PowerUpKind::Heart  => { game.lives = (game.lives + 1).min(game.max_lives()); }
+                    PowerUpKind::Teleport => { game.teleport = true; }
+                    PowerUpKind::Rewind => { game.rewind = true; }
+                    PowerUpKind::Reverse => { game.reverse = REVERSE_DURATION; }
+                    PowerUpKind::Haste => { game.haste = HASTE_DURATION; }
+                    PowerUpKind::Blackout => { game.blackout = BLACKOUT_DURATION; }
+                    PowerUpKind::Ammo   => { game.ammo = (game.ammo + shooting::AMMO_PICKUP).min(shooting::MAX_AMMO); }
+                    PowerUpKind::Bomb   => detonate_bomb(game, &feel),
+                }
+            }
+
+            // —— 分数里程碑：横幅、慢动作、边框光晕，并在几秒内补一个道具 —— 
+            if matches!(game.play, PlayMode::Standard | PlayMode::Practice) {
+                while game.score >= game.next_milestone {
+                    game.banners.show(format!("{}!", game.next_milestone), "", GOLD, MILESTONE_BANNER_TIME);
+                    game.next_milestone += MILESTONE_EVERY;
+                    game.effects.slow_mo(&feel);
+                    game.milestone_pulse = 1.0;
+                    if game.milestone_pu.is_none() { game.milestone_pu = Some(rand::gen_range(0.5, MILESTONE_PU_WITHIN)); }
+                }
+            }
+            game.milestone_pulse = (game.milestone_pulse - dt / MILESTONE_PULSE_TIME).max(0.0);
+            if let Some(t) = game.milestone_pu {
+                if t - dt <= 0.0 {
+                    game.milestone_pu = None;
+                    let x = rand::gen_range(PU_SIZE, game.field.x - PU_SIZE);
+                    spawn_powerup(game, x, random_powerup(game));
+                } else {
+                    game.milestone_pu = Some(t - dt);
+                }
+            }
+
+            // —— 碰撞（护盾可抵消；命中盒瘦身；合作局两人各自判定，倒地的不算） —— 
+            let fw = game.field.x;
+            let (inset, grace) = game.forgiveness();
+            let bodies: Vec<(usize, Rect)> = coop::standing(game).into_iter().map(|(who, r)| (who, inset_hitbox(r.x, r.w, inset))).collect();
+            let mut grazes = [0; 2];
+            for &(who, hit) in &bodies {
+                grazes[who] += game.obs.count_near_misses(hit) + wrap_ghost(hit, fw).map_or(0, |g| game.obs.count_near_misses(g));
+            }
+            let near_misses = grazes[0] + grazes[1];
+            if near_misses > 0 {
+                game.effects.slow_mo(&feel);
+                game.stats.near_misses += near_misses;
+                missions::bump(game, Metric::Graze, near_misses);
+                let pts = award_points(game, near_misses as i32 * NEAR_MISS_BONUS);
+                if let Some(&(_, hit)) = bodies.iter().find(|(who, _)| grazes[*who] > 0) {
+                    game.texts.spawn(hit.center() - vec2(0.0, 24.0), &format!("close! +{}", pts), GOLD);
+                }
+                bump_combo(game);
+            }
+
+            // —— 擦边计量：攒满存一颗炸弹，[B] 手动引爆 —— 
+            if !game.stored_bomb {
+                game.graze += near_misses;
+                if game.graze >= GRAZE_FOR_BOMB { game.graze = GRAZE_FOR_BOMB; game.stored_bomb = true; }
+            }
+            if game.stored_bomb && inp.bomb {
+                game.stored_bomb = false;
+                game.graze = 0;
+                detonate_bomb(game, &feel);
+            }
+
+            // —— 合作救援：队友没能及时擦够次数，本局结束 —— 
+            if let Some(hit) = coop::tick(game, dt, grazes, &feel) {
+                game_over(game, &feel, hit, tr("没救回队友"), None);
+                return;
+            }
+
+            // (障碍下标, 撞上的玩家, 他的命中盒)
+            let mut collided: Option<(usize, usize, Rect)> = None;
+            game.invuln = (game.invuln - dt).max(0.0);
+            for (i, o) in game.obs.live.iter().enumerate() {
+                if game.invuln > 0.0 || game.overdrive > 0.0 { break; } // 无敌 / 超载时穿过障碍
+                if !collision::interacts(Layer::PLAYER, o.layer()) { continue; }
+                let touches = bodies.iter().find(|(_, h)| rects_overlap(o.rect, *h) || wrap_ghost(*h, fw).is_some_and(|g| rects_overlap(o.rect, g)));
+                if let Some(&(who, h)) = touches { collided = Some((i, who, h)); break; }
+            }
+            // 命中宽限：连续压够步数才算撞上
+            game.overlap = if collided.is_some() { game.overlap + 1 } else { 0 };
+            if game.overlap <= grace { collided = None; }
+            if let Some((i, who, hit)) = collided {
+                if game.shield > 0 {
+                    // 护盾抵消一次：把障碍弹回去（短时间内可撞碎别的障碍）、护盾-1、短暂无敌、轻微震屏
+                    let o = &mut game.obs.live[i];
+                    o.bounce_back();
+                    game.effects.emit(&feel, o.rect.center(), &particles::SHIELD);
+                    game.effects.hit_stop(&feel, effects::HIT_STOP_SHIELD);
+                    audio::play(game, Sfx::Shield);
+                    game.shield -= 1;
+                    game.stats.shields_used += 1;
+                    game.invuln = game.invuln.max(SHIELD_INVULN_TIME);
+                    break_combo(game);
+                    game.shake.add(Jolt::Shield);
+                    rumble(game);
+                } else if game.harmless() {
+                    // 禅模式 / 教程 / 无敌练习：只把障碍轻轻弹开
+                    let o = &mut game.obs.live[i];
+                    o.bounce_back();
+                    game.effects.burst(&feel, o.rect.center(), LIGHTGRAY, 8);
+                    game.shake.add(Jolt::Bump);
+                } else if game.lives > 1 {
+                    // 还有命：扣一条，清掉身边的障碍并短暂无敌
+                    game.lives -= 1;
+                    game.stats.deaths += 1;
+                    game.invuln = LIFE_INVULN_TIME;
+                    let c = hit.center();
+                    let mut j = 0;
+                    while j < game.obs.live.len() {
+                        if game.obs.live[j].rect.center().distance(c) < LIFE_CLEAR_RADIUS {
+                            let dead = game.obs.live.swap_remove(j);
+                            game.effects.burst(&feel, dead.rect.center(), PINK, 6);
+                            game.anims.crumble(dead.rect);
+                            game.obs.dead.push(dead);
+                        } else {
+                            j += 1;
+                        }
+                    }
+                    game.shake.add(Jolt::LifeLost);
+                } else if coop::can_go_down(game) {
+                    // 合作：被撞的一方倒地，等队友来救
+                    game.obs.live[i].bounce_back();
+                    coop::go_down(game, who, &feel);
+                } else if game.rewind && !game.rewind_buf.is_empty() {
+                    // 时光倒流：回到约 2 秒前，充能用掉
+                    rewind_world(game);
+                    game.effects.burst(&feel, game.player.rect().center(), MAGENTA, 24);
+                    game.shake.add(Jolt::Rewind);
+                } else {
+                    let killer = game.obs.live[i];
+                    game_over(game, &feel, hit, death_cause(&killer), Some(killer.rect));
+                }
+            }
+
+            game.director.tick(dt, near_misses, collided.is_some());
+
+            // 联机对战不能暂停：对手那边不会停
+            if game.play == PlayMode::Versus {
+                versus::tick(game);
+            } else if !replay::headless(game) && pause_pressed(game) {
+                enter_pause(game);
+            }
+        }
+        GameMode::Paused if game.confirm_quit.is_some() => {}
+        GameMode::Paused => {
+            if pause_pressed(game) { game.resume(); }
+            // 每日挑战只有一次机会：不能重开，中途退出按当前得分记录
+            if game.settings.keys.restart.pressed() && game.can_retry() { game.reset_round(); }
+        }
+        GameMode::Killcam => killcam::update(game, dt),
+        GameMode::GameOver => {
+            let feel = game.feel_now();
+            game.effects.tick(&feel, dt, game.player.rect(), trail_speed(game), trail_boost(game));
+            game.texts.update(dt);
+            if game.play == PlayMode::Versus {
+                versus::tick(game);
+                if is_key_pressed(KeyCode::Escape) { versus::back_to_lobby(game); }
+                return;
+            }
+            if game.play == PlayMode::Hotseat { return hotseat::game_over_input(game); }
+            if (is_key_pressed(KeyCode::C) && game.can_continue()) || replay::should_continue(game) { game.continue_run(); }
+            if game.settings.keys.restart.pressed() && game.can_retry() { game.end_over(true); }
+            if is_key_pressed(KeyCode::Escape) { game.end_over(false); }
+        }
+    }
+
+    // 震动衰减（减少动态效果时直接关闭）
+    if motion::calm(game) { game.shake.clear(); }
+    game.shake.update(dt);
+}
+
+// 本局结束（自适应模式的成绩不计入最高分）；hit 是倒下那位的命中盒，killer 是撞死他的障碍（合作局没救回队友时为 None）
+fn game_over(game: &mut Game, feel: &Feel, hit: Rect, cause: &str, killer: Option<Rect>) {
+    let prev_best = game.best();
+    game.record_best();
+    online::submit_run(game);
+    game.finish_stats(prev_best);
+    achievements::check(game); // 结算发的经验可能升级
+    if game.play != PlayMode::Tutorial && !game.replay.watching() {
+        game.pending_log = Some(HistoryEntry {
+            id: (macroquad::miniquad::date::now() * 1000.0) as u64,
+            day: challenge::today(),
+            mode: game.mode_label(),
+            seed: game.seed,
+            score: game.score,
+            duration: game.elapsed,
+            cause: cause.to_string(),
+            replay: false,
+        });
+    }
+    if let Some(killer) = killer.filter(|_| game.play != PlayMode::Tutorial) {
+        let fw = game.field.x;
+        game.deaths.push(DeathRecord {
+            x: hit.center().x / fw,
+            elapsed: game.elapsed,
+            on_screen: game.obs.live.len() as u32,
+            killer_x: killer.center().x / fw,
+            killer_w: killer.w / fw,
+        });
+    }
+    game.director.end_run(game.elapsed);
+    save_game(game);
+    killcam::start(game, hit.center());
+    game.shake.add(Jolt::Death);
+    audio::play(game, Sfx::Death);
+    rumble(game);
+    game.effects.emit(feel, hit.center(), &particles::DEATH);
+}
+
+// 手柄震动：跟着这一下之后的创伤值走（减少动态效果不影响震动，设置里单独开关）
+#[cfg(feature = "gamepad")]
+fn rumble(game: &Game) {
+    if game.settings.rumble && !game.replay.watching() { pad::rumble(game.shake.trauma() * 10.0, game.settings.rumble_strength); }
+}
+#[cfg(not(feature = "gamepad"))]
+fn rumble(_: &Game) {}
+
+// 暂停中退出这一局，回菜单
+fn quit_run(game: &mut Game) {
+    // 轮流赛：放弃这一局，按当前得分算成绩
+    if game.play == PlayMode::Hotseat { return hotseat::finish_turn(game); }
+    abandon_run(game);
+    game.mode = GameMode::Menu;
+}
+
+// 中途离开一局（回菜单或直接关窗）：只有一次机会的挑战按当前得分记录，能挂起的挂起，再还原玩家自己的规则并存档
+fn abandon_run(game: &mut Game) {
+    if matches!(game.mode, GameMode::Playing | GameMode::Paused) && !game.can_retry() { game.record_best(); }
+    if suspend::can_suspend(game) { suspend::suspend(game); }
+    game.leave_run(); // 练习参数等也随之保存
+}
+
+// 离开前告诉玩家这一局会怎样
+fn quit_warning(game: &Game) -> &'static str {
+    if game.play == PlayMode::Hotseat { tr("这一局按当前得分算成绩") }
+    else if suspend::can_suspend(game) { tr("这一局会挂起，之后可以在菜单里接着玩") }
+    else if !game.can_retry() { tr("只有一次机会，按当前得分记录，不能再来") }
+    else { tr("这一局的进度会丢失") }
+}
+
+// —— 主菜单 ——
+// 一列入口，[↑/↓] / 鼠标选、[ENTER] 确定；“开始游戏”再展开一列玩法。两边上次选的项记在设置里，下次打开默认选它。
+// 各界面的字母快捷键照旧可用。
+const MENU_X: f32 = 150.0; // 菜单列的中线
+
+struct MenuEntry { label: &'static str, open: fn(&mut Game) }
+
+const MAIN_ENTRIES: [MenuEntry; 6] = [
+    MenuEntry { label: "开始游戏", open: |g| g.play_menu = true },
+    MenuEntry { label: "每日挑战", open: |g| g.open_challenges(Challenge::Daily) },
+    MenuEntry { label: "排行榜", open: open_leaderboard },
+    MenuEntry { label: "统计", open: open_stats },
+    MenuEntry { label: "设置", open: |g| options::enter(g, 0) },
+    MenuEntry { label: "退出", open: |g| g.quitting = true },
+];
+
+// 玩法：(文字, 快捷键, 开始)
+struct MenuMode { label: &'static str, key: Option<KeyCode>, start: fn(&mut Game) }
+
+const MENU_MODES: [MenuMode; 7] = [
+    MenuMode { label: "开始", key: None, start: |g| g.mode = GameMode::PreRun },
+    MenuMode { label: "禅模式 Z", key: Some(KeyCode::Z), start: |g| { g.play = PlayMode::Zen; g.reset_round(); } },
+    MenuMode { label: "练习 T", key: Some(KeyCode::T), start: |g| { g.play = PlayMode::Practice; g.reset_round(); } },
+    MenuMode { label: "教程 H", key: Some(KeyCode::H), start: Game::start_tutorial },
+    MenuMode { label: "双人合作 J", key: Some(KeyCode::J), start: coop::start },
+    MenuMode { label: "同屏对决 X", key: Some(KeyCode::X), start: duel::enter },
+    MenuMode { label: "轮流赛 U", key: Some(KeyCode::U), start: hotseat::enter },
+];
+
+fn main_items() -> Vec<Item<'static>> { MAIN_ENTRIES.iter().map(|e| Item { label: tr(e.label), enabled: true }).collect() }
+fn main_rects() -> Vec<Rect> { widget::column(MAIN_ENTRIES.len(), MENU_X, 230.0, vec2(220.0, 40.0), 8.0) }
+
+// 玩法列表最后多一项“返回”
+fn play_items() -> Vec<Item<'static>> {
+    MENU_MODES.iter().map(|m| m.label).chain(["返回"]).map(|label| Item { label: tr(label), enabled: true }).collect()
+}
+fn play_rects() -> Vec<Rect> { widget::column(MENU_MODES.len() + 1, MENU_X, 226.0, vec2(220.0, 32.0), 6.0) }
+
+fn update_main_menu(game: &mut Game) {
+    let mouse = mouse_world(game);
+    if game.play_menu {
+        let picked = game.play_list.update(&play_items(), &play_rects(), mouse);
+        if is_key_pressed(KeyCode::Escape) || picked == Some(MENU_MODES.len()) { game.play_menu = false; return; }
+        let Some(i) = picked else { return };
+        game.settings.play_entry = i;
+        game.settings.save();
+        game.play_menu = false;
+        return (MENU_MODES[i].start)(game);
+    }
+    let Some(i) = game.main_menu.update(&main_items(), &main_rects(), mouse) else { return };
+    game.settings.menu_entry = i;
+    game.settings.save();
+    (MAIN_ENTRIES[i].open)(game);
+}
+
+fn open_leaderboard(game: &mut Game) {
+    game.top_rank = None;
+    game.top_cursor = 0;
+    game.top_msg = None;
+    game.top_global = false;
+    game.mode = GameMode::Leaderboard;
+}
+
+fn open_stats(game: &mut Game) {
+    game.export_msg = None;
+    game.mode = GameMode::Stats;
+}
+
+fn pause_items(game: &Game) -> [Item<'static>; 4] {
+    let quit = if suspend::can_suspend(game) { tr("挂起并回菜单") } else { tr("回菜单") };
+    [
+        Item { label: tr("继续"), enabled: true },
+        Item { label: tr("重开"), enabled: game.can_retry() },
+        Item { label: tr("设置"), enabled: true },
+        Item { label: quit, enabled: true },
+    ]
+}
+
+fn pause_rects(game: &Game) -> Vec<Rect> { widget::column(4, game.field.x * 0.5, 220.0, vec2(280.0, 46.0), 12.0) }
+
+fn confirm_items() -> [Item<'static>; 2] { [Item { label: tr("继续游戏"), enabled: true }, Item { label: tr("确认离开"), enabled: true }] }
+
+fn confirm_rects(game: &Game) -> Vec<Rect> { widget::column(2, game.field.x * 0.5, 290.0, vec2(240.0, 42.0), 10.0) }
+
+// 回菜单先确认；看回放时没什么可丢的，直接走
+fn ask_quit(game: &mut Game) {
+    if game.replay.watching() { return quit_run(game); }
+    game.confirm_quit = Some(ListMenu::default());
+}
+
+// 暂停菜单（每帧）；暂停键和重开键的快捷操作在 update_game 里
+fn update_pause_menu(game: &mut Game) {
+    let mouse = mouse_world(game);
+    let confirm = confirm_rects(game);
+    if let Some(menu) = game.confirm_quit.as_mut() {
+        let picked = menu.update(&confirm_items(), &confirm, mouse);
+        if is_key_pressed(KeyCode::Escape) || picked == Some(0) { game.confirm_quit = None; }
+        if picked == Some(1) {
+            game.confirm_quit = None;
+            quit_run(game);
+        }
+        return;
+    }
+    if is_key_pressed(KeyCode::Escape) { return ask_quit(game); }
+    let (items, rects) = (pause_items(game), pause_rects(game));
+    match game.pause_menu.update(&items, &rects, mouse) {
+        Some(0) => game.resume(),
+        Some(1) => game.reset_round(),
+        Some(2) => options::enter_from_pause(game),
+        Some(_) => ask_quit(game),
+        None => {}
+    }
+}
+
+// 鼠标在场地坐标里的位置
+fn mouse_world(game: &Game) -> Vec2 { field_camera(view_size(game), Vec2::ZERO).screen_to_world(mouse_position().into()) }
+
+fn enter_pause(game: &mut Game) {
+    game.mode = GameMode::Paused;
+    game.pause_menu.cursor = 0;
+    game.confirm_quit = None;
+    game.auto_paused = None;
+}
+
+// —— 自动暂停 ——
+// macroquad 拿不到窗口焦点，只能看迹象：一帧卡了很久（最小化、网页切到后台标签页时画面会停），
+// 或者很久没有任何移动输入（多半切到别的窗口了）。恢复只能靠玩家自己在暂停菜单里操作。
+// 安卓也收不到切后台的事件（macroquad 只拿它停声音），但切走时不再出帧，回来第一帧必然卡过：这种一律暂停，不看设置。
+// 切走期间进程被杀的情况靠 suspend::checkpoint 兜底。
+const STALL_SECS: f32 = 0.5;
+const IDLE_SECS: f32 = 25.0; // 比“不动如山”成就的 20 秒长
+
+// 每帧；返回 true 表示这一帧卡住的时间不该补算
+fn auto_pause(game: &mut Game, dt: f32) -> bool {
+    let watch = (game.settings.auto_pause || (cfg!(target_os = "android") && dt > STALL_SECS)) && game.mode == GameMode::Playing && game.play != PlayMode::Versus && !game.replay.watching();
+    game.idle = if !watch || input_axis(game) != 0.0 { 0.0 } else { game.idle + dt };
+    if !watch { return false; }
+    let reason = if dt > STALL_SECS { tr("画面停了一会儿（窗口最小化或切到了后台）") } else if game.idle > IDLE_SECS { tr("很久没有操作") } else { return false };
+    enter_pause(game);
+    game.auto_paused = Some(reason);
+    dt > STALL_SECS
+}
+
+// 暂停键：键位里的暂停键，或手柄的 Start
+fn pause_pressed(game: &Game) -> bool { game.settings.keys.pause.pressed() || is_key_pressed(KeyCode::Pause) }
+
+// ===== 绘制 =====
+// 提示里的按键名：手柄是当前设备时换成手柄上的按键
+#[cfg(feature = "gamepad")]
+fn glyph(key: KeyCode, keyboard: &'static str) -> &'static str { pad::glyph(key).unwrap_or(keyboard) }
+#[cfg(not(feature = "gamepad"))]
+fn glyph(_: KeyCode, keyboard: &'static str) -> &'static str { keyboard }
+
+// w 为逻辑场地宽度（见 field_camera）
+// 本局用时 mm:ss.t（倍率道具之后分数和存活时间不再同步涨，两个都要看）
+fn run_clock(secs: f32) -> String {
+    let ds = (secs.max(0.0) * 10.0) as u32;
+    format!("{:02}:{:02}.{}", ds / 600, ds / 10 % 60, ds % 10)
+}
+
+fn draw_text_center(font: &Font, w: f32, text: &str, y: f32, size: f32, color: Color) {
+    let dim = measure_text(text, Some(font), size as u16, 1.0);
+    let x = w * 0.5 - dim.width * 0.5;
+    draw_text_ex(text, x, y, TextParams { font: Some(font), font_size: size as u16, color, ..Default::default() });
+}
+
+fn draw_hud(font: &Font, game: &Game) {
+    use hud::Anchor::{Bottom, BottomLeft, BottomRight, Top, TopLeft, TopRight};
+    if screenshot::hides_hud(game) { return; }
+    let hud = Hud::new(game);
+    let ts = hud.s;
+    // HUD 的颜色跟着主题走（见 palette.rs）
+    let pal = &game.palette().hud;
+    let (text, score, best, info, warn, good) = (pal.text.color(), pal.score.color(), pal.best.color(), pal.info.color(), pal.warn.color(), pal.good.color());
+    match game.settings.hud {
+        HudMode::Full => {}
+        HudMode::Minimal => {
+            hud.text(font, &format!("{}  {}", game.score, run_clock(game.elapsed)), TopLeft, vec2(16.0, 28.0), 24.0, score);
+            return status::draw_tray(font, game, &hud.scaled(0.6));
+        }
+        HudMode::Hidden => return,
+    }
+    let keys = &game.settings.keys;
+    let bar_bg = if game.settings.high_contrast { BLACK } else { pal.bar.color() };
+    draw_rectangle(0.0, 0.0, game.field.x, hud.px(hud::BAR_H), bar_bg);
+    // 第一行：左边分数，中间偏左生命，右边道具状态
+    if game.play == PlayMode::Zen {
+        hud.text(font, &trf("ZEN  {}", &[&run_clock(game.elapsed)]), TopLeft, vec2(16.0, 30.0), 28.0, good);
+        hud.text(font, &trf("{}  BEST: {}", &[&game.score, &game.best()]), TopLeft, vec2(190.0, 30.0), 22.0, best);
+    } else {
+        hud.text(font, &trf("SCORE: {:>4}", &[&game.score]), TopLeft, vec2(16.0, 30.0), 28.0, score);
+        hud.text(font, &trf("BEST:  {:>4}", &[&game.best()]), TopLeft, vec2(190.0, 30.0), 28.0, best);
+    }
+    for i in 0..game.lives {
+        let p = hud.at(TopLeft, vec2(380.0 + i as f32 * 26.0, 23.0));
+        draw_heart(p.x, p.y, hud.px(9.0), pal.lives.color());
+    }
+    status::draw_tray(font, game, &hud);
+    // 擦边计量条：贴在顶栏下沿左侧，存满后变金色
+    let meter = vec2(160.0, 4.0);
+    hud.meter(TopLeft, vec2(16.0, hud::BAR_H - 5.0), meter, game.graze as f32 / GRAZE_FOR_BOMB as f32, if game.stored_bomb { best } else { warn });
+    if game.stored_bomb {
+        hud.text(font, &trf("[{}] 炸弹", &[&glyph(keys.bomb.primary(), keys.bomb.label())]), TopLeft, vec2(16.0 + meter.x + 8.0, hud::BAR_H + 4.0), 16.0, best);
+    }
+    // 大招充能条：顶栏下沿右侧
+    hud.meter(TopRight, vec2(16.0 + meter.x, hud::BAR_H - 5.0), meter, game.ult, if game.ult >= 1.0 { info } else { BLUE });
+    if game.overdrive > 0.0 {
+        hud.text(font, &trf("超载 {:.1}s", &[&game.overdrive]), Top, vec2(0.0, hud::BAR_H + 50.0), 22.0, info);
+    } else if game.ult >= 1.0 {
+        hud.text(font, &trf("[{}] 超载", &[&glyph(keys.overdrive.primary(), keys.overdrive.label())]), TopRight, vec2(16.0 + meter.x + 8.0, hud::BAR_H + 4.0), 16.0, info);
+    }
+    // 第二行：左边用时和世界事件，中间喘息，右边本局的规则标签和弹药 / 瞬移（禅模式的用时在顶栏里）
+    let row2 = hud::BAR_H + 24.0;
+    let ev_x = if game.play == PlayMode::Zen { 16.0 } else {
+        hud.text(font, &run_clock(game.elapsed), TopLeft, vec2(16.0, row2), 22.0, text);
+        112.0
+    };
+    if let Some(ev) = &game.events.active {
+        hud.text(font, &format!("{} {:.1}s", tr(ev.def.name), ev.remaining.max(0.0)), TopLeft, vec2(ev_x, row2), 22.0, warn);
+    }
+    if game.breather { hud.text(font, tr("喘息"), Top, vec2(0.0, row2), 22.0, good); }
+    if let Some(c) = &game.challenge {
+        hud.text(font, if c.kind == Challenge::Daily { tr("每日") } else { tr("每周") }, TopRight, vec2(16.0, row2), 22.0, info);
+    } else if game.director.enabled {
+        hud.text(font, tr("自适应"), TopRight, vec2(16.0, row2), 22.0, warn);
+    } else if game.settings.assisted() {
+        hud.text(font, tr("辅助移动"), TopRight, vec2(16.0, row2), 22.0, warn);
+    } else if game.settings.slowed() {
+        hud.text(font, &trf("慢速 {}%", &[&(game.settings.speed_scale * 100.0).round()]), TopRight, vec2(16.0, row2), 22.0, warn);
+    }
+    if game.shooting { hud.text(font, &trf("AMMO:{}", &[&game.ammo]), TopRight, vec2(96.0, row2), 22.0, score); }
+    if game.teleport {
+        let blink_txt = if game.shooting { tr("BLINK [E]").to_string() } else { trf("BLINK [{}]", &[&glyph(KeyCode::Space, "SPACE")]) };
+        hud.text(font, &blink_txt, TopRight, vec2(200.0, row2), 22.0, game.palette().powerups.teleport.color());
+    }
+    // 第三行：连击倍率与剩余窗口
+    if game.combo > 1 {
+        let row3 = hud::BAR_H + 48.0;
+        hud.text(font, &trf("COMBO x{}", &[&game.combo]), TopLeft, vec2(16.0, row3), 18.0, pal.combo.color());
+        hud.meter(TopLeft, vec2(16.0, row3 + 5.0), vec2(90.0, 3.0), game.combo_timer / COMBO_WINDOW, pal.combo.color());
+    }
+    // 陷阱警告：屏幕中上方逐行列出
+    let traps = [(game.reverse, tr("左右颠倒")), (game.haste, tr("障碍加速")), (game.blackout, tr("黑暗"))];
+    for (i, (t, name)) in traps.iter().filter(|(t, _)| *t > 0.0).enumerate() {
+        hud.text(font, &format!("⚠ {} {:.1}s", name, t), Top, vec2(0.0, hud::BAR_H + 76.0 + i as f32 * 24.0), 22.0, pal.danger.color());
+    }
+    // 底部：左角色名（非标准角色），右金币，中间提示
+    if game.character != 0 {
+        let ch = game.character();
+        hud.text(font, tr(ch.name), BottomLeft, vec2(16.0, 12.0), 18.0, ch.body_color());
+    }
+    hud.text(font, &trf("COIN:{}", &[&game.wallet.coins]), BottomRight, vec2(16.0, 12.0), 18.0, best);
+    if game.replay.watching() {
+        hud.text(font, tr("回放中"), Bottom, vec2(0.0, 34.0), 20.0, info);
+    } else if game.field_changed {
+        hud.text(font, tr("窗口尺寸已变化：按 [Y] 应用到本局，否则下一局生效"), Bottom, vec2(0.0, 8.0), 18.0, LIGHTGRAY);
+    }
+    missions::draw_toast(game, font, ts);
+    coop::draw_hud(font, game, ts);
+    #[cfg(feature = "twitch")]
+    twitch::draw_hud(font, game, ts);
+}
+
+// 两个圆 + 一个倒三角拼出的小红心
+fn draw_heart(cx: f32, cy: f32, r: f32, color: Color) {
+    draw_circle(cx - r * 0.5, cy - r * 0.2, r * 0.55, color);
+    draw_circle(cx + r * 0.5, cy - r * 0.2, r * 0.55, color);
+    draw_triangle(vec2(cx - r * 1.02, cy), vec2(cx + r * 1.02, cy), vec2(cx, cy + r), color);
+}
+
+fn draw_blast(game: &Game) {
+    if let Some(b) = &game.blast {
+        let t = (b.age / BLAST_TIME).min(1.0);
+        let c = game.palette().blast.color();
+        draw_circle_lines(b.center.x, b.center.y, b.radius * t, 4.0, Color { a: c.a * (1.0 - t * 0.7), ..c });
+    }
+}
+
+// 拖影按挡板的速度伸缩，冲刺和超载时加长加亮（见 effects.rs）
+fn trail_speed(game: &Game) -> f32 { game.player.vx.abs() / game.player.speed_max.max(1.0) }
+fn trail_boost(game: &Game) -> bool { game.player.dash_timer > 0.0 || game.overdrive > 0.0 }
+
+fn draw_player(game: &Game, res: &Resources) {
+    let skin = game.skin();
+    let body = if game.settings.high_contrast {
+        Color::from_rgba(0, 255, 255, 255)
+    } else {
+        skin.body.map(cosmetics::rgb).or(game.palette().player.map(Paint::color)).unwrap_or(game.character().body_color())
+    };
+    game.effects.draw_trail(body);
+    // 无敌期间闪烁；减少动态效果时改成外面一圈呼吸的边框（画在最后）
+    if game.invuln > 0.0 && !motion::blink(game, game.invuln, 6.0) { return; }
+    // 合作局两块挡板；跨边缘时两侧各画一份。有贴图用贴图（乘上皮肤颜色），没有就画色块加条纹
+    let shapes = game.settings.high_contrast;
+    let mut batch = res.sprites.batch();
+    for (who, p) in coop::players(game) {
+        let body = coop::tint(game, who, body);
+        let r = p.rect();
+        for r in std::iter::once(r).chain(wrap_ghost(r, game.field.x)) {
+            if shapes || !batch.push(res.sprites.frame("player", game.anims.clock()), r, body) {
+                draw_rectangle(r.x, r.y, r.w, r.h, body);
+                draw_rectangle(r.x + 10.0, r.y + 4.0, r.w - 20.0, 3.0, cosmetics::rgb(skin.stripe));
+            }
+            // 若有护盾，画一圈外发光
+            if game.shield > 0 {
+                let g = skin.glow;
+                draw_rectangle_lines(r.x - 4.0, r.y - 4.0, r.w + 8.0, r.h + 8.0, 2.0, Color::from_rgba(g[0], g[1], g[2], 220));
+            }
+        }
+    }
+    batch.flush();
+    if game.invuln > 0.0 && motion::calm(game) {
+        let a = 0.25 + 0.5 * motion::pulse(game.invuln);
+        for (_, p) in coop::players(game) {
+            let r = p.rect();
+            draw_rectangle_lines(r.x - 7.0, r.y - 7.0, r.w + 14.0, r.h + 14.0, 2.0, Color { a, ..body });
+        }
+    }
+}
+
+// 三档危险等级的障碍色（见 threat.rs）；默认主题的基准色随阶段变化
+fn obstacle_palette(game: &Game, res: &Resources) -> [Color; 3] {
+    let base = game.theme().obstacle.map_or(res.content.stages.def(game.stage).obstacle_color(), cosmetics::rgb);
+    threat::palette(game.theme(), base)
+}
+
+// 熄灯时只看得见挡板附近的障碍
+fn visible_obstacles(game: &Game) -> impl Iterator<Item = &Obstacle> {
+    let center = game.player.rect().center();
+    game.obs.live.iter().filter(move |o| game.blackout <= 0.0 || o.rect.center().distance(center) <= BLACKOUT_RADIUS)
+}
+
+fn draw_obstacles(game: &Game, res: &Resources) {
+    // 高对比度：纯色填充 + 粗白边（危险等级只看记号）；否则按等级取主题配色，描边是同色调的亮色
+    let high_contrast = game.settings.high_contrast;
+    let palette = obstacle_palette(game, res);
+    let style = |tier| {
+        if high_contrast { return (Color::from_rgba(255, 40, 40, 255), WHITE, 4.0); }
+        let c = threat::color(&palette, tier);
+        let edge = Color::new(c.r + (1.0 - c.r) * 0.5, c.g + (1.0 - c.g) * 0.5, c.b + (1.0 - c.b) * 0.5, 0.94);
+        (Color::new(c.r, c.g, c.b, 0.9), sky(game).outline(edge), 2.0)
+    };
+    let fill = |tier| {
+        let (fill, _, _) = style(tier);
+        // 冻结时障碍偏冰蓝
+        if game.freeze.active() { Color::new((fill.r + FREEZE_COLOR.r) * 0.5, (fill.g + FREEZE_COLOR.g) * 0.5, (fill.b + FREEZE_COLOR.b) * 0.5, fill.a) } else { fill }
+    };
+    let tier = |o: &Obstacle| threat::Tier::of(o.vy, game.fall_speed);
+    let visible = || visible_obstacles(game);
+    // 贴图先按页画完，裂纹 / 装甲、等级记号和被弹开的描边再统一压在上面
+    let mut batch = res.sprites.batch();
+    for o in visible() {
+        if !high_contrast && batch.push(res.sprites.frame("obstacle", game.anims.clock()), o.rect, fill(tier(o))) { continue; }
+        let (_, edge, thick) = style(tier(o));
+        let edge = if o.deflect > 0.0 { SKYBLUE } else if game.freeze.active() { FREEZE_COLOR } else { edge };
+        draw_rectangle(o.rect.x, o.rect.y, o.rect.w, o.rect.h, fill(tier(o)));
+        draw_rectangle_lines(o.rect.x, o.rect.y, o.rect.w, o.rect.h, thick, edge);
+    }
+    batch.flush();
+    if !high_contrast { game.anims.draw_crumbles(&res.sprites, fill(threat::Tier::Normal)); }
+    let sprite = !high_contrast && res.sprites.has("obstacle");
+    for o in visible() {
+        let (_, edge, thick) = style(tier(o));
+        if sprite && o.deflect > 0.0 { draw_rectangle_lines(o.rect.x, o.rect.y, o.rect.w, o.rect.h, thick, SKYBLUE); }
+        threat::draw_marks(o.rect, tier(o), o.vx, edge);
+        shooting::draw_armor(o.rect, o.armor);
+    }
+    if game.settings.spawn_warnings && game.blackout <= 0.0 { draw_spawn_warnings(game); }
+}
+
+// 来袭预警：还在场地上方的障碍，在顶栏下方对应的 x 处画一个向下的“!”；越快的越大越红，快进场时越亮
+fn draw_spawn_warnings(game: &Game) {
+    let hud = Hud::new(game);
+    let y = hud.px(hud::BAR_H) + 6.0;
+    for o in game.obs.live.iter().filter(|o| o.vy > 0.0 && o.rect.y + o.rect.h < 0.0) {
+        let eta = -(o.rect.y + o.rect.h) / o.vy;
+        let alpha = (1.0 - eta / (SPAWN_LEAD * 2.0)).clamp(0.2, 1.0);
+        let fast = (o.vy / game.fall_speed.max(1.0) - 0.8).clamp(0.0, 1.0); // 普通方块约 0，飞镖 1
+        let color = Color::new(1.0, 0.85 - 0.6 * fast, 0.2 - 0.1 * fast, alpha);
+        let (cx, s) = (o.rect.center().x, 7.0 + 5.0 * fast);
+        draw_triangle(vec2(cx - s, y), vec2(cx + s, y), vec2(cx, y + s * 1.4), color);
+        draw_rectangle(cx - 1.0, y + 2.0, 2.0, s * 0.55, BLACK);
+        draw_rectangle(cx - 1.0, y + 3.0 + s * 0.65, 2.0, 2.0, BLACK);
+    }
+}
+
+// 道具颜色跟着主题走（见 palette.rs）
+// 选了色觉模式时用它的配色，否则用主题的
+fn powerup_palette(game: &Game) -> &palette::PowerUpPalette {
+    palette::PowerUpPalette::for_vision(game.settings.color_vision).unwrap_or(&game.palette().powerups)
+}
+fn powerup_color(game: &Game, kind: PowerUpKind) -> Color { powerup_palette(game).get(kind) }
+
+// 图集里的贴图名
+fn powerup_sprite(kind: PowerUpKind) -> &'static str {
+    match kind {
+        PowerUpKind::Shield => "powerup_shield",
+        PowerUpKind::Slow => "powerup_slow",
+        PowerUpKind::Bomb => "powerup_bomb",
+        PowerUpKind::Heart => "powerup_heart",
+        PowerUpKind::Teleport => "powerup_teleport",
+        PowerUpKind::Ammo => "powerup_ammo",
+        PowerUpKind::Rewind => "powerup_rewind",
+        PowerUpKind::Multiplier => "powerup_multiplier",
+        PowerUpKind::Freeze => "powerup_freeze",
+        PowerUpKind::Reverse => "powerup_reverse",
+        PowerUpKind::Haste => "powerup_haste",
+        PowerUpKind::Blackout => "powerup_blackout",
+    }
+}
+
+fn draw_powerups(game: &Game, res: &Resources) {
+    let shapes = game.settings.high_contrast;
+    let mut batch = res.sprites.batch();
+    for p in &game.pus.live {
+        // 道具的旋转动画按位置错开相位，不会整齐划一地转
+        let t = game.anims.clock() + p.rect.x * 0.01;
+        if shapes || !batch.push(res.sprites.frame(powerup_sprite(p.kind), t), p.rect, powerup_color(game, p.kind)) {
+            draw_circle(p.rect.x + p.rect.w/2.0, p.rect.y + p.rect.h/2.0, p.rect.w*0.45, powerup_color(game, p.kind));
+        }
+    }
+    batch.flush();
+    if game.settings.powerup_glyphs {
+        for p in &game.pus.live { glyphs::draw(p.kind, p.rect, powerup_color(game, p.kind)); }
+    }
+    // 陷阱的边框默认是暗红色，仔细看才分得出
+    let trap_border = powerup_palette(game).trap_border.color();
+    for p in &game.pus.live {
+        let border = if p.kind.is_trap() { trap_border } else { WHITE };
+        draw_rectangle_lines(p.rect.x, p.rect.y, p.rect.w, p.rect.h, 1.5, border);
+    }
+}
+
+fn draw_menu(font: &Font, game: &Game) {
+    let ts = game.settings.text_scale();
+    let w = game.field.x;
+    let text = |txt: &str, x: f32, y: f32, size: f32, color: Color| {
+        draw_text_ex(txt, x, y, TextParams { font: Some(font), font_size: (size * ts) as u16, color, ..Default::default() });
+    };
+    draw_text_center(font, w, "Dodge Rush", 110.0, 62.0 * ts, SKYBLUE);
+    let profile = &game.profiles.list[game.profiles.current()];
+    text(&trf("档案：{}  [P]", &[&profile.name]), 16.0, 30.0, 20.0, LIGHTGRAY);
+    if let Some(warn) = &game.save_warning {
+        draw_text_center(font, w, &format!("⚠ {}", tr(warn)), 62.0, 22.0 * ts, ORANGE);
+    }
+    if let Some(status) = game.cloud.as_ref().and_then(|c| c.status.as_ref()) {
+        let size = measure_text(status, Some(font), (18.0 * ts) as u16, 1.0);
+        text(status, w - size.width - 16.0, 30.0, 18.0, GRAY);
+    }
+    draw_text_center(font, w, tr("左右移动躲避方块，[SHIFT] 冲刺，收集道具增强能力"), 156.0, 22.0 * ts, LIGHTGRAY);
+    if !game.tutorial_done {
+        draw_text_center(font, w, tr("第一次玩？按 [H] 进入新手教程"), 186.0, 20.0 * ts, LIME);
+    }
+
+    // 左边一列是菜单，右边一列是各种状态
+    if game.play_menu {
+        text(tr("选择玩法   [ESC] 返回"), MENU_X - 110.0, 214.0, 18.0, GRAY);
+        game.play_list.draw(font, &play_items(), &play_rects(), 18.0 * ts);
+    } else {
+        game.main_menu.draw(font, &main_items(), &main_rects(), 22.0 * ts);
+    }
+    let x = MENU_X + 140.0;
+    let best = game.records.score(&records::key("standard", game.difficulty, ""));
+    text(&trf("难度：< {} >   最高：{}", &[&game.difficulty.label(), &best]), x, 250.0, 24.0, GOLD);
+    let (daily, day) = (Challenge::Daily, challenge::today());
+    let daily_txt = match challenge::result_for(&game.daily_history, day) {
+        Some(r) => trf("[D] {} {}：已完成，得分 {}", &[&daily.label(), &daily.period_label(day), &r.score]),
+        None => trf("[D] {} {}：{}", &[&daily.label(), &daily.period_label(day), &daily.mutators(day).labels()]),
+    };
+    text(&daily_txt, x, 286.0, 18.0, SKYBLUE);
+    let (weekly, week) = (Challenge::Weekly, challenge::this_week());
+    let week_best = game.weekly_bests.get(&week).copied().unwrap_or(0);
+    let weekly_txt = trf("[W] {}（{}）：{}   本周最佳 {}", &[&weekly.label(), &weekly.period_label(week), &weekly.mutators(week).labels(), &week_best]);
+    text(&weekly_txt, x, 312.0, 18.0, SKYBLUE);
+    let adaptive_txt = if game.director.enabled { tr("[G] 自适应难度：开（成绩不计入最高分）") } else { tr("[G] 自适应难度：关") };
+    text(adaptive_txt, x, 348.0, 18.0, if game.director.enabled { ORANGE } else { GRAY });
+    let ch = game.character();
+    text(&trf("[C] 角色：{}（{}）", &[&tr(ch.name), &tr(ch.blurb)]), x, 384.0, 20.0, ch.body_color());
+    text(&trf("Lv {}   金币：{}", &[&game.level(), &game.wallet.coins]), x, 416.0, 20.0, GOLD);
+    if game.suspended { text(tr("[R] 继续上次中断的一局"), x, 456.0, 22.0, LIME); }
+    draw_text_center(font, w, tr("[A] 无障碍  [F] 手感  [S] 统计  [L] 历史  [O] 排行榜  [N] 联机对战  [V] 观战  [B] 商店  [K] 换装  [M] 任务  [E] 成就"), game.field.y - 36.0, 18.0 * ts, GRAY);
+    if cfg!(debug_assertions) {
+        draw_text_center(font, w, tr("[F9] 手感沙盒"), game.field.y - 12.0, 16.0 * ts, DARKGRAY);
+    }
+}
+
+fn draw_feel_panel(font: &Font, game: &Game) {
+    let ts = game.settings.text_scale();
+    draw_text_center(font, game.field.x, tr("手感设置"), 100.0, 40.0 * ts, SKYBLUE);
+    let presets = FEEL_PRESETS.iter().enumerate()
+        .map(|(i, p)| format!("[{}] {}{}", i + 1, p.label(), if game.feel == p.feel() { "✓" } else { "" }))
+        .collect::<Vec<_>>().join("   ");
+    draw_text_center(font, game.field.x, &presets, 150.0, 22.0 * ts, LIGHTGRAY);
+    for (i, item) in FEEL_ITEMS.iter().enumerate() {
+        let sel = i == game.feel_cursor;
+        let txt = trf("{}{}：< {} >", &[&if sel { "▶ " } else { "" }, &tr(item.name), &(item.show)(&game.feel)]);
+        draw_text_center(font, game.field.x, &txt, 200.0 + i as f32 * 34.0 * ts, 24.0 * ts, if sel { YELLOW } else { WHITE });
+    }
+    let y = 200.0 + FEEL_ITEMS.len() as f32 * 34.0 * ts + 20.0;
+    if motion::calm(game) {
+        draw_text_center(font, game.field.x, tr("已开启“减少动态效果”：震屏、顿帧、慢动作与镜头倾斜不会生效，粒子减半"), y, 18.0 * ts, ORANGE);
+    }
+    draw_text_center(font, game.field.x, tr("[↑/↓] 选择   [←/→] 调整   [ENTER/ESC] 保存返回"), y + 34.0, 20.0 * ts, GRAY);
+}
+
+fn draw_prerun(font: &Font, game: &Game) {
+    let ts = game.settings.text_scale();
+    draw_text_center(font, game.field.x, tr("开局设置：变异规则"), 100.0, 40.0 * ts, SKYBLUE);
+    let level = game.level();
+    for (i, m) in MUTATORS.iter().enumerate() {
+        let on = (m.get)(&game.mutators);
+        let y = 160.0 + i as f32 * 34.0 * ts;
+        if !xp::unlocked(Reward::Mutator(m.key), level) {
+            let lv = xp::required_level(Reward::Mutator(m.key)).unwrap_or(0);
+            draw_text_center(font, game.field.x, &trf("[{}] {}  （Lv {} 解锁）", &[&(i + 1), &tr(m.label), &lv]), y, 24.0 * ts, DARKGRAY);
+            continue;
+        }
+        let txt = format!("[{}] {}  {}", i + 1, tr(m.label), tr(if on { "开" } else { "关" }));
+        draw_text_center(font, game.field.x, &txt, y, 24.0 * ts, if on { ORANGE } else { LIGHTGRAY });
+    }
+    let y = 160.0 + MUTATORS.len() as f32 * 34.0 * ts + 20.0;
+    let lanes_on = game.mover.scheme == Scheme::Lanes;
+    let txt = trf("[L] {}（{} 条车道）：{}", &[&Scheme::Lanes.label(), &movement::LANES, &tr(if lanes_on { "开" } else { "关" })]);
+    draw_text_center(font, game.field.x, &txt, y - 10.0, 22.0 * ts, if lanes_on { ORANGE } else { LIGHTGRAY });
+    let y = y + 26.0 * ts;
+    let wrap_txt = trf("[X] 穿墙（从一侧出去、另一侧进来）：{}", &[&tr(if game.mover.wrap { "开" } else { "关" })]);
+    draw_text_center(font, game.field.x, &wrap_txt, y, 22.0 * ts, if game.mover.wrap { ORANGE } else { LIGHTGRAY });
+    let y = y + 26.0 * ts;
+    let shoot_txt = trf("[S] 射击（[SPACE] 开火，拾取弹药补充）：{}", &[&tr(if game.shooting { "开" } else { "关" })]);
+    draw_text_center(font, game.field.x, &shoot_txt, y, 22.0 * ts, if game.shooting { ORANGE } else { LIGHTGRAY });
+    let y = y + 26.0 * ts;
+    let lives_txt = trf("[-/=] 开局命数：{}", &[&game.start_lives]);
+    draw_text_center(font, game.field.x, &lives_txt, y, 22.0 * ts, if game.start_lives > 1 { ORANGE } else { LIGHTGRAY });
+    let y = y + 26.0 * ts;
+    let (w_mul, speed_mul) = loadout_muls(game.loadout);
+    let slider: String = (-LOADOUT_STEPS..=LOADOUT_STEPS).map(|i| if i == game.loadout { '■' } else { '□' }).collect();
+    let loadout_txt = trf("[ [/] ] 体型：窄 {} 宽   {}（宽度 x{:.2}，速度 x{:.2}）", &[&slider, &loadout_label(game.loadout), &w_mul, &speed_mul]);
+    draw_text_center(font, game.field.x, &loadout_txt, y, 22.0 * ts, if game.loadout != 0 { ORANGE } else { LIGHTGRAY });
+    let y = y + 26.0 * ts;
+    let ghost_txt = trf("[G] 最佳纪录幽灵（和自己的最好一局赛跑）：{}", &[&tr(if game.settings.ghost { "开" } else { "关" })]);
+    draw_text_center(font, game.field.x, &ghost_txt, y, 22.0 * ts, if game.settings.ghost { ORANGE } else { LIGHTGRAY });
+    let y = y + 26.0 * ts;
+    let cast_txt = trf("[B] 局域网直播（别人可从菜单 [V] 观战）：{}", &[&tr(if game.settings.broadcast { "开" } else { "关" })]);
+    draw_text_center(font, game.field.x, &cast_txt, y, 22.0 * ts, if game.settings.broadcast { ORANGE } else { LIGHTGRAY });
+    let y = y + 26.0 * ts;
+    match &game.seed_entry {
+        Some(field) => field.draw(font, game.field.x * 0.5, y, 22.0 * ts, tr("[E] 种子（留空随机）："), YELLOW),
+        None => {
+            let seed = game.forced_seed.map_or_else(|| tr("随机").to_string(), |s| s.to_string());
+            draw_text_center(font, game.field.x, &trf("[E] 种子：{}", &[&seed]), y, 22.0 * ts, if game.forced_seed.is_some() { ORANGE } else { LIGHTGRAY });
+        }
+    }
+    let y = y + 30.0 * ts;
+    let note = if game.modified() { tr("已开启变异：成绩按规则组合单独记录") } else { tr("标准规则") };
+    draw_text_center(font, game.field.x, note, y, 20.0 * ts, GRAY);
+    draw_text_center(font, game.field.x, &trf("当前规则最高：{}", &[&game.best()]), y + 30.0, 22.0 * ts, GOLD);
+    draw_text_center(font, game.field.x, tr("[SPACE] 开始   [BACKSPACE] 全部关闭   [ESC] 返回"), y + 70.0, 22.0 * ts, WHITE);
+}
+
+// 结算界面：逐项列出本局统计，刷新历史最佳的项金色高亮
+fn draw_summary(font: &Font, game: &Game) {
+    let ts = game.settings.text_scale();
+    let (st, nb) = (&game.stats, &game.new_bests);
+    draw_text_center(font, game.field.x, tr("本局总结"), 90.0, 40.0 * ts, SKYBLUE);
+    if let Some(rank) = game.top_rank {
+        draw_text_center(font, game.field.x, &trf("★ 排行榜第 {} 名 ★", &[&(rank + 1)]), 126.0, 22.0 * ts, GOLD);
+    } else if nb.any() {
+        draw_text_center(font, game.field.x, tr("★ 刷新了个人纪录 ★"), 126.0, 22.0 * ts, GOLD);
+    }
+    let rows = [
+        (trf("得分：{}", &[&game.score]), nb.score),
+        (trf("存活时间：{}", &[&run_clock(st.elapsed)]), nb.elapsed),
+        (trf("躲过障碍：{}", &[&st.dodged]), nb.dodged),
+        (trf("擦边次数：{}", &[&st.near_misses]), nb.near_misses),
+        (trf("最高连击：x{}", &[&st.max_combo.max(1)]), nb.max_combo),
+        (trf("消耗护盾：{}", &[&st.shields_used]), false),
+        (trf("拾取道具：{}", &[&st.total_pickups()]), false),
+        (trf("金币：+{}（共 {}）", &[&game.coins.run, &game.wallet.coins]), false),
+    ];
+    for (i, (txt, best)) in rows.iter().enumerate() {
+        let txt = if *best { trf("{}   新纪录！", &[&txt]) } else { txt.clone() };
+        draw_text_center(font, game.field.x, &txt, 170.0 + i as f32 * 32.0 * ts, 24.0 * ts, if *best { GOLD } else { WHITE });
+    }
+    // 各种道具的拾取次数，只列出拿到过的
+    let by_kind: Vec<String> = ALL_POWERUPS.iter().filter(|k| st.pickups[**k as usize] > 0).map(|k| format!("{}×{}", tr(k.label()), st.pickups[*k as usize])).collect();
+    let y = 170.0 + rows.len() as f32 * 32.0 * ts;
+    if !by_kind.is_empty() {
+        draw_text_center(font, game.field.x, &by_kind.join("  "), y, 18.0 * ts, LIGHTGRAY);
+    }
+    let y = y + 30.0 * ts;
+    if game.xp_gain > 0 { draw_xp_bar(font, game, y); }
+    let again = if game.can_retry() { trf("[{}] 再来一局   [ENTER] 返回菜单", &[&game.settings.keys.restart.label()]) } else { tr("[ENTER] 返回菜单").to_string() };
+    draw_text_center(font, game.field.x, &again, y + 100.0 * ts, 24.0 * ts, ORANGE);
+}
+
+// 经验条：从本局之前的经验涨到现在，跨过等级时弹出“升级”并列出新解锁的内容
+fn draw_xp_bar(font: &Font, game: &Game, y: f32) {
+    let ts = game.settings.text_scale();
+    let before = game.xp - game.xp_gain;
+    let t = (game.xp_anim / XP_FILL_TIME).min(1.0);
+    let shown = before + (game.xp_gain as f32 * (1.0 - (1.0 - t).powi(2))) as u32;
+    let (level, into, need) = xp::level_of(shown);
+    let (bw, bh) = (game.field.x * 0.5, 12.0);
+    let bx = (game.field.x - bw) * 0.5;
+    draw_text_center(font, game.field.x, &trf("Lv {}   经验 +{}   {}/{}", &[&level, &game.xp_gain, &into, &need]), y, 20.0 * ts, SKYBLUE);
+    draw_rectangle(bx, y + 8.0, bw, bh, Color::new(1.0, 1.0, 1.0, 0.15));
+    draw_rectangle(bx, y + 8.0, bw * into as f32 / need as f32, bh, SKYBLUE);
+    let old = xp::level_of(before).0;
+    if level <= old { return; }
+    // 升级字样随经验条跨级时放大再回落
+    let pulse = if motion::calm(game) { 0.0 } else { (game.xp_anim * 6.0).sin().abs() * 0.15 };
+    let mut gained: Vec<String> = xp::UNLOCKS.iter().filter(|(lv, _)| *lv > old && *lv <= level).map(|(_, r)| match *r {
+        Reward::Character(key) => trf("角色「{}」", &[&CHARACTERS.iter().find(|c| c.key == key).map_or(key, |c| tr(c.name))]),
+        Reward::Mutator(key) => trf("变异「{}」", &[&MUTATORS.iter().find(|m| m.key == key).map_or(key, |m| tr(m.label))]),
+    }).collect();
+    let cos = &game.cosmetics;
+    let names = cos.skins.iter().map(|s| (&s.name, s.unlock)).chain(cos.themes.iter().map(|t| (&t.name, t.unlock)));
+    gained.extend(names.filter(|(_, u)| matches!(u, cosmetics::Requirement::Level { level: l } if *l > old && *l <= level)).map(|(n, _)| trf("外观「{}」", &[&n])));
+    draw_text_center(font, game.field.x, &trf("升级！Lv {}", &[&level]), y + 50.0 * ts, 30.0 * ts * (1.0 + pulse), GOLD);
+    if !gained.is_empty() {
+        draw_text_center(font, game.field.x, &trf("解锁：{}", &[&gained.join(tr("、"))]), y + 76.0 * ts, 18.0 * ts, LIME);
+    }
+}
+
+// 生涯统计（统计界面第一页）
+fn draw_lifetime(font: &Font, game: &Game) {
+    let ts = game.settings.text_scale();
+    let lt = &game.lifetime;
+    draw_text_center(font, game.field.x, tr("生涯统计"), 90.0, 40.0 * ts, SKYBLUE);
+    let t = lt.playtime as u64;
+    let longest = lt.longest_run as u32;
+    let avg = if lt.runs > 0 { lt.total_score / lt.runs as i64 } else { 0 };
+    let rows = [
+        trf("总游戏时间：{}:{:02}:{:02}", &[&(t / 3600), &(t / 60 % 60), &(t % 60)]),
+        trf("总局数：{}", &[&lt.runs]),
+        trf("总得分：{}（平均每局 {}）", &[&lt.total_score, &avg]),
+        trf("掉命次数：{}", &[&lt.deaths]),
+        trf("最长一局：{:02}:{:02}", &[&(longest / 60), &(longest % 60)]),
+        trf("拾取道具：{}", &[&lt.pickups.iter().sum::<u32>()]),
+    ];
+    for (i, txt) in rows.iter().enumerate() {
+        draw_text_center(font, game.field.x, txt, 150.0 + i as f32 * 32.0 * ts, 24.0 * ts, WHITE);
+    }
+    // 按种类列出，每行 4 种
+    let y = 150.0 + rows.len() as f32 * 32.0 * ts + 10.0;
+    for (i, chunk) in ALL_POWERUPS.chunks(4).enumerate() {
+        let line: Vec<String> = chunk.iter().map(|k| format!("{}×{}", tr(k.label()), lt.pickups[*k as usize])).collect();
+        draw_text_center(font, game.field.x, &line.join("   "), y + i as f32 * 24.0 * ts, 18.0 * ts, LIGHTGRAY);
+    }
+    draw_text_center(font, game.field.x, tr("[←/→] 翻页   [X] 导出 CSV/JSON   [ESC] 返回"), y + 3.0 * 24.0 * ts + 40.0, 22.0 * ts, ORANGE);
+    if let Some(msg) = &game.export_msg {
+        draw_text_center(font, game.field.x, msg, y + 3.0 * 24.0 * ts + 70.0, 18.0 * ts, LIGHTGRAY);
+    }
+}
+
+// 死亡统计：横向热力条（越红死得越多）+ 左中右三段的平均存活时间
+fn draw_death_stats(font: &Font, game: &Game) {
+    let ts = game.settings.text_scale();
+    let log = &game.deaths;
+    draw_text_center(font, game.field.x, tr("死亡统计"), 90.0, 40.0 * ts, SKYBLUE);
+    draw_text_center(font, game.field.x, &trf("最近 {} 次死亡的位置", &[&log.deaths.len()]), 130.0, 20.0 * ts, LIGHTGRAY);
+    let heat = log.heat();
+    let peak = heat.iter().copied().max().unwrap_or(0).max(1) as f32;
+    let (x0, y0, h) = (40.0, 170.0, 60.0);
+    let bw = (game.field.x - 2.0 * x0) / HEAT_BINS as f32;
+    for (i, n) in heat.iter().enumerate() {
+        let t = *n as f32 / peak;
+        let c = if *n == 0 { Color::new(0.12, 0.12, 0.18, 1.0) } else { Color::new(0.2 + 0.8 * t, 0.5 * (1.0 - t), 0.6 * (1.0 - t), 1.0) };
+        draw_rectangle(x0 + i as f32 * bw, y0, bw - 1.0, h, c);
+    }
+    draw_rectangle_lines(x0, y0, game.field.x - 2.0 * x0, h, 2.0, GRAY);
+    // 三等分的分隔线与各段平均存活
+    let third = (game.field.x - 2.0 * x0) / 3.0;
+    for k in 1..3 {
+        draw_line(x0 + k as f32 * third, y0 - 6.0, x0 + k as f32 * third, y0 + h + 6.0, 2.0, WHITE);
+    }
+    let names = [tr("左"), tr("中"), tr("右")];
+    for (k, avg) in log.avg_by_third().iter().enumerate() {
+        let txt = match avg {
+            Some(a) => trf("{}：平均 {:.1} 秒", &[&names[k], &a]),
+            None => trf("{}：暂无", &[&names[k]]),
+        };
+        let w = measure_text(&txt, Some(font), (20.0 * ts) as u16, 1.0).width;
+        draw_text_ex(&txt, x0 + (k as f32 + 0.5) * third - w * 0.5, y0 + h + 32.0, TextParams { font: Some(font), font_size: (20.0 * ts) as u16, color: WHITE, ..Default::default() });
+    }
+    if !log.deaths.is_empty() {
+        draw_text_center(font, game.field.x, &trf("死亡时场上平均 {:.1} 个障碍", &[&log.avg_on_screen()]), y0 + h + 80.0, 20.0 * ts, LIGHTGRAY);
+    }
+    draw_text_center(font, game.field.x, tr("[←/→] 翻页   [X] 导出 CSV/JSON   [ESC] 返回"), y0 + h + 140.0, 22.0 * ts, ORANGE);
+    if let Some(msg) = &game.export_msg {
+        draw_text_center(font, game.field.x, msg, y0 + h + 170.0, 18.0 * ts, LIGHTGRAY);
+    }
+}
+
+// 商店：上下选择，[ENTER] 购买；买下的外观直接换上
+fn draw_shop(font: &Font, game: &Game) {
+    let ts = game.settings.text_scale();
+    draw_text_center(font, game.field.x, tr("商店"), 90.0, 40.0 * ts, SKYBLUE);
+    draw_text_center(font, game.field.x, &trf("金币：{}", &[&game.wallet.coins]), 130.0, 24.0 * ts, GOLD);
+    for (i, item) in game.shop.items.iter().enumerate() {
+        let owned = game.wallet.owns(&item.key);
+        let state = if !owned {
+            trf("{} 金币", &[&item.price])
+        } else if game.look.skin == item.key || game.look.theme == item.key {
+            tr("使用中").to_string()
+        } else {
+            tr("已拥有").to_string()
+        };
+        let mark = if i == game.shop_cursor { "▶ " } else { "  " };
+        let color = if owned { LIME } else if game.wallet.coins >= item.price { WHITE } else { GRAY };
+        let y = 180.0 + i as f32 * 30.0 * ts;
+        draw_text_center(font, game.field.x, &trf("{}{}：{}   [{}]", &[&mark, &tr(&item.name), &tr(&item.desc), &state]), y, 22.0 * ts, color);
+    }
+    let y = 180.0 + game.shop.items.len() as f32 * 30.0 * ts + 30.0;
+    draw_text_center(font, game.field.x, tr("天赋和命数上限在挑战与教程中不生效"), y, 18.0 * ts, LIGHTGRAY);
+    draw_text_center(font, game.field.x, tr("[↑/↓] 选择   [ENTER] 购买   [ESC] 返回"), y + 36.0 * ts, 22.0 * ts, ORANGE);
+}
+
+// 换装：皮肤和主题各一组，未解锁的显示解锁条件；右侧画当前皮肤的小样
+fn draw_customize(font: &Font, game: &Game) {
+    let ts = game.settings.text_scale();
+    draw_text_center(font, game.field.x, tr("换装"), 80.0, 40.0 * ts, SKYBLUE);
+    let progress = game.progress();
+    let (skin, theme) = (game.skin(), game.theme());
+    let mut y = 125.0;
+    let mut row = 0;
+    let mut line = |name: &str, key: &str, unlock: &cosmetics::Requirement, in_use: bool, y: f32| {
+        let open = unlock.met(key, &progress);
+        let state = if in_use { tr("使用中").to_string() } else if open { tr("已解锁").to_string() } else { unlock.label() };
+        let mark = if row == game.look_cursor { "▶ " } else { "  " };
+        let color = if in_use { LIME } else if open { WHITE } else { GRAY };
+        draw_text_center(font, game.field.x, &format!("{}{}   [{}]", mark, tr(name), state), y, 20.0 * ts, color);
+        row += 1;
+    };
+    draw_text_center(font, game.field.x, tr("—— 皮肤 ——"), y, 20.0 * ts, GOLD);
+    for s in &game.cosmetics.skins {
+        y += 26.0 * ts;
+        line(&s.name, &s.key, &s.unlock, s.key == skin.key, y);
+    }
+    y += 36.0 * ts;
+    draw_text_center(font, game.field.x, tr("—— 配色主题 ——"), y, 20.0 * ts, GOLD);
+    for t in &game.cosmetics.themes {
+        y += 26.0 * ts;
+        line(&t.name, &t.key, &t.unlock, t.key == theme.key, y);
+    }
+    // 小样：当前皮肤 + 主题背景上的一个障碍和一个护盾道具
+    let (px, py) = (game.field.x - 150.0, 150.0);
+    draw_rectangle(px - 20.0, py - 20.0, 140.0, 110.0, theme.background.map_or(Color::from_rgba(14, 17, 22, 255), cosmetics::rgb));
+    let ob = theme.obstacle.map_or(Color::from_rgba(255, 100, 100, 255), cosmetics::rgb);
+    draw_rectangle(px + 60.0, py - 6.0, 30.0, 30.0, ob);
+    draw_circle(px + 15.0, py + 9.0, 11.0, theme.palette.powerups.shield.color());
+    let body = skin.body.map(cosmetics::rgb).or(theme.palette.player.map(Paint::color)).unwrap_or(game.character().body_color());
+    draw_rectangle(px, py + 50.0, 70.0, 18.0, body);
+    draw_rectangle(px + 10.0, py + 54.0, 50.0, 3.0, cosmetics::rgb(skin.stripe));
+    draw_text_center(font, game.field.x, tr("[↑/↓] 选择   [ENTER] 使用   [ESC] 返回"), y + 46.0 * ts, 22.0 * ts, ORANGE);
+}
+
+// 今日任务：进度条 + 奖励，完成的打勾
+fn draw_missions(font: &Font, game: &Game) {
+    let ts = game.settings.text_scale();
+    draw_text_center(font, game.field.x, tr("今日任务"), 90.0, 40.0 * ts, SKYBLUE);
+    draw_text_center(font, game.field.x, tr("每天换一批，只有普通模式的局计入进度"), 130.0, 20.0 * ts, LIGHTGRAY);
+    let (bw, bx) = (game.field.x * 0.6, game.field.x * 0.2);
+    for (i, m) in game.missions.active.iter().enumerate() {
+        let Some(d) = missions::def(&m.key) else { continue };
+        let y = 190.0 + i as f32 * 80.0 * ts;
+        let head = if m.done { format!("✔ {}", tr(d.label)) } else { tr(d.label).to_string() };
+        draw_text_center(font, game.field.x, &head, y, 24.0 * ts, if m.done { LIME } else { WHITE });
+        draw_rectangle(bx, y + 12.0, bw, 8.0, Color::new(1.0, 1.0, 1.0, 0.15));
+        draw_rectangle(bx, y + 12.0, bw * m.progress as f32 / d.goal as f32, 8.0, if m.done { LIME } else { SKYBLUE });
+        let sub = trf("{}/{}{}   奖励：{} 金币  {} 经验", &[&m.progress, &d.goal, &tr(if d.per_run { "（单局）" } else { "" }), &d.coins, &d.xp]);
+        draw_text_center(font, game.field.x, &sub, y + 42.0 * ts, 18.0 * ts, GRAY);
+    }
+    draw_text_center(font, game.field.x, tr("[ESC] 返回"), 190.0 + 3.0 * 80.0 * ts + 20.0, 22.0 * ts, ORANGE);
+}
+
+// 成就：已解锁的显示日期，未解锁的变灰；[↑/↓] 滚动
+fn draw_achievements(font: &Font, game: &Game) {
+    let ts = game.settings.text_scale();
+    let list = &game.achievement_defs.list;
+    let got = list.iter().filter(|a| game.achievements.contains_key(&a.key)).count();
+    draw_text_center(font, game.field.x, tr("成就"), 80.0, 40.0 * ts, SKYBLUE);
+    draw_text_center(font, game.field.x, &trf("已解锁 {}/{}", &[&got, &list.len()]), 116.0, 20.0 * ts, GOLD);
+    let row_h = 44.0 * ts;
+    let rows = ((game.field.y - 220.0) / row_h).max(1.0) as usize;
+    for (i, a) in list.iter().skip(game.ach_scroll).take(rows).enumerate() {
+        let y = 160.0 + i as f32 * row_h;
+        let (head, color) = match game.achievements.get(&a.key) {
+            Some(t) => (format!("★ {}   {}", tr(&a.name), challenge::date_string((*t / 86_400) as u32)), GOLD),
+            None => (format!("☆ {}", tr(&a.name)), GRAY),
+        };
+        draw_text_center(font, game.field.x, &head, y, 22.0 * ts, color);
+        draw_text_center(font, game.field.x, tr(&a.desc), y + 20.0 * ts, 16.0 * ts, if game.achievements.contains_key(&a.key) { LIGHTGRAY } else { DARKGRAY });
+    }
+    draw_text_center(font, game.field.x, tr("[↑/↓] 滚动   [ESC] 返回"), game.field.y - 30.0, 22.0 * ts, ORANGE);
+}
+
+// 对局历史：最新的在上；[ENTER] 用同一种子再来一局，[V] 看回放
+fn draw_history(font: &Font, game: &Game) {
+    let ts = game.settings.text_scale();
+    draw_text_center(font, game.field.x, tr("对局历史"), 70.0, 40.0 * ts, SKYBLUE);
+    if game.history.is_empty() {
+        draw_text_center(font, game.field.x, tr("还没有记录"), 200.0, 24.0 * ts, GRAY);
+    }
+    let row_h = 26.0 * ts;
+    let rows = ((game.field.y - 190.0) / row_h).max(1.0) as usize;
+    let first = game.history_cursor.saturating_sub(rows - 1);
+    for (i, e) in game.history.iter().rev().enumerate().skip(first).take(rows) {
+        let y = 120.0 + (i - first) as f32 * row_h;
+        let t = e.duration as u32;
+        let txt = trf("{}{}  {}  {:>5} 分  {:02}:{:02}  {}  #{}{}", &[
+            &if i == game.history_cursor { "▶ " } else { "  " }, &challenge::date_string(e.day), &tr(&e.mode), &e.score, &(t / 60), &(t % 60), &tr(&e.cause), &(e.seed % 100_000),
+            &if e.replay { tr("  [回放]") } else { "" }]);
+        draw_text_center(font, game.field.x, &txt, y, 18.0 * ts, if i == game.history_cursor { WHITE } else { LIGHTGRAY });
+    }
+    draw_text_center(font, game.field.x, tr("[↑/↓] 选择   [ENTER] 同种子再来一局   [V] 看回放   [ESC] 返回"), game.field.y - 30.0, 20.0 * ts, ORANGE);
+}
+
+// 把导入的档案并进第 i 个档案（正在用的先存盘，并完重新读档）
+fn import_profile(game: &mut Game, i: usize, bytes: &[u8]) -> String {
+    let (name, incoming) = match transfer::unpack(bytes) {
+        Ok(x) => x,
+        Err(e) => return trf("导入失败：{}", &[&e]),
+    };
+    let current = i == game.profiles.current();
+    if current { save_game(game); }
+    let path = game.profiles.save_path(i);
+    let mut save = storage::read_to_string(&path).ok().and_then(|s| parse_save(&s)).unwrap_or_default();
+    transfer::merge(&mut save, incoming);
+    save.version = save.version.max(migrate::SAVE_VERSION);
+    save.saved_at = macroquad::miniquad::date::now() as u64;
+    if let Err(e) = write_save(&path, &save) { return trf("导入失败：{}", &[&e]); }
+    if current {
+        game.reload_profile();
+        game.profile_cursor = i;
+        game.mode = GameMode::Profiles;
+    }
+    trf("已把「{}」并入「{}」", &[&name, &game.profiles.list[i].name])
+}
+
+// 连上当前档案的云端并对账：云端较新就重新读档，两边都改过就让玩家选
+fn sync_profile(game: &mut Game) {
+    game.cloud = sync::connect(&game.profiles.list[game.profiles.current()].id);
+    let Some(cloud) = game.cloud.as_mut() else { return };
+    match cloud.reconcile() {
+        sync::Outcome::Pulled => game.reload_profile(),
+        sync::Outcome::Conflict => game.mode = GameMode::SyncConflict,
+        sync::Outcome::Unchanged | sync::Outcome::Pushed => {}
+    }
+}
+
+fn update_sync_conflict(game: &mut Game) {
+    let Some(cloud) = game.cloud.as_mut() else { game.mode = GameMode::Menu; return };
+    // 先不管：这次只用本地，冲突留到下次启动
+    if is_key_pressed(KeyCode::Escape) {
+        cloud.conflict = None;
+        cloud.status = Some(tr("云端存档有冲突，本次未同步").to_string());
+        game.mode = GameMode::Menu;
+        return;
+    }
+    let use_remote = if is_key_pressed(KeyCode::Key1) { false } else if is_key_pressed(KeyCode::Key2) { true } else { return };
+    if let Err(e) = cloud.resolve(use_remote) { cloud.status = Some(trf("同步失败：{}", &[&e])); }
+    if use_remote { game.reload_profile(); }
+    game.mode = GameMode::Menu;
+}
+
+fn sync_time(t: u64) -> String {
+    if t == 0 { return tr("未知").to_string(); }
+    format!("{} {:02}:{:02} (UTC)", challenge::date_string((t / 86_400) as u32), t % 86_400 / 3600, t % 3600 / 60)
+}
+
+fn draw_sync_conflict(font: &Font, game: &Game) {
+    let ts = game.settings.text_scale();
+    let Some(c) = game.cloud.as_ref().and_then(|c| c.conflict.as_ref()) else { return };
+    draw_text_center(font, game.field.x, tr("存档冲突"), 120.0, 40.0 * ts, ORANGE);
+    draw_text_center(font, game.field.x, tr("上次同步之后，本机和云端的存档都改动过"), 165.0, 22.0 * ts, LIGHTGRAY);
+    let newer = |a: u64, b: u64| if a > b { tr("（较新）") } else { "" };
+    draw_text_center(font, game.field.x, &trf("[1] 保留本机：{}{}", &[&sync_time(c.local), &newer(c.local, c.remote)]), 240.0, 26.0 * ts, WHITE);
+    draw_text_center(font, game.field.x, &trf("[2] 使用云端：{}{}", &[&sync_time(c.remote), &newer(c.remote, c.local)]), 285.0, 26.0 * ts, WHITE);
+    draw_text_center(font, game.field.x, tr("没选中的一方会被覆盖   [ESC] 暂不同步"), game.field.y - 40.0, 22.0 * ts, ORANGE);
+}
+
+fn draw_profiles(font: &Font, game: &Game) {
+    let ts = game.settings.text_scale();
+    draw_text_center(font, game.field.x, tr("玩家档案"), 80.0, 40.0 * ts, SKYBLUE);
+    draw_text_center(font, game.field.x, tr("每个档案有自己的分数、解锁、设置和统计"), 115.0, 20.0 * ts, LIGHTGRAY);
+    let cur = game.profiles.current();
+    for (i, p) in game.profiles.list.iter().enumerate() {
+        let mark = if i == cur { tr("（使用中）") } else { "" };
+        let arrow = if i == game.profile_cursor { "▶ " } else { "  " };
+        let y = 170.0 + i as f32 * 34.0 * ts;
+        if let Some(e) = game.profile_edit.as_ref().filter(|e| e.index == Some(i)) {
+            e.field.draw(font, game.field.x * 0.5, y, 24.0 * ts, arrow, YELLOW);
+            continue;
+        }
+        let color = if i == game.profile_cursor { WHITE } else { LIGHTGRAY };
+        draw_text_center(font, game.field.x, &format!("{}{}{}", arrow, p.name, mark), y, 24.0 * ts, color);
+    }
+    let y = 170.0 + game.profiles.list.len() as f32 * 34.0 * ts + 20.0;
+    if let Some(e) = game.profile_edit.as_ref().filter(|e| e.index.is_none()) {
+        e.field.draw(font, game.field.x * 0.5, y, 24.0 * ts, tr("新档案："), YELLOW);
+    }
+    if let Some(e) = &game.profile_edit {
+        e.field.draw_picker(font, game.field.x * 0.5, game.field.y - 130.0, 22.0 * ts);
+    }
+    let hint = if game.profile_edit.is_some() {
+        tr("输入名字   [TAB] 拼音选字   [ENTER] 确定   [ESC] 取消").to_string()
+    } else if let Some(i) = game.profile_delete {
+        trf("再按一次 [DEL] 删除「{}」及其全部进度", &[&game.profiles.list[i].name])
+    } else {
+        let new_txt = if game.profiles.full() { "" } else { tr("[N] 新建   ") };
+        trf("[ENTER] 切换   {}[R] 改名   [DEL] 删除   [X] 导出   [V] 导入   [ESC] 返回", &[&new_txt])
+    };
+    if let Some(msg) = &game.profile_msg {
+        draw_text_center(font, game.field.x, msg, game.field.y - 75.0, 18.0 * ts, LIGHTGRAY);
+    }
+    let color = if game.profile_delete.is_some() { RED } else { ORANGE };
+    draw_text_center(font, game.field.x, &hint, game.field.y - 40.0, 22.0 * ts, color);
+}
+
+// 挑战选择：本期规则 + 本地排行（自己的每局成绩和导入的朋友成绩）
+fn draw_challenges(font: &Font, game: &Game) {
+    let ts = game.settings.text_scale();
+    let kind = game.challenge_tab;
+    let period = kind.current();
+    let tab = |k: Challenge| if k == kind { trf("【{}】", &[&k.label()]) } else { format!(" {} ", k.label()) };
+    draw_text_center(font, game.field.x, &format!("{}   {}", tab(Challenge::Daily), tab(Challenge::Weekly)), 70.0, 32.0 * ts, SKYBLUE);
+    draw_text_center(font, game.field.x, &trf("{}：{}", &[&kind.period_label(period), &kind.mutators(period).labels()]), 110.0, 20.0 * ts, LIGHTGRAY);
+    let done = kind == Challenge::Daily && challenge::result_for(&game.daily_history, period).is_some();
+    let start_txt = if done { tr("今天已经挑战过了") } else { tr("[ENTER] 开始挑战") };
+    draw_text_center(font, game.field.x, start_txt, 142.0, 22.0 * ts, if done { GRAY } else { LIME });
+    let row_h = 26.0 * ts;
+    match game.rivals.board(kind, period).filter(|b| !b.entries.is_empty()) {
+        None => draw_text_center(font, game.field.x, tr("本期还没有成绩"), 200.0, 22.0 * ts, GRAY),
+        Some(b) => {
+            for (i, e) in b.entries.iter().take(12).enumerate() {
+                let name = if e.mine { trf("{}（你）", &[&e.name]) } else { e.name.clone() };
+                let txt = trf("{:>2}. {:<14} {:>6} 分  {}", &[&(i + 1), &name, &e.score, &run_clock(e.elapsed)]);
+                draw_text_center(font, game.field.x, &txt, 190.0 + i as f32 * row_h, 20.0 * ts, if e.mine { GOLD } else { WHITE });
+            }
+        }
+    }
+    if game.tampered {
+        draw_text_center(font, game.field.x, tr("存档被改动过：成绩不再计入挑战排行"), game.field.y - 128.0, 18.0 * ts, ORANGE);
+    }
+    match &game.naming {
+        Some(field) => {
+            field.draw(font, game.field.x * 0.5, game.field.y - 96.0, 20.0 * ts, tr("名字："), YELLOW);
+            draw_text_center(font, game.field.x, tr("[TAB] 拼音选字   [ENTER] 确定   [ESC] 取消"), game.field.y - 64.0, 18.0 * ts, LIGHTGRAY);
+            field.draw_picker(font, game.field.x * 0.5, game.field.y - 160.0, 20.0 * ts);
+        }
+        None => draw_text_center(font, game.field.x, &trf("名字：{}   [N] 修改", &[&game.player_name]), game.field.y - 96.0, 20.0 * ts, LIGHTGRAY),
+    }
+    if let Some(msg) = game.rival_msg.as_ref().filter(|_| game.naming.is_none()) {
+        draw_text_center(font, game.field.x, msg, game.field.y - 64.0, 20.0 * ts, YELLOW);
+    }
+    draw_text_center(font, game.field.x, tr("[←/→] 每日/每周   [C] 复制分享码   [V] 导入朋友的分享码   [ESC] 返回"), game.field.y - 30.0, 20.0 * ts, ORANGE);
+}
+
+fn draw_name_entry(font: &Font, game: &Game) {
+    let ts = game.settings.text_scale();
+    let Some(entry) = &game.name_entry else { return };
+    draw_text_center(font, game.field.x, tr("进入前十！"), 150.0, 44.0 * ts, GOLD);
+    draw_text_center(font, game.field.x, &trf("{}   {} 分", &[&game.mode_label(), &game.score]), 200.0, 24.0 * ts, WHITE);
+    entry.field.draw(font, game.field.x * 0.5, 270.0, 32.0 * ts, tr("名字："), YELLOW);
+    draw_text_center(font, game.field.x, tr("直接输入，或 [↑/↓] 换字符 [→] 加一位 [BACKSPACE] 删一位"), 330.0, 20.0 * ts, LIGHTGRAY);
+    draw_text_center(font, game.field.x, tr("[TAB] 拼音选字   [ENTER] 确定"), 370.0, 22.0 * ts, ORANGE);
+    entry.field.draw_picker(font, game.field.x * 0.5, 420.0, 22.0 * ts);
+}
+
+// 前十名；刚进榜的一行高亮。全球榜最多 100 名，只画光标附近的一屏
+fn draw_leaderboard(font: &Font, game: &Game) {
+    let ts = game.settings.text_scale();
+    let row = |i: usize, y: usize, txt: String, color: Color| {
+        let arrow = if i == game.top_cursor { "▶" } else { " " };
+        draw_text_center(font, game.field.x, &format!("{}{}", arrow, txt), 140.0 + y as f32 * 34.0 * ts, 22.0 * ts, color);
+    };
+    if let Some(b) = global_board(game) {
+        draw_text_center(font, game.field.x, &trf("全球排行榜 · {}", &[&game.base_mode_label()]), 80.0, 40.0 * ts, SKYBLUE);
+        if b.entries.is_empty() {
+            draw_text_center(font, game.field.x, tr("这个规则组合还没有人上榜"), 200.0, 24.0 * ts, GRAY);
+        }
+        const PAGE: usize = 12;
+        let first = game.top_cursor.saturating_sub(PAGE / 2).min(b.entries.len().saturating_sub(PAGE));
+        for (y, (i, e)) in b.entries.iter().enumerate().skip(first).take(PAGE).enumerate() {
+            let mark = if e.ghost.is_some() { tr("  [幽灵]") } else { "" };
+            let mine = b.me.is_some_and(|m| m.rank == i + 1);
+            let color = if mine { GOLD } else if i < 3 { WHITE } else { LIGHTGRAY };
+            let time = if e.elapsed > 0.0 { run_clock(e.elapsed) } else { "--:--.-".to_string() };
+            row(i, y, format!("{:>3}. {:<12} {:>6}  {}   {}{}", i + 1, e.name, e.score, time, challenge::date_string(e.day), mark), color);
+        }
+        let me = match b.me {
+            Some(m) => trf("我的名次：第 {} 名（{}）", &[&m.rank, &m.score]),
+            None => tr("我还没有上传过这个规则组合的成绩").to_string(),
+        };
+        draw_text_center(font, game.field.x, &me, game.field.y - 105.0, 20.0 * ts, GOLD);
+    } else {
+        draw_text_center(font, game.field.x, tr("排行榜"), 80.0, 40.0 * ts, SKYBLUE);
+        if game.top.entries().is_empty() {
+            draw_text_center(font, game.field.x, tr("还没有记录"), 200.0, 24.0 * ts, GRAY);
+        }
+        for (i, e) in game.top.entries().iter().enumerate() {
+            let mark = if e.ghost.is_some() { tr("  [幽灵]") } else { "" };
+            let color = if game.top_rank == Some(i) { GOLD } else if i < 3 { WHITE } else { LIGHTGRAY };
+            let time = if e.elapsed > 0.0 { run_clock(e.elapsed) } else { "--:--.-".to_string() };
+            row(i, i, format!("{:>2}. {:<12} {:>6}  {}   {}   {}{}", i + 1, e.name, e.score, time, challenge::date_string(e.day), e.mode, mark), color);
+        }
+        // 想看全球榜但拿不到：说明原因，先看本地的
+        if game.top_global {
+            let note = match &game.online.status {
+                _ if !game.settings.online => tr("全球排行榜未开启，按 [U] 参加（会上传名字、分数和回放）").to_string(),
+                online::Status::Offline(e) => trf("离线（{}），显示本地前十名", &[&e]),
+                _ => tr("正在连接全球排行榜…").to_string(),
+            };
+            draw_text_center(font, game.field.x, &note, game.field.y - 105.0, 20.0 * ts, GRAY);
+        }
+    }
+    let last = game.online.last_rank.map(|r| trf("上一局全球第 {} 名", &[&r]));
+    if let Some(msg) = game.top_msg.clone().or(last) {
+        draw_text_center(font, game.field.x, &msg, game.field.y - 75.0, 20.0 * ts, LIGHTGRAY);
+    }
+    let online = if game.settings.online { tr("[U] 退出全球榜") } else { tr("[U] 参加全球榜") };
+    let keys = if global_board(game).is_some() {
+        trf("[ENTER] 和幽灵赛跑   [R] 刷新   [TAB] 本地榜   {}   [ESC] 返回", &[&online])
+    } else {
+        trf("[ENTER] 赛跑   [C] 复制幽灵   [V] 导入幽灵（或拖入 .drg）   [TAB] 全球榜   {}   [ESC] 返回", &[&online])
+    };
+    draw_text_center(font, game.field.x, &keys, game.field.y - 40.0, 20.0 * ts, ORANGE);
+}
+
+fn draw_feedback(font: &Font, game: &Game) {
+    let ts = game.settings.text_scale();
+    let form = &game.feedback;
+    let on = |b: bool| if b { tr("是") } else { tr("否") };
+    draw_text_center(font, game.field.x, tr("反馈问题"), 90.0, 40.0 * ts, SKYBLUE);
+    draw_text_center(font, game.field.x, tr("请描述遇到的问题（直接输入）："), 140.0, 22.0 * ts, LIGHTGRAY);
+    draw_rectangle_lines(60.0, 160.0, game.field.x - 120.0, 120.0, 2.0, GRAY);
+    // 按宽度粗略折行
+    let per_line = ((game.field.x - 140.0) / (20.0 * ts)).max(10.0) as usize;
+    let chars: Vec<char> = form.text.chars().collect();
+    for (i, line) in chars.chunks(per_line).take(4).enumerate() {
+        let line: String = line.iter().collect();
+        draw_text_ex(&line, 70.0, 188.0 + i as f32 * 26.0, TextParams { font: Some(font), font_size: (20.0 * ts) as u16, color: WHITE, ..Default::default() });
+    }
+    draw_text_center(font, game.field.x, &trf("[F1] 附带截图：{}", &[&on(form.with_screenshot)]), 320.0, 22.0 * ts, LIGHTGRAY);
+    let upload_txt = match feedback::endpoint() {
+        Some(_) => trf("[F2] 发送到服务器：{}（否则保存到本地）", &[&on(form.upload)]),
+        None => trf("未设置 {}，报告将保存到本地", &[&feedback::ENDPOINT_ENV]),
+    };
+    draw_text_center(font, game.field.x, &upload_txt, 352.0, 20.0 * ts, LIGHTGRAY);
+    draw_text_center(font, game.field.x, tr("[ENTER] 提交   [ESC] 返回"), 410.0, 22.0 * ts, ORANGE);
+    if let Some(st) = &form.status {
+        draw_text_center(font, game.field.x, st, 460.0, 22.0 * ts, YELLOW);
+    }
+}
+
+// ===== 虚拟分辨率：逻辑场地等比缩放到窗口内（必要时留黑边）=====
+// 镜头要框住的范围：联机对战局内右边并排一块对手的场地
+fn view_size(game: &Game) -> Vec2 {
+    let versus = game.play == PlayMode::Versus && matches!(game.mode, GameMode::Playing | GameMode::GameOver);
+    if game.mode == GameMode::Duel { return vec2(versus::FIELD.0 * 2.0, versus::FIELD.1); }
+    if versus { vec2(game.field.x * 2.0, game.field.y) } else { game.field }
+}
+
+fn field_camera(field: Vec2, shake: Vec2) -> Camera2D {
+    let (sw, sh) = (screen_width(), screen_height());
+    let scale = (sw / field.x).min(sh / field.y);
+    Camera2D {
+        target: field * 0.5 + shake,
+        zoom: vec2(2.0 * scale / sw, 2.0 * scale / sh), // 不取负号，保持 y 向下
+        render_target: postfx::target(), // 开着后期处理时画进它的纹理
+        ..Default::default()
+    }
+}
+
+// 场地该有的大小：默认就是窗口；竖屏固定时是一块 9:16 的虚拟场地，横过来也不变，由 field_camera 缩放并留黑边。
+// 挡板高度 PLAYER_Y 不变，竖屏下面多出来的一截正好给手指拖，不挡住挡板
+const PORTRAIT_FIELD: (f32, f32) = (450.0, 800.0);
+
+fn target_field(game: &Game) -> Vec2 {
+    match game.settings.playfield {
+        Playfield::Window => vec2(screen_width(), screen_height()),
+        Playfield::Portrait => vec2(PORTRAIT_FIELD.0, PORTRAIT_FIELD.1),
+    }
+}
+
+// 局外跟随窗口（或竖屏设置）；局内锁定，变化需要玩家确认后才生效
+fn sync_field(game: &mut Game) {
+    // 联机对战两边的场地必须一样大，局内不跟窗口
+    if game.play == PlayMode::Versus && matches!(game.mode, GameMode::Playing | GameMode::GameOver) { return; }
+    // 观战时场地大小跟主机走
+    if game.mode == GameMode::Spectate { return; }
+    let screen = target_field(game);
+    if screen == game.field { game.field_changed = false; return; }
+    match game.mode {
+        // 网页上画布跟着浏览器窗口变（转屏、地址栏收起），局内一变就先停下，等玩家确认
+        #[cfg(target_arch = "wasm32")]
+        GameMode::Playing if !game.field_changed => {
+            game.field_changed = true;
+            enter_pause(game);
+            game.auto_paused = Some(tr("浏览器窗口大小变了"));
+        }
+        // 手机上跟随窗口时转屏也一样
+        #[cfg(target_os = "android")]
+        GameMode::Playing if !game.field_changed => {
+            game.field_changed = true;
+            enter_pause(game);
+            game.auto_paused = Some(tr("屏幕转向了"));
+        }
+        GameMode::Playing | GameMode::Paused | GameMode::Feedback => game.field_changed = true,
+        _ => game.field = screen,
+    }
+}
+
+fn apply_field_change(game: &mut Game) {
+    game.field = target_field(game);
+    game.field_changed = false;
+    // 场地中途变了，录下的输入回放不出同样的局，这局不再录
+    if matches!(game.replay, ReplayState::Recording(_)) { game.replay = ReplayState::Off; }
+    game.player.x = game.player.x.clamp(0.0, (game.field.x - game.player.w).max(0.0));
+}
+
+// 阶段背景色；切换时从上一阶段渐变过来
+fn background_color(game: &Game, res: &Resources) -> Color {
+    if game.settings.high_contrast { return BLACK; }
+    if let Some(bg) = game.theme().background { return sky(game).background(cosmetics::rgb(bg)); }
+    let to = res.content.stages.def(game.stage).bg_color();
+    let t = game.stage_fade;
+    let from = game.prev_bg;
+    sky(game).background(Color::new(to.r + (from.r - to.r) * t, to.g + (from.g - to.g) * t, to.b + (from.b - to.b) * t, 1.0))
+}
+
+// 局内的昼夜天色（见 background.rs）
+fn sky(game: &Game) -> Sky {
+    let in_run = matches!(game.mode, GameMode::Playing | GameMode::Paused | GameMode::Killcam | GameMode::GameOver);
+    if in_run && game.settings.day_night && !game.settings.high_contrast { Sky::at(game.elapsed) } else { Sky::NONE }
+}
+
+fn draw_backdrop(game: &Game) {
+    if game.settings.high_contrast { return; }
+    game.backdrop.draw(game.field, get_time() as f32, motion::calm(game), sky(game).stars);
+}
+
+// 里程碑：场地边缘一圈金色光晕，缓慢淡出（不做全屏闪白）
+fn draw_milestone_pulse(game: &Game) {
+    if game.milestone_pulse <= 0.0 { return; }
+    let c = game.palette().milestone.color();
+    let a = game.milestone_pulse * 0.35 * c.a;
+    for i in 0..3 {
+        let inset = i as f32 * 5.0;
+        draw_rectangle_lines(inset, inset, game.field.x - inset * 2.0, game.field.y - inset * 2.0, 6.0, Color { a: a / (i + 1) as f32, ..c });
+    }
+}
+
+// 底部提示条压在所有界面和触屏按钮之上
+fn draw_toast(font: &Font, game: &Game) {
+    let view = view_size(game);
+    set_camera(&field_camera(view, Vec2::ZERO));
+    game.toast.draw(font, view, game.settings.text_scale());
+    set_default_camera();
+}
+
+fn draw_game(game: &Game, res: &Resources) {
+    // 相机震动（见 shake.rs，噪声按真实时间取，不消耗玩法随机数）
+    let feel = game.feel_now();
+    let mut shake = game.shake.offset(feel.shake * game.settings.shake_intensity, get_time() as f32);
+    // 镜头朝移动方向略微倾斜
+    if game.mode == GameMode::Playing { shake.pos.x += game.player.vx / game.player.speed_max * feel.camera_lean; }
+    set_camera(&shake.camera(field_camera(view_size(game), Vec2::ZERO)));
+
+    let bg = background_color(game, res);
+    clear_background(bg);
+    draw_backdrop(game);
+    contrast::dim(game);
+    let ts = game.settings.text_scale();
+
+    match game.mode {
+        GameMode::Menu => draw_menu(&res.font, game),
+        GameMode::PreRun => draw_prerun(&res.font, game),
+        GameMode::Settings => options::draw(&res.font, game),
+        GameMode::FeelPanel => draw_feel_panel(&res.font, game),
+        GameMode::Feedback => draw_feedback(&res.font, game),
+        GameMode::Summary => draw_summary(&res.font, game),
+        GameMode::Stats if game.stats_page == 0 => draw_lifetime(&res.font, game),
+        GameMode::Stats => draw_death_stats(&res.font, game),
+        GameMode::Shop => draw_shop(&res.font, game),
+        GameMode::Customize => draw_customize(&res.font, game),
+        GameMode::Missions => draw_missions(&res.font, game),
+        GameMode::Achievements => draw_achievements(&res.font, game),
+        GameMode::History => draw_history(&res.font, game),
+        GameMode::Challenges => draw_challenges(&res.font, game),
+        GameMode::Profiles => draw_profiles(&res.font, game),
+        GameMode::SyncConflict => draw_sync_conflict(&res.font, game),
+        GameMode::NameEntry => draw_name_entry(&res.font, game),
+        GameMode::Leaderboard => draw_leaderboard(&res.font, game),
+        GameMode::Lobby => versus::draw_lobby(&res.font, game),
+        GameMode::Spectate => spectate::draw(game, res),
+        GameMode::Ranked => ladder::draw(&res.font, game),
+        GameMode::Sandbox => sandbox::draw(game, res),
+        GameMode::Duel => duel::draw(&res.font, game),
+        GameMode::Hotseat => hotseat::draw(&res.font, game),
+        GameMode::Keybinds => keybinds::draw(&res.font, game),
+        GameMode::Playing => {
+            draw_hud(&res.font, game);
+            wind::draw(game);
+            gravity::draw(game);
+            ghost::draw(game, &res.font);
+            draw_player(game, res);
+            draw_obstacles(game, res);
+            draw_powerups(game, res);
+            coins::draw(game);
+            shooting::draw(game);
+            draw_blast(game);
+            contrast::draw(game);
+            game.effects.draw_particles();
+            game.texts.draw(&res.font, 20.0 * ts);
+            draw_milestone_pulse(game);
+            if !screenshot::hides_hud(game) { game.banners.draw(&res.font, game.field.x, ts, motion::calm(game)); }
+            if game.play == PlayMode::Practice { practice::draw(game, &res.font); }
+            if game.play == PlayMode::Tutorial {
+                draw_text_center(&res.font, game.field.x, game.tutorial.prompt(), 150.0, 30.0 * ts, WHITE);
+                if game.tutorial.shows_legend() { threat::draw_legend(&res.font, 24.0, 200.0, &obstacle_palette(game, res), ts); }
+            }
+            if game.play == PlayMode::Versus { versus::draw(game, &res.font); }
+            if game.resume_in > 0.0 {
+                let n = (game.resume_in / RESUME_BEAT).ceil() as i32;
+                draw_rectangle(0.0, 0.0, game.field.x, game.field.y, Color::new(0.0, 0.0, 0.0, 0.25));
+                draw_text_center(&res.font, game.field.x, &n.to_string(), game.field.y * 0.45, 72.0 * ts, GOLD);
+            }
+        }
+        GameMode::Paused => {
+            // 定格的画面模糊、压暗，中间是暂停菜单
+            res.overlay.frozen(game, bg, shake, || {
+                draw_hud(&res.font, game);
+                wind::draw(game);
+                gravity::draw(game);
+                ghost::draw(game, &res.font);
+                draw_player(game, res);
+                draw_obstacles(game, res);
+                draw_powerups(game, res);
+                coins::draw(game);
+                shooting::draw(game);
+                draw_blast(game);
+                contrast::draw(game);
+            });
+            draw_text_center(&res.font, game.field.x, tr("已暂停"), 180.0, 40.0 * ts, YELLOW);
+            if let Some(why) = game.auto_paused { draw_text_center(&res.font, game.field.x, &trf("{}，已自动暂停", &[&why]), 140.0, 20.0 * ts, LIGHTGRAY); }
+            game.pause_menu.draw(&res.font, &pause_items(game), &pause_rects(game), 24.0 * ts);
+            let keys = &game.settings.keys;
+            let (p, r, esc) = (glyph(keys.pause.primary(), keys.pause.label()), keys.restart.label(), glyph(KeyCode::Escape, "ESC"));
+            let hint = trf("[↑/↓] 选择  [ENTER] 确定   快捷键：[{}] 继续  [{}] 重开  [{}] 回菜单  [F] 反馈问题", &[&p, &r, &esc]);
+            draw_text_center(&res.font, game.field.x, &hint, 470.0, 18.0 * ts, GRAY);
+            if let Some(menu) = &game.confirm_quit {
+                let (w, h) = (420.0, 200.0);
+                let x = (game.field.x - w) * 0.5;
+                draw_rectangle(x, 200.0, w, h, Color::new(0.05, 0.06, 0.1, 0.95));
+                draw_rectangle_lines(x, 200.0, w, h, 2.0, YELLOW);
+                draw_text_center(&res.font, game.field.x, tr("离开这一局？"), 240.0, 30.0 * ts, YELLOW);
+                draw_text_center(&res.font, game.field.x, quit_warning(game), 272.0, 18.0 * ts, LIGHTGRAY);
+                menu.draw(&res.font, &confirm_items(), &confirm_rects(game), 22.0 * ts);
+            }
+        }
+        // 死亡镜头：不画 HUD，场地跟着镜头推近，画完换回场地相机写跳过提示
+        GameMode::Killcam => {
+            set_camera(&killcam::camera(game, shake));
+            wind::draw(game);
+            gravity::draw(game);
+            draw_player(game, res);
+            draw_obstacles(game, res);
+            draw_powerups(game, res);
+            coins::draw(game);
+            shooting::draw(game);
+            draw_blast(game);
+            contrast::draw(game);
+            game.effects.draw_particles();
+            game.texts.draw(&res.font, 20.0 * ts);
+            set_camera(&field_camera(view_size(game), Vec2::ZERO));
+            draw_text_center(&res.font, game.field.x, tr("[SPACE] 跳过"), game.field.y - 30.0, 18.0 * ts, Color::new(1.0, 1.0, 1.0, 0.5));
+        }
+        GameMode::GameOver => {
+            let draw_field = || {
+                draw_hud(&res.font, game);
+                wind::draw(game);
+                gravity::draw(game);
+                draw_player(game, res);
+                draw_obstacles(game, res);
+                draw_powerups(game, res);
+                coins::draw(game);
+                shooting::draw(game);
+                draw_blast(game);
+                contrast::draw(game);
+                game.effects.draw_particles();
+                game.texts.draw(&res.font, 20.0 * ts);
+            };
+            // 对战两块场地并排，结算就写在各自场地上，不糊
+            if game.play == PlayMode::Versus {
+                draw_field();
+                versus::draw(game, &res.font);
+                set_default_camera();
+                return;
+            }
+            res.overlay.frozen(game, bg, shake, draw_field);
+            draw_text_center(&res.font, game.field.x, tr("💥 游戏结束!"), 250.0, 44.0 * ts, RED);
+            if game.play == PlayMode::Hotseat {
+                hotseat::draw_over(&res.font, game);
+                set_default_camera();
+                return;
+            }
+            draw_text_center(&res.font, game.field.x, &trf("得分：{}   最高（{}）：{}   躲过：{}", &[&game.score, &game.difficulty.label(), &game.best(), &game.dodged]), 300.0, 28.0 * ts, WHITE);
+            draw_text_center(&res.font, game.field.x, &trf("用时 {} · 种子 {} · {}", &[&run_clock(game.elapsed), &game.seed, &game.backdrop.name()]), 335.0, 20.0 * ts, GRAY);
+            if let Some(c) = &game.challenge {
+                let note = if c.kind.single_attempt() { tr("（今日机会已用完）") } else { "" };
+                draw_text_center(&res.font, game.field.x, &format!("{} {} · {}{}", c.kind.label(), c.kind.period_label(c.period), game.mutators.labels(), note), 410.0, 20.0 * ts, SKYBLUE);
+            } else if game.director.enabled {
+                draw_text_center(&res.font, game.field.x, tr("自适应难度：本局不计入最高分"), 410.0, 20.0 * ts, ORANGE);
+            } else if game.crowd {
+                draw_text_center(&res.font, game.field.x, tr("观众投票干预过：本局不计入最高分"), 410.0, 20.0 * ts, ORANGE);
+            } else if game.settings.assisted() {
+                draw_text_center(&res.font, game.field.x, tr("辅助移动：本局不进排行榜"), 410.0, 20.0 * ts, ORANGE);
+            } else if game.lenient() {
+                draw_text_center(&res.font, game.field.x, tr("宽松判定：本局不进排行榜"), 410.0, 20.0 * ts, ORANGE);
+            } else if game.settings.slowed() {
+                draw_text_center(&res.font, game.field.x, &trf("游戏速度 {}%：本局不进排行榜", &[&(game.settings.speed_scale * 100.0).round()]), 410.0, 20.0 * ts, ORANGE);
+            } else if game.play == PlayMode::Coop {
+                draw_text_center(&res.font, game.field.x, tr("双人合作：本局不计入最高分"), 410.0, 20.0 * ts, ORANGE);
+            } else if game.modified() {
+                draw_text_center(&res.font, game.field.x, &trf("变异：{}（单独记录）", &[&game.rules_labels()]), 410.0, 20.0 * ts, ORANGE);
+            }
+            let again = if !game.can_retry() { tr("[ESC] 本局总结").to_string() } else { trf("[{}] 再来一局   [ESC] 本局总结", &[&game.settings.keys.restart.label()]) };
+            draw_text_center(&res.font, game.field.x, &again, 370.0, 24.0 * ts, ORANGE);
+            if game.can_continue() {
+                draw_text_center(&res.font, game.field.x, tr("[C] 续关（每局 1 次，之后的成绩不计入最高分）"), 445.0, 22.0 * ts, LIME);
+            }
+        }
+    }
+
+    set_default_camera();
+}
+
+// ===== 入口：命令行工具 / 启动窗口 =====
+fn main() {
+    // 数据目录：cargo run -- --save-dir path
+    let args: Vec<String> = std::env::args().collect();
+    let arg_after = |flag: &str| args.iter().position(|a| a == flag).and_then(|i| args.get(i + 1)).cloned();
+    // 输入轨迹：cargo run -- --record trace.bin / --play trace.bin（见 trace.rs）
+    if let Some(file) = arg_after("--play") {
+        if let Err(e) = trace::play(&file) {
+            eprintln!("读不了输入轨迹 {}：{}", file, e);
+            std::process::exit(1);
+        }
+    } else {
+        paths::init(arg_after("--save-dir").map(std::path::PathBuf::from));
+    }
+    let mut profiles = Profiles::load();
+    profiles.activate(profiles.current());
+    if let Some(file) = arg_after("--record") {
+        if let Err(e) = trace::record(&file) { eprintln!("开不了输入轨迹 {}：{}", file, e); }
+    }
+    // 离线评估阵型难度：cargo run -- --rate-waves
+    if args.iter().any(|a| a == "--rate-waves") {
+        rating::print_report(&waves::load_waves(waves::WAVES_DIR));
+        return;
+    }
+    // 无窗口重跑回放、核对分数：cargo run -- --verify ghost.drg（回放 JSON 另加 --score N；- 表示标准输入）
+    if let Some(file) = arg_after("--verify") {
+        match replay::verify_file(&file, arg_after("--score").and_then(|s| s.parse().ok())) {
+            Ok(score) => println!("校验通过：{} 分", score),
+            Err(e) => {
+                eprintln!("校验失败：{}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+    // 导出对局历史与生涯统计：cargo run -- --export path
+    if args.iter().any(|a| a == "--export") {
+        let base = arg_after("--export").unwrap_or_else(export::default_base);
+        let save = load_save();
+        let history = history::load();
+        let data = export::ExportData { history: &history, lifetime: &save.lifetime, bests: &save.stat_bests, deaths: &save.deaths.deaths };
+        match export::write(&base, &data) {
+            Ok(files) => for f in files { println!("{}", f); },
+            Err(e) => eprintln!("导出失败：{}", e),
+        }
+        return;
+    }
+    macroquad::Window::from_config(window_conf(&Settings::load().unwrap_or_default()), run());
+}
+
+// ===== 主循环（固定物理步 + 渲染分离）=====
+async fn run() {
+    // 字体
+    let font = load_ttf_font("assets/NotoSansCJKsc-Regular.otf")
+        .await
+        .expect("无法加载中文字体：assets/NotoSansCJKsc-Regular.otf");
+    // 网页版和安卓版先把 assets/ 下的配置取到内存里，后面的读取照常同步进行（见 storage.rs）
+    storage::preload().await;
+
+    let res = Resources { font, content: Content::load(), overlay: overlay::Overlay::load(), sprites: sprites::Atlas::load(sprites::ATLAS_PATH), postfx: postfx::PostFx::load(), audio: audio::Audio::load().await };
+    let mut game = Game::new(load_save());
+    game.field = target_field(&game);
+    game.player.x = game.field.x * 0.5 - PLAYER_W * 0.5;
+    sync_profile(&mut game);
+    display::restore(&game.settings);
+    #[cfg(feature = "steam")]
+    { game.steam = steam::init(game.achievements.keys()); }
+
+    let mut acc = 0.0f32;
+    // 关窗前先把进行中的一局挂起
+    prevent_quit();
+
+    loop {
+        trace::frame();
+        #[cfg(feature = "gamepad")]
+        pad::poll();
+        touch::poll(&game);
+        if is_quit_requested() || game.quitting {
+            // 关窗也不丢东西：进行中的一局照回菜单处理，设置再写一次盘
+            abandon_run(&mut game);
+            game.settings.save();
+            versus::close(&mut game);
+            #[cfg(feature = "discord")]
+            presence::close(&mut game);
+            #[cfg(feature = "steam")]
+            if let Some(s) = game.steam.take() { s.shutdown(); }
+            if let Some(c) = game.cloud.as_mut() { c.push(); }
+            break;
+        }
+        let dt = get_frame_time();
+        game.online.poll();
+        #[cfg(feature = "twitch")]
+        twitch::poll(&mut game, dt);
+        #[cfg(feature = "discord")]
+        presence::update(&mut game);
+        #[cfg(feature = "steam")]
+        if let Some(s) = game.steam.as_mut() {
+            s.frame();
+            // 覆盖层打开时局内自动暂停（联机对战停不了）
+            if s.overlay && game.mode == GameMode::Playing && game.play != PlayMode::Versus { enter_pause(&mut game); }
+        }
+        display::update(&mut game);
+        sync_field(&mut game);
+        acc += if auto_pause(&mut game, dt) { 0.0 } else { dt };
+        if game.console.is_open() { acc = 0.0; } // 控制台开着时时间停住
+        let mut steps = 0;
+        while acc >= FIXED_DT {
+            update_game(&mut game, FIXED_DT, &res.content);
+            acc -= FIXED_DT;
+            steps += 1;
+        }
+        game.debug.set_steps(steps);
+        #[cfg(target_os = "android")]
+        suspend::checkpoint(&mut game, dt);
+        // 放在物理步之后：本帧切换出的界面不会再被同一次按键触发
+        if game.mode != GameMode::Paused { game.anims.update(dt, &res.sprites); }
+        handle_frame_input(&mut game);
+        #[cfg(feature = "tts")]
+        speech::tick(&mut game);
+        spectate::broadcast(&mut game);
+        res.audio.flush(&mut game, dt);
+        res.postfx.begin(&game);
+        draw_game(&game, &res);
+        res.postfx.end(&game);
+        if !screenshot::hides_hud(&game) { touch::draw(&res.font, &game); }
+        debug::draw(&res.font, &game);
+        console::draw(&res.font, &game);
+        screenshot::take(&mut game);
+        game.toast.tick(dt);
+        draw_toast(&res.font, &game);
+        if game.capture_pending {
+            // 截下暂停画面，再进入反馈表单
+            game.capture_pending = false;
+            game.feedback.screenshot = Some(get_screen_data());
+            game.mode = GameMode::Feedback;
+        }
+        next_frame().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::Keys;
+
+    const W: f32 = 800.0;
+    const H: f32 = 600.0;
+
+    fn run_until_empty(pool: &mut ObstaclePool) -> u32 {
+        let mut dodged = 0;
+        for _ in 0..2000 {
+            dodged += pool.update_and_sweep(W, H, PLAYER_Y, FIXED_DT);
+            if pool.live.is_empty() { break; }
+        }
+        assert!(pool.live.is_empty(), "obstacle never left the field");
+        dodged
+    }
+
+    // 默认规则的回放开头
+    fn header(seed: u64) -> replay::ReplayHeader {
+        replay::ReplayHeader {
+            seed, field: (W, H), difficulty: Difficulty::Normal, adaptive: false, mutators: Mutators::default(),
+            scheme: Scheme::Free, wrap: false, shooting: false, start_lives: 1, loadout: 0, character: 0, owned: Vec::new(),
+            unlocks: true, feel: Feel::default(), reduced_motion: false, speed_scale: 1.0, hitbox_inset: 6, hit_grace: 0, challenge: None,
+        }
+    }
+
+    #[test]
+    fn bottom_exit_counts_as_dodged() {
+        let mut pool = ObstaclePool::new();
+        pool.spawn(Rect::new(100.0, -40.0, 30.0, 30.0), 0.0, 300.0);
+        assert_eq!(run_until_empty(&mut pool), 1);
+    }
+
+    #[test]
+    fn side_exit_within_player_band_counts() {
+        let mut pool = ObstaclePool::new();
+        pool.spawn(Rect::new(400.0, PLAYER_Y, 30.0, 30.0), -400.0, 0.0);
+        pool.spawn(Rect::new(400.0, PLAYER_Y, 30.0, 30.0), 400.0, 0.0);
+        assert_eq!(run_until_empty(&mut pool), 2);
+    }
+
+    #[test]
+    fn side_exit_above_player_band_does_not_count() {
+        let mut pool = ObstaclePool::new();
+        pool.spawn(Rect::new(400.0, 100.0, 30.0, 30.0), -400.0, 0.0);
+        pool.spawn(Rect::new(400.0, 100.0, 30.0, 30.0), 400.0, 0.0);
+        assert_eq!(run_until_empty(&mut pool), 0);
+    }
+
+    #[test]
+    fn diagonal_mover_reaching_band_before_side_exit_counts() {
+        let mut pool = ObstaclePool::new();
+        pool.spawn(Rect::new(700.0, PLAYER_Y - 60.0, 30.0, 30.0), 200.0, 200.0);
+        assert_eq!(run_until_empty(&mut pool), 1);
+    }
+
+    #[test]
+    fn top_exit_only_counts_after_passing_band() {
+        let mut pool = ObstaclePool::new();
+        pool.spawn(Rect::new(200.0, PLAYER_Y + 5.0, 30.0, 30.0), 0.0, -500.0);
+        pool.spawn(Rect::new(500.0, 50.0, 30.0, 30.0), 0.0, -500.0);
+        assert_eq!(run_until_empty(&mut pool), 1);
+    }
+
+    #[test]
+    fn fresh_spawn_above_field_is_not_swept() {
+        let o = Obstacle { rect: Rect::new(100.0, -60.0, 40.0, 40.0), vx: 0.0, vy: 200.0, passed_band: false, grazed: false, deflect: 0.0, armor: Armor::Plain, cued: false };
+        assert_eq!(exit_edge(&o, W, H), None);
+    }
+
+    #[test]
+    fn exit_edge_reports_direction() {
+        let at = |x, y, vy| Obstacle { rect: Rect::new(x, y, 20.0, 20.0), vx: 0.0, vy, passed_band: true, grazed: false, deflect: 0.0, armor: Armor::Plain, cued: false };
+        assert_eq!(exit_edge(&at(100.0, H + 10.0, 1.0), W, H), Some(ExitEdge::Bottom));
+        assert_eq!(exit_edge(&at(-40.0, 300.0, 1.0), W, H), Some(ExitEdge::Left));
+        assert_eq!(exit_edge(&at(W + 10.0, 300.0, 1.0), W, H), Some(ExitEdge::Right));
+        assert_eq!(exit_edge(&at(100.0, -40.0, -1.0), W, H), Some(ExitEdge::Top));
+    }
+
+    #[test]
+    fn v0_save_migrates_legacy_bests_into_records() {
+        let s = parse_save(r#"{"best": 120, "bests": {"easy": 50, "normal": 80, "hard": 0}, "mutator_bests": {"hard/tiny": 30}, "xp": 7}"#).unwrap();
+        assert_eq!(s.version, migrate::SAVE_VERSION);
+        assert_eq!(s.records.score("standard/easy/"), 50);
+        assert_eq!(s.records.score("standard/normal/"), 120); // 单一最高分和按难度的取大
+        assert_eq!(s.records.score("standard/hard/"), 0);
+        assert_eq!(s.records.score("standard/hard/tiny"), 30);
+        assert_eq!(s.xp, 7);
+        assert!(s.extra.is_empty(), "legacy fields should be consumed by the migration");
+    }
+
+    #[test]
+    fn current_save_is_not_migrated_again() {
+        let text = format!(r#"{{"version": {}, "records": {{"standard/normal/": {{"score": 10, "day": 3, "seed": 9}}}}, "best": 500}}"#, migrate::SAVE_VERSION);
+        let s = parse_save(&text).unwrap();
+        let e = s.records.get("standard/normal/").unwrap();
+        assert_eq!((e.score, e.day, e.seed), (10, 3, 9));
+    }
+
+    #[test]
+    fn unknown_fields_survive_a_round_trip() {
+        let s = parse_save(r#"{"version": 99, "xp": 3, "profiles": [1, 2]}"#).unwrap();
+        assert_eq!(s.version, 99);
+        let out = serde_json::to_value(&s).unwrap();
+        assert_eq!(out["profiles"], serde_json::json!([1, 2]));
+        assert_eq!(out["xp"], 3);
+    }
+
+    #[test]
+    fn sealed_save_verifies_and_edits_are_flagged() {
+        let mut save = Save { version: migrate::SAVE_VERSION, xp: 40, ..Save::default() };
+        save.records.record("standard/hard/".into(), BestEntry { score: 77, day: 20000, seed: u64::MAX });
+        let mut v = serde_json::to_value(&save).unwrap();
+        integrity::seal(&mut v);
+        let text = v.to_string();
+        let ok = parse_save(&text).unwrap();
+        assert!(!ok.tampered && ok.warning.is_none());
+        assert_eq!(ok.records.score("standard/hard/"), 77);
+        let bad = parse_save(&text.replace("\"score\":77", "\"score\":9999")).unwrap();
+        assert!(bad.tampered);
+        assert_eq!(bad.records.score("standard/hard/"), 9999); // 照常读入，只是打上标记
+        let mut unsigned = v.clone();
+        unsigned.as_object_mut().unwrap().remove("sig");
+        assert!(parse_save(&unsigned.to_string()).unwrap().tampered);
+        // 去掉签名、改回 v1 再改分：一样算改动过
+        let obj = unsigned.as_object_mut().unwrap();
+        obj.remove("tampered");
+        obj.insert("version".into(), serde_json::json!(1));
+        let downgraded = parse_save(&unsigned.to_string().replace("\"score\":77", "\"score\":9999")).unwrap();
+        assert!(downgraded.tampered);
+    }
+
+    #[test]
+    fn empty_and_non_object_saves() {
+        assert_eq!(parse_save("{}").unwrap().version, migrate::SAVE_VERSION);
+        assert!(parse_save("[1, 2]").is_none());
+        assert!(parse_save("{\"xp\": 1").is_none());
+    }
+
+    #[test]
+    fn settings_round_trip_and_legacy_fields_are_dropped() {
+        let mut st = Settings { large_text: true, difficulty: Difficulty::Hard, ..Settings::default() };
+        st.keys.left = settings::Binding([Some(KeyCode::J), None]);
+        let text = serde_json::to_string(&st).unwrap();
+        assert!(serde_json::from_str::<Settings>(&text).unwrap() == st);
+        // 认不出的键名跳过，剩下的顶上
+        let keys: Keys = serde_json::from_str(r#"{"dash": ["Hyper", "Space"]}"#).unwrap();
+        assert!(keys.dash == settings::Binding([Some(KeyCode::Space), None]));
+        assert!(keys.left == Keys::default().left);
+        // 旧设置文件没有重开键：用默认的 R；改键查冲突时能找到占用者
+        assert!(keys.restart.primary() == KeyCode::R);
+        assert!(keys.owner(KeyCode::Space) == Some(settings::Action::Dash) && keys.owner(KeyCode::J).is_none());
+        // 旧存档里的设置和难度只读进来，不再写回存档
+        let save = parse_save(r#"{"difficulty": "Hard", "settings": {"large_text": true}}"#).unwrap();
+        assert!(save.difficulty == Difficulty::Hard && save.settings.as_ref().is_some_and(|s| s.large_text));
+        let out = serde_json::to_value(&save).unwrap();
+        assert!(out.get("settings").is_none() && out.get("difficulty").is_none());
+    }
+
+    #[test]
+    fn stick_deadzone_and_curve() {
+        let mut stick = settings::Stick { deadzone: 0.2, curve: settings::StickCurve::Linear };
+        assert_eq!(stick.apply(0.15), 0.0);
+        assert!((stick.apply(-0.6) + 0.5).abs() < 1e-6);
+        assert_eq!(stick.apply(1.0), 1.0);
+        stick.curve = settings::StickCurve::Quadratic;
+        assert!((stick.apply(0.6) - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn imported_profiles_merge_keeping_the_higher_scores() {
+        let key = records::key("standard", Difficulty::Normal, "");
+        let mut mine = Save { xp: 50, ..Save::default() };
+        mine.records.record(key.clone(), BestEntry { score: 300, day: 1, seed: 1 });
+        mine.achievements.insert("first".into(), 100);
+        let mut theirs = Save { xp: 20, ..Save::default() };
+        theirs.records.record(key.clone(), BestEntry { score: 900, day: 2, seed: 2 });
+        theirs.records.record("zen/normal/".into(), BestEntry { score: 40, day: 2, seed: 3 });
+        theirs.achievements.insert("first".into(), 50);
+        theirs.wallet.owned.push("skin_gold".into());
+        transfer::merge(&mut mine, theirs);
+        assert_eq!(mine.records.score(&key), 900);
+        assert_eq!(mine.records.score("zen/normal/"), 40);
+        assert_eq!(mine.xp, 50);
+        assert_eq!(mine.achievements["first"], 50);
+        assert!(mine.wallet.owns("skin_gold"));
+
+        for bytes in [&b""[..], b"a", b"ab", b"abc", b"\x00\xff\x10dodge"] {
+            let code = format!("{}{}", transfer::SHARE_PREFIX, transfer::base64(bytes));
+            assert_eq!(transfer::decode_share(&code).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn ghost_files_round_trip_and_reject_mismatches() {
+        let header = header(42);
+        let r = replay::Replay { header, runs: vec![(30, replay::StepInput::default())], continues: Vec::new(), track: vec![0.5, 0.52] };
+        let f = ghost::GhostFile::new("AAA", 120, &r);
+        let back = ghost::GhostFile::from_share(&f.share_code().unwrap()).unwrap();
+        assert_eq!((back.name.as_str(), back.score, back.seed), ("AAA", 120, 42));
+
+        let bad_seed = ghost::GhostFile { seed: 7, ..f.clone() };
+        assert!(ghost::GhostFile::decode(&bad_seed.encode().unwrap()).is_err());
+        let bad_version = ghost::GhostFile { version: ghost::GHOST_VERSION + 1, ..f.clone() };
+        assert!(ghost::GhostFile::decode(&bad_version.encode().unwrap()).is_err());
+        assert!(ghost::GhostFile::decode(b"not a zip").is_err());
+    }
+
+    #[test]
+    fn replays_verify_headlessly_against_the_claimed_score() {
+        let header = header(7);
+        let content = Content::load();
+        // 原地不动，总会被砸中；先跑一遍得到这一局真正的长度和分数
+        let idle = |n| replay::Replay { header: header.clone(), runs: vec![(n, replay::StepInput::default())], continues: Vec::new(), track: Vec::new() };
+        let (score, used) = replay::simulate(idle(120 * 600), &content).unwrap();
+        assert_eq!(replay::simulate(idle(used), &content).unwrap(), (score, used));
+        assert_eq!(replay::verify(idle(used), score, &content), Ok(score));
+        assert!(replay::verify(idle(used), score + 1, &content).is_err());
+        assert!(replay::verify(idle(used + 50), score, &content).is_err());
+        assert!(replay::verify(idle(used - 1), score, &content).is_err());
+    }
+
+    #[test]
+    fn versus_results_wait_until_the_opponent_has_passed_my_death() {
+        use versus::{judge, Outcome, Snapshot};
+        let alive = |step| Snapshot { step, ..Snapshot::default() };
+        let dead = |step| Snapshot { step, died_at: Some(step), ..Snapshot::default() };
+        // 我在第 100 步倒下：对方的快照还停在 90 步时不能判
+        assert_eq!(judge(100, Some(100), &alive(90)), None);
+        assert_eq!(judge(100, Some(100), &alive(101)), Some(Outcome::Lose));
+        assert_eq!(judge(120, None, &dead(100)), Some(Outcome::Win));
+        assert_eq!(judge(100, Some(100), &dead(95)), Some(Outcome::Win));
+        assert_eq!(judge(100, Some(100), &dead(100)), Some(Outcome::Draw));
+    }
+
+    #[test]
+    fn ladder_ratings_shrink_gains_against_a_beaten_friend() {
+        use versus::Outcome;
+        let mut l = Ladder::default();
+        assert_eq!(l.record("阿明", &Outcome::Void("掉线".to_string()), 3, 1), None);
+        assert!(l.get("阿明").is_none());
+        let first = l.record("阿明", &Outcome::Win, 3, 1).unwrap();
+        let second = l.record("阿明", &Outcome::Win, 2, 0).unwrap();
+        assert_eq!(first, 16);
+        assert!(second < first);
+        let o = l.get("阿明").unwrap();
+        assert_eq!((o.wins, o.sent, o.got, o.history.len()), (2, 5, 1, 2));
+    }
+
+    #[test]
+    fn trf_fills_arguments_like_format() {
+        assert_eq!(i18n::trf("SCORE: {:>4}", &[&42]), format!("SCORE: {:>4}", 42));
+        assert_eq!(i18n::trf("排位 {:+}   {:<3}|{:.1}s", &[&5, &"ab", &2.25]), format!("排位 {:+}   {:<3}|{:.1}s", 5, "ab", 2.25));
+        assert_eq!(i18n::trf("{:02}:{:02}", &[&3, &7]), "03:07");
+        // 译文可以用 {N} 调换参数次序；参数不够时占位原样留着
+        assert_eq!(i18n::trf("{1} 对 {0}", &[&"甲", &"乙"]), "乙 对 甲");
+        assert_eq!(i18n::trf("{} 和 {}", &[&1]), "1 和 {}");
+    }
+
+    #[test]
+    fn pinyin_picker_prefers_whole_syllables() {
+        let c = textfield::candidates("zh");
+        assert!(c.contains(&'张') && c.contains(&'周'));
+        let c = textfield::candidates("li");
+        assert_eq!(&c[..4], &['李', '丽', '力', '黎']);
+        assert!(c.contains(&'林') && c.contains(&'刘'));
+        assert!(textfield::candidates("").is_empty() && textfield::candidates("q1").is_empty());
     }
 }
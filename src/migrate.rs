@@ -0,0 +1,42 @@
+use serde_json::{json, Map, Value};
+
+// ===== 存档版本与迁移 =====
+// 存档先按 JSON 读进来，从文件里的 version（没有则为 0）开始逐步升级到 SAVE_VERSION，再反序列化成 Save。
+// MIGRATIONS[i] 把版本 i 升到 i + 1；改存档结构时在末尾追加一步并把 SAVE_VERSION 加一。
+// 比当前程序更新的存档不动，交给 serde 尽量读（不认识的字段由 Save::extra 原样保留）。
+pub const SAVE_VERSION: u32 = 2;
+
+type Migration = fn(&mut Map<String, Value>);
+const MIGRATIONS: [Migration; SAVE_VERSION as usize] = [v0_records, v1_signed];
+
+// 返回文件原本的版本
+pub fn upgrade(save: &mut Value) -> u32 {
+    let Some(obj) = save.as_object_mut() else { return 0 };
+    let from = obj.get("version").and_then(Value::as_u64).unwrap_or(0) as u32;
+    for step in MIGRATIONS.iter().skip(from as usize) { step(obj); }
+    if from < SAVE_VERSION { obj.insert("version".into(), json!(SAVE_VERSION)); }
+    from
+}
+
+// v0 → v1：单一最高分 best、按难度的 bests、“难度/标签”的 mutator_bests
+// 并进以“模式/难度/标签”为键的 records（没有日期和种子，记为 0）
+fn v0_records(obj: &mut Map<String, Value>) {
+    let score = |v: Option<&Value>| v.and_then(Value::as_i64).unwrap_or(0);
+    let mut old: Vec<(String, i64)> = vec![("normal/".to_string(), score(obj.get("best")))];
+    if let Some(Value::Object(b)) = obj.get("bests") {
+        for d in ["easy", "normal", "hard"] { old.push((format!("{}/", d), score(b.get(d)))); }
+    }
+    if let Some(Value::Object(m)) = obj.get("mutator_bests") {
+        for (k, v) in m { old.push((k.clone(), score(Some(v)))); }
+    }
+    for k in ["best", "bests", "mutator_bests"] { obj.remove(k); }
+    let records = obj.entry("records").or_insert_with(|| json!({}));
+    let Some(records) = records.as_object_mut() else { return };
+    for (k, s) in old.into_iter().filter(|(_, s)| *s > 0) {
+        let e = records.entry(format!("standard/{}", k)).or_insert_with(|| json!({ "score": 0, "day": 0, "seed": 0 }));
+        if s > score(e.get("score")) { *e = json!({ "score": s, "day": 0, "seed": 0 }); }
+    }
+}
+
+// v1 → v2：结构不变，标记文件带签名（见 integrity.rs）；v1 和它同一批发布，也按带签名算，缺签名就是改动过
+fn v1_signed(_: &mut Map<String, Value>) {}
@@ -0,0 +1,147 @@
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{tr, trf};
+use crate::rng::{Rng, Stream};
+use crate::{Game, PlayMode};
+
+// ===== 每日任务 =====
+// 同时有 ACTIVE 个任务，按日期从 MISSIONS 表里抽，每天换一批（同一天同一批）。
+// 进度由局内的钩子推进：bump 累加计数，tick 每步刷新“达到某值”类的指标。
+// 单局任务每局从 0 算起；累计任务跨局累加并存进存档。只有普通模式的局才算。
+const ACTIVE: usize = 3;
+const TOAST_TIME: f32 = 2.5;
+const DONE_COLOR: Color = Color::new(0.55, 1.0, 0.45, 1.0);
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Metric {
+    Shields,  // 拾取护盾
+    NoPickup, // 连续不拾取道具的秒数
+    Graze,    // 擦边
+    Dodged,   // 躲过障碍
+    Coins,    // 金币
+    Survive,  // 存活秒数
+    Combo,    // 连击倍率
+}
+
+pub struct MissionDef {
+    pub key: &'static str,
+    pub label: &'static str,
+    pub metric: Metric,
+    pub goal: u32,
+    pub per_run: bool, // 单局内完成（否则累计）
+    pub coins: u32,
+    pub xp: u32,
+}
+
+pub const MISSIONS: [MissionDef; 8] = [
+    MissionDef { key: "shields3", label: "单局收集 3 个护盾", metric: Metric::Shields, goal: 3, per_run: true, coins: 15, xp: 40 },
+    MissionDef { key: "nopu60", label: "连续 60 秒不拾取道具", metric: Metric::NoPickup, goal: 60, per_run: true, coins: 20, xp: 50 },
+    MissionDef { key: "graze20", label: "累计擦边 20 次", metric: Metric::Graze, goal: 20, per_run: false, coins: 15, xp: 40 },
+    MissionDef { key: "dodge150", label: "单局躲过 150 个障碍", metric: Metric::Dodged, goal: 150, per_run: true, coins: 20, xp: 50 },
+    MissionDef { key: "coins30", label: "累计收集 30 枚金币", metric: Metric::Coins, goal: 30, per_run: false, coins: 10, xp: 30 },
+    MissionDef { key: "survive90", label: "单局存活 90 秒", metric: Metric::Survive, goal: 90, per_run: true, coins: 20, xp: 50 },
+    MissionDef { key: "combo4", label: "连击达到 x4", metric: Metric::Combo, goal: 4, per_run: true, coins: 15, xp: 40 },
+    MissionDef { key: "dodge500", label: "累计躲过 500 个障碍", metric: Metric::Dodged, goal: 500, per_run: false, coins: 25, xp: 60 },
+];
+
+pub fn def(key: &str) -> Option<&'static MissionDef> { MISSIONS.iter().find(|m| m.key == key) }
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ActiveMission {
+    pub key: String,
+    pub progress: u32,
+    pub done: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct MissionBoard {
+    pub day: u32,
+    pub active: Vec<ActiveMission>,
+    #[serde(skip)]
+    since_pickup: f32,
+    #[serde(skip)]
+    toast: Option<(String, f32)>, // 局内进度提示（文字, 剩余时间）
+}
+
+impl MissionBoard {
+    // 换日（或第一次）时按日期抽一批新任务
+    pub fn refresh(&mut self, day: u32) {
+        if self.day == day && !self.active.is_empty() { return; }
+        let mut rng = Rng::stream(day as u64, Stream::Missions);
+        let mut pool: Vec<usize> = (0..MISSIONS.len()).collect();
+        self.active = (0..ACTIVE.min(pool.len()))
+            .map(|_| pool.swap_remove(rng.index(pool.len())))
+            .map(|i| ActiveMission { key: MISSIONS[i].key.to_string(), progress: 0, done: false })
+            .collect();
+        self.day = day;
+    }
+
+    // 开局：单局任务的进度清零
+    pub fn start_run(&mut self) {
+        self.since_pickup = 0.0;
+        self.toast = None;
+        for m in self.active.iter_mut().filter(|m| !m.done) {
+            if def(&m.key).is_some_and(|d| d.per_run) { m.progress = 0; }
+        }
+    }
+}
+
+fn counts(game: &Game) -> bool { game.play == PlayMode::Standard }
+
+// 累加类指标
+pub fn bump(game: &mut Game, metric: Metric, n: u32) {
+    if n == 0 || !counts(game) { return; }
+    advance(game, metric, |p| p + n);
+}
+
+// 每个模拟步：刷新“达到某值”类指标
+pub fn tick(game: &mut Game, dt: f32) {
+    if let Some((_, t)) = &mut game.missions.toast {
+        *t -= dt;
+        if *t <= 0.0 { game.missions.toast = None; }
+    }
+    if !counts(game) { return; }
+    game.missions.since_pickup += dt;
+    let (quiet, alive, combo) = (game.missions.since_pickup as u32, game.elapsed as u32, game.combo);
+    advance(game, Metric::NoPickup, |p| p.max(quiet));
+    advance(game, Metric::Survive, |p| p.max(alive));
+    advance(game, Metric::Combo, |p| p.max(combo));
+}
+
+pub fn on_pickup(game: &mut Game, shield: bool) {
+    game.missions.since_pickup = 0.0;
+    if shield { bump(game, Metric::Shields, 1); }
+}
+
+fn advance(game: &mut Game, metric: Metric, f: impl Fn(u32) -> u32) {
+    let mut finished = Vec::new();
+    let mut toast = None;
+    for m in game.missions.active.iter_mut().filter(|m| !m.done) {
+        let Some(d) = def(&m.key).filter(|d| d.metric == metric) else { continue };
+        let p = f(m.progress).min(d.goal);
+        if p == m.progress { continue; }
+        // 进度提示：目标小的每步都提示，目标大的每过四分之一提示一次
+        let step = (d.goal / 4).max(1);
+        if p / step != m.progress / step && p < d.goal { toast = Some(format!("{}  {}/{}", tr(d.label), p, d.goal)); }
+        m.progress = p;
+        if p >= d.goal {
+            m.done = true;
+            finished.push(d);
+        }
+    }
+    if let Some(t) = toast { game.missions.toast = Some((t, TOAST_TIME)); }
+    for d in finished {
+        game.wallet.coins += d.coins;
+        game.xp += d.xp;
+        game.banners.show(tr("任务完成"), trf("{}   +{} 金币  +{} 经验", &[&tr(d.label), &d.coins, &d.xp]), DONE_COLOR, 2.0);
+    }
+}
+
+// 局内的进度提示：场地底部居中，淡出
+pub fn draw_toast(game: &Game, font: &Font, ts: f32) {
+    let Some((txt, t)) = &game.missions.toast else { return };
+    let a = (t / 0.5).min(1.0);
+    crate::draw_text_center(font, game.field.x, txt, game.field.y - 40.0 * ts, 20.0 * ts, Color::new(0.8, 0.95, 0.8, a));
+}
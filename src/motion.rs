@@ -0,0 +1,19 @@
+use std::f32::consts::TAU;
+
+use crate::Game;
+
+// ===== 减少动态效果（光敏安全）=====
+// 无障碍里的“减少动态效果”是所有打击感效果共用的一个总开关，各处都到这里问，不各自去读设置；新加的效果也先问这里。开着时：
+//   震屏、顿帧、慢动作、镜头倾斜关掉，粒子减半（Feel::calmed）；死亡镜头不推近，直接进结算（killcam.rs）；
+//   高频闪烁（无敌、快到期的状态、风区预警）改成常亮，需要提醒的地方换成缓慢呼吸的边框或明暗；
+//   横幅不淡入淡出，出现时就是静止的全亮文字；背景星星、风线、引力井的旋臂和升级文字都不动。
+// 呼吸的频率远低于光敏风险开始的每秒 3 次。
+const PULSE_HZ: f32 = 0.8;
+
+pub fn calm(game: &Game) -> bool { game.settings.reduced_motion }
+
+// 每秒闪 hz 次的东西这一刻画不画（t 是它自己的计时）；减少动态效果时一直画
+pub fn blink(game: &Game, t: f32, hz: f32) -> bool { calm(game) || (t * hz * 2.0) as i32 % 2 == 1 }
+
+// 替代闪烁的缓慢呼吸：0..1
+pub fn pulse(t: f32) -> f32 { 0.5 - 0.5 * (t * PULSE_HZ * TAU).cos() }
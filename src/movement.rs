@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+
+use crate::physics::Physics;
+use crate::{step_player, MoveInput, Player};
+
+// ===== 移动方式 =====
+// Free：原本的加速度/衰减手感；Lanes：场地分成 LANES 条车道，左右键在车道间短暂过渡。
+// wrap：两种方式都可以叠加穿墙——从一侧出去，从另一侧进来。
+pub const LANES: usize = 5;
+const LANE_TWEEN: f32 = 0.09; // 换道过渡时长
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum Scheme {
+    #[default]
+    Free,
+    Lanes,
+}
+
+impl Scheme {
+    pub fn label(self) -> &'static str {
+        crate::i18n::tr(match self {
+            Scheme::Free => "自由移动",
+            Scheme::Lanes => "车道模式",
+        })
+    }
+}
+
+pub fn lane_center(lane: usize, field_w: f32) -> f32 { (lane as f32 + 0.5) * field_w / LANES as f32 }
+
+// 把横坐标吸附到最近的车道中心
+pub fn snap_to_lane(x: f32, field_w: f32) -> f32 {
+    let lane = ((x / field_w * LANES as f32) as usize).min(LANES - 1);
+    lane_center(lane, field_w)
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Mover {
+    pub scheme: Scheme,
+    #[serde(default)]
+    pub wrap: bool,
+    lane: usize,
+    from_x: f32,
+    tween: f32,    // 剩余过渡时间
+    last_dir: f32, // 上一步的方向，用来识别“刚按下”
+}
+
+impl Mover {
+    pub fn new(scheme: Scheme, wrap: bool) -> Self { Self { scheme, wrap, lane: LANES / 2, from_x: 0.0, tween: 0.0, last_dir: 0.0 } }
+
+    // 开局：车道模式下把玩家放到中间车道
+    pub fn start(&mut self, p: &mut Player, field_w: f32) {
+        *self = Self::new(self.scheme, self.wrap);
+        if self.scheme == Scheme::Lanes { p.x = lane_center(self.lane, field_w) - p.w * 0.5; }
+    }
+
+    // 瞬移：车道模式下落到最近的车道，不做过渡
+    pub fn warp(&mut self, p: &mut Player, x: f32, field_w: f32) {
+        let cx = match self.scheme {
+            Scheme::Free => x,
+            Scheme::Lanes => {
+                self.lane = ((x / field_w * LANES as f32) as usize).min(LANES - 1);
+                self.tween = 0.0;
+                lane_center(self.lane, field_w)
+            }
+        };
+        p.x = if self.wrap { (cx - p.w * 0.5).rem_euclid(field_w) } else { (cx - p.w * 0.5).clamp(0.0, field_w - p.w) };
+        p.vx = 0.0;
+    }
+
+    pub fn step(&mut self, p: &mut Player, phys: &Physics, input: MoveInput, acc_mul: f32, dt: f32, field_w: f32) {
+        match self.scheme {
+            Scheme::Free => step_player(p, phys, input, acc_mul, dt, field_w, self.wrap),
+            Scheme::Lanes => self.step_lanes(p, input, dt, field_w),
+        }
+    }
+
+    // 按键沿触发换道（固定步里 is_key_pressed 会在同一帧的多步里重复为真）
+    fn step_lanes(&mut self, p: &mut Player, input: MoveInput, dt: f32, field_w: f32) {
+        let dir = input.dir.signum();
+        if dir != 0.0 && dir != self.last_dir {
+            let next = self.lane as i32 + dir as i32;
+            let next = if self.wrap { next.rem_euclid(LANES as i32) } else { next.clamp(0, LANES as i32 - 1) } as usize;
+            if next != self.lane {
+                // 穿过边缘换道：起点挪到场地另一侧之外，过渡时从边缘滑进来
+                let crossed = (next as i32 - self.lane as i32).signum() as f32 != dir;
+                self.from_x = if crossed { p.x - dir * field_w } else { p.x };
+                self.lane = next;
+                self.tween = LANE_TWEEN;
+            }
+        }
+        self.last_dir = dir;
+        let target = lane_center(self.lane, field_w) - p.w * 0.5;
+        let prev = p.x;
+        self.tween = (self.tween - dt).max(0.0);
+        let t = 1.0 - self.tween / LANE_TWEEN;
+        let t = t * t * (3.0 - 2.0 * t); // smoothstep
+        let x = self.from_x + (target - self.from_x) * t;
+        p.x = if self.wrap { x.rem_euclid(field_w) } else { x.clamp(0.0, field_w - p.w) };
+        // 跨边缘那一步按最短方向算位移
+        let mut dx = p.x - prev;
+        if self.wrap && dx.abs() > field_w * 0.5 { dx -= field_w * dx.signum(); }
+        p.vx = if dt > 0.0 { dx / dt } else { 0.0 };
+    }
+}
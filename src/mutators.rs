@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::tr;
+
+// ===== 本局变异规则（开局前选择）=====
+// 任意一项开启都会给成绩打上标签，单独记录最高分。
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Mutators {
+    pub tiny: bool,         // 玩家变小
+    pub ice: bool,          // 冰面：松手不减速、加速变慢
+    pub double_spawn: bool, // 障碍生成翻倍
+    pub no_powerups: bool,  // 没有道具
+    pub giant: bool,        // 巨型障碍
+}
+
+pub struct MutatorInfo {
+    pub key: &'static str,
+    pub label: &'static str,
+    pub get: fn(&Mutators) -> bool,
+    pub toggle: fn(&mut Mutators),
+}
+
+pub const MUTATORS: [MutatorInfo; 5] = [
+    MutatorInfo { key: "tiny", label: "迷你玩家", get: |m| m.tiny, toggle: |m| m.tiny = !m.tiny },
+    MutatorInfo { key: "ice", label: "冰面操控", get: |m| m.ice, toggle: |m| m.ice = !m.ice },
+    MutatorInfo { key: "double", label: "双倍生成", get: |m| m.double_spawn, toggle: |m| m.double_spawn = !m.double_spawn },
+    MutatorInfo { key: "nopu", label: "没有道具", get: |m| m.no_powerups, toggle: |m| m.no_powerups = !m.no_powerups },
+    MutatorInfo { key: "giant", label: "巨型障碍", get: |m| m.giant, toggle: |m| m.giant = !m.giant },
+];
+
+pub const TINY_SCALE: f32 = 0.5;
+pub const GIANT_SCALE: f32 = 1.6;
+pub const ICE_ACC_MUL: f32 = 0.5;
+
+impl Mutators {
+    pub fn any(&self) -> bool { MUTATORS.iter().any(|m| (m.get)(self)) }
+    // 成绩标签，如 "tiny+ice"；无变异时为空串
+    pub fn tag(&self) -> String {
+        MUTATORS.iter().filter(|m| (m.get)(self)).map(|m| m.key).collect::<Vec<_>>().join("+")
+    }
+    pub fn labels(&self) -> String {
+        MUTATORS.iter().filter(|m| (m.get)(self)).map(|m| tr(m.label)).collect::<Vec<_>>().join(tr("、"))
+    }
+    pub fn player_scale(&self) -> f32 { if self.tiny { TINY_SCALE } else { 1.0 } }
+    pub fn obstacle_scale(&self) -> f32 { if self.giant { GIANT_SCALE } else { 1.0 } }
+    pub fn spawn_mul(&self) -> f32 { if self.double_spawn { 0.5 } else { 1.0 } }
+}
@@ -0,0 +1,178 @@
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+use serde::{Deserialize, Serialize};
+
+use crate::ghost::GhostFile;
+use crate::i18n::{tr, trf};
+use crate::replay::ReplayState;
+use crate::{http, transfer, Game, PlayMode};
+
+// ===== 全球排行榜（可选）=====
+// 在设置里打开、并用 DODGE_LEADERBOARD_URL 指定服务器后才会联网。接口：
+//   POST <url>/scores          提交 Submission，回 {"rank": n}；服务器应先把 replay 解码后交给
+//                              `dodge-rush --verify -` 重跑，分数对得上才收
+//   GET  <url>/top?mode=&player=  前 100 名和我的名次（Board）
+//   GET  <url>/ghosts/<id>     下载某条成绩的幽灵（.drg 原始字节）
+// 请求都在后台线程里做，结果经通道送回，每帧 poll 一次，渲染循环从不等网络。
+// 连不上就照常显示本地前十名。成绩和回放只经 https 提交（本机 localhost / 127.0.0.1 上的测试服除外）。
+pub const URL_ENV: &str = "DODGE_LEADERBOARD_URL";
+pub const TOP_N: usize = 100;
+
+pub fn endpoint() -> Option<String> {
+    std::env::var(URL_ENV).ok().map(|s| s.trim().trim_end_matches('/').to_string()).filter(|s| !s.is_empty())
+}
+
+#[derive(Serialize)]
+pub struct Submission {
+    pub name: String,
+    pub score: i32,
+    pub mode: String,         // 最高分表的键（模式/难度/规则标签）
+    pub seed: u64,
+    pub elapsed: f32,         // 存活秒数（仅供显示，服务器以回放为准）
+    pub replay_hash: String,  // 回放 JSON 的 FNV-1a 64
+    pub replay: String,       // 整局回放打成的幽灵文件（base64），用来校验，也供别人下载赛跑
+}
+
+#[derive(Clone, Deserialize)]
+pub struct OnlineEntry {
+    pub name: String,
+    pub score: i32,
+    #[serde(default)]
+    pub day: u32,
+    #[serde(default)]
+    pub ghost: Option<String>, // 服务器上的幽灵 id
+    #[serde(default)]
+    pub elapsed: f32,
+}
+
+#[derive(Clone, Copy, Deserialize)]
+pub struct MyRank {
+    pub rank: usize, // 从 1 起
+    pub score: i32,
+}
+
+#[derive(Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct Board {
+    pub entries: Vec<OnlineEntry>,
+    pub me: Option<MyRank>,
+}
+
+enum Reply {
+    Board(Result<Board, String>),
+    Submitted(Result<usize, String>),
+    Ghost(Result<GhostFile, String>),
+}
+
+#[derive(Clone, PartialEq)]
+pub enum Status { Idle, Loading, Ready, Offline(String) }
+
+pub struct Online {
+    tx: Sender<Reply>,
+    rx: Receiver<Reply>,
+    pub status: Status,
+    pub board: Option<Board>,
+    pub last_rank: Option<usize>,       // 最近一次提交后的全球名次
+    pub ghost: Option<Result<GhostFile, String>>, // 下载好的幽灵，等排行榜界面取走
+}
+
+impl Online {
+    pub fn new() -> Self {
+        let (tx, rx) = channel();
+        Self { tx, rx, status: Status::Idle, board: None, last_rank: None, ghost: None }
+    }
+
+    // 在后台线程里跑一个请求
+    fn spawn(&self, job: impl FnOnce() -> Reply + Send + 'static) {
+        let tx = self.tx.clone();
+        std::thread::spawn(move || { let _ = tx.send(job()); });
+    }
+
+    pub fn fetch(&mut self, mode: &str, player: &str) {
+        let Some(url) = endpoint() else { self.status = Status::Offline(tr("没有配置服务器").to_string()); return };
+        self.status = Status::Loading;
+        let url = format!("{}/top?mode={}&player={}&limit={}", url, escape(mode), escape(player), TOP_N);
+        self.spawn(move || Reply::Board(get_json(&url)));
+    }
+
+    pub fn submit(&self, s: Submission) {
+        let Some(url) = endpoint() else { return };
+        self.spawn(move || {
+            if !http::secure(&url) { return Reply::Submitted(Err(tr("成绩只经 https:// 提交").to_string())); }
+            let body = serde_json::to_vec(&s).unwrap();
+            Reply::Submitted(http::request("POST", &format!("{}/scores", url), Some(&body)).and_then(|(status, body)| {
+                if !(200..300).contains(&status) { return Err(format!("HTTP {}", status)); }
+                #[derive(Deserialize)]
+                struct Rank { rank: usize }
+                serde_json::from_slice::<Rank>(&body).map(|r| r.rank).map_err(|e| e.to_string())
+            }))
+        });
+    }
+
+    pub fn fetch_ghost(&mut self, id: &str) {
+        let Some(url) = endpoint() else { return };
+        let url = format!("{}/ghosts/{}", url, escape(id));
+        self.spawn(move || Reply::Ghost(http::request("GET", &url, None).and_then(|(status, body)| {
+            if status != 200 { return Err(trf("下载幽灵失败：HTTP {}", &[&status])); }
+            GhostFile::decode(&body)
+        })));
+    }
+
+    // 每帧收一次后台结果
+    pub fn poll(&mut self) {
+        while let Ok(reply) = self.rx.try_recv() {
+            match reply {
+                Reply::Board(Ok(mut b)) => {
+                    b.entries.truncate(TOP_N);
+                    self.board = Some(b);
+                    self.status = Status::Ready;
+                }
+                Reply::Board(Err(e)) => self.status = Status::Offline(e),
+                Reply::Submitted(Ok(rank)) => self.last_rank = Some(rank),
+                Reply::Submitted(Err(e)) => crate::logfile::warn(&format!("提交全球排行失败：{}", e)),
+                Reply::Ghost(g) => self.ghost = Some(g),
+            }
+        }
+    }
+}
+
+// 一局结束时提交：只收会记最高分的局（普通模式、非挑战、未续关、未开自适应、全速、无辅助移动、标准判定、录了回放），改过的存档不提交
+pub fn submit_run(game: &Game) {
+    if !game.settings.online || game.play != PlayMode::Standard || game.challenge.is_some() || game.continued
+        || game.crowd || game.director.enabled || game.settings.slowed() || game.settings.assisted() || game.lenient() || game.tampered { return; }
+    let ReplayState::Recording(r) = &game.replay else { return };
+    let Ok(ghost) = GhostFile::new(&game.player_name, game.score, r).encode() else { return };
+    game.online.submit(Submission {
+        name: game.player_name.clone(),
+        score: game.score,
+        mode: game.best_key(),
+        seed: game.seed,
+        elapsed: game.elapsed,
+        replay_hash: replay_hash(&serde_json::to_string(r).unwrap()),
+        replay: transfer::base64(&ghost),
+    });
+}
+
+fn get_json<T: for<'de> Deserialize<'de>>(url: &str) -> Result<T, String> {
+    let (status, body) = http::request("GET", url, None)?;
+    if status != 200 { return Err(format!("HTTP {}", status)); }
+    serde_json::from_slice(&body).map_err(|e| e.to_string())
+}
+
+// 查询参数里的百分号编码（名字可能是中文）
+fn escape(s: &str) -> String {
+    s.bytes().map(|b| match b {
+        b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+        _ => format!("%{:02X}", b),
+    }).collect()
+}
+
+// 提交时附带的回放指纹，服务器可以据此去重、抽查
+pub fn replay_hash(json: &str) -> String {
+    let mut h = 0xcbf2_9ce4_8422_2325u64;
+    for b in json.bytes() {
+        h ^= b as u64;
+        h = h.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    format!("{:016x}", h)
+}
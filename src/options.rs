@@ -0,0 +1,371 @@
+use macroquad::prelude::*;
+
+use crate::i18n::{tr, trf};
+use crate::settings::{Action, Settings, PRESETS};
+use crate::trace::{is_key_pressed, is_mouse_button_pressed};
+use crate::{draw_text_center, keybinds, mouse_world, Game, GameMode};
+
+// ===== 设置 =====
+// 主菜单“设置”和暂停菜单“设置”进来。分五页：操作、声音、画面、无障碍、玩法；[TAB] / [1~5] 或鼠标点标签换页。
+// 每一行是一个控件：开关（[ENTER] 切换）、滑条（[←/→] 调整，鼠标点条上直接定位）、选项（[←/→] 轮换）、按键（[ENTER] 后按新键）。
+// 读写的都是 settings.json 里的 Settings，改一项当场生效、当场写盘；全屏立刻切，难度立刻换。
+// 局内（从暂停进来）游戏速度、减少动态效果和难度记在回放头里，这一局不能改，对应的行画成灰色。
+pub const TAB_A11Y: usize = 3;
+const ROWS_VISIBLE: usize = 11;
+const ROW_H: f32 = 34.0;
+const TOP: f32 = 170.0;
+const PANEL_W: f32 = 560.0;
+const BAR_W: f32 = 200.0;
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Toggle { get: fn(&Settings) -> bool, set: fn(&mut Settings, bool) },
+    // 数值按百分比显示，每次调 step
+    Slider { get: fn(&Settings) -> f32, set: fn(&mut Settings, f32), min: f32, max: f32, step: f32 },
+    Choice { show: fn(&Settings) -> &'static str, cycle: fn(&mut Settings, i32) },
+    Key(Action), // 主键；备用键在改键界面改
+    Open(fn(&mut Game)),
+}
+
+struct Row {
+    name: &'static str,
+    kind: Kind,
+    pad: bool,    // 只有 gamepad 特性时才显示
+    speech: bool, // 只有 tts 特性时才显示
+    fixed: bool,  // 局内不能改
+}
+
+const fn row(name: &'static str, kind: Kind) -> Row { Row { name, kind, pad: false, speech: false, fixed: false } }
+
+const CONTROLS: [Row; 15] = [
+    row("左移", Kind::Key(Action::MoveLeft)),
+    row("右移", Kind::Key(Action::MoveRight)),
+    row("冲刺", Kind::Key(Action::Dash)),
+    row("暂停", Kind::Key(Action::Pause)),
+    row("重开", Kind::Key(Action::Restart)),
+    row("炸弹", Kind::Key(Action::Bomb)),
+    row("超载", Kind::Key(Action::Overdrive)),
+    row("定住（辅助移动）", Kind::Key(Action::Hold)),
+    row("备用键 / 恢复默认", Kind::Open(keybinds::enter)),
+    row("鼠标操控", Kind::Toggle { get: |s| s.mouse, set: |s, v| s.mouse = v }),
+    row("触屏跟手", Kind::Choice { show: |s| s.touch.label(), cycle: |s, _| s.touch = s.touch.toggle() }),
+    Row { pad: true, ..row("手柄震动", Kind::Toggle { get: |s| s.rumble, set: |s, v| s.rumble = v }) },
+    Row { pad: true, ..row("震动强度", Kind::Slider { get: |s| s.rumble_strength, set: |s, v| s.rumble_strength = v, min: 0.1, max: 1.0, step: 0.1 }) },
+    Row { pad: true, ..row("摇杆死区", Kind::Slider { get: |s| s.stick.deadzone, set: |s, v| s.stick.deadzone = v, min: 0.0, max: crate::settings::STICK_DEADZONE_MAX, step: 0.05 }) },
+    Row { pad: true, ..row("摇杆响应曲线", Kind::Choice { show: |s| s.stick.curve.label(), cycle: |s, _| s.stick.curve = s.stick.curve.toggle() }) },
+];
+
+const AUDIO: [Row; 4] = [
+    row("总音量", Kind::Slider { get: |s| s.volume.master, set: |s, v| s.volume.master = v, min: 0.0, max: 1.0, step: 0.1 }),
+    row("音乐", Kind::Slider { get: |s| s.volume.music, set: |s, v| s.volume.music = v, min: 0.0, max: 1.0, step: 0.1 }),
+    row("音效", Kind::Slider { get: |s| s.volume.sfx, set: |s, v| s.volume.sfx = v, min: 0.0, max: 1.0, step: 0.1 }),
+    row("界面音", Kind::Slider { get: |s| s.volume.ui, set: |s, v| s.volume.ui = v, min: 0.0, max: 1.0, step: 0.1 }),
+];
+
+const VIDEO: [Row; 15] = [
+    row("全屏", Kind::Toggle { get: |s| s.fullscreen, set: |s, v| s.fullscreen = v }),
+    row("场地形状", Kind::Choice { show: |s| s.playfield.label(), cycle: |s, _| s.playfield = s.playfield.toggle() }),
+    row("配色主题", Kind::Open(open_looks)),
+    row("HUD（局内 F4）", Kind::Choice { show: |s| s.hud.label(), cycle: |s, d| s.hud = s.hud.cycle(d) }),
+    row("渲染比例", Kind::Slider { get: |s| s.render_scale, set: |s, v| s.render_scale = v, min: crate::postfx::SCALE_MIN, max: crate::postfx::SCALE_MAX, step: 0.25 }),
+    row("平滑缩放", Kind::Toggle { get: |s| s.render_smooth, set: |s, v| s.render_smooth = v }),
+    row("HUD 缩放", Kind::Slider { get: |s| s.ui_scale, set: |s, v| s.ui_scale = v, min: crate::hud::UI_SCALE_MIN, max: crate::hud::UI_SCALE_MAX, step: 0.05 }),
+    row("昼夜变化", Kind::Toggle { get: |s| s.day_night, set: |s, v| s.day_night = v }),
+    row("来袭预警", Kind::Toggle { get: |s| s.spawn_warnings, set: |s, v| s.spawn_warnings = v }),
+    row("暂停时模糊背景", Kind::Toggle { get: |s| s.blur_overlays, set: |s, v| s.blur_overlays = v }),
+    row("最佳纪录幽灵", Kind::Toggle { get: |s| s.ghost, set: |s, v| s.ghost = v }),
+    row("CRT 扫描线", Kind::Toggle { get: |s| s.fx_crt, set: |s, v| s.fx_crt = v }),
+    row("暗角", Kind::Toggle { get: |s| s.fx_vignette, set: |s, v| s.fx_vignette = v }),
+    row("震屏色差", Kind::Toggle { get: |s| s.fx_aberration, set: |s, v| s.fx_aberration = v }),
+    row("泛光", Kind::Toggle { get: |s| s.fx_bloom, set: |s, v| s.fx_bloom = v }),
+];
+
+const A11Y: [Row; 13] = [
+    row("一键预设", Kind::Choice { show: preset_label, cycle: cycle_preset }),
+    row("高对比度", Kind::Toggle { get: |s| s.high_contrast, set: |s, v| s.high_contrast = v }),
+    row("高对比描边（保留主题）", Kind::Toggle { get: |s| s.contrast_outlines, set: |s, v| s.contrast_outlines = v }),
+    row("大字号", Kind::Toggle { get: |s| s.large_text, set: |s, v| s.large_text = v }),
+    row("色觉模式", Kind::Choice { show: |s| s.color_vision.label(), cycle: |s, d| s.color_vision = s.color_vision.cycle(d) }),
+    row("道具图标", Kind::Toggle { get: |s| s.powerup_glyphs, set: |s, v| s.powerup_glyphs = v }),
+    Row { speech: true, ..row("朗读菜单", Kind::Toggle { get: |s| s.narrate, set: |s, v| s.narrate = v }) },
+    Row { fixed: true, ..row("减少动态效果（光敏安全）", Kind::Toggle { get: |s| s.reduced_motion, set: |s, v| s.reduced_motion = v }) },
+    row("震屏总强度", Kind::Slider { get: |s| s.shake_intensity, set: |s, v| s.shake_intensity = v, min: 0.0, max: 1.5, step: 0.1 }),
+    row("声音预警", Kind::Toggle { get: |s| s.audio_cues, set: |s, v| s.audio_cues = v }),
+    Row { fixed: true, ..row("辅助移动（不进排行榜）", Kind::Choice { show: |s| s.assist.label(), cycle: |s, d| s.assist = s.assist.cycle(d) }) },
+    row("辅助力度", Kind::Slider { get: |s| s.assist_strength, set: |s, v| s.assist_strength = v, min: crate::assist::STRENGTH_MIN, max: crate::assist::STRENGTH_MAX, step: 0.1 }),
+    Row { fixed: true, ..row("游戏速度（慢速不进排行榜）", Kind::Slider { get: |s| s.speed_scale, set: |s, v| s.speed_scale = v, min: crate::settings::SPEED_SCALE_MIN, max: crate::settings::SPEED_SCALE_MAX, step: 0.1 }) },
+];
+
+const GAMEPLAY: [Row; 7] = [
+    row("语言 / Language", Kind::Choice { show: |s| s.lang.label(), cycle: |s, d| s.lang = s.lang.cycle(d) }),
+    Row { fixed: true, ..row("难度", Kind::Choice { show: |s| s.difficulty.label(), cycle: |s, d| s.difficulty = s.difficulty.cycle(d) }) },
+    Row { fixed: true, ..row("命中盒收窄", Kind::Choice { show: |s| s.inset_label(), cycle: |s, d| s.cycle_inset(d) }) },
+    Row { fixed: true, ..row("命中宽限", Kind::Choice { show: |s| s.grace_label(), cycle: |s, d| s.cycle_grace(d) }) },
+    row("切走时自动暂停", Kind::Toggle { get: |s| s.auto_pause, set: |s, v| s.auto_pause = v }),
+    row("参加全球排行榜", Kind::Toggle { get: |s| s.online, set: |s, v| s.online = v }),
+    row("局域网直播", Kind::Toggle { get: |s| s.broadcast, set: |s, v| s.broadcast = v }),
+];
+
+const TABS: [(&str, &[Row]); 5] = [("操作", &CONTROLS), ("声音", &AUDIO), ("画面", &VIDEO), ("无障碍", &A11Y), ("玩法", &GAMEPLAY)];
+
+// 当前设置正好是哪个预设；都不是就算自定义
+fn preset_label(s: &Settings) -> &'static str {
+    PRESETS.iter().find(|p| p.settings(s.clone()) == *s).map_or(tr("自定义"), |p| p.label())
+}
+
+fn cycle_preset(s: &mut Settings, step: i32) {
+    let i = PRESETS.iter().position(|p| p.settings(s.clone()) == *s).map_or(if step > 0 { -1 } else { 0 }, |i| i as i32);
+    let p = PRESETS[(i + step).rem_euclid(PRESETS.len() as i32) as usize];
+    *s = p.settings(s.clone());
+}
+
+#[derive(Default)]
+pub struct Options {
+    tab: usize,
+    cursor: usize,
+    capturing: bool,
+    from_pause: bool,
+    looks: bool, // 从“配色主题”进了换装页，[ESC] 回这里
+    msg: Option<String>,
+    last_mouse: Vec2,
+}
+
+impl Options {
+    pub fn at(tab: usize) -> Self { Self { tab, ..Default::default() } }
+}
+
+fn rows(tab: usize) -> Vec<&'static Row> {
+    TABS[tab].1.iter().filter(|r| (!r.pad || cfg!(feature = "gamepad")) && (!r.speech || cfg!(feature = "tts"))).collect()
+}
+
+// 朗读用：当前页名、选中的行和它的值
+#[cfg(feature = "tts")]
+pub fn focus(game: &Game) -> String {
+    let o = &game.options;
+    let st = &game.settings;
+    let tab = tr(TABS[o.tab].0);
+    let Some(r) = rows(o.tab).get(o.cursor).copied() else { return tab.to_string() };
+    let value = match r.kind {
+        Kind::Toggle { get, .. } => tr(if get(st) { "开" } else { "关" }).to_string(),
+        Kind::Slider { get, .. } => format!("{:.0}%", get(st) * 100.0),
+        Kind::Choice { show, .. } => show(st).to_string(),
+        Kind::Key(a) => st.keys.get(a).label().to_string(),
+        Kind::Open(_) => return trf("{}，{}", &[&tab, &tr(r.name)]),
+    };
+    trf("{}，{}，{}", &[&tab, &tr(r.name), &value])
+}
+
+// 行太多时只画光标附近的一段
+fn first_visible(n: usize, cursor: usize) -> usize {
+    (cursor + 1).saturating_sub(ROWS_VISIBLE).min(n.saturating_sub(ROWS_VISIBLE))
+}
+
+fn tab_rects(game: &Game) -> Vec<Rect> {
+    let w = PANEL_W / TABS.len() as f32;
+    let x0 = (game.field.x - PANEL_W) * 0.5;
+    (0..TABS.len()).map(|i| Rect::new(x0 + i as f32 * w, 110.0, w - 6.0, 34.0)).collect()
+}
+
+fn row_rect(game: &Game, slot: usize) -> Rect {
+    Rect::new((game.field.x - PANEL_W) * 0.5, TOP + slot as f32 * ROW_H, PANEL_W, ROW_H - 4.0)
+}
+
+fn bar_rect(r: Rect) -> Rect { Rect::new(r.right() - BAR_W - 70.0, r.y + 10.0, BAR_W, r.h - 20.0) }
+
+// 主菜单 / 首次启动进来，tab 是默认打开的页
+pub fn enter(game: &mut Game, tab: usize) {
+    game.options = Options::at(tab);
+    game.mode = GameMode::Settings;
+}
+
+pub fn enter_from_pause(game: &mut Game) {
+    game.options = Options { from_pause: true, ..Options::default() };
+    game.mode = GameMode::Settings;
+}
+
+// 进换装页选主题，光标停在当前主题上
+fn open_looks(game: &mut Game) {
+    let current = game.cosmetics.themes.iter().position(|t| t.key == game.theme().key).unwrap_or(0);
+    game.look_cursor = game.cosmetics.skins.len() + current;
+    game.options.looks = true;
+    game.mode = GameMode::Customize;
+}
+
+// 换装页按 [ESC]：从设置进来的回设置
+pub fn back_from_looks(game: &mut Game) -> bool {
+    if !std::mem::take(&mut game.options.looks) { return false; }
+    game.mode = GameMode::Settings;
+    true
+}
+
+fn leave(game: &mut Game) {
+    game.settings.save();
+    game.mode = if game.options.from_pause { GameMode::Paused } else { GameMode::Menu };
+}
+
+// 改完一项：局内锁住的项改回去，其余当场生效并写盘
+fn apply(game: &mut Game, before: &Settings) {
+    if game.options.from_pause {
+        game.settings.speed_scale = before.speed_scale;
+        game.settings.reduced_motion = before.reduced_motion;
+        game.settings.difficulty = before.difficulty;
+    }
+    if game.settings == *before { return; }
+    if game.settings.fullscreen != before.fullscreen { crate::display::set(game, game.settings.fullscreen); }
+    if game.settings.lang != before.lang { crate::i18n::set(game.settings.lang); }
+    if !game.options.from_pause { game.difficulty = game.settings.difficulty; }
+    game.settings.save();
+}
+
+// 对齐到 step 的整数倍，免得 0.1 累加出 0.30000001
+fn snap(v: f32, min: f32, max: f32, step: f32) -> f32 { ((v / step).round() * step).clamp(min, max) }
+
+// [←/→]：滑条和选项调一格，开关按方向开关
+fn adjust(s: &mut Settings, kind: Kind, step: i32) {
+    match kind {
+        Kind::Toggle { set, .. } => set(s, step > 0),
+        Kind::Slider { get, set, min, max, step: d } => set(s, snap(get(s) + step as f32 * d, min, max, d)),
+        Kind::Choice { cycle, .. } => cycle(s, step),
+        Kind::Key(_) | Kind::Open(_) => {}
+    }
+}
+
+// [ENTER] / 点击
+fn activate(game: &mut Game, kind: Kind) {
+    match kind {
+        Kind::Toggle { get, set } => {
+            let v = get(&game.settings);
+            set(&mut game.settings, !v);
+        }
+        Kind::Choice { cycle, .. } => cycle(&mut game.settings, 1),
+        Kind::Key(_) => {
+            game.options.capturing = true;
+            game.options.msg = None;
+        }
+        Kind::Open(open) => open(game),
+        Kind::Slider { .. } => {}
+    }
+}
+
+pub fn update(game: &mut Game) {
+    let list = rows(game.options.tab);
+    let before = game.settings.clone();
+    if game.options.capturing {
+        if is_key_pressed(KeyCode::Escape) { game.options.capturing = false; return; }
+        let Some(k) = keybinds::captured() else { return };
+        game.options.capturing = false;
+        if let Kind::Key(a) = list[game.options.cursor].kind {
+            game.options.msg = keybinds::bind(&mut game.settings.keys, a, 0, k).err();
+        }
+        return apply(game, &before);
+    }
+    if is_key_pressed(KeyCode::Escape) { return leave(game); }
+    // 换页
+    let o = &mut game.options;
+    let mut tab = o.tab;
+    if is_key_pressed(KeyCode::Tab) { tab = (tab + 1) % TABS.len(); }
+    let digits = [KeyCode::Key1, KeyCode::Key2, KeyCode::Key3, KeyCode::Key4, KeyCode::Key5];
+    if let Some(i) = digits.iter().position(|k| is_key_pressed(*k)) { tab = i; }
+    let mouse = mouse_world(game);
+    let clicked = is_mouse_button_pressed(MouseButton::Left);
+    if clicked {
+        if let Some(i) = tab_rects(game).iter().position(|r| r.contains(mouse)) { tab = i; }
+    }
+    let o = &mut game.options;
+    if tab != o.tab {
+        o.tab = tab;
+        o.cursor = 0;
+        o.msg = None;
+        return;
+    }
+    // 选行
+    let n = list.len();
+    if is_key_pressed(KeyCode::Up) { o.cursor = (o.cursor + n - 1) % n; }
+    if is_key_pressed(KeyCode::Down) { o.cursor = (o.cursor + 1) % n; }
+    let first = first_visible(n, o.cursor);
+    let hover = (first..n.min(first + ROWS_VISIBLE)).find(|i| row_rect(game, i - first).contains(mouse));
+    let o = &mut game.options;
+    if mouse != o.last_mouse {
+        o.last_mouse = mouse;
+        if let Some(i) = hover { o.cursor = i; }
+    }
+    let r = list[o.cursor];
+    if r.fixed && o.from_pause { return; }
+    let step = is_key_pressed(KeyCode::Right) as i32 - is_key_pressed(KeyCode::Left) as i32;
+    if step != 0 { adjust(&mut game.settings, r.kind, step); }
+    if is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::Space) { activate(game, r.kind); }
+    if clicked && hover == Some(game.options.cursor) {
+        // 点在滑条上：直接定到那个位置
+        let bar = bar_rect(row_rect(game, game.options.cursor - first));
+        match r.kind {
+            Kind::Slider { set, min, max, step, .. } if bar.contains(mouse) => {
+                let t = (mouse.x - bar.x) / bar.w;
+                set(&mut game.settings, snap(min + t * (max - min), min, max, step));
+            }
+            kind => activate(game, kind),
+        }
+    }
+    apply(game, &before);
+}
+
+fn text(font: &Font, s: &str, x: f32, y: f32, size: f32, color: Color) {
+    draw_text_ex(s, x, y, TextParams { font: Some(font), font_size: size as u16, color, ..Default::default() });
+}
+
+pub fn draw(font: &Font, game: &Game) {
+    let ts = game.settings.text_scale();
+    let o = &game.options;
+    let st = &game.settings;
+    draw_text_center(font, game.field.x, tr("设置"), 80.0, 40.0 * ts, SKYBLUE);
+    for (i, (r, (name, _))) in tab_rects(game).iter().zip(TABS).enumerate() {
+        let (bg, fg) = if i == o.tab { (Color::new(0.95, 0.75, 0.2, 0.9), BLACK) } else { (Color::new(0.1, 0.12, 0.18, 0.8), WHITE) };
+        draw_rectangle(r.x, r.y, r.w, r.h, bg);
+        let name = tr(name);
+        let dim = measure_text(name, Some(font), (20.0 * ts) as u16, 1.0);
+        text(font, name, r.x + (r.w - dim.width) * 0.5, r.y + (r.h + dim.offset_y) * 0.5, 20.0 * ts, fg);
+    }
+    let list = rows(o.tab);
+    let first = first_visible(list.len(), o.cursor);
+    for (slot, (i, r)) in list.iter().enumerate().skip(first).take(ROWS_VISIBLE).enumerate() {
+        let rect = row_rect(game, slot);
+        let selected = i == o.cursor;
+        let locked = r.fixed && o.from_pause;
+        if selected { draw_rectangle(rect.x, rect.y, rect.w, rect.h, Color::new(1.0, 1.0, 1.0, 0.08)); }
+        let color = if locked { DARKGRAY } else if selected { GOLD } else { WHITE };
+        let y = rect.y + rect.h * 0.5 + 7.0 * ts;
+        text(font, &format!("{}{}", if selected { "▶ " } else { "  " }, tr(r.name)), rect.x + 8.0, y, 22.0 * ts, color);
+        let vx = rect.right() - 250.0;
+        match r.kind {
+            Kind::Toggle { get, .. } => {
+                let on = get(st);
+                let pill = Rect::new(rect.right() - 80.0, rect.y + 5.0, 56.0, rect.h - 10.0);
+                draw_rectangle(pill.x, pill.y, pill.w, pill.h, if on { Color::new(0.2, 0.7, 0.3, 0.9) } else { Color::new(0.3, 0.3, 0.35, 0.9) });
+                text(font, tr(if on { "开" } else { "关" }), pill.x + 18.0, y, 20.0 * ts, if locked { GRAY } else { WHITE });
+            }
+            Kind::Slider { get, min, max, .. } => {
+                let bar = bar_rect(rect);
+                let t = ((get(st) - min) / (max - min)).clamp(0.0, 1.0);
+                draw_rectangle(bar.x, bar.y, bar.w, bar.h, Color::new(0.3, 0.3, 0.35, 0.9));
+                draw_rectangle(bar.x, bar.y, bar.w * t, bar.h, if locked { GRAY } else { SKYBLUE });
+                text(font, &format!("{:.0}%", get(st) * 100.0), bar.right() + 12.0, y, 20.0 * ts, color);
+            }
+            Kind::Choice { show, .. } => text(font, &format!("<  {}  >", show(st)), vx, y, 22.0 * ts, color),
+            Kind::Key(a) => {
+                let s = if selected && o.capturing { tr("[ 按键… ]").to_string() } else { format!("[ {} ]", st.keys.get(a).label()) };
+                text(font, &s, vx, y, 22.0 * ts, color);
+            }
+            Kind::Open(_) => text(font, "→", vx, y, 22.0 * ts, color),
+        }
+    }
+    let y = TOP + ROWS_VISIBLE.min(list.len()) as f32 * ROW_H + 24.0;
+    if list.len() > ROWS_VISIBLE { draw_text_center(font, game.field.x, &trf("（{}/{}）", &[&(o.cursor + 1), &list.len()]), y - 8.0, 16.0 * ts, GRAY); }
+    let note = match (&o.msg, o.tab) {
+        (Some(m), _) => Some((m.as_str(), ORANGE)),
+        (None, 1) => Some((tr("游戏暂时还没有声音，音量先记下来"), GRAY)),
+        _ if o.from_pause => Some((tr("局内不能改游戏速度、减少动态效果和难度"), GRAY)),
+        _ => None,
+    };
+    if let Some((m, c)) = note { draw_text_center(font, game.field.x, m, y + 16.0, 18.0 * ts, c); }
+    let hint = tr(if o.capturing { "按下新的按键   [ESC] 取消" } else { "[TAB/1~5] 换页   [↑/↓] 选择   [←/→] 调整   [ENTER] 切换 / 改键   [ESC] 返回" });
+    draw_text_center(font, game.field.x, hint, game.field.y - 40.0, 18.0 * ts, ORANGE);
+}
@@ -0,0 +1,104 @@
+use std::cell::RefCell;
+
+use macroquad::prelude::*;
+
+use crate::shake::Offset;
+use crate::{draw_backdrop, field_camera, postfx, view_size, Game};
+
+// ===== 暂停 / 结算的背景 =====
+// 暂停和游戏结束时场地还定格在后面，文字直接压在满屏障碍上不好认。
+// 先把定格的画面画进一张和窗口一样大的纹理，再用模糊 shader 贴回来，上面压一层暗色，菜单和结算文字画在最上面。
+// shader 编不过（老显卡、没有 GLSL 的后端）或设置里关掉了，就照常画场地、只压一层更深的暗色。
+const DIM_BLUR: f32 = 0.3;
+const DIM_PLAIN: f32 = 0.55;
+const RADIUS: f32 = 2.5; // 模糊半径（采样间隔，屏幕像素）
+
+const BLUR_FRAGMENT: &str = r#"#version 100
+precision lowp float;
+varying vec2 uv;
+varying vec4 color;
+
+uniform sampler2D Texture;
+uniform vec2 Step;
+
+void main() {
+    vec4 sum = vec4(0.0);
+    for (int x = -2; x <= 2; x++) {
+        for (int y = -2; y <= 2; y++) {
+            sum += texture2D(Texture, uv + vec2(float(x), float(y)) * Step);
+        }
+    }
+    gl_FragColor = sum / 25.0 * color;
+}
+"#;
+
+const BLUR_VERTEX: &str = r#"#version 100
+attribute vec3 position;
+attribute vec2 texcoord;
+attribute vec4 color0;
+
+varying lowp vec2 uv;
+varying lowp vec4 color;
+
+uniform mat4 Model;
+uniform mat4 Projection;
+
+void main() {
+    gl_Position = Projection * Model * vec4(position, 1);
+    color = color0 / 255.0;
+    uv = texcoord;
+}
+"#;
+
+pub struct Overlay {
+    blur: Option<Material>,
+    target: RefCell<Option<RenderTarget>>, // 跟着窗口大小重建
+}
+
+impl Overlay {
+    pub fn load() -> Self {
+        let params = MaterialParams { uniforms: vec![UniformDesc::new("Step", UniformType::Float2)], ..Default::default() };
+        let blur = load_material(ShaderSource::Glsl { vertex: BLUR_VERTEX, fragment: BLUR_FRAGMENT }, params)
+            .map_err(|e| crate::logfile::warn(&format!("模糊 shader 不可用，暂停背景只压暗：{:?}", e)))
+            .ok();
+        Self { blur, target: RefCell::new(None) }
+    }
+
+    fn target(&self, w: u32, h: u32) -> RenderTarget {
+        let mut t = self.target.borrow_mut();
+        if !t.as_ref().is_some_and(|rt| rt.texture.width() as u32 == w && rt.texture.height() as u32 == h) {
+            let rt = render_target(w, h);
+            rt.texture.set_filter(FilterMode::Linear);
+            *t = Some(rt);
+        }
+        t.clone().unwrap()
+    }
+
+    // draw_field 画定格的场地（不含背景）；画完相机回到场地相机，调用方接着画菜单文字
+    pub fn frozen(&self, game: &Game, bg: Color, shake: Offset, draw_field: impl FnOnce()) {
+        let view = view_size(game);
+        let blur = self.blur.as_ref().filter(|_| game.settings.blur_overlays);
+        let Some(mat) = blur else {
+            draw_field();
+            draw_rectangle(0.0, 0.0, view.x, view.y, Color::new(0.0, 0.0, 0.0, DIM_PLAIN));
+            return;
+        };
+        // 跟画布一样大：开着渲染比例时按比例缩放后的尺寸
+        let (w, h) = postfx::target().map_or((screen_width().max(1.0) as u32, screen_height().max(1.0) as u32), |c| (c.texture.width() as u32, c.texture.height() as u32));
+        let rt = self.target(w, h);
+        let cam = shake.camera(field_camera(view, Vec2::ZERO));
+        set_camera(&Camera2D { render_target: Some(rt.clone()), ..cam });
+        clear_background(bg);
+        draw_backdrop(game);
+        draw_field();
+
+        set_camera(&postfx::screen_camera());
+        gl_use_material(mat);
+        mat.set_uniform("Step", vec2(RADIUS / w as f32, RADIUS / h as f32));
+        draw_texture_ex(&rt.texture, 0.0, 0.0, WHITE, DrawTextureParams { dest_size: Some(vec2(screen_width(), screen_height())), ..Default::default() });
+        gl_use_default_material();
+
+        set_camera(&shake.camera(field_camera(view, Vec2::ZERO)));
+        draw_rectangle(0.0, 0.0, view.x, view.y, Color::new(0.0, 0.0, 0.0, DIM_BLUR));
+    }
+}
@@ -0,0 +1,107 @@
+use std::cell::RefCell;
+
+use gilrs::ff::{BaseEffect, BaseEffectType, Effect, EffectBuilder, Replay, Ticks};
+use gilrs::{Axis, Button, Gilrs, GamepadId};
+use macroquad::prelude::{get_keys_pressed, KeyCode};
+
+// ===== 手柄（gamepad 特性）=====
+// 用 gilrs 读手柄，把按键和摇杆翻译成键盘按键，经 trace.rs 的输入函数并进键盘输入：
+// 游戏里其余代码（包括菜单导航、键位绑定和输入轨迹）都不用知道手柄的存在。
+// 左摇杆 / 十字键 = 方向键，A 确认，B 返回，Start 暂停；其余见 BUTTONS。
+// 局内移动另外直接读左摇杆的模拟量（stick_x，按设置里的死区和曲线换算），推一半就走一半的速度。
+// 最近一次操作来自手柄时，HUD 上的按键提示换成手柄按键名（见 glyph），护盾被撞、炸弹、死亡时手柄震一下（见 rumble）。
+// Linux 上 gilrs 需要 libudev。
+const DEADZONE: f32 = 0.5;
+
+// 手柄按键 → 键盘按键、提示里显示的名字
+const BUTTONS: [(Button, KeyCode, &str); 12] = [
+    (Button::South, KeyCode::Enter, "A"),
+    (Button::East, KeyCode::Escape, "B"),
+    (Button::West, KeyCode::Space, "X"),
+    (Button::North, KeyCode::B, "Y"),
+    (Button::LeftTrigger, KeyCode::LeftShift, "LB"),
+    (Button::RightTrigger, KeyCode::LeftShift, "RB"),
+    (Button::RightTrigger2, KeyCode::Q, "RT"),
+    (Button::Start, KeyCode::Pause, "Start"),
+    (Button::DPadLeft, KeyCode::Left, "←"),
+    (Button::DPadRight, KeyCode::Right, "→"),
+    (Button::DPadUp, KeyCode::Up, "↑"),
+    (Button::DPadDown, KeyCode::Down, "↓"),
+];
+
+#[derive(Default)]
+struct State {
+    gilrs: Option<Gilrs>,
+    tried: bool,
+    down: Vec<KeyCode>,
+    pressed: Vec<KeyCode>, // 本帧刚按下
+    active: bool,          // 最近一次操作来自手柄
+    stick_x: f32,          // 左摇杆横向推得最远的那个手柄的读数
+    effect: Option<Effect>, // 正在震的效果；drop 掉会立刻停，所以留到下一次震动
+}
+
+thread_local! {
+    static STATE: RefCell<State> = RefCell::new(State::default());
+}
+
+// 每帧开头调用一次：收事件、刷新按键状态
+pub fn poll() {
+    STATE.with_borrow_mut(|s| {
+        if !s.tried {
+            s.tried = true;
+            match Gilrs::new() {
+                Ok(g) => s.gilrs = Some(g),
+                Err(e) => crate::logfile::warn(&format!("手柄不可用：{}", e)),
+            }
+        }
+        let Some(g) = &mut s.gilrs else { return };
+        while g.next_event().is_some() {}
+        let mut down = Vec::new();
+        s.stick_x = 0.0;
+        for (_, pad) in g.gamepads() {
+            for (b, k, _) in BUTTONS {
+                if pad.is_pressed(b) { down.push(k); }
+            }
+            let (x, y) = (pad.value(Axis::LeftStickX), pad.value(Axis::LeftStickY));
+            if x.abs() > s.stick_x.abs() { s.stick_x = x; }
+            if x < -DEADZONE { down.push(KeyCode::Left); }
+            if x > DEADZONE { down.push(KeyCode::Right); }
+            if y > DEADZONE { down.push(KeyCode::Up); }
+            if y < -DEADZONE { down.push(KeyCode::Down); }
+        }
+        s.pressed = down.iter().filter(|k| !s.down.contains(k)).copied().collect();
+        s.down = down;
+        if !s.pressed.is_empty() { s.active = true; }
+        if !get_keys_pressed().is_empty() { s.active = false; }
+    });
+}
+
+pub fn key_down(k: KeyCode) -> bool { STATE.with_borrow(|s| s.down.contains(&k)) }
+pub fn key_pressed(k: KeyCode) -> bool { STATE.with_borrow(|s| s.pressed.contains(&k)) }
+pub fn stick_x() -> f32 { STATE.with_borrow(|s| s.stick_x) }
+
+// 手柄是当前设备时，键盘按键对应的手柄按键名
+pub fn glyph(k: KeyCode) -> Option<&'static str> {
+    if !STATE.with_borrow(|s| s.active) { return None; }
+    BUTTONS.iter().find(|b| b.1 == k).map(|b| b.2)
+}
+
+// 震一下：震屏幅度 shake（0~10）决定力度和时长，strength 是设置里的强度倍率。只在手柄是当前设备时震
+pub fn rumble(shake: f32, strength: f32) {
+    STATE.with_borrow_mut(|s| {
+        if !s.active { return; }
+        let Some(g) = &mut s.gilrs else { return };
+        let ids: Vec<GamepadId> = g.gamepads().filter(|(_, p)| p.is_ff_supported()).map(|(id, _)| id).collect();
+        if ids.is_empty() { return; }
+        let magnitude = ((shake / 10.0).min(1.0) * strength * u16::MAX as f32) as u16;
+        let ms = (80.0 + shake * 20.0) as u32;
+        let effect = EffectBuilder::new()
+            .add_effect(BaseEffect { kind: BaseEffectType::Strong { magnitude }, scheduling: Replay { play_for: Ticks::from_ms(ms), ..Default::default() }, ..Default::default() })
+            .gamepads(&ids)
+            .finish(g);
+        match effect.and_then(|e| e.play().map(|_| e)) {
+            Ok(e) => s.effect = Some(e),
+            Err(e) => crate::logfile::warn(&format!("手柄震动失败：{}", e)),
+        }
+    });
+}
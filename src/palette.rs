@@ -0,0 +1,200 @@
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::settings::ColorVision;
+use crate::PowerUpKind;
+
+// ===== 配色 =====
+// 外观主题（cosmetics.json 的 themes）除了障碍色、背景色和三档危险色，还可以带一段 "palette"，改 HUD、挡板、道具和特效的颜色。
+// 没写的键沿用下面的默认值（也就是原来各处写死的颜色），一个主题只写想改的几项就行，例如
+//   "palette": { "hud": { "danger": [213, 94, 0] }, "powerups": { "slow": [0, 158, 115] } }
+// 颜色写 [r, g, b] 或 [r, g, b, a]。高对比度模式下挡板、障碍和顶栏仍用它自己的纯色；无障碍里选了色觉模式时道具用固定的色盲友好配色。
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Paint { Rgb([u8; 3]), Rgba([u8; 4]) }
+
+impl Paint {
+    pub fn color(self) -> Color {
+        match self {
+            Paint::Rgb([r, g, b]) => Color::from_rgba(r, g, b, 255),
+            Paint::Rgba([r, g, b, a]) => Color::from_rgba(r, g, b, a),
+        }
+    }
+}
+
+impl From<Color> for Paint {
+    fn from(c: Color) -> Self { Paint::Rgba(c.into()) }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Palette {
+    pub hud: HudPalette,
+    pub player: Option<Paint>, // None：角色自己的颜色；皮肤指定了车身色时以皮肤为准
+    pub powerups: PowerUpPalette,
+    pub blast: Paint,          // 炸弹冲击波
+    pub milestone: Paint,      // 里程碑光晕
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self {
+            hud: HudPalette::default(),
+            player: None,
+            powerups: PowerUpPalette::default(),
+            blast: Color::new(1.0, 0.6, 0.15, 1.0).into(),
+            milestone: Color::new(1.0, 0.84, 0.0, 1.0).into(),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HudPalette {
+    pub bar: Paint,    // 顶栏底色
+    pub text: Paint,   // 普通文字（用时）
+    pub score: Paint,
+    pub best: Paint,   // 最高分、金币
+    pub info: Paint,   // 挑战标签、超载、回放中
+    pub warn: Paint,   // 世界事件、自适应、擦边计量条
+    pub danger: Paint, // 陷阱警告
+    pub good: Paint,   // 喘息、禅模式
+    pub lives: Paint,
+    pub combo: Paint,
+}
+
+impl Default for HudPalette {
+    fn default() -> Self {
+        Self {
+            bar: Color::from_rgba(20, 24, 32, 220).into(),
+            text: WHITE.into(),
+            score: YELLOW.into(),
+            best: GOLD.into(),
+            info: SKYBLUE.into(),
+            warn: ORANGE.into(),
+            danger: RED.into(),
+            good: LIME.into(),
+            lives: PINK.into(),
+            combo: crate::COMBO_COLOR.into(),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PowerUpPalette {
+    pub shield: Paint,
+    pub slow: Paint,
+    pub bomb: Paint,
+    pub heart: Paint,
+    pub teleport: Paint,
+    pub ammo: Paint,
+    pub rewind: Paint,
+    pub multiplier: Paint,
+    pub freeze: Paint,
+    // 陷阱默认仿照 减速 / 护盾 / 炸弹，颜色略暗
+    pub reverse: Paint,
+    pub haste: Paint,
+    pub blackout: Paint,
+    pub trap_border: Paint,
+}
+
+impl Default for PowerUpPalette {
+    fn default() -> Self {
+        Self {
+            shield: SKYBLUE.into(),
+            slow: LIME.into(),
+            bomb: ORANGE.into(),
+            heart: PINK.into(),
+            teleport: VIOLET.into(),
+            ammo: YELLOW.into(),
+            rewind: MAGENTA.into(),
+            multiplier: crate::MULT_COLOR.into(),
+            freeze: crate::FREEZE_COLOR.into(),
+            reverse: Color::new(0.5, 0.78, 0.2, 1.0).into(),
+            haste: Color::new(0.3, 0.65, 0.85, 1.0).into(),
+            blackout: Color::new(0.85, 0.5, 0.12, 1.0).into(),
+            trap_border: Color::new(0.75, 0.3, 0.3, 1.0).into(),
+        }
+    }
+}
+
+impl PowerUpPalette {
+    pub fn get(&self, kind: PowerUpKind) -> Color {
+        match kind {
+            PowerUpKind::Shield => self.shield,
+            PowerUpKind::Slow => self.slow,
+            PowerUpKind::Bomb => self.bomb,
+            PowerUpKind::Heart => self.heart,
+            PowerUpKind::Teleport => self.teleport,
+            PowerUpKind::Ammo => self.ammo,
+            PowerUpKind::Rewind => self.rewind,
+            PowerUpKind::Multiplier => self.multiplier,
+            PowerUpKind::Freeze => self.freeze,
+            PowerUpKind::Reverse => self.reverse,
+            PowerUpKind::Haste => self.haste,
+            PowerUpKind::Blackout => self.blackout,
+        }
+        .color()
+    }
+}
+
+// —— 色觉模式的道具配色 ——
+// 选了色觉模式时道具改用下面这几套，主题里写的道具颜色不再生效。
+// 底子是 Okabe-Ito 色盲友好色板，十二种道具分不出十二种色相，所以相近的几种再拉开明暗；真正一眼认出还得靠道具图标。
+const fn paint(r: u8, g: u8, b: u8) -> Paint { Paint::Rgb([r, g, b]) }
+
+// 红绿色弱：靠蓝 / 橙 / 黄和明暗区分，避开红配绿
+const DEUTERANOPIA: PowerUpPalette = PowerUpPalette {
+    shield: paint(0, 114, 178),
+    slow: paint(240, 228, 66),
+    bomb: paint(213, 94, 0),
+    heart: paint(204, 121, 167),
+    teleport: paint(86, 180, 233),
+    ammo: paint(230, 159, 0),
+    rewind: paint(235, 235, 235),
+    multiplier: paint(255, 205, 140),
+    freeze: paint(175, 220, 255),
+    reverse: paint(150, 140, 40),
+    haste: paint(30, 80, 140),
+    blackout: paint(130, 60, 10),
+    trap_border: paint(230, 159, 0),
+};
+
+// 红色弱看红色发暗，红橙系整体提亮
+const PROTANOPIA: PowerUpPalette = PowerUpPalette {
+    bomb: paint(245, 130, 40),
+    heart: paint(225, 160, 205),
+    blackout: paint(160, 90, 30),
+    trap_border: paint(240, 228, 66),
+    ..DEUTERANOPIA
+};
+
+// 蓝黄色弱：靠红 / 青 / 品红和明暗区分，避开蓝配黄
+const TRITANOPIA: PowerUpPalette = PowerUpPalette {
+    shield: paint(0, 170, 190),
+    slow: paint(255, 130, 130),
+    bomb: paint(213, 0, 0),
+    heart: paint(255, 170, 205),
+    teleport: paint(190, 90, 200),
+    ammo: paint(245, 245, 245),
+    rewind: paint(204, 121, 167),
+    multiplier: paint(255, 100, 60),
+    freeze: paint(160, 235, 235),
+    reverse: paint(0, 110, 110),
+    haste: paint(140, 40, 40),
+    blackout: paint(110, 70, 120),
+    trap_border: paint(213, 0, 0),
+};
+
+impl PowerUpPalette {
+    // None：标准色觉，用主题自己的
+    pub fn for_vision(cv: ColorVision) -> Option<&'static PowerUpPalette> {
+        match cv {
+            ColorVision::Normal => None,
+            ColorVision::Deuteranopia => Some(&DEUTERANOPIA),
+            ColorVision::Protanopia => Some(&PROTANOPIA),
+            ColorVision::Tritanopia => Some(&TRITANOPIA),
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use std::cell::RefCell;
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+use macroquad::models::{Mesh, Vertex};
+use macroquad::prelude::*;
+
+use crate::rng::{Rng, Stream};
+
+// ===== 粒子池 =====
+// 容量固定，满了就从最早放进来的那颗开始覆盖，局中不再分配内存。
+// 一次爆发的样子由 Burst 参数表决定：数量、初速度、朝向和扩散角、重力、阻尼、寿命，颜色随寿命从 from 渐变到 to。
+// 更新跟着 Effects::tick 按真实时间走；画的时候所有粒子拼成一个网格，一次提交。
+// 无窗口校验回放时池子关掉（见 Game::reset_round），发射和更新直接返回。
+const CAPACITY: usize = 512;
+
+#[derive(Clone, Copy)]
+pub struct Burst {
+    pub count: usize,
+    pub speed: (f32, f32),
+    pub dir: f32,    // 朝向（弧度，0 向右，y 向下）
+    pub spread: f32, // 扩散角，TAU 为四面八方
+    pub gravity: f32,
+    pub drag: f32, // 速度每秒按 e^(-drag) 衰减
+    pub life: (f32, f32),
+    pub size: f32,
+    pub from: Color,
+    pub to: Color,
+}
+
+impl Burst {
+    // 换个颜色，到寿命尽头淡出
+    pub const fn tint(self, c: Color) -> Self { Self { from: c, to: Color::new(c.r, c.g, c.b, 0.0), ..self } }
+    pub const fn times(self, count: usize) -> Self { Self { count, ..self } }
+}
+
+// 通用火花：四散、很快停住（擦边、瞬移、击碎之类的小反馈）
+pub const SPARK: Burst = Burst {
+    count: 8, speed: (60.0, 260.0), dir: 0.0, spread: TAU, gravity: 0.0, drag: 10.0,
+    life: (0.5, 0.6), size: 4.0, from: WHITE, to: Color::new(1.0, 1.0, 1.0, 0.0),
+};
+// 护盾挡下：蓝白碎光向四周弹开
+pub const SHIELD: Burst = Burst {
+    count: 18, speed: (120.0, 320.0), dir: 0.0, spread: TAU, gravity: 0.0, drag: 7.0,
+    life: (0.35, 0.6), size: 4.0, from: WHITE, to: Color::new(0.4, 0.75, 1.0, 0.0),
+};
+// 炸弹炸掉的障碍：橙色碎块，带重力往下落
+pub const BLAST: Burst = Burst {
+    count: 6, speed: (60.0, 220.0), dir: 0.0, spread: TAU, gravity: 420.0, drag: 4.0,
+    life: (0.5, 0.9), size: 5.0, from: Color::new(1.0, 0.8, 0.3, 1.0), to: Color::new(0.6, 0.1, 0.0, 0.0),
+};
+// 拾取道具：向上飘的一小簇，颜色跟道具走（用 tint）
+pub const PICKUP: Burst = Burst {
+    count: 12, speed: (80.0, 200.0), dir: -FRAC_PI_2, spread: 1.8, gravity: -80.0, drag: 5.0,
+    life: (0.4, 0.8), size: 3.0, from: WHITE, to: Color::new(1.0, 1.0, 1.0, 0.0),
+};
+// 撞死：一大团先黄后红，落下去
+pub const DEATH: Burst = Burst {
+    count: 36, speed: (100.0, 420.0), dir: 0.0, spread: TAU, gravity: 500.0, drag: 3.0,
+    life: (0.7, 1.3), size: 5.0, from: YELLOW, to: Color::new(0.8, 0.05, 0.05, 0.0),
+};
+
+#[derive(Clone, Copy)]
+struct Particle {
+    pos: Vec2,
+    vel: Vec2,
+    life: f32,
+    max_life: f32,
+    gravity: f32,
+    drag: f32,
+    size: f32,
+    from: Color,
+    to: Color,
+}
+
+pub struct Particles {
+    pool: Vec<Particle>,
+    next: usize, // 满了以后下一颗覆盖的位置
+    rng: Rng,
+    enabled: bool,
+    mesh: RefCell<Mesh>, // 绘制用的临时网格，留着复用
+}
+
+impl Particles {
+    pub fn new(seed: u64) -> Self {
+        let mesh = Mesh { vertices: Vec::with_capacity(CAPACITY * 4), indices: Vec::with_capacity(CAPACITY * 6), texture: None };
+        Self { pool: Vec::with_capacity(CAPACITY), next: 0, rng: Rng::stream(seed, Stream::Cosmetic), enabled: true, mesh: RefCell::new(mesh) }
+    }
+
+    // (活着的, 还空着的)
+    pub fn counts(&self) -> (usize, usize) { (self.pool.len(), CAPACITY - self.pool.len()) }
+
+    pub fn disable(&mut self) {
+        self.enabled = false;
+        self.pool.clear();
+    }
+
+    // density：Feel 里的粒子密度倍率
+    pub fn emit(&mut self, at: Vec2, b: &Burst, density: f32) {
+        if !self.enabled { return; }
+        for _ in 0..(b.count as f32 * density) as usize {
+            let a = b.dir + self.rng.range(-0.5, 0.5) * b.spread;
+            let life = self.rng.range(b.life.0, b.life.1);
+            let p = Particle {
+                pos: at,
+                vel: vec2(a.cos(), a.sin()) * self.rng.range(b.speed.0, b.speed.1),
+                life,
+                max_life: life,
+                gravity: b.gravity,
+                drag: b.drag,
+                size: b.size,
+                from: b.from,
+                to: b.to,
+            };
+            if self.pool.len() < CAPACITY {
+                self.pool.push(p);
+            } else {
+                self.pool[self.next] = p;
+                self.next = (self.next + 1) % CAPACITY;
+            }
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        if self.pool.is_empty() { return; }
+        for p in &mut self.pool {
+            p.vel.y += p.gravity * dt;
+            p.vel *= (-p.drag * dt).exp();
+            p.pos += p.vel * dt;
+            p.life -= dt;
+        }
+        self.pool.retain(|p| p.life > 0.0);
+        if self.next >= self.pool.len() { self.next = 0; }
+    }
+
+    pub fn draw(&self) {
+        if self.pool.is_empty() { return; }
+        let mut mesh = self.mesh.borrow_mut();
+        mesh.vertices.clear();
+        mesh.indices.clear();
+        for p in &self.pool {
+            let t = 1.0 - (p.life / p.max_life).clamp(0.0, 1.0);
+            let c = Color::new(
+                p.from.r + (p.to.r - p.from.r) * t,
+                p.from.g + (p.to.g - p.from.g) * t,
+                p.from.b + (p.to.b - p.from.b) * t,
+                p.from.a + (p.to.a - p.from.a) * t,
+            );
+            let h = p.size * 0.5;
+            let i = mesh.vertices.len() as u16;
+            for (dx, dy) in [(-h, -h), (h, -h), (h, h), (-h, h)] {
+                mesh.vertices.push(Vertex::new(p.pos.x + dx, p.pos.y + dy, 0.0, 0.0, 0.0, c));
+            }
+            mesh.indices.extend_from_slice(&[i, i + 1, i + 2, i, i + 2, i + 3]);
+        }
+        draw_mesh(&mesh);
+    }
+}
@@ -0,0 +1,103 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use crate::{logfile, storage};
+
+// ===== 玩家数据目录 =====
+// 存档、挂起、历史、回放、日志等都放在按用户的数据目录里（不随启动目录变化）：
+// Windows 为 %APPDATA%/dodge-rush，Linux 为 ~/.local/share/dodge-rush，macOS 为 ~/Library/Application Support/dodge-rush。
+// 安卓为应用私有的内部存储 /data/data/<包名>/files，别的应用读不到，卸载时一起删掉。
+// --save-dir 可以指定别的目录。assets/ 下的配置仍按当前目录读取。
+#[cfg(not(target_os = "android"))]
+const APP_DIR: &str = "dodge-rush";
+// 旧版本写在当前目录的文件，首次启动时搬过去（日志排第一，后面的迁移记录才会写进新位置）
+const LEGACY: [&str; 5] = ["dodge.log", "save.json", "suspend.json", "history.jsonl", "replays"];
+
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+// 启动时调用一次；没调用（比如测试）时一切按当前目录
+pub fn init(over: Option<PathBuf>) {
+    let dir = over.or_else(default_dir).unwrap_or_default();
+    if !dir.as_os_str().is_empty() {
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            // 先定下退回的当前目录，日志才有地方写
+            let _ = DATA_DIR.set(PathBuf::new());
+            logfile::warn(&format!("无法创建数据目录 {}：{}，改用当前目录", dir.display(), e));
+            return;
+        }
+    }
+    let _ = DATA_DIR.set(dir.clone());
+    migrate(&dir);
+}
+
+#[cfg(not(target_os = "android"))]
+fn default_dir() -> Option<PathBuf> { directories::BaseDirs::new().map(|b| b.data_dir().join(APP_DIR)) }
+
+// 安卓上没有 HOME 这类约定，directories 找不到目录；包名从进程的命令行读（安卓进程名就是包名）
+#[cfg(target_os = "android")]
+fn default_dir() -> Option<PathBuf> {
+    let cmdline = std::fs::read("/proc/self/cmdline").ok()?;
+    let package = String::from_utf8_lossy(cmdline.split(|b| *b == 0).next()?).into_owned();
+    (!package.is_empty()).then(|| PathBuf::from("/data/data").join(package).join("files"))
+}
+
+// 重放输入轨迹时用的临时目录：不迁移当前目录里的旧文件
+pub fn init_scratch(dir: PathBuf) {
+    let _ = DATA_DIR.set(dir);
+}
+
+pub fn data(name: &str) -> PathBuf {
+    DATA_DIR.get().map_or_else(|| PathBuf::from(name), |d| d.join(name))
+}
+
+// —— 玩家档案 ——
+// 存档、挂起、历史、回放按档案分开放在数据目录下的子目录里；默认档案的子目录为空，即数据目录本身（兼容旧存档）。
+static PROFILE_DIR: Mutex<String> = Mutex::new(String::new());
+
+pub fn set_profile(sub: &str) {
+    if let Ok(mut p) = PROFILE_DIR.lock() { *p = sub.to_string(); }
+}
+
+pub fn profile(name: &str) -> PathBuf {
+    let sub = PROFILE_DIR.lock().map(|p| p.clone()).unwrap_or_default();
+    if sub.is_empty() { data(name) } else { data(&sub).join(name) }
+}
+
+// 目标目录里还没有的才搬；跨分区改名失败时复制文件（目录就留在原处）
+fn migrate(dir: &Path) {
+    if dir.as_os_str().is_empty() || std::fs::canonicalize(dir).ok() == std::env::current_dir().ok() { return; }
+    for name in LEGACY {
+        let (from, to) = (Path::new(name), dir.join(name));
+        if !from.exists() || to.exists() { continue; }
+        let moved = std::fs::rename(from, &to).is_ok() || (from.is_file() && std::fs::copy(from, &to).is_ok());
+        if moved {
+            logfile::warn(&format!("已把 {} 迁移到 {}", name, to.display()));
+        } else {
+            logfile::warn(&format!("迁移 {} 到 {} 失败", name, to.display()));
+        }
+    }
+}
+
+// —— 崩溃安全的写入 ——
+// 先写同目录的临时文件并落盘（见 storage.rs），再把旧文件改名为 .bak、临时文件改名为正式文件。
+// 任何时刻断电，正式文件和 .bak 至少有一个是完整的。
+pub fn backup_of(path: &Path) -> PathBuf { suffixed(path, "bak") }
+
+fn suffixed(path: &Path, ext: &str) -> PathBuf {
+    let mut s = path.as_os_str().to_owned();
+    s.push(".");
+    s.push(ext);
+    PathBuf::from(s)
+}
+
+pub fn write_atomic(path: &Path, text: &str) -> std::io::Result<()> {
+    let tmp = suffixed(path, "tmp");
+    storage::write(&tmp, text)?;
+    if storage::exists(path) { storage::rename(path, backup_of(path))?; }
+    storage::rename(&tmp, path)
+}
+
+// 读不出来的文件留一份 .corrupt 以便排查（只留最近一份：下次再读坏时覆盖）
+pub fn keep_corrupt(path: &Path) {
+    if let Ok(bytes) = storage::read(path) { let _ = storage::write(suffixed(path, "corrupt"), bytes); }
+}
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{PLAYER_ACC, PLAYER_DECAY, PLAYER_SPEED_MAX};
+
+// ===== 玩家手感参数（assets/physics.json，可由沙盒导出）=====
+pub const PHYSICS_PATH: &str = "assets/physics.json";
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Physics {
+    pub acc: f32,
+    pub decay: f32,
+    pub speed_max: f32,
+    pub dash_speed: f32,    // 冲刺瞬间速度
+    pub dash_time: f32,     // 冲刺持续时间
+    pub dash_cooldown: f32, // 冲刺冷却
+}
+
+impl Default for Physics {
+    fn default() -> Self {
+        Self {
+            acc: PLAYER_ACC,
+            decay: PLAYER_DECAY,
+            speed_max: PLAYER_SPEED_MAX,
+            dash_speed: 1100.0,
+            dash_time: 0.12,
+            dash_cooldown: 1.2,
+        }
+    }
+}
+
+impl Physics {
+    pub fn load(path: &str) -> Self {
+        crate::storage::read_asset_string(path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+    pub fn export(&self, path: &str) -> std::io::Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self).unwrap())
+    }
+}
+
+// —— 沙盒滑条：名称、取值范围、读写 ——
+pub struct Param {
+    pub name: &'static str,
+    pub min: f32,
+    pub max: f32,
+    pub get: fn(&Physics) -> f32,
+    pub set: fn(&mut Physics, f32),
+}
+
+pub const PARAMS: [Param; 6] = [
+    Param { name: "加速度", min: 400.0, max: 6000.0, get: |p| p.acc, set: |p, v| p.acc = v },
+    Param { name: "松手衰减", min: 0.0, max: 0.01, get: |p| p.decay, set: |p, v| p.decay = v },
+    Param { name: "最高速度", min: 150.0, max: 1200.0, get: |p| p.speed_max, set: |p, v| p.speed_max = v },
+    Param { name: "冲刺速度", min: 300.0, max: 2400.0, get: |p| p.dash_speed, set: |p, v| p.dash_speed = v },
+    Param { name: "冲刺时长", min: 0.02, max: 0.5, get: |p| p.dash_time, set: |p, v| p.dash_time = v },
+    Param { name: "冲刺冷却", min: 0.0, max: 4.0, get: |p| p.dash_cooldown, set: |p, v| p.dash_cooldown = v },
+];
@@ -0,0 +1,132 @@
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+use macroquad::audio::{load_sound_from_bytes, play_sound, set_sound_volume, stop_sound, PlaySoundParams, Sound};
+
+use crate::paths;
+use crate::rng::{Rng, Stream};
+
+// ===== 自带音乐 =====
+// 把 OGG / WAV 放进 assets/music/ 或用户数据目录的 music/，就用它们代替内置音乐：开局洗一次牌，按顺序放，放完再洗。
+// 换歌时底部提示条显示曲名（文件名）；[F7] 下一首，[F8] 音乐静音（记在设置里）。
+// 声音后端报不出播放进度，曲长从文件头自己算（WAV 看 data 块，OGG 看最后一页的采样位置）；算不出来的那首就一直循环，等按 [F7]。
+// 一次只解码当前这一首，列表再长也不占内存。
+const DIR: &str = "music";
+const ASSET_DIR: &str = "assets/music";
+const EXTENSIONS: [&str; 2] = ["ogg", "wav"];
+
+struct Track {
+    sound: Sound,
+    left: Option<f32>, // 还剩几秒；None：不知道曲长，循环到手动切歌
+    applied: f32,      // 上次设下去的音量
+}
+
+pub struct Playlist {
+    files: Vec<PathBuf>,
+    order: Vec<usize>,
+    at: usize,
+    rng: Rng,
+    current: Option<Track>,
+}
+
+fn scan(dir: &Path, out: &mut Vec<PathBuf>) {
+    let found = crate::storage::list_assets(dir).into_iter()
+        .filter(|p| p.extension().and_then(|e| e.to_str()).is_some_and(|e| EXTENSIONS.contains(&e.to_ascii_lowercase().as_str())));
+    out.extend(found);
+}
+
+// 在原生平台上解码是同步完成的，轮询一次就拿到结果；拿不到（网页版要等下一帧）就当这首放不了
+fn decode(bytes: &[u8]) -> Option<Sound> {
+    match pin!(load_sound_from_bytes(bytes)).as_mut().poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(r) => r.ok(),
+        Poll::Pending => None,
+    }
+}
+
+// WAV：fmt 块里的每秒字节数 + data 块的长度
+fn wav_secs(b: &[u8]) -> Option<f32> {
+    if b.len() < 12 || &b[0..4] != b"RIFF" || &b[8..12] != b"WAVE" { return None; }
+    let (mut i, mut rate) = (12, None);
+    while i + 8 <= b.len() {
+        let len = u32::from_le_bytes(b[i + 4..i + 8].try_into().ok()?) as usize;
+        match &b[i..i + 4] {
+            b"fmt " if i + 20 <= b.len() => rate = Some(u32::from_le_bytes(b[i + 16..i + 20].try_into().ok()?)),
+            b"data" => return rate.filter(|r| *r > 0).map(|r| len as f32 / r as f32),
+            _ => {}
+        }
+        i += 8 + len + (len & 1);
+    }
+    None
+}
+
+// OGG Vorbis：识别头里的采样率 + 最后一页的采样位置
+fn ogg_secs(b: &[u8]) -> Option<f32> {
+    let head = b.windows(7).position(|w| w == b"\x01vorbis")?;
+    let rate = u32::from_le_bytes(b.get(head + 12..head + 16)?.try_into().ok()?);
+    let last = b.windows(4).rposition(|w| w == b"OggS")?;
+    let granule = u64::from_le_bytes(b.get(last + 6..last + 14)?.try_into().ok()?);
+    (rate > 0).then(|| granule as f32 / rate as f32)
+}
+
+fn title(path: &Path) -> String {
+    path.file_stem().map_or_else(|| path.display().to_string(), |s| s.to_string_lossy().into_owned())
+}
+
+impl Playlist {
+    pub fn load() -> Self {
+        let mut files = Vec::new();
+        scan(Path::new(ASSET_DIR), &mut files);
+        scan(&paths::data(DIR), &mut files);
+        let seed = (macroquad::miniquad::date::now() * 1000.0) as u64;
+        let mut list = Self { order: Vec::new(), at: 0, rng: Rng::stream(seed, Stream::Cosmetic), current: None, files };
+        list.shuffle();
+        list
+    }
+
+    pub fn is_empty(&self) -> bool { self.files.is_empty() }
+
+    fn shuffle(&mut self) {
+        self.order = (0..self.files.len()).collect();
+        for i in (1..self.order.len()).rev() {
+            let j = self.rng.index(i + 1);
+            self.order.swap(i, j);
+        }
+        self.at = 0;
+    }
+
+    // 换到下一首（第一次调用时是第一首）；返回曲名给提示条。放不了的文件跳过，一整轮都放不了就停
+    pub fn next(&mut self, volume: f32) -> Option<String> {
+        if let Some(t) = self.current.take() { stop_sound(&t.sound); }
+        for _ in 0..self.files.len() {
+            if self.at >= self.order.len() { self.shuffle(); }
+            let path = &self.files[self.order[self.at]];
+            self.at += 1;
+            let Ok(bytes) = crate::storage::read_asset(path) else { continue };
+            let Some(sound) = decode(&bytes) else {
+                crate::logfile::warn(&format!("音乐文件 {} 读不出来，跳过", path.display()));
+                continue;
+            };
+            let left = wav_secs(&bytes).or_else(|| ogg_secs(&bytes));
+            play_sound(&sound, PlaySoundParams { looped: left.is_none(), volume });
+            let name = title(path);
+            self.current = Some(Track { sound, left, applied: volume });
+            return Some(name);
+        }
+        None
+    }
+
+    // 每帧调用：跟着音量走；这首放完了就换下一首，返回新曲名
+    pub fn tick(&mut self, volume: f32, dt: f32) -> Option<String> {
+        let Some(t) = self.current.as_mut() else { return self.next(volume) };
+        if t.applied != volume {
+            set_sound_volume(&t.sound, volume);
+            t.applied = volume;
+        }
+        let left = t.left.as_mut()?;
+        *left -= dt;
+        if *left > 0.0 { return None; }
+        self.next(volume)
+    }
+}
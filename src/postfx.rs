@@ -0,0 +1,151 @@
+use std::cell::RefCell;
+
+use macroquad::prelude::*;
+
+use crate::Game;
+
+// ===== 后期处理与渲染分辨率 =====
+// 设置 → 画面里四个开关，默认全关：CRT 扫描线、暗角、震屏时的色差、亮处泛光（道具、金色字这些亮色会晕开）。
+// 另有渲染比例（50%–200%）：弱显卡调低省填充率，高分屏调高更细腻；只改画面的像素数，场地和模拟的逻辑尺寸不变。
+// 开着任何效果或比例不是 100% 时，draw_game 整帧先画进一张“窗口 × 比例”大小的纹理（field_camera / screen_camera 自动指向它，
+// 相机只管归一化坐标，纹理多大都对得上），画完再拉伸贴回屏幕：有效果就过一遍 shader，没有就直接贴，放大时按设置取平滑或像素风。
+// 触屏按钮和底部提示条在这之后画，不受影响。shader 编不过时效果当没开，渲染比例照样生效。
+pub const SCALE_MIN: f32 = 0.5;
+pub const SCALE_MAX: f32 = 2.0;
+const TARGET_MAX: f32 = 8192.0; // 显卡支持的纹理边长一般到这里
+const FRAGMENT: &str = r#"#version 100
+precision mediump float;
+varying vec2 uv;
+varying vec4 color;
+
+uniform sampler2D Texture;
+uniform vec2 Texel;
+uniform float Scanlines;
+uniform float Vignette;
+uniform float Aberration;
+uniform float Bloom;
+
+void main() {
+    vec2 shift = vec2(Aberration * 4.0 * Texel.x, 0.0);
+    vec3 col = vec3(
+        texture2D(Texture, uv + shift).r,
+        texture2D(Texture, uv).g,
+        texture2D(Texture, uv - shift).b
+    );
+    if (Bloom > 0.0) {
+        vec3 glow = vec3(0.0);
+        for (int i = 0; i < 12; i++) {
+            float a = float(i) * 0.5236;
+            for (int r = 1; r <= 2; r++) {
+                vec3 s = texture2D(Texture, uv + vec2(cos(a), sin(a)) * Texel * 5.0 * float(r)).rgb;
+                glow += max(s - vec3(0.6), vec3(0.0));
+            }
+        }
+        col += glow / 24.0 * 2.5 * Bloom;
+    }
+    if (Scanlines > 0.0) {
+        float line = 0.5 + 0.5 * sin(uv.y / Texel.y * 3.14159);
+        col *= 1.0 - 0.22 * Scanlines * line;
+    }
+    if (Vignette > 0.0) {
+        float d = distance(uv, vec2(0.5));
+        col *= 1.0 - Vignette * 0.65 * smoothstep(0.4, 0.85, d);
+    }
+    gl_FragColor = vec4(col, 1.0) * color;
+}
+"#;
+
+const VERTEX: &str = r#"#version 100
+attribute vec3 position;
+attribute vec2 texcoord;
+attribute vec4 color0;
+
+varying lowp vec2 uv;
+varying lowp vec4 color;
+
+uniform mat4 Model;
+uniform mat4 Projection;
+
+void main() {
+    gl_Position = Projection * Model * vec4(position, 1);
+    color = color0 / 255.0;
+    uv = texcoord;
+}
+"#;
+
+thread_local! {
+    // 这一帧画进去的纹理；None 时直接画屏幕
+    static TARGET: RefCell<Option<RenderTarget>> = const { RefCell::new(None) };
+}
+
+pub struct PostFx {
+    material: Option<Material>,
+    target: RefCell<Option<RenderTarget>>, // 跟着窗口大小重建
+}
+
+fn effects(game: &Game) -> bool {
+    let s = &game.settings;
+    s.fx_crt || s.fx_vignette || s.fx_aberration || s.fx_bloom
+}
+
+fn scale(game: &Game) -> f32 { game.settings.render_scale.clamp(SCALE_MIN, SCALE_MAX) }
+
+// 当前帧的画布：开着后期处理时是那张纹理
+pub fn target() -> Option<RenderTarget> { TARGET.with_borrow(|t| t.clone()) }
+
+// 窗口像素坐标、y 向下的相机（代替 set_default_camera，开着后期处理时也画进纹理）
+pub fn screen_camera() -> Camera2D {
+    let (w, h) = (screen_width(), screen_height());
+    Camera2D { target: vec2(w, h) * 0.5, zoom: vec2(2.0 / w, 2.0 / h), render_target: target(), ..Default::default() }
+}
+
+impl PostFx {
+    pub fn load() -> Self {
+        let uniforms = ["Texel"].into_iter().map(|n| UniformDesc::new(n, UniformType::Float2))
+            .chain(["Scanlines", "Vignette", "Aberration", "Bloom"].into_iter().map(|n| UniformDesc::new(n, UniformType::Float1)))
+            .collect();
+        let material = load_material(ShaderSource::Glsl { vertex: VERTEX, fragment: FRAGMENT }, MaterialParams { uniforms, ..Default::default() })
+            .map_err(|e| crate::logfile::warn(&format!("后期处理 shader 不可用，已停用：{:?}", e)))
+            .ok();
+        Self { material, target: RefCell::new(None) }
+    }
+
+    fn shading(&self, game: &Game) -> bool { self.material.is_some() && effects(game) }
+
+    // draw_game 之前调用：需要的话把这一帧的画布换成纹理
+    pub fn begin(&self, game: &Game) {
+        let k = scale(game);
+        if !self.shading(game) && k == 1.0 { return; }
+        let size = |v: f32| (v * k).round().clamp(1.0, TARGET_MAX) as u32;
+        let (w, h) = (size(screen_width()), size(screen_height()));
+        let mut t = self.target.borrow_mut();
+        if !t.as_ref().is_some_and(|rt| rt.texture.width() as u32 == w && rt.texture.height() as u32 == h) {
+            *t = Some(render_target(w, h));
+        }
+        if let Some(rt) = t.as_ref() {
+            rt.texture.set_filter(if game.settings.render_smooth { FilterMode::Linear } else { FilterMode::Nearest });
+        }
+        TARGET.set(t.clone());
+    }
+
+    // draw_game 之后调用：带着效果贴回屏幕
+    pub fn end(&self, game: &Game) {
+        let Some(rt) = TARGET.take() else { return };
+        let params = DrawTextureParams { dest_size: Some(vec2(screen_width(), screen_height())), ..Default::default() };
+        set_default_camera();
+        let Some(mat) = self.material.as_ref().filter(|_| effects(game)) else {
+            return draw_texture_ex(&rt.texture, 0.0, 0.0, WHITE, params);
+        };
+        let s = &game.settings;
+        let on = |b: bool| if b { 1.0f32 } else { 0.0 };
+        let shake = (game.shake.amount() * game.feel_now().shake * game.settings.shake_intensity).clamp(0.0, 1.0);
+        gl_use_material(mat);
+        mat.set_uniform("Texel", vec2(1.0 / rt.texture.width(), 1.0 / rt.texture.height()));
+        mat.set_uniform("Scanlines", on(s.fx_crt));
+        mat.set_uniform("Vignette", on(s.fx_vignette));
+        mat.set_uniform("Aberration", on(s.fx_aberration) * shake);
+        mat.set_uniform("Bloom", on(s.fx_bloom));
+        draw_texture_ex(&rt.texture, 0.0, 0.0, WHITE, params);
+        gl_use_default_material();
+    }
+}
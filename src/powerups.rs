@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+
+use crate::difficulty::Difficulty;
+use crate::PowerUpKind;
+
+// ===== 道具调参（assets/powerups.json）=====
+// 重复拾取规则与掉落表；文件缺失或字段缺省时沿用原来的行为。
+pub const POWERUPS_PATH: &str = "assets/powerups.json";
+pub const SHIELD_MAX: u32 = 3; // 护盾默认上限
+
+// 重复拾取的规则
+//   计时类（减速 / 倍率 / 冻结）：
+//     refresh           计时重置为满
+//     extend            剩余时间累加，最多 cap 秒
+//     stack             层数 +1（最多 max 层）并重置计时，每多一层效果按 falloff 递减
+//     convert_to_score  已在生效时不再延长，直接换成 points 分
+//   计数类（护盾）：stack 的 max 为上限；convert_to_score 在已满时换成分数；其它规则等同默认上限
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize, Debug)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum Stacking {
+    Refresh,
+    Extend { cap: f32 },
+    Stack { max: u32, falloff: f32 },
+    ConvertToScore { points: i32 },
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StackingRules {
+    pub slow: Stacking,
+    pub multiplier: Stacking,
+    pub freeze: Stacking,
+    pub shield: Stacking,
+}
+
+impl Default for StackingRules {
+    fn default() -> Self {
+        Self {
+            slow: Stacking::Refresh,
+            multiplier: Stacking::Refresh,
+            freeze: Stacking::Refresh,
+            shield: Stacking::Stack { max: SHIELD_MAX, falloff: 1.0 },
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct PowerUpTuning {
+    pub stacking: StackingRules,
+    pub spawn: SpawnTables,
+}
+
+impl PowerUpTuning {
+    pub fn load(path: &str) -> Self {
+        crate::storage::read_asset_string(path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+}
+
+// —— 计时类效果槽 ——
+#[derive(Clone, Copy, Serialize, Deserialize, Default)]
+pub struct EffectSlot {
+    pub remaining: f32,
+    pub stacks: u32,
+}
+
+impl EffectSlot {
+    pub fn active(&self) -> bool { self.remaining > 0.0 }
+    pub fn tick(&mut self, dt: f32) {
+        self.remaining = (self.remaining - dt).max(0.0);
+        if self.remaining <= 0.0 { self.stacks = 0; }
+    }
+    pub fn clear(&mut self) { *self = Self::default(); }
+
+    // 按规则吃下一次拾取，返回换成的分数
+    pub fn apply(&mut self, rule: Stacking, duration: f32) -> i32 {
+        match rule {
+            Stacking::Refresh => { self.remaining = duration; self.stacks = 1; }
+            Stacking::Extend { cap } => { self.remaining = (self.remaining + duration).min(cap.max(duration)); self.stacks = 1; }
+            Stacking::Stack { max, .. } => { self.remaining = duration; self.stacks = (self.stacks + 1).min(max.max(1)); }
+            Stacking::ConvertToScore { points } => {
+                if self.active() { return points; }
+                self.remaining = duration;
+                self.stacks = 1;
+            }
+        }
+        0
+    }
+
+    // 叠层后的总强度（以一层为 1）：1 + falloff + falloff² + …
+    pub fn strength(&self, rule: Stacking) -> f32 {
+        if !self.active() { return 0.0; }
+        let falloff = match rule { Stacking::Stack { falloff, .. } => falloff, _ => 0.0 };
+        (0..self.stacks).map(|i| falloff.powi(i as i32)).sum()
+    }
+}
+
+// 计数类（护盾）：返回 (新的数量, 换成的分数)
+pub fn add_count(count: u32, rule: Stacking, default_max: u32) -> (u32, i32) {
+    match rule {
+        Stacking::Stack { max, .. } => ((count + 1).min(max), 0),
+        Stacking::ConvertToScore { points } if count >= default_max => (count, points),
+        _ => ((count + 1).min(default_max), 0),
+    }
+}
+
+// ===== 道具掉落表 =====
+// 按难度分表，每张表再按本局时间分段：取 from 不超过当前时间的最后一段。
+// 陷阱与弹药另外判定（见 random_powerup），这里只管普通道具。
+// 连续 pity_time 秒没出过防御类道具（护盾 / 红心 / 炸弹）时，立刻按 pity 表补一个。
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Bracket {
+    pub from: f32,                       // 本段起始时间（秒）
+    pub weights: Vec<(PowerUpKind, u32)>, // (道具, 权重)
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpawnTables {
+    pub easy: Vec<Bracket>,
+    pub normal: Vec<Bracket>,
+    pub hard: Vec<Bracket>,
+    pub pity_time: f32,
+    pub pity: Vec<(PowerUpKind, u32)>,
+}
+
+// 缺省时三档难度共用原来的固定比例
+fn classic_table() -> Vec<Bracket> {
+    use PowerUpKind::*;
+    vec![Bracket {
+        from: 0.0,
+        weights: vec![(Shield, 12), (Slow, 11), (Bomb, 9), (Teleport, 4), (Rewind, 2), (Heart, 2), (Multiplier, 2), (Freeze, 2)],
+    }]
+}
+
+impl Default for SpawnTables {
+    fn default() -> Self {
+        Self {
+            easy: classic_table(),
+            normal: classic_table(),
+            hard: classic_table(),
+            pity_time: 25.0,
+            pity: vec![(PowerUpKind::Shield, 1)],
+        }
+    }
+}
+
+impl SpawnTables {
+    pub fn weights(&self, difficulty: Difficulty, t: f32) -> &[(PowerUpKind, u32)] {
+        let table = match difficulty {
+            Difficulty::Easy => &self.easy,
+            Difficulty::Normal => &self.normal,
+            Difficulty::Hard => &self.hard,
+        };
+        table.iter().rev().find(|b| b.from <= t).or(table.first()).map_or(&[], |b| &b.weights)
+    }
+}
+
+pub fn total_weight(weights: &[(PowerUpKind, u32)]) -> u32 { weights.iter().map(|w| w.1).sum() }
+
+// roll 为 0 ~ total_weight-1 的随机整数；表为空时给护盾
+pub fn pick(weights: &[(PowerUpKind, u32)], mut roll: u32) -> PowerUpKind {
+    for &(kind, w) in weights {
+        if roll < w { return kind; }
+        roll -= w;
+    }
+    PowerUpKind::Shield
+}
@@ -0,0 +1,101 @@
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{tr, trf};
+use crate::trace::{is_key_pressed, is_mouse_button_down, is_mouse_button_pressed, mouse_position};
+use crate::{field_camera, Game, OB_MAX_SIZE, OB_MIN_SIZE, PU_SPAWN_INTERVAL};
+
+// ===== 练习模式：实时调整生成参数，可选无敌 =====
+// 参数随存档保存，下次练习沿用。
+const PANEL_X: f32 = 16.0;
+const PANEL_Y: f32 = 60.0;
+const SLIDER_X: f32 = 120.0;
+const SLIDER_W: f32 = 160.0;
+const SLIDER_GAP: f32 = 26.0;
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Practice {
+    pub fall_speed: f32,
+    pub spawn_interval: f32,
+    pub size_min: f32,
+    pub size_max: f32,
+    pub pu_interval: f32, // 道具生成间隔（秒）
+    pub invincible: bool,
+}
+
+impl Default for Practice {
+    fn default() -> Self {
+        Self { fall_speed: 240.0, spawn_interval: 0.6, size_min: OB_MIN_SIZE, size_max: OB_MAX_SIZE, pu_interval: PU_SPAWN_INTERVAL, invincible: true }
+    }
+}
+
+pub struct Knob {
+    pub name: &'static str,
+    pub min: f32,
+    pub max: f32,
+    pub get: fn(&Practice) -> f32,
+    pub set: fn(&mut Practice, f32),
+}
+
+pub const KNOBS: [Knob; 5] = [
+    Knob { name: "下落速度", min: 60.0, max: 900.0, get: |p| p.fall_speed, set: |p, v| p.fall_speed = v },
+    Knob { name: "生成间隔", min: 0.1, max: 2.0, get: |p| p.spawn_interval, set: |p, v| p.spawn_interval = v },
+    Knob { name: "最小尺寸", min: 10.0, max: 160.0, get: |p| p.size_min, set: |p, v| p.size_min = v.min(p.size_max) },
+    Knob { name: "最大尺寸", min: 10.0, max: 160.0, get: |p| p.size_max, set: |p, v| p.size_max = v.max(p.size_min) },
+    Knob { name: "道具间隔", min: 1.0, max: 20.0, get: |p| p.pu_interval, set: |p, v| p.pu_interval = v },
+];
+
+pub struct PracticePanel {
+    pub hidden: bool,
+    dragging: Option<usize>,
+}
+
+impl PracticePanel {
+    pub fn new() -> Self { Self { hidden: false, dragging: None } }
+}
+
+fn slider_rect(i: usize) -> Rect { Rect::new(SLIDER_X, PANEL_Y + 14.0 + i as f32 * SLIDER_GAP, SLIDER_W, 10.0) }
+
+// 逐帧：鼠标拖动滑条，[I] 切换无敌，[H] 收起面板
+pub fn ui(game: &mut Game) {
+    if is_key_pressed(KeyCode::H) { game.practice_panel.hidden = !game.practice_panel.hidden; }
+    if is_key_pressed(KeyCode::I) { game.practice.invincible = !game.practice.invincible; }
+    if game.practice_panel.hidden { game.practice_panel.dragging = None; return; }
+    let mouse = field_camera(game.field, Vec2::ZERO).screen_to_world(mouse_position().into());
+    if is_mouse_button_pressed(MouseButton::Left) {
+        game.practice_panel.dragging = (0..KNOBS.len()).find(|&i| {
+            let r = slider_rect(i);
+            Rect::new(r.x - 6.0, r.y - 6.0, r.w + 12.0, r.h + 12.0).contains(mouse)
+        });
+    }
+    if !is_mouse_button_down(MouseButton::Left) { game.practice_panel.dragging = None; }
+    if let Some(i) = game.practice_panel.dragging {
+        let k = &KNOBS[i];
+        let t = ((mouse.x - SLIDER_X) / SLIDER_W).clamp(0.0, 1.0);
+        (k.set)(&mut game.practice, k.min + (k.max - k.min) * t);
+    }
+}
+
+pub fn draw(game: &Game, font: &Font) {
+    let label = |color| TextParams { font: Some(font), font_size: 16, color, ..Default::default() };
+    if game.practice_panel.hidden {
+        draw_text_ex(tr("练习 [H] 展开面板"), PANEL_X, PANEL_Y + 14.0, label(GRAY));
+        return;
+    }
+    let h = KNOBS.len() as f32 * SLIDER_GAP + 44.0;
+    draw_rectangle(PANEL_X - 6.0, PANEL_Y - 6.0, SLIDER_X + SLIDER_W + 70.0, h, Color::new(0.0, 0.0, 0.0, 0.55));
+    for (i, k) in KNOBS.iter().enumerate() {
+        let r = slider_rect(i);
+        let v = (k.get)(&game.practice);
+        let t = ((v - k.min) / (k.max - k.min)).clamp(0.0, 1.0);
+        draw_text_ex(tr(k.name), PANEL_X, r.y + 10.0, label(LIGHTGRAY));
+        draw_rectangle(r.x, r.y + 4.0, r.w, 3.0, GRAY);
+        draw_rectangle(r.x, r.y + 4.0, r.w * t, 3.0, LIME);
+        draw_circle(r.x + r.w * t, r.y + 5.5, 6.0, if game.practice_panel.dragging == Some(i) { YELLOW } else { WHITE });
+        draw_text_ex(&format!("{:.2}", v), r.x + r.w + 12.0, r.y + 10.0, label(LIGHTGRAY));
+    }
+    let y = PANEL_Y + 14.0 + KNOBS.len() as f32 * SLIDER_GAP + 6.0;
+    let inv = if game.practice.invincible { tr("开") } else { tr("关") };
+    draw_text_ex(&trf("[I] 无敌：{}   [H] 收起", &[&inv]), PANEL_X, y, label(if game.practice.invincible { LIME } else { ORANGE }));
+}
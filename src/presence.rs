@@ -0,0 +1,88 @@
+use discord_rich_presence::activity::{Activity, Timestamps};
+use discord_rich_presence::{DiscordIpc, DiscordIpcClient};
+
+use crate::{Game, GameMode, PlayMode};
+
+// ===== Discord 状态（discord 特性）=====
+// 本机开着 Discord 时，把“在玩什么、多少分”显示在个人状态上。
+// 应用 id 编译时或运行时用 DODGE_DISCORD_APP_ID 给出，没有就什么都不做。
+// 只在状态变化时发（菜单 → 局内 → 结束…），局内的分数每 SCORE_EVERY 秒顺带刷新一次，不撞 Discord 的频率限制。
+const APP_ID_ENV: &str = "DODGE_DISCORD_APP_ID";
+const SCORE_EVERY: f64 = 15.0;
+const RETRY_EVERY: f64 = 30.0; // Discord 没开时隔这么久再试
+
+#[derive(Default)]
+pub struct Presence {
+    client: Option<DiscordIpcClient>,
+    last_try: f64,
+    shown: Option<(String, String)>, // 已发出的 details / state
+    shown_at: f64,
+    phase: Option<GameMode>,
+    started: i64, // 本局开始的 Unix 毫秒，给 Discord 显示已进行时间
+}
+
+fn now() -> f64 { macroquad::miniquad::date::now() }
+
+fn app_id() -> Option<String> {
+    option_env!("DODGE_DISCORD_APP_ID").map(str::to_string).or_else(|| std::env::var(APP_ID_ENV).ok()).filter(|s| !s.trim().is_empty())
+}
+
+// 当前画面对应的两行文字；不在局里时 state 为空
+fn describe(game: &Game) -> (String, String) {
+    let run = |verb: &str| format!("{}, score {} (best {})", verb, game.score, game.best());
+    let details = match game.mode {
+        _ if game.replay.watching() => "Watching a replay".to_string(),
+        GameMode::Playing if game.play == PlayMode::Versus => format!("Versus, score {}", game.score),
+        GameMode::Playing => run("Playing"),
+        GameMode::Paused | GameMode::Feedback => run("Paused"),
+        GameMode::Killcam | GameMode::GameOver => run("Game over"),
+        GameMode::Lobby | GameMode::Ranked => "In the versus lobby".to_string(),
+        GameMode::Spectate => "Spectating a friend".to_string(),
+        _ => "In the menus".to_string(),
+    };
+    (details, if in_run(game.mode) { game.mode_label() } else { String::new() })
+}
+
+fn in_run(mode: GameMode) -> bool { matches!(mode, GameMode::Playing | GameMode::Paused | GameMode::Feedback | GameMode::Killcam | GameMode::GameOver) }
+
+// 每帧调用
+pub fn update(game: &mut Game) {
+    let t = now();
+    let p = &mut game.presence;
+    if p.client.is_none() {
+        if t - p.last_try < RETRY_EVERY && p.last_try > 0.0 { return; }
+        p.last_try = t;
+        let Some(id) = app_id() else { return };
+        let mut c = DiscordIpcClient::new(id);
+        if c.connect().is_err() { return; }
+        p.client = Some(c);
+        p.shown = None;
+    }
+    // 进局时记下开始时间（暂停、结束都沿用）
+    if game.mode == GameMode::Playing && !p.phase.is_some_and(|m| m != GameMode::GameOver && in_run(m)) {
+        p.started = ((t - game.elapsed as f64) * 1000.0) as i64;
+    }
+    let changed = p.phase != Some(game.mode);
+    p.phase = Some(game.mode);
+    let text = describe(game);
+    let p = &mut game.presence;
+    if p.shown.as_ref() == Some(&text) || (!changed && t - p.shown_at < SCORE_EVERY) { return; }
+    let mut activity = Activity::new().details(&text.0);
+    if !text.1.is_empty() { activity = activity.state(&text.1).timestamps(Timestamps::new().start(p.started)); }
+    let Some(c) = p.client.as_mut() else { return };
+    if c.set_activity(activity).is_err() {
+        // Discord 关掉了：丢掉连接，过一会儿重连
+        p.client = None;
+        return;
+    }
+    p.shown = Some(text);
+    p.shown_at = t;
+}
+
+// 退出时清掉状态并断开
+pub fn close(game: &mut Game) {
+    if let Some(mut c) = game.presence.client.take() {
+        let _ = c.clear_activity();
+        let _ = c.close();
+    }
+}
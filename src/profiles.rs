@@ -0,0 +1,99 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::textfield::TextField;
+use crate::{paths, storage, SAVE_PATH};
+
+// ===== 玩家档案 =====
+// 一台电脑几个人玩：每个档案有自己的存档（分数、解锁、设置、统计）、挂起、历史和回放。
+// 档案列表和上次用的档案记在数据目录的 profiles.json，启动时自动选上次的。
+pub const PROFILES_PATH: &str = "profiles.json";
+const DEFAULT_ID: &str = "default"; // 默认档案用数据目录本身，老存档就是它
+const PROFILES_MAX: usize = 8;
+pub const NAME_MAX: usize = 12;
+// 删除档案时清掉的文件（默认档案和数据目录里的其他东西混放，只能按名字删）
+const PROFILE_FILES: [&str; 8] = [
+    "save.json", "save.json.bak", "save.json.corrupt", "settings.json", "settings.json.bak", "suspend.json", "history.jsonl", "sync.json",
+];
+const PROFILE_DIRS: [&str; 2] = ["replays", "ghosts"];
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Profile {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Profiles {
+    pub last: String,
+    pub list: Vec<Profile>,
+}
+
+fn sub_dir(id: &str) -> String { if id == DEFAULT_ID { String::new() } else { format!("profiles/{}", id) } }
+
+impl Profiles {
+    // 至少保证有一个默认档案
+    pub fn load() -> Self {
+        let mut p: Self = storage::read_to_string(paths::data(PROFILES_PATH))
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+        if p.list.is_empty() { p.list.push(Profile { id: DEFAULT_ID.to_string(), name: "玩家 1".to_string() }); }
+        if !p.list.iter().any(|x| x.id == p.last) { p.last = p.list[0].id.clone(); }
+        p
+    }
+    fn save(&self) {
+        let _ = storage::write(paths::data(PROFILES_PATH), serde_json::to_string_pretty(self).unwrap());
+    }
+
+    // 第 i 个档案的存档文件（不用先切过去）
+    pub fn save_path(&self, i: usize) -> PathBuf { paths::data(&sub_dir(&self.list[i].id)).join(SAVE_PATH) }
+
+    pub fn current(&self) -> usize { self.list.iter().position(|x| x.id == self.last).unwrap_or(0) }
+    pub fn full(&self) -> bool { self.list.len() >= PROFILES_MAX }
+
+    // 切到第 i 个档案（之后的读写都走它的目录）
+    pub fn activate(&mut self, i: usize) {
+        let Some(p) = self.list.get(i) else { return };
+        let sub = sub_dir(&p.id);
+        paths::set_profile(&sub);
+        self.last = p.id.clone();
+        self.save();
+    }
+
+    pub fn create(&mut self, name: &str) -> Option<usize> {
+        if self.full() { return None; }
+        let id = format!("p{}", (macroquad::miniquad::date::now() * 1000.0) as u64);
+        self.list.push(Profile { id, name: name.to_string() });
+        self.save();
+        Some(self.list.len() - 1)
+    }
+
+    pub fn rename(&mut self, i: usize, name: &str) {
+        if let Some(p) = self.list.get_mut(i) { p.name = name.to_string(); }
+        self.save();
+    }
+
+    // 正在用的档案和最后一个档案不能删
+    pub fn delete(&mut self, i: usize) -> bool {
+        if i == self.current() || self.list.len() <= 1 || i >= self.list.len() { return false; }
+        let p = self.list.remove(i);
+        let sub = sub_dir(&p.id);
+        if sub.is_empty() {
+            for f in PROFILE_FILES { let _ = storage::remove_file(paths::data(f)); }
+            for d in PROFILE_DIRS { let _ = storage::remove_dir_all(paths::data(d)); }
+        } else {
+            let _ = storage::remove_dir_all(paths::data(&sub));
+        }
+        self.save();
+        true
+    }
+}
+
+// 档案界面里正在输入的名字：index 为 None 表示新建
+pub struct ProfileEdit {
+    pub index: Option<usize>,
+    pub field: TextField,
+}
@@ -0,0 +1,135 @@
+use macroquad::prelude::Rect;
+
+use crate::rng::{Rng, Stream};
+use crate::waves::{SpawnKind, WavePattern};
+use crate::physics::Physics;
+use crate::{player_hitbox, rects_overlap, step_player, MoveInput, Player, FIXED_DT, OB_START_SPEED, PLAYER_W};
+
+// ===== 阵型难度评估：无窗口模拟 + 简单躲避 AI =====
+// 载入阵型时按评估结果从易到难排好，局内随存活时间逐个解锁（见 waves::unlocked）；
+// cargo run -- --rate-waves 打印同一份评估的报告，调阵型时看。
+const FIELD_W: f32 = 800.0;
+const FIELD_H: f32 = 600.0;
+const TRIALS: u32 = 64;
+
+pub struct Skill {
+    pub name: &'static str,
+    pub react: f32,      // 重新规划的间隔（秒）
+    pub lookahead: f32,  // 预判多远的障碍（秒）
+    pub jitter: f32,     // 目标位置的随机误差（像素）
+}
+
+pub const SKILLS: [Skill; 4] = [
+    Skill { name: "新手", react: 0.35, lookahead: 0.45, jitter: 60.0 },
+    Skill { name: "普通", react: 0.20, lookahead: 0.70, jitter: 30.0 },
+    Skill { name: "熟练", react: 0.10, lookahead: 1.00, jitter: 12.0 },
+    Skill { name: "高手", react: 0.05, lookahead: 1.40, jitter: 0.0 },
+];
+
+pub struct WaveRating {
+    pub name: String,
+    pub survival: [f32; SKILLS.len()],
+}
+impl WaveRating {
+    // 0 = 谁都能过，1 = 谁都过不去
+    pub fn difficulty(&self) -> f32 {
+        1.0 - self.survival.iter().sum::<f32>() / self.survival.len() as f32
+    }
+}
+
+struct SimOb { rect: Rect, vx: f32, vy: f32 }
+
+fn plan_target(x: f32, obs: &[SimOb], skill: &Skill, rng: &mut Rng) -> f32 {
+    let mut best = x;
+    let mut best_cost = f32::MAX;
+    let mut cx = 0.0;
+    while cx <= FIELD_W - PLAYER_W {
+        let hit = player_hitbox(cx, PLAYER_W);
+        let mut danger = 0.0;
+        for o in obs {
+            let bottom = o.rect.y + o.rect.h;
+            if bottom > hit.y + hit.h { continue; }
+            let eta = (hit.y - bottom) / o.vy;
+            if eta > skill.lookahead { continue; }
+            let lane = Rect::new(o.rect.x - 8.0, hit.y, o.rect.w + 16.0, hit.h);
+            if rects_overlap(lane, hit) { danger += 1.0 + (skill.lookahead - eta.max(0.0)); }
+        }
+        let cost = danger * 1000.0 + (cx - x).abs();
+        if cost < best_cost { best_cost = cost; best = cx; }
+        cx += 10.0;
+    }
+    let j = (rng.next_f32() * 2.0 - 1.0) * skill.jitter;
+    (best + j).clamp(0.0, FIELD_W - PLAYER_W)
+}
+
+// 跑一局阵型，返回是否存活
+fn simulate(w: &WavePattern, skill: &Skill, rng: &mut Rng) -> bool {
+    let mut pending: Vec<_> = w.spawns.iter().filter(|s| s.kind == SpawnKind::Block).collect();
+    pending.sort_by(|a, b| b.delay.total_cmp(&a.delay));
+    let mut obs: Vec<SimOb> = Vec::new();
+    let phys = Physics::default();
+    let mut player = Player::new(rng.next_f32() * (FIELD_W - PLAYER_W), PLAYER_W);
+    let mut target = player.x;
+    let mut plan_timer = 0.0;
+    let mut t = 0.0;
+
+    while !pending.is_empty() || !obs.is_empty() {
+        t += FIXED_DT;
+        while let Some(s) = pending.last() {
+            if s.delay > t { break; }
+            let x = (s.x * FIELD_W).clamp(0.0, FIELD_W - s.size);
+            obs.push(SimOb { rect: Rect::new(x, -s.size - 10.0, s.size, s.size), vx: s.vx, vy: OB_START_SPEED * s.speed });
+            pending.pop();
+        }
+
+        plan_timer -= FIXED_DT;
+        if plan_timer <= 0.0 {
+            plan_timer = skill.react;
+            target = plan_target(player.x, &obs, skill, rng);
+        }
+        // 朝目标点的期望速度，超速时反向刹车
+        let want_vx = ((target - player.x) * 6.0).clamp(-phys.speed_max, phys.speed_max);
+        let dir = if (want_vx - player.vx).abs() > 20.0 { (want_vx - player.vx).signum() } else { 0.0 };
+        step_player(&mut player, &phys, MoveInput { dir, dash: false, push: 0.0 }, 1.0, FIXED_DT, FIELD_W, false);
+
+        for o in &mut obs { o.rect.x += o.vx * FIXED_DT; o.rect.y += o.vy * FIXED_DT; }
+        obs.retain(|o| o.rect.y <= FIELD_H + 5.0 && o.rect.x + o.rect.w >= -5.0 && o.rect.x <= FIELD_W + 5.0);
+
+        let hit = player_hitbox(player.x, player.w);
+        if obs.iter().any(|o| rects_overlap(o.rect, hit)) { return false; }
+        if t > 60.0 { break; }
+    }
+    true
+}
+
+pub fn rate_wave(w: &WavePattern) -> WaveRating {
+    let mut survival = [0.0; SKILLS.len()];
+    for (i, skill) in SKILLS.iter().enumerate() {
+        // 固定种子：每次报告结果一致
+        let mut rng = Rng::stream(0x5eed ^ i as u64, Stream::Tool);
+        let alive = (0..TRIALS).filter(|_| simulate(w, skill, &mut rng)).count();
+        survival[i] = alive as f32 / TRIALS as f32;
+    }
+    WaveRating { name: w.name.clone(), survival }
+}
+
+// 按难度从易到难排序；评估用固定种子，每次排出来一样，无窗口校验回放时也一致
+pub fn sort_by_difficulty(waves: &mut Vec<WavePattern>) {
+    let mut rated: Vec<(f32, WavePattern)> = waves.drain(..).map(|w| (rate_wave(&w).difficulty(), w)).collect();
+    rated.sort_by(|a, b| a.0.total_cmp(&b.0));
+    waves.extend(rated.into_iter().map(|(_, w)| w));
+}
+
+// 打印按难度从易到难排序的报告
+pub fn print_report(waves: &[WavePattern]) {
+    let mut ratings: Vec<WaveRating> = waves.iter().map(rate_wave).collect();
+    ratings.sort_by(|a, b| a.difficulty().total_cmp(&b.difficulty()));
+    print!("{:<16}", "阵型");
+    for s in &SKILLS { print!("{:>8}", s.name); }
+    println!("{:>8}", "难度");
+    for r in &ratings {
+        print!("{:<16}", r.name);
+        for v in r.survival { print!("{:>7.0}%", v * 100.0); }
+        println!("{:>8.2}", r.difficulty());
+    }
+}
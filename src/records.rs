@@ -0,0 +1,40 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::difficulty::{Difficulty, DIFFICULTIES};
+
+// ===== 最高分表 =====
+// 键为“模式/难度/规则标签”（未改规则时标签为空），每项记下分数、日期和种子，
+// 禅模式、各难度、各种变异组合互不覆盖。每日/每周挑战仍记在各自的历史里。
+#[derive(Clone, Copy, Serialize, Deserialize, Default)]
+pub struct BestEntry {
+    pub score: i32,
+    pub day: u32,
+    pub seed: u64,
+}
+
+#[derive(Clone, Serialize, Deserialize, Default)]
+#[serde(transparent)]
+pub struct Records(BTreeMap<String, BestEntry>);
+
+pub fn key(mode: &str, d: Difficulty, tag: &str) -> String { format!("{}/{}/{}", mode, d.key(), tag) }
+
+impl Records {
+    pub fn get(&self, key: &str) -> Option<&BestEntry> { self.0.get(key) }
+    pub fn score(&self, key: &str) -> i32 { self.get(key).map_or(0, |e| e.score) }
+
+    // 超过原纪录才替换
+    pub fn record(&mut self, key: String, e: BestEntry) {
+        let slot = self.0.entry(key).or_default();
+        if e.score > slot.score { *slot = e; }
+    }
+    pub fn merge(&mut self, other: &Records) {
+        for (k, e) in &other.0 { self.record(k.clone(), *e); }
+    }
+
+    // 普通模式、未改规则时各难度里最高的一项（外观解锁用）
+    pub fn top_plain(&self) -> i32 {
+        DIFFICULTIES.iter().map(|d| self.score(&key("standard", *d, ""))).max().unwrap_or(0)
+    }
+}
@@ -0,0 +1,263 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::challenge::{Challenge, ChallengeRun};
+use crate::difficulty::Difficulty;
+use crate::feel::Feel;
+use crate::ghost::GhostFile;
+use crate::movement::Scheme;
+use crate::mutators::Mutators;
+use crate::{paths, storage};
+use crate::{save_game, update_game, Content, Game, GameMode, PlayMode, Save, FIXED_DT};
+
+// ===== 回放 =====
+// 普通模式的每一局都逐步记录输入（游戏逻辑只从 StepInput 读按键，见 update_game）。
+// 模拟是确定的：同样的种子 + 规则 + 逐步输入 → 同样的一局。输入按连续相同的步数压缩存储。
+// 观看回放时不写存档，看完从存档重新载入，回放里发生的一切都不会留下。
+pub const REPLAY_DIR: &str = "replays";
+
+// 一个固定步的输入
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub struct StepInput {
+    pub dir: i8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub analog: Option<u8>, // 摇杆推动幅度（百分比）；None 为推满（键盘等）
+    pub dash: bool,
+    pub teleport: bool,
+    pub aim: Option<f32>, // 鼠标瞄准的瞬移目标（场地坐标），否则瞬移到最大空隙
+    pub overdrive: bool,
+    pub bomb: bool,
+    pub fire: bool,
+}
+
+// 重现一局需要的全部规则
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ReplayHeader {
+    pub seed: u64,
+    pub field: (f32, f32),
+    pub difficulty: Difficulty,
+    pub adaptive: bool,
+    pub mutators: Mutators,
+    pub scheme: Scheme,
+    pub wrap: bool,
+    pub shooting: bool,
+    pub start_lives: u32,
+    pub loadout: i32,
+    pub character: usize,
+    pub owned: Vec<String>, // 商店已购（天赋、命数上限）
+    pub unlocks: bool,      // 解锁内容是否生效（固定规则时不生效）
+    pub feel: Feel,
+    pub reduced_motion: bool,
+    pub speed_scale: f32,
+    #[serde(default = "standard_inset")]
+    pub hitbox_inset: u8,
+    #[serde(default)]
+    pub hit_grace: u8,
+    pub challenge: Option<(Challenge, u32)>,
+}
+
+// 没记宽容度的旧回放都是标准判定
+fn standard_inset() -> u8 { crate::settings::HITBOX_INSET }
+
+impl ReplayHeader {
+    fn of(game: &Game) -> Self {
+        Self {
+            seed: game.seed,
+            field: (game.field.x, game.field.y),
+            difficulty: game.difficulty,
+            adaptive: game.director.enabled,
+            mutators: game.mutators,
+            scheme: game.mover.scheme,
+            wrap: game.mover.wrap,
+            shooting: game.shooting,
+            start_lives: game.start_lives,
+            loadout: game.loadout,
+            character: game.character,
+            owned: game.wallet.owned.clone(),
+            unlocks: game.unlocks_apply(),
+            feel: game.feel,
+            reduced_motion: game.settings.reduced_motion,
+            speed_scale: game.settings.speed_scale,
+            hitbox_inset: game.settings.hitbox_inset,
+            hit_grace: game.settings.hit_grace,
+            challenge: game.challenge.as_ref().map(|c| (c.kind, c.period)),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub header: ReplayHeader,
+    pub runs: Vec<(u32, StepInput)>, // (连续步数, 输入)
+    pub continues: Vec<u32>,         // 在第几步结束时续了关
+    #[serde(default)]
+    pub track: Vec<f32>,             // 玩家中心位置轨迹（幽灵用，见 ghost.rs）
+}
+
+impl Replay {
+    pub fn steps(&self) -> u32 { self.runs.iter().map(|(n, _)| n).sum() }
+
+    pub fn save(&self, id: u64) -> std::io::Result<()> {
+        storage::write(path(id), serde_json::to_string(self).unwrap())
+    }
+    pub fn load(id: u64) -> Option<Self> {
+        storage::read_to_string(path(id)).ok().and_then(|s| serde_json::from_str(&s).ok())
+    }
+}
+
+pub fn path(id: u64) -> PathBuf { paths::profile(REPLAY_DIR).join(format!("{}.json", id)) }
+pub fn remove(id: u64) { let _ = storage::remove_file(path(id)); }
+
+pub struct Playback {
+    pub replay: Replay,
+    run: usize,
+    used: u32,
+    pub step: u32,
+    pub headless: bool, // 无窗口校验：不读键盘
+}
+
+impl Playback {
+    fn next(&mut self) -> StepInput {
+        self.step += 1;
+        let Some((n, inp)) = self.replay.runs.get(self.run) else { return StepInput::default() };
+        self.used += 1;
+        if self.used >= *n { self.run += 1; self.used = 0; }
+        *inp
+    }
+}
+
+pub enum ReplayState {
+    Off,
+    Recording(Replay),
+    Watching(Box<Playback>),
+}
+
+impl ReplayState {
+    pub fn watching(&self) -> bool { matches!(self, ReplayState::Watching(_)) }
+    // 观看时的种子与场地（开局时用）
+    pub fn header(&self) -> Option<&ReplayHeader> {
+        match self { ReplayState::Watching(p) => Some(&p.replay.header), _ => None }
+    }
+}
+
+// 开局：普通模式开始录制（观看回放时不录）
+pub fn start(game: &mut Game) {
+    if game.replay.watching() { return; }
+    game.replay = if game.play == PlayMode::Standard {
+        ReplayState::Recording(Replay { header: ReplayHeader::of(game), runs: Vec::new(), continues: Vec::new(), track: Vec::new() })
+    } else {
+        ReplayState::Off
+    };
+}
+
+// 每个固定步取一次输入：观看时从回放里读，否则读键盘（并在录制时记下）
+pub fn step_input(game: &mut Game, live: impl FnOnce(&mut Game) -> StepInput) -> StepInput {
+    if let ReplayState::Watching(p) = &mut game.replay { return p.next(); }
+    let inp = live(game);
+    if let ReplayState::Recording(r) = &mut game.replay {
+        match r.runs.last_mut() {
+            Some((n, last)) if *last == inp => *n += 1,
+            _ => r.runs.push((1, inp)),
+        }
+    }
+    inp
+}
+
+pub fn on_continue(game: &mut Game) {
+    if let ReplayState::Recording(r) = &mut game.replay {
+        let at = r.steps();
+        r.continues.push(at);
+    }
+}
+
+// 观看时：回放在这一步续过关就照做
+pub fn should_continue(game: &Game) -> bool {
+    match &game.replay {
+        ReplayState::Watching(p) => p.replay.continues.contains(&p.step),
+        _ => false,
+    }
+}
+
+// 开始观看：先把当前进度存盘，再换上回放里的规则开局
+pub fn watch(game: &mut Game, replay: Replay) {
+    save_game(game);
+    play_back(game, replay, false);
+}
+
+fn play_back(game: &mut Game, replay: Replay, headless: bool) {
+    let h = replay.header.clone();
+    if !h.unlocks { game.use_fixed_rules(); }
+    game.difficulty = h.difficulty;
+    game.director.enabled = h.adaptive;
+    game.mutators = h.mutators;
+    game.mover.scheme = h.scheme;
+    game.mover.wrap = h.wrap;
+    game.shooting = h.shooting;
+    game.start_lives = h.start_lives;
+    game.loadout = h.loadout;
+    game.character = h.character;
+    game.wallet.owned = h.owned;
+    game.feel = h.feel;
+    game.settings.reduced_motion = h.reduced_motion;
+    game.settings.speed_scale = h.speed_scale;
+    game.settings.hitbox_inset = h.hitbox_inset;
+    game.settings.hit_grace = h.hit_grace;
+    game.challenge = h.challenge.map(|(kind, period)| ChallengeRun { kind, period });
+    game.play = PlayMode::Standard;
+    game.replay = ReplayState::Watching(Box::new(Playback { replay, run: 0, used: 0, step: 0, headless }));
+    game.reset_round();
+}
+
+// 无窗口时不能碰键盘（macroquad 的输入要窗口上下文）
+pub fn headless(game: &Game) -> bool {
+    matches!(&game.replay, ReplayState::Watching(p) if p.headless)
+}
+
+// —— 校验 ——
+// 在无窗口的模拟里把回放从头跑到结束，算出分数和声称的比对。
+// 本地 --verify 和排行榜服务器用的是同一段逻辑；规则、种子、逐步输入都来自回放本身，
+// 和本机的存档、设置无关（资源文件和程序版本要一致，幽灵文件里带着版本号）。
+// 返回分数和这一局实际用掉的步数
+pub fn simulate(replay: Replay, content: &Content) -> Result<(i32, u32), String> {
+    let steps = replay.steps();
+    let mut game = Game::new(Save::default());
+    play_back(&mut game, replay, true);
+    loop {
+        let step = match &game.replay { ReplayState::Watching(p) => p.step, _ => unreachable!() };
+        match game.mode {
+            GameMode::Playing if step < steps => update_game(&mut game, FIXED_DT, content),
+            GameMode::Playing => return Err(format!("回放只有 {} 步，这一局还没结束", steps)),
+            GameMode::GameOver if should_continue(&game) => game.continue_run(),
+            GameMode::GameOver => return Ok((game.score, step)),
+            _ => return Err("模拟进入了意外的状态".to_string()),
+        }
+    }
+}
+
+pub fn verify(replay: Replay, claimed: i32, content: &Content) -> Result<i32, String> {
+    let steps = replay.steps();
+    let (score, used) = simulate(replay, content)?;
+    if used != steps { return Err(format!("这一局在第 {} 步就结束了，回放却有 {} 步", used, steps)); }
+    if score != claimed { return Err(format!("重跑得到 {} 分，和声称的 {} 分不符", score, claimed)); }
+    Ok(score)
+}
+
+// 命令行 --verify：.drg 幽灵文件（自带分数）或回放 JSON（要用 --score 给出分数）；路径为 - 时读标准输入
+pub fn verify_file(path: &str, score: Option<i32>) -> Result<i32, String> {
+    let bytes = if path == "-" {
+        let mut b = Vec::new();
+        std::io::Read::read_to_end(&mut std::io::stdin(), &mut b).map_err(|e| e.to_string())?;
+        b
+    } else {
+        std::fs::read(path).map_err(|e| format!("读不了 {}：{}", path, e))?
+    };
+    let (replay, claimed) = match GhostFile::decode(&bytes) {
+        Ok(f) => (f.replay, score.unwrap_or(f.score)),
+        Err(_) => {
+            let r = serde_json::from_slice(&bytes).map_err(|_| "既不是幽灵文件也不是回放".to_string())?;
+            (r, score.ok_or("回放文件需要用 --score 给出声称的分数")?)
+        }
+    };
+    verify(replay, claimed, &Content::load())
+}
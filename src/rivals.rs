@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+
+use crate::challenge::Challenge;
+
+// ===== 每日 / 每周挑战的本地排行 =====
+// 同一天（同一周）所有人的种子相同，分数可以直接比。不走服务器：
+// 自己的每次成绩自动记上，朋友的成绩靠分享码导入（剪贴板），存进存档。
+// 分享码：DR1-<D|W><期数>-<分数>-<时间×10>-<校验>-<名字>，校验只防抄错，不防作弊。
+const BOARDS_MAX: usize = 120; // 只保留最近这么多期
+const ENTRIES_MAX: usize = 20; // 每期最多这么多条
+pub const NAME_MAX: usize = 12;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Entry {
+    pub name: String,
+    pub score: i32,
+    pub elapsed: f32,
+    pub mine: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Board {
+    pub kind: Challenge,
+    pub period: u32,
+    pub entries: Vec<Entry>, // 按分数从高到低
+}
+
+#[derive(Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Rivals {
+    pub boards: Vec<Board>,
+}
+
+impl Rivals {
+    pub fn board(&self, kind: Challenge, period: u32) -> Option<&Board> {
+        self.boards.iter().find(|b| b.kind == kind && b.period == period)
+    }
+
+    // 自己的成绩每局一条；朋友的同名成绩只留最好的
+    pub fn add(&mut self, kind: Challenge, period: u32, e: Entry) {
+        let i = match self.boards.iter().position(|b| b.kind == kind && b.period == period) {
+            Some(i) => i,
+            None => {
+                self.boards.push(Board { kind, period, entries: Vec::new() });
+                if self.boards.len() > BOARDS_MAX { self.boards.remove(0); }
+                self.boards.len() - 1
+            }
+        };
+        let entries = &mut self.boards[i].entries;
+        match entries.iter_mut().find(|o| !e.mine && !o.mine && o.name == e.name) {
+            Some(o) => if e.score > o.score { *o = e; },
+            None => entries.push(e),
+        }
+        entries.sort_by_key(|e| std::cmp::Reverse(e.score));
+        entries.truncate(ENTRIES_MAX);
+    }
+
+    pub fn my_best(&self, kind: Challenge, period: u32) -> Option<&Entry> {
+        self.board(kind, period)?.entries.iter().find(|e| e.mine)
+    }
+}
+
+fn kind_char(kind: Challenge) -> char { if kind == Challenge::Daily { 'D' } else { 'W' } }
+
+// FNV-1a 取低 16 位
+fn checksum(s: &str) -> u16 {
+    let h = s.bytes().fold(0x811c_9dc5u32, |h, b| (h ^ b as u32).wrapping_mul(0x0100_0193));
+    (h ^ (h >> 16)) as u16
+}
+
+pub fn share_code(kind: Challenge, period: u32, e: &Entry) -> String {
+    let body = format!("{}{}-{}-{}", kind_char(kind), period, e.score, (e.elapsed * 10.0).round() as u32);
+    format!("DR1-{}-{:04x}-{}", body, checksum(&format!("{}-{}", body, e.name)), e.name)
+}
+
+pub fn parse_code(code: &str) -> Option<(Challenge, u32, Entry)> {
+    let mut parts = code.trim().splitn(6, '-');
+    if parts.next()? != "DR1" { return None; }
+    let (head, score, tenths, sum, name) = (parts.next()?, parts.next()?, parts.next()?, parts.next()?, parts.next()?);
+    let body = format!("{}-{}-{}", head, score, tenths);
+    if u16::from_str_radix(sum, 16).ok()? != checksum(&format!("{}-{}", body, name)) { return None; }
+    let kind = match head.chars().next()? {
+        'D' => Challenge::Daily,
+        'W' => Challenge::Weekly,
+        _ => return None,
+    };
+    let name: String = name.chars().filter(|c| !c.is_control()).take(NAME_MAX).collect();
+    let e = Entry { name, score: score.parse().ok()?, elapsed: tenths.parse::<u32>().ok()? as f32 / 10.0, mine: false };
+    Some((kind, head[1..].parse().ok()?, e))
+}
@@ -0,0 +1,37 @@
+// ===== 种子随机数服务 =====
+// 同一个种子派生出相互独立的流：玩法用的流被消耗多少不影响外观流，
+// 因此同一种子的背景永远一致。
+#[derive(Clone, Copy)]
+pub enum Stream {
+    Cosmetic = 1,
+    Tool = 2,
+    Daily = 3,
+    Weekly = 4,
+    Coins = 5,
+    Missions = 6,
+    Versus = 7,
+    Duel = 8,
+}
+
+#[derive(Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn stream(seed: u64, s: Stream) -> Self {
+        let mut r = Self(seed ^ (s as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        r.next_u64();
+        r
+    }
+    // splitmix64
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+    // [0, 1)
+    pub fn next_f32(&mut self) -> f32 { (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32 }
+    pub fn range(&mut self, lo: f32, hi: f32) -> f32 { lo + (hi - lo) * self.next_f32() }
+    pub fn index(&mut self, n: usize) -> usize { (self.next_u64() % n.max(1) as u64) as usize }
+}
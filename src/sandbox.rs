@@ -0,0 +1,120 @@
+use macroquad::prelude::*;
+
+use crate::effects::Effects;
+use crate::i18n::{tr, trf};
+use crate::physics::{Physics, PARAMS, PHYSICS_PATH};
+use crate::trace::{is_key_pressed, is_mouse_button_down, is_mouse_button_pressed, mouse_position};
+use crate::{
+    draw_obstacles, draw_player, draw_text_center, field_camera, move_input, player_hitbox, rects_overlap, step_player,
+    Game, GameMode, Player, Resources, PLAYER_W,
+};
+
+// ===== 手感调试沙盒（仅 debug 构建可进入）=====
+// 拖动滑条即时生效，测试生成器持续掉落方块，碰撞只计数不结束。
+const TEST_SPAWN_EVERY: f32 = 0.55;
+const TEST_FALL_SPEED: f32 = 260.0;
+const SLIDER_X: f32 = 240.0;
+const SLIDER_W: f32 = 320.0;
+const SLIDER_Y0: f32 = 110.0;
+const SLIDER_GAP: f32 = 34.0;
+
+pub struct Sandbox {
+    pub spawner_on: bool,
+    timer: f32,
+    hits: u32,
+    dragging: Option<usize>,
+    status: Option<String>,
+}
+
+impl Sandbox {
+    pub fn new() -> Self { Self { spawner_on: true, timer: 0.0, hits: 0, dragging: None, status: None } }
+}
+
+pub fn enter(game: &mut Game) {
+    game.sandbox = Sandbox::new();
+    game.obs.clear_all();
+    game.effects = Effects::new(0);
+    game.player = Player::new(game.field.x * 0.5 - PLAYER_W * 0.5, PLAYER_W);
+    game.mode = GameMode::Sandbox;
+}
+
+// 固定步：移动 + 测试生成器
+pub fn update(game: &mut Game, dt: f32) {
+    // 沙盒里直接用正在调的手感参数（不套开局体型）
+    game.player.acc = game.phys.acc;
+    game.player.speed_max = game.phys.speed_max;
+    let input = move_input(game);
+    step_player(&mut game.player, &game.phys, input, 1.0, dt, game.field.x, false);
+    if game.sandbox.spawner_on {
+        game.sandbox.timer += dt;
+        if game.sandbox.timer >= TEST_SPAWN_EVERY {
+            game.sandbox.timer = 0.0;
+            let size = rand::gen_range(26.0, 48.0);
+            let x = rand::gen_range(0.0, game.field.x - size);
+            game.obs.spawn(Rect::new(x, -size - 10.0, size, size), 0.0, TEST_FALL_SPEED);
+        }
+    }
+    game.obs.update_and_sweep(game.field.x, game.field.y, crate::PLAYER_Y, dt);
+    let hit = player_hitbox(game.player.x, game.player.w);
+    if let Some(i) = game.obs.live.iter().position(|o| rects_overlap(o.rect, hit)) {
+        let dead = game.obs.live.swap_remove(i);
+        game.obs.dead.push(dead);
+        game.sandbox.hits += 1;
+    }
+}
+
+fn slider_rect(i: usize) -> Rect { Rect::new(SLIDER_X, SLIDER_Y0 + i as f32 * SLIDER_GAP - 10.0, SLIDER_W, 14.0) }
+
+// 逐帧：滑条拖动与快捷键
+pub fn ui(game: &mut Game) {
+    let mouse = field_camera(game.field, Vec2::ZERO).screen_to_world(mouse_position().into());
+    if is_mouse_button_pressed(MouseButton::Left) {
+        game.sandbox.dragging = (0..PARAMS.len()).find(|&i| {
+            let r = slider_rect(i);
+            Rect::new(r.x - 8.0, r.y - 8.0, r.w + 16.0, r.h + 16.0).contains(mouse)
+        });
+    }
+    if !is_mouse_button_down(MouseButton::Left) { game.sandbox.dragging = None; }
+    if let Some(i) = game.sandbox.dragging {
+        let p = &PARAMS[i];
+        let t = ((mouse.x - SLIDER_X) / SLIDER_W).clamp(0.0, 1.0);
+        (p.set)(&mut game.phys, p.min + (p.max - p.min) * t);
+    }
+    if is_key_pressed(KeyCode::T) { game.sandbox.spawner_on = !game.sandbox.spawner_on; }
+    if is_key_pressed(KeyCode::Backspace) { game.phys = Physics::default(); }
+    if is_key_pressed(KeyCode::E) {
+        game.sandbox.status = Some(match game.phys.export(PHYSICS_PATH) {
+            Ok(()) => trf("已导出到 {}", &[&PHYSICS_PATH]),
+            Err(e) => trf("导出失败：{}", &[&e]),
+        });
+    }
+    if is_key_pressed(KeyCode::Escape) {
+        game.obs.clear_all();
+        game.mode = GameMode::Menu;
+    }
+}
+
+pub fn draw(game: &Game, res: &Resources) {
+    let font = &res.font;
+    draw_player(game, res);
+    draw_obstacles(game, res);
+    draw_text_center(font, game.field.x, tr("手感沙盒（开发用）"), 50.0, 30.0, SKYBLUE);
+    for (i, p) in PARAMS.iter().enumerate() {
+        let r = slider_rect(i);
+        let v = (p.get)(&game.phys);
+        let t = ((v - p.min) / (p.max - p.min)).clamp(0.0, 1.0);
+        let label = TextParams { font: Some(font), font_size: 20, color: LIGHTGRAY, ..Default::default() };
+        draw_text_ex(tr(p.name), r.x - 140.0, r.y + 13.0, label.clone());
+        draw_rectangle(r.x, r.y + 5.0, r.w, 4.0, GRAY);
+        draw_rectangle(r.x, r.y + 5.0, r.w * t, 4.0, SKYBLUE);
+        draw_circle(r.x + r.w * t, r.y + 7.0, 8.0, if game.sandbox.dragging == Some(i) { YELLOW } else { WHITE });
+        draw_text_ex(&format!("{:.4}", v), r.x + r.w + 16.0, r.y + 13.0, label);
+    }
+    let y = SLIDER_Y0 + PARAMS.len() as f32 * SLIDER_GAP + 10.0;
+    let spawner = if game.sandbox.spawner_on { tr("开") } else { tr("关") };
+    draw_text_center(font, game.field.x, &trf("命中 {}   [T] 测试生成器：{}   [SHIFT] 冲刺", &[&game.sandbox.hits, &spawner]), y, 20.0, LIGHTGRAY);
+    draw_text_center(font, game.field.x, tr("[E] 导出配置   [BACKSPACE] 恢复默认   [ESC] 返回"), y + 30.0, 20.0, ORANGE);
+    if let Some(st) = &game.sandbox.status {
+        draw_text_center(font, game.field.x, st, y + 60.0, 20.0, YELLOW);
+    }
+}
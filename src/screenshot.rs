@@ -0,0 +1,40 @@
+use macroquad::prelude::*;
+
+use crate::i18n::trf;
+use crate::paths;
+use crate::trace::{is_key_down, is_key_pressed};
+use crate::Game;
+
+// ===== 截图 =====
+// [F12] 把当前画面存成 PNG，放在用户数据目录的 screenshots/ 下，文件名是截图时间（UTC）；[SHIFT]+[F12] 截不带 HUD 的。
+// 按键在每帧输入里记下，等这一帧画完（next_frame 之前）再截，这样不带 HUD 的那张当帧就能把 HUD 省掉。
+// 回执走底部提示条，截完之后才弹出，不会被截进去。
+pub const DIR: &str = "screenshots";
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Shot { Full, Clean }
+
+pub fn poll(game: &mut Game) {
+    if !is_key_pressed(KeyCode::F12) { return; }
+    let shift = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+    game.shot = Some(if shift { Shot::Clean } else { Shot::Full });
+}
+
+// 这一帧要截不带 HUD 的图：HUD、横幅和触屏按钮都不画
+pub fn hides_hud(game: &Game) -> bool { game.shot == Some(Shot::Clean) }
+
+// 本帧画完后调用
+pub fn take(game: &mut Game) {
+    if game.shot.take().is_none() { return; }
+    let dir = paths::data(DIR);
+    if let Err(e) = std::fs::create_dir_all(&dir) {
+        game.toast.show(trf("截图失败：{}", &[&e]));
+        return;
+    }
+    let ms = (macroquad::miniquad::date::now() * 1000.0) as u64;
+    let secs = ms / 1000 % 86_400;
+    let name = format!("dodge-rush_{}_{:02}{:02}{:02}_{:03}.png", crate::challenge::date_string((ms / 86_400_000) as u32), secs / 3600, secs / 60 % 60, secs % 60, ms % 1000);
+    let path = dir.join(name);
+    get_screen_data().export_png(&path.to_string_lossy());
+    game.toast.show(trf("已截图：{}", &[&path.display()]));
+}
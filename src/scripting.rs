@@ -0,0 +1,106 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use rhai::{Engine, Scope, AST};
+
+use crate::waves::SpawnKind;
+
+// ===== 脚本钩子（cargo feature: scripting）=====
+// assets/scripts/*.rhai 中定义 `fn on_tick(elapsed, api)`，每个物理步调用一次。
+pub const SCRIPTS_DIR: &str = "assets/scripts";
+
+#[derive(Clone, Copy)]
+pub enum ScriptCmd {
+    Obstacle { x: f32, size: f32, speed: f32, vx: f32 },
+    PowerUp { x: f32, kind: SpawnKind },
+    FallSpeed(f32),
+}
+
+// 传给脚本的 api 对象；rhai 会克隆参数，所以命令队列用 Rc 共享
+#[derive(Clone)]
+pub struct ScriptApi {
+    player_x: f32,
+    width: f32,
+    cmds: Rc<RefCell<Vec<ScriptCmd>>>,
+}
+
+impl ScriptApi {
+    fn push(&mut self, c: ScriptCmd) { self.cmds.borrow_mut().push(c); }
+}
+
+pub struct ScriptHost {
+    engine: Engine,
+    scripts: Vec<AST>,
+    broken: RefCell<Vec<bool>>, // 出错的脚本报告一次后停用
+    cmds: Rc<RefCell<Vec<ScriptCmd>>>,
+}
+
+impl ScriptHost {
+    pub fn load(dir: &str) -> Self {
+        let mut engine = Engine::new();
+        engine
+            .register_type_with_name::<ScriptApi>("Api")
+            .register_get("player_x", |a: &mut ScriptApi| a.player_x as f64)
+            .register_get("width", |a: &mut ScriptApi| a.width as f64)
+            .register_fn("spawn_obstacle", |a: &mut ScriptApi, x: f64, size: f64, speed: f64| {
+                a.push(ScriptCmd::Obstacle { x: x as f32, size: size as f32, speed: speed as f32, vx: 0.0 });
+            })
+            .register_fn("spawn_obstacle", |a: &mut ScriptApi, x: f64, size: f64, speed: f64, vx: f64| {
+                a.push(ScriptCmd::Obstacle { x: x as f32, size: size as f32, speed: speed as f32, vx: vx as f32 });
+            })
+            .register_fn("spawn_powerup", |a: &mut ScriptApi, x: f64, kind: &str| {
+                let kind = match kind {
+                    "shield" => SpawnKind::Shield,
+                    "slow" => SpawnKind::Slow,
+                    "bomb" => SpawnKind::Bomb,
+                    "heart" => SpawnKind::Heart,
+                    "teleport" => SpawnKind::Teleport,
+                    "ammo" => SpawnKind::Ammo,
+                    "rewind" => SpawnKind::Rewind,
+                    "multiplier" => SpawnKind::Multiplier,
+                    "freeze" => SpawnKind::Freeze,
+                    "reverse" => SpawnKind::Reverse,
+                    "haste" => SpawnKind::Haste,
+                    "blackout" => SpawnKind::Blackout,
+                    _ => return,
+                };
+                a.push(ScriptCmd::PowerUp { x: x as f32, kind });
+            })
+            .register_fn("set_fall_speed", |a: &mut ScriptApi, v: f64| {
+                a.push(ScriptCmd::FallSpeed(v as f32));
+            });
+
+        let mut paths: Vec<_> = std::fs::read_dir(dir)
+            .map(|rd| rd.filter_map(|e| e.ok().map(|e| e.path())).collect())
+            .unwrap_or_default();
+        paths.retain(|p| p.extension().is_some_and(|ext| ext == "rhai"));
+        paths.sort();
+        let scripts: Vec<AST> = paths
+            .iter()
+            .filter_map(|p| match engine.compile_file(p.clone()) {
+                Ok(ast) => Some(ast),
+                Err(e) => {
+                    crate::logfile::warn(&format!("跳过脚本 {}：{}", p.display(), e));
+                    None
+                }
+            })
+            .collect();
+        let broken = RefCell::new(vec![false; scripts.len()]);
+        Self { engine, scripts, broken, cmds: Rc::new(RefCell::new(Vec::new())) }
+    }
+
+    // 运行所有脚本的 on_tick，返回它们下达的命令
+    pub fn tick(&self, elapsed: f32, player_x: f32, width: f32) -> Vec<ScriptCmd> {
+        let mut broken = self.broken.borrow_mut();
+        for (ast, broken) in self.scripts.iter().zip(broken.iter_mut()) {
+            if *broken { continue; }
+            let api = ScriptApi { player_x, width, cmds: self.cmds.clone() };
+            let mut scope = Scope::new();
+            if let Err(e) = self.engine.call_fn::<()>(&mut scope, ast, "on_tick", (elapsed as f64, api)) {
+                crate::logfile::warn(&format!("脚本 on_tick 出错，已停用：{}", e));
+                *broken = true;
+            }
+        }
+        std::mem::take(&mut *self.cmds.borrow_mut())
+    }
+}
@@ -0,0 +1,507 @@
+use macroquad::prelude::KeyCode;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::difficulty::Difficulty;
+use crate::{paths, storage};
+use crate::trace::{is_key_down, is_key_pressed};
+
+// ===== 用户设置 =====
+// 按键、音量、默认难度、无障碍开关和显示选项，存在档案目录的 settings.json，和玩法存档分开。
+// 启动时读入，改动的地方当场写盘；旧版本存档里的 settings 字段只在第一次读入时导过来。
+pub const SETTINGS_PATH: &str = "settings.json";
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub high_contrast: bool,
+    pub contrast_outlines: bool, // 保留主题配色，只叠加粗白描边、压暗背景、加亮底线（见 contrast.rs）
+    pub speed_scale: f32,     // 游戏整体速度倍率（70%–100%；低于 100% 的局不进排行榜）
+    pub reduced_motion: bool, // 关闭震屏、闪烁等剧烈画面运动（光敏安全，见 motion.rs）
+    pub shake_intensity: f32, // 震屏总强度（乘在手感的震屏倍率上）
+    pub audio_cues: bool,     // 障碍逼近时的立体声提示音（见 cues.rs）
+    pub color_vision: ColorVision, // 色觉模式：道具换成对应色盲也分得清的配色（见 palette.rs）
+    pub powerup_glyphs: bool, // 道具里画图标，不只靠颜色区分
+    pub narrate: bool,        // 朗读菜单、结算和提示条（tts 特性，见 speech.rs）
+    pub assist: Assist,       // 辅助移动：挡板自动往返或自动找空隙（见 assist.rs；开着的局不进排行榜）
+    pub assist_strength: f32, // 辅助推挡板的力度
+    pub hitbox_inset: u8,     // 命中盒两侧各收多少像素（0–12，标准 6）
+    pub hit_grace: u8,        // 压着障碍多少帧才算撞上（0 为一碰就算；比标准宽松的局不进排行榜）
+    pub large_text: bool,
+    pub lang: Lang,           // 界面语言（见 i18n.rs）
+    pub difficulty: Difficulty, // 玩家自己选的难度（挑战等固定规则不改它）
+    pub keys: Keys,
+    pub volume: Volume,
+    pub fullscreen: bool,     // 无边框全屏（[ALT+ENTER] 切换，见 display.rs）
+    pub window: WindowPlace,  // 上次窗口化时的大小和位置，下次启动照原样打开
+    pub ghost: bool,          // 普通模式里显示最佳一局的幽灵
+    pub online: bool,         // 参加全球排行榜（默认关，见 online.rs）
+    pub broadcast: bool,      // 在局域网直播自己的对局供观战（见 spectate.rs）
+    pub twitch_channel: String, // Twitch 聊天互动的频道和 oauth 令牌（twitch 特性，见 twitch.rs）
+    pub twitch_token: String,
+    pub rumble: bool,         // 手柄震动（gamepad 特性，见 pad.rs）
+    pub rumble_strength: f32, // 震动强度倍率
+    pub stick: Stick,         // 手柄摇杆的死区和响应曲线
+    pub touch: TouchMode,     // 触屏拖动的跟手方式（见 touch.rs）
+    pub mouse: bool,          // 鼠标操控：开着时鼠标一动，挡板就朝光标开过去
+    pub menu_entry: usize,    // 主菜单上次选的入口，下次打开默认选它
+    pub play_entry: usize,    // “开始游戏”里上次选的玩法
+    pub auto_pause: bool,     // 切走或长时间没操作时自动暂停（见 main.rs 的 auto_pause）
+    pub spawn_warnings: bool, // 场地上沿画障碍的来袭预警
+    pub ui_scale: f32,        // HUD 缩放（在按窗口大小自动缩放之上再乘，见 hud.rs）
+    pub hud: HudMode,         // 局内 [F4] 轮换
+    pub blur_overlays: bool,  // 暂停 / 结算时把后面定格的场地模糊掉（见 overlay.rs）
+    pub day_night: bool,      // 局内背景随用时昼夜变化（见 background.rs）
+    pub fx_crt: bool,         // 后期处理（见 postfx.rs）：CRT 扫描线
+    pub fx_vignette: bool,    // 暗角
+    pub fx_aberration: bool,  // 震屏时的色差
+    pub fx_bloom: bool,       // 亮处泛光
+    pub render_scale: f32,    // 渲染比例（画面像素数，不影响场地大小）
+    pub render_smooth: bool,  // 渲染比例不是 100% 时放大 / 缩小用平滑过滤，关掉是像素风
+    pub playfield: Playfield, // 场地跟窗口走，还是固定成竖屏（见 main.rs 的 target_field）
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            high_contrast: false,
+            contrast_outlines: false,
+            speed_scale: 1.0,
+            reduced_motion: false,
+            shake_intensity: 1.0,
+            audio_cues: false,
+            color_vision: ColorVision::Normal,
+            powerup_glyphs: false,
+            narrate: false,
+            assist: Assist::Off,
+            assist_strength: 0.6,
+            hitbox_inset: HITBOX_INSET,
+            hit_grace: 0,
+            large_text: false,
+            lang: Lang::ZhCn,
+            difficulty: Difficulty::default(),
+            keys: Keys::default(),
+            volume: Volume::default(),
+            fullscreen: false,
+            window: WindowPlace::default(),
+            ghost: true,
+            online: false,
+            broadcast: false,
+            twitch_channel: String::new(),
+            twitch_token: String::new(),
+            rumble: true,
+            rumble_strength: 1.0,
+            stick: Stick::default(),
+            touch: TouchMode::Direct,
+            mouse: false,
+            menu_entry: 0,
+            play_entry: 0,
+            auto_pause: true,
+            spawn_warnings: true,
+            ui_scale: 1.0,
+            hud: HudMode::Full,
+            blur_overlays: true,
+            day_night: true,
+            fx_crt: false,
+            fx_vignette: false,
+            fx_aberration: false,
+            fx_bloom: false,
+            render_scale: 1.0,
+            render_smooth: true,
+            // 手机上竖着拿，默认竖屏场地；桌面和网页照旧跟窗口
+            playfield: if cfg!(target_os = "android") { Playfield::Portrait } else { Playfield::Window },
+        }
+    }
+}
+
+// —— 读写 ——
+impl Settings {
+    pub fn load() -> Option<Self> {
+        let text = storage::read_to_string(paths::profile(SETTINGS_PATH)).ok()?;
+        let s: Settings = serde_json::from_str(&text).unwrap_or_default();
+        // 旧版本允许更低的速度
+        Some(Settings { speed_scale: s.speed_scale.clamp(SPEED_SCALE_MIN, SPEED_SCALE_MAX), ..s })
+    }
+    pub fn save(&self) {
+        if let Err(e) = paths::write_atomic(&paths::profile(SETTINGS_PATH), &serde_json::to_string_pretty(self).unwrap()) {
+            crate::logfile::warn(&format!("设置写入失败：{}", e));
+        }
+    }
+    // 没有 settings.json 时从旧存档导入（导入后立即写出）；两边都没有就是第一次启动，返回 None
+    pub fn load_or_import(legacy: Option<Settings>, difficulty: Difficulty) -> Option<Self> {
+        if let Some(s) = Self::load() { return Some(s); }
+        let s = Settings { difficulty, ..legacy? };
+        s.save();
+        Some(s)
+    }
+}
+
+// —— 按键 ——
+// 每个动作一个主键加一个备用键；文件里写键名，认不出的键名忽略。改键界面见 keybinds.rs
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Keys {
+    pub left: Binding,
+    pub right: Binding,
+    pub dash: Binding,
+    pub pause: Binding,
+    pub restart: Binding,
+    pub overdrive: Binding,
+    pub bomb: Binding,
+    pub hold: Binding, // 辅助移动时定住挡板
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Action { MoveLeft, MoveRight, Dash, Pause, Restart, Bomb, Overdrive, Hold }
+
+pub const ACTIONS: [Action; 8] = [Action::MoveLeft, Action::MoveRight, Action::Dash, Action::Pause, Action::Restart, Action::Bomb, Action::Overdrive, Action::Hold];
+
+impl Action {
+    pub fn label(self) -> &'static str {
+        crate::i18n::tr(match self {
+            Action::MoveLeft => "左移",
+            Action::MoveRight => "右移",
+            Action::Dash => "冲刺",
+            Action::Pause => "暂停",
+            Action::Restart => "重开",
+            Action::Bomb => "炸弹",
+            Action::Overdrive => "超载",
+            Action::Hold => "定住",
+        })
+    }
+}
+
+impl Keys {
+    pub fn get(&self, a: Action) -> &Binding {
+        match a {
+            Action::MoveLeft => &self.left,
+            Action::MoveRight => &self.right,
+            Action::Dash => &self.dash,
+            Action::Pause => &self.pause,
+            Action::Restart => &self.restart,
+            Action::Bomb => &self.bomb,
+            Action::Overdrive => &self.overdrive,
+            Action::Hold => &self.hold,
+        }
+    }
+    pub fn get_mut(&mut self, a: Action) -> &mut Binding {
+        match a {
+            Action::MoveLeft => &mut self.left,
+            Action::MoveRight => &mut self.right,
+            Action::Dash => &mut self.dash,
+            Action::Pause => &mut self.pause,
+            Action::Restart => &mut self.restart,
+            Action::Bomb => &mut self.bomb,
+            Action::Overdrive => &mut self.overdrive,
+            Action::Hold => &mut self.hold,
+        }
+    }
+    // 已经绑了这个键的动作（改键时查冲突）
+    pub fn owner(&self, k: KeyCode) -> Option<Action> { ACTIONS.into_iter().find(|a| self.get(*a).0.contains(&Some(k))) }
+}
+
+impl Default for Keys {
+    fn default() -> Self {
+        Self {
+            left: Binding([Some(KeyCode::Left), Some(KeyCode::A)]),
+            right: Binding([Some(KeyCode::Right), Some(KeyCode::D)]),
+            dash: Binding([Some(KeyCode::LeftShift), Some(KeyCode::RightShift)]),
+            pause: Binding([Some(KeyCode::P), None]),
+            restart: Binding([Some(KeyCode::R), None]),
+            overdrive: Binding([Some(KeyCode::Q), None]),
+            bomb: Binding([Some(KeyCode::B), None]),
+            hold: Binding([Some(KeyCode::Enter), None]),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct Binding(pub [Option<KeyCode>; 2]);
+
+impl Binding {
+    pub fn down(&self) -> bool { self.0.iter().flatten().any(|k| is_key_down(*k)) }
+    pub fn pressed(&self) -> bool { self.0.iter().flatten().any(|k| is_key_pressed(*k)) }
+    // 主键（主键不会为空：读入时至少有一个键，改键界面也不让清空）
+    pub fn primary(&self) -> KeyCode { self.0[0].unwrap_or(KeyCode::Unknown) }
+    // 提示里显示的键名
+    pub fn label(&self) -> &'static str { key_name(self.primary()) }
+}
+
+pub const KEY_NAMES: [(&str, KeyCode); 44] = [
+    ("Left", KeyCode::Left), ("Right", KeyCode::Right), ("Up", KeyCode::Up), ("Down", KeyCode::Down),
+    ("LeftShift", KeyCode::LeftShift), ("RightShift", KeyCode::RightShift),
+    ("LeftControl", KeyCode::LeftControl), ("RightControl", KeyCode::RightControl),
+    ("LeftAlt", KeyCode::LeftAlt), ("RightAlt", KeyCode::RightAlt),
+    ("Space", KeyCode::Space), ("Enter", KeyCode::Enter), ("Tab", KeyCode::Tab), ("Backspace", KeyCode::Backspace),
+    ("Escape", KeyCode::Escape), ("Comma", KeyCode::Comma), ("Period", KeyCode::Period), ("Slash", KeyCode::Slash),
+    ("A", KeyCode::A), ("B", KeyCode::B), ("C", KeyCode::C), ("D", KeyCode::D), ("E", KeyCode::E), ("F", KeyCode::F),
+    ("G", KeyCode::G), ("H", KeyCode::H), ("I", KeyCode::I), ("J", KeyCode::J), ("K", KeyCode::K), ("L", KeyCode::L),
+    ("M", KeyCode::M), ("N", KeyCode::N), ("O", KeyCode::O), ("P", KeyCode::P), ("Q", KeyCode::Q), ("R", KeyCode::R),
+    ("S", KeyCode::S), ("T", KeyCode::T), ("U", KeyCode::U), ("V", KeyCode::V), ("W", KeyCode::W), ("X", KeyCode::X),
+    ("Y", KeyCode::Y), ("Z", KeyCode::Z),
+];
+
+pub fn key_name(k: KeyCode) -> &'static str { KEY_NAMES.iter().find(|(_, c)| *c == k).map_or("?", |(n, _)| n) }
+fn key_by_name(name: &str) -> Option<KeyCode> { KEY_NAMES.iter().find(|(n, _)| n.eq_ignore_ascii_case(name)).map(|(_, k)| *k) }
+
+impl Serialize for Binding {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_seq(self.0.iter().flatten().map(|k| key_name(*k)))
+    }
+}
+
+impl<'de> Deserialize<'de> for Binding {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let names = Vec::<String>::deserialize(d)?;
+        let mut keys = names.iter().filter_map(|n| key_by_name(n));
+        let first = keys.next().ok_or_else(|| de::Error::custom("没有可用的按键"))?;
+        Ok(Binding([Some(first), keys.next()]))
+    }
+}
+
+// —— 音量（0..=1）——
+// 音量（见 audio.rs）：实际音量是总音量乘各声道
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Volume {
+    pub master: f32,
+    pub music: f32,
+    pub sfx: f32,
+    pub ui: f32, // 菜单提示音
+    pub music_muted: bool, // 局内外 [F8] 切换
+}
+
+impl Default for Volume {
+    fn default() -> Self { Self { master: 1.0, music: 0.8, sfx: 1.0, ui: 0.7, music_muted: false } }
+}
+
+// —— 摇杆 ——
+// 摇杆推动幅度 → 移动输入（-1..=1）：死区以内为 0，之后按曲线从 0 涨到 1；平方曲线小幅推动更细，方便微调位置
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum StickCurve { Linear, Quadratic }
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Stick {
+    pub deadzone: f32,
+    pub curve: StickCurve,
+}
+
+impl Default for Stick {
+    fn default() -> Self { Self { deadzone: 0.15, curve: StickCurve::Linear } }
+}
+
+pub const STICK_DEADZONE_MAX: f32 = 0.5;
+
+impl Stick {
+    pub fn apply(&self, x: f32) -> f32 {
+        let t = ((x.abs() - self.deadzone) / (1.0 - self.deadzone)).clamp(0.0, 1.0);
+        let t = if self.curve == StickCurve::Quadratic { t * t } else { t };
+        t * x.signum()
+    }
+}
+
+impl StickCurve {
+    pub fn label(self) -> &'static str { crate::i18n::tr(if self == StickCurve::Linear { "线性" } else { "平方" }) }
+    pub fn toggle(self) -> Self { if self == StickCurve::Linear { StickCurve::Quadratic } else { StickCurve::Linear } }
+}
+
+// —— 触屏跟手方式 ——
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TouchMode {
+    Direct,   // 挡板跑向手指正下方
+    Relative, // 像触控板：手指挪多少，挡板的目标就挪多少
+}
+
+impl TouchMode {
+    pub fn label(self) -> &'static str {
+        crate::i18n::tr(match self {
+            TouchMode::Direct => "直接",
+            TouchMode::Relative => "相对",
+        })
+    }
+    pub fn toggle(self) -> Self { if self == TouchMode::Direct { TouchMode::Relative } else { TouchMode::Direct } }
+}
+
+// —— 窗口位置 ——
+// 大小是逻辑像素（和 screen_width 一个单位）；位置是窗口左上角在整个桌面上的坐标，多显示器时也就定了在哪块屏上。
+// 拿不到位置的平台（macOS、网页、安卓）为 None，交给系统摆
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowPlace {
+    pub size: (f32, f32),
+    pub pos: Option<(u32, u32)>,
+}
+
+impl Default for WindowPlace {
+    fn default() -> Self { Self { size: (800.0, 600.0), pos: None } }
+}
+
+// —— 场地形状 ——
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Playfield {
+    Window,   // 场地就是窗口大小
+    Portrait, // 固定 9:16 的竖屏场地，窗口怎么变都按比例缩放、留黑边
+}
+
+impl Playfield {
+    pub fn label(self) -> &'static str {
+        crate::i18n::tr(match self {
+            Playfield::Window => "跟随窗口",
+            Playfield::Portrait => "竖屏固定（9:16）",
+        })
+    }
+    pub fn toggle(self) -> Self { if self == Playfield::Window { Playfield::Portrait } else { Playfield::Window } }
+}
+
+// —— HUD 显示方式 ——
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum HudMode {
+    Full,
+    Minimal, // 只留分数和缩小的道具图标
+    Hidden,  // 局内什么都不画（录视频用），成绩只在结束画面看
+}
+
+impl HudMode {
+    pub fn label(self) -> &'static str {
+        crate::i18n::tr(match self {
+            HudMode::Full => "完整",
+            HudMode::Minimal => "精简",
+            HudMode::Hidden => "隐藏",
+        })
+    }
+    pub fn cycle(self, step: i32) -> Self {
+        const ALL: [HudMode; 3] = [HudMode::Full, HudMode::Minimal, HudMode::Hidden];
+        let i = ALL.iter().position(|m| *m == self).unwrap_or(0) as i32;
+        ALL[(i + step).rem_euclid(ALL.len() as i32) as usize]
+    }
+}
+
+// —— 色觉模式 ——
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ColorVision {
+    Normal,
+    Deuteranopia, // 绿色弱（最常见）
+    Protanopia,   // 红色弱
+    Tritanopia,   // 蓝黄色弱
+}
+
+impl ColorVision {
+    pub fn label(self) -> &'static str {
+        crate::i18n::tr(match self {
+            ColorVision::Normal => "标准",
+            ColorVision::Deuteranopia => "绿色弱",
+            ColorVision::Protanopia => "红色弱",
+            ColorVision::Tritanopia => "蓝黄色弱",
+        })
+    }
+    pub fn cycle(self, step: i32) -> Self {
+        const ALL: [ColorVision; 4] = [ColorVision::Normal, ColorVision::Deuteranopia, ColorVision::Protanopia, ColorVision::Tritanopia];
+        let i = ALL.iter().position(|m| *m == self).unwrap_or(0) as i32;
+        ALL[(i + step).rem_euclid(ALL.len() as i32) as usize]
+    }
+}
+
+// —— 界面语言 ——
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Lang { ZhCn, EnUs }
+
+impl Lang {
+    // 语言名用各自的文字写，切错了也认得出来
+    pub fn label(self) -> &'static str {
+        match self {
+            Lang::ZhCn => "简体中文",
+            Lang::EnUs => "English",
+        }
+    }
+    // assets/lang/ 下的文件名
+    pub fn code(self) -> &'static str {
+        match self {
+            Lang::ZhCn => "zh-CN",
+            Lang::EnUs => "en-US",
+        }
+    }
+    pub fn cycle(self, step: i32) -> Self {
+        const ALL: [Lang; 2] = [Lang::ZhCn, Lang::EnUs];
+        let i = ALL.iter().position(|l| *l == self).unwrap_or(0) as i32;
+        ALL[(i + step).rem_euclid(ALL.len() as i32) as usize]
+    }
+}
+
+// —— 辅助移动 ——
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Assist {
+    Off,
+    Sweep, // 自动往返
+    Seek,  // 自动找空隙
+}
+
+impl Assist {
+    pub fn label(self) -> &'static str {
+        crate::i18n::tr(match self {
+            Assist::Off => "关",
+            Assist::Sweep => "自动往返",
+            Assist::Seek => "自动找空隙",
+        })
+    }
+    pub fn cycle(self, step: i32) -> Self {
+        const ALL: [Assist; 3] = [Assist::Off, Assist::Sweep, Assist::Seek];
+        let i = ALL.iter().position(|m| *m == self).unwrap_or(0) as i32;
+        ALL[(i + step).rem_euclid(ALL.len() as i32) as usize]
+    }
+}
+
+// —— 命中宽容度 ——
+// 挑战和对战类玩法一律用标准值（见 main.rs 的 forgiveness）
+pub const HITBOX_INSET: u8 = 6;
+pub const HITBOX_INSET_MAX: u8 = 12;
+pub const HIT_GRACE_MAX: u8 = 6;
+const INSET_LABELS: [&str; 7] = ["0 像素（严格）", "2 像素", "4 像素", "6 像素（标准）", "8 像素", "10 像素", "12 像素"];
+const GRACE_LABELS: [&str; 7] = ["0 帧（标准）", "1 帧", "2 帧", "3 帧", "4 帧", "5 帧", "6 帧"];
+
+pub const SPEED_SCALE_MIN: f32 = 0.7;
+pub const SPEED_SCALE_MAX: f32 = 1.0;
+
+impl Settings {
+    pub fn text_scale(&self) -> f32 { if self.large_text { 1.25 } else { 1.0 } }
+    pub fn slowed(&self) -> bool { self.speed_scale < SPEED_SCALE_MAX }
+    pub fn assisted(&self) -> bool { self.assist != Assist::Off }
+    pub fn inset_label(&self) -> &'static str { crate::i18n::tr(INSET_LABELS[(self.hitbox_inset.min(HITBOX_INSET_MAX) / 2) as usize]) }
+    pub fn grace_label(&self) -> &'static str { crate::i18n::tr(GRACE_LABELS[self.hit_grace.min(HIT_GRACE_MAX) as usize]) }
+    // 每档 2 像素
+    pub fn cycle_inset(&mut self, step: i32) { self.hitbox_inset = (self.hitbox_inset as i32 + step * 2).clamp(0, HITBOX_INSET_MAX as i32) as u8; }
+    pub fn cycle_grace(&mut self, step: i32) { self.hit_grace = (self.hit_grace as i32 + step).clamp(0, HIT_GRACE_MAX as i32) as u8; }
+}
+
+// —— 一键预设 ——
+#[derive(Clone, Copy, PartialEq)]
+pub enum A11yPreset { Standard, LowVision, Motor, Photosensitive, CognitiveLoad }
+
+pub const PRESETS: [A11yPreset; 5] = [
+    A11yPreset::Standard,
+    A11yPreset::LowVision,
+    A11yPreset::Motor,
+    A11yPreset::Photosensitive,
+    A11yPreset::CognitiveLoad,
+];
+
+impl A11yPreset {
+    pub fn label(self) -> &'static str {
+        crate::i18n::tr(match self {
+            A11yPreset::Standard => "标准",
+            A11yPreset::LowVision => "低视力",
+            A11yPreset::Motor => "运动障碍",
+            A11yPreset::Photosensitive => "光敏",
+            A11yPreset::CognitiveLoad => "降低认知负担",
+        })
+    }
+    // 预设只是一组开关的起点，选完仍可逐项微调；按键、音量等其他设置保持不变
+    pub fn settings(self, cur: Settings) -> Settings {
+        let base = Settings { high_contrast: false, contrast_outlines: false, speed_scale: 1.0, reduced_motion: false, large_text: false, audio_cues: false, powerup_glyphs: false, assist: Assist::Off, ..cur };
+        match self {
+            A11yPreset::Standard => base,
+            A11yPreset::LowVision => Settings { high_contrast: true, large_text: true, audio_cues: true, powerup_glyphs: true, ..base },
+            A11yPreset::Motor => Settings { speed_scale: 0.7, assist: Assist::Seek, ..base },
+            A11yPreset::Photosensitive => Settings { reduced_motion: true, ..base },
+            A11yPreset::CognitiveLoad => Settings { speed_scale: 0.8, reduced_motion: true, large_text: true, ..base },
+        }
+    }
+}
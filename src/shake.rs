@@ -0,0 +1,118 @@
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// ===== 震屏（创伤值模型）=====
+// 事件不直接给偏移，而是往“创伤值”（0–1）里加一笔，各事件加多少见 assets/shake.json（缺文件用下面的默认值）。
+// 画面偏移幅度 = 创伤值²：小磕碰几乎看不出，叠上几下或撞死才会猛震；偏移和轻微转动取自平滑噪声，不会一帧一跳。
+// 创伤值按指数衰减。最终幅度再乘手感里的震屏倍率和无障碍里的震屏总强度，减少动态效果时为 0。
+// 只是画面：创伤值不参与任何判定，噪声按真实时间取，不消耗玩法随机数。
+pub const SHAKE_PATH: &str = "assets/shake.json";
+const DECAY: f32 = 2.5;       // 每秒衰减到 e^-DECAY
+const CUTOFF: f32 = 0.005;    // 低于这个直接归零
+const MAX_OFFSET: f32 = 14.0; // 创伤值满时的最大偏移（场地像素）
+const MAX_ROLL: f32 = 1.5;    // 创伤值满时的最大转动（度）
+const FREQ: f32 = 16.0;       // 噪声每秒变化的次数
+
+// 每种事件加多少创伤值
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShakeTuning {
+    pub bump: f32,      // 禅模式 / 教程里轻轻弹开
+    pub shield: f32,    // 护盾挡下
+    pub bomb: f32,      // 炸弹引爆
+    pub life_lost: f32, // 扣一条命
+    pub rewind: f32,    // 时光倒流
+    pub down: f32,      // 合作里队友倒地
+    pub death: f32,     // 撞死
+}
+
+impl Default for ShakeTuning {
+    fn default() -> Self {
+        Self { bump: 0.3, shield: 0.45, bomb: 0.55, life_lost: 0.6, rewind: 0.6, down: 0.6, death: 1.0 }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Jolt { Bump, Shield, Bomb, LifeLost, Rewind, Down, Death }
+
+impl ShakeTuning {
+    pub fn load(path: &str) -> Self {
+        crate::storage::read_asset_string(path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    fn trauma(&self, jolt: Jolt) -> f32 {
+        match jolt {
+            Jolt::Bump => self.bump,
+            Jolt::Shield => self.shield,
+            Jolt::Bomb => self.bomb,
+            Jolt::LifeLost => self.life_lost,
+            Jolt::Rewind => self.rewind,
+            Jolt::Down => self.down,
+            Jolt::Death => self.death,
+        }
+    }
+}
+
+// 一帧的镜头偏移：平移（场地像素）和转动（度）
+#[derive(Clone, Copy, Default)]
+pub struct Offset {
+    pub pos: Vec2,
+    pub roll: f32,
+}
+
+impl Offset {
+    pub fn camera(self, cam: Camera2D) -> Camera2D {
+        Camera2D { target: cam.target + self.pos, rotation: cam.rotation + self.roll, ..cam }
+    }
+}
+
+pub struct Shake {
+    trauma: f32,
+    tuning: ShakeTuning,
+}
+
+impl Shake {
+    pub fn new(tuning: ShakeTuning) -> Self { Self { trauma: 0.0, tuning } }
+
+    pub fn add(&mut self, jolt: Jolt) { self.trauma = (self.trauma + self.tuning.trauma(jolt)).min(1.0); }
+    pub fn clear(&mut self) { self.trauma = 0.0; }
+    // 手柄震动按创伤值定力度
+    #[cfg(feature = "gamepad")]
+    pub fn trauma(&self) -> f32 { self.trauma }
+    // 实际震动幅度（0–1）
+    pub fn amount(&self) -> f32 { self.trauma * self.trauma }
+
+    pub fn update(&mut self, dt: f32) {
+        self.trauma *= (-DECAY * dt).exp();
+        if self.trauma < CUTOFF { self.trauma = 0.0; }
+    }
+
+    // scale 是手感倍率 × 总强度；t 是真实时间（秒）
+    pub fn offset(&self, scale: f32, t: f32) -> Offset {
+        let k = self.amount() * scale;
+        if k <= 0.0 { return Offset::default(); }
+        let x = t * FREQ;
+        Offset { pos: vec2(noise(x, 1), noise(x, 2)) * MAX_OFFSET * k, roll: noise(x, 3) * MAX_ROLL * k }
+    }
+}
+
+// 整数格点上的伪随机值（-1..1）
+fn lattice(i: i32, seed: u32) -> f32 {
+    let mut h = (i as u32).wrapping_mul(0x9E37_79B1) ^ seed.wrapping_mul(0x85EB_CA6B);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x2C1B_3C6D);
+    h ^= h >> 12;
+    h as f32 / u32::MAX as f32 * 2.0 - 1.0
+}
+
+// 一维平滑值噪声：格点之间用 smoothstep 插值
+fn noise(x: f32, seed: u32) -> f32 {
+    let i = x.floor();
+    let f = x - i;
+    let f = f * f * (3.0 - 2.0 * f);
+    let (a, b) = (lattice(i as i32, seed), lattice(i as i32 + 1, seed));
+    a + (b - a) * f
+}
@@ -0,0 +1,124 @@
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::collision::{self, Layer};
+use crate::feel::Feel;
+use crate::{award_points_at, rects_overlap, Game, PLAYER_Y};
+
+// ===== 射击（可选规则）=====
+// 空格向上发射一颗慢速子弹，弹药靠 Ammo 道具补充。
+// 障碍分三种：普通的挨一发会裂开，裂开的再挨一发就碎（加分），装甲的打不动、只吃掉子弹。
+const SHOT_W: f32 = 6.0;
+const SHOT_H: f32 = 14.0;
+const SHOT_SPEED: f32 = 420.0;
+const FIRE_COOLDOWN: f32 = 0.2; // 同一帧的多个固定步里 is_key_pressed 会重复为真
+pub const START_AMMO: u32 = 3;
+pub const AMMO_PICKUP: u32 = 4;
+pub const MAX_AMMO: u32 = 12;
+const SHATTER_BONUS: i32 = 5;
+// 开启射击时新障碍的种类概率
+const CRACKED_CHANCE: f32 = 0.2;
+const ARMORED_CHANCE: f32 = 0.15;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum Armor {
+    #[default]
+    Plain,
+    Cracked,
+    Armored,
+}
+
+// roll 为 0~1 随机数
+pub fn roll_armor(roll: f32) -> Armor {
+    if roll < CRACKED_CHANCE { Armor::Cracked }
+    else if roll < CRACKED_CHANCE + ARMORED_CHANCE { Armor::Armored }
+    else { Armor::Plain }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct Projectile {
+    #[serde(with = "crate::suspend::RectDef")]
+    pub rect: Rect,
+}
+
+pub struct ProjectilePool {
+    pub live: Vec<Projectile>,
+    dead: Vec<Projectile>,
+}
+
+impl ProjectilePool {
+    pub fn new() -> Self { Self { live: Vec::new(), dead: Vec::new() } }
+    pub fn clear(&mut self) { self.dead.append(&mut self.live); }
+    pub fn counts(&self) -> (usize, usize) { (self.live.len(), self.dead.len()) }
+    fn spawn(&mut self, rect: Rect) {
+        match self.dead.pop() {
+            Some(mut p) => { p.rect = rect; self.live.push(p); }
+            None => self.live.push(Projectile { rect }),
+        }
+    }
+}
+
+pub fn fire(game: &mut Game) {
+    if game.ammo == 0 || game.fire_cd > 0.0 { return; }
+    game.ammo -= 1;
+    game.fire_cd = FIRE_COOLDOWN;
+    let c = game.player.rect().center();
+    game.shots.spawn(Rect::new(c.x - SHOT_W * 0.5, PLAYER_Y - SHOT_H, SHOT_W, SHOT_H));
+}
+
+// 子弹上移并与障碍结算
+pub fn update(game: &mut Game, feel: &Feel, dt: f32) {
+    game.fire_cd = (game.fire_cd - dt).max(0.0);
+    let mut i = 0;
+    while i < game.shots.live.len() {
+        let shot = &mut game.shots.live[i];
+        shot.rect.y -= SHOT_SPEED * dt;
+        let r = shot.rect;
+        let target = game.obs.live.iter().position(|o| collision::interacts(Layer::PROJECTILE, o.layer()) && rects_overlap(o.rect, r));
+        if let Some(j) = target {
+            let o = &mut game.obs.live[j];
+            match o.armor {
+                Armor::Plain => {
+                    o.armor = Armor::Cracked;
+                    game.effects.burst(feel, r.center(), LIGHTGRAY, 4);
+                }
+                Armor::Cracked => {
+                    let dead = game.obs.live.swap_remove(j);
+                    game.effects.burst(feel, dead.rect.center(), ORANGE, 14);
+                    game.anims.crumble(dead.rect);
+                    award_points_at(game, SHATTER_BONUS, dead.rect.center(), ORANGE);
+                    game.obs.dead.push(dead);
+                }
+                Armor::Armored => game.effects.burst(feel, r.center(), GRAY, 3),
+            }
+        }
+        if target.is_some() || r.y + r.h < 0.0 {
+            let dead = game.shots.live.swap_remove(i);
+            game.shots.dead.push(dead);
+        } else {
+            i += 1;
+        }
+    }
+}
+
+// 障碍上的裂纹 / 装甲板
+pub fn draw_armor(r: Rect, armor: Armor) {
+    match armor {
+        Armor::Plain => {}
+        Armor::Cracked => {
+            let c = Color::new(0.1, 0.1, 0.1, 0.8);
+            draw_line(r.x + r.w * 0.2, r.y + r.h * 0.1, r.x + r.w * 0.5, r.y + r.h * 0.55, 2.0, c);
+            draw_line(r.x + r.w * 0.5, r.y + r.h * 0.55, r.x + r.w * 0.35, r.y + r.h * 0.9, 2.0, c);
+            draw_line(r.x + r.w * 0.5, r.y + r.h * 0.55, r.x + r.w * 0.85, r.y + r.h * 0.4, 2.0, c);
+        }
+        Armor::Armored => {
+            draw_rectangle_lines(r.x + 3.0, r.y + 3.0, r.w - 6.0, r.h - 6.0, 3.0, Color::new(0.75, 0.78, 0.82, 0.95));
+        }
+    }
+}
+
+pub fn draw(game: &Game) {
+    for s in &game.shots.live {
+        draw_rectangle(s.rect.x, s.rect.y, s.rect.w, s.rect.h, YELLOW);
+    }
+}
@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+
+// ===== 商店（assets/shop.json）+ 钱包 =====
+// 金币和分数分开算，跨局累计存进存档；商店用金币买永久解锁。
+// 目录全由数据驱动：每件商品一个 key（存档里按 key 记已购）、价格和解锁内容。
+pub const SHOP_PATH: &str = "assets/shop.json";
+
+// 开局天赋
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Perk {
+    StartShield, // 开局多 1 层护盾
+    CoinMagnet,  // 附近的金币被吸过来
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Unlock {
+    Perk { perk: Perk },
+    Cosmetic,                // 外观（key 对应 assets/cosmetics.json 里的皮肤/主题），在换装界面选用
+    LifeCap,                 // 命数上限 +1
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ShopItem {
+    pub key: String,
+    pub name: String,
+    pub desc: String,
+    pub price: u32,
+    pub unlock: Unlock,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Catalog {
+    pub items: Vec<ShopItem>,
+}
+
+fn item(key: &str, name: &str, desc: &str, price: u32, unlock: Unlock) -> ShopItem {
+    ShopItem { key: key.into(), name: name.into(), desc: desc.into(), price, unlock }
+}
+
+impl Default for Catalog {
+    fn default() -> Self {
+        Self {
+            items: vec![
+                item("perk_shield", "初始护盾", "开局多 1 层护盾", 60, Unlock::Perk { perk: Perk::StartShield }),
+                item("perk_magnet", "金币磁铁", "附近的金币会被吸过来", 40, Unlock::Perk { perk: Perk::CoinMagnet }),
+                item("life_cap", "命数上限", "命数上限 +1", 120, Unlock::LifeCap),
+                item("skin_ember", "余烬", "橙红色外观", 25, Unlock::Cosmetic),
+                item("skin_violet", "紫罗兰", "紫色外观", 25, Unlock::Cosmetic),
+            ],
+        }
+    }
+}
+
+impl Catalog {
+    pub fn load(path: &str) -> Self {
+        crate::storage::read_asset_string(path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Wallet {
+    pub coins: u32,
+    pub owned: Vec<String>, // 已购商品的 key
+}
+
+impl Wallet {
+    pub fn owns(&self, key: &str) -> bool { self.owned.iter().any(|k| k == key) }
+
+    // 买得起且没买过才成交
+    pub fn buy(&mut self, item: &ShopItem) -> bool {
+        if self.owns(&item.key) || self.coins < item.price { return false; }
+        self.coins -= item.price;
+        self.owned.push(item.key.clone());
+        true
+    }
+
+    fn owned_items<'a>(&'a self, cat: &'a Catalog) -> impl Iterator<Item = &'a ShopItem> {
+        cat.items.iter().filter(|i| self.owns(&i.key))
+    }
+    pub fn has_perk(&self, cat: &Catalog, perk: Perk) -> bool {
+        self.owned_items(cat).any(|i| matches!(i.unlock, Unlock::Perk { perk: p } if p == perk))
+    }
+    pub fn extra_lives(&self, cat: &Catalog) -> u32 {
+        self.owned_items(cat).filter(|i| matches!(i.unlock, Unlock::LifeCap)).count() as u32
+    }
+}
@@ -0,0 +1,199 @@
+use std::net::UdpSocket;
+
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::effects::Effects;
+use crate::i18n::{tr, trf};
+use crate::trace::is_key_pressed;
+use crate::{draw_obstacles, draw_player, draw_powerups, draw_text_center, Game, GameMode, Player, PowerUpKind, Resources};
+
+// ===== 局域网观战 =====
+// 主机在开局前按 [B] 打开直播后，每个渲染帧把场上的状态压成一个小快照，UDP 广播到局域网；
+// 另一台（或同一台的另一个窗口）从菜单按 [V] 进观战，收到就画，延迟大约一帧。
+// 快照只为了画面：玩家、障碍外框、道具和几项计数，观战端不跑模拟。
+// 设 DODGE_SPECTATE_ADDR（ip:端口）时改为发给这一个地址，用于不转发广播的网络。
+pub const PORT: u16 = 47801;
+const ADDR_ENV: &str = "DODGE_SPECTATE_ADDR";
+const IDLE_EVERY: f64 = 0.5; // 不在局内时只偶尔发一帧，让观战端知道主机还在
+const SILENT: f64 = 3.0;     // 这么久没收到算主机下线
+const OBS_MAX: usize = 96;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Phase { Idle, Playing, Paused, Over }
+
+#[derive(Serialize, Deserialize)]
+pub struct Frame {
+    seq: u64,
+    name: String,
+    phase: Phase,
+    label: String, // 模式标签
+    field: (f32, f32),
+    player: Player,
+    obs: Vec<[f32; 4]>,
+    pus: Vec<(f32, f32, PowerUpKind)>,
+    score: i32,
+    lives: u32,
+    shield: u32,
+    invuln: f32,
+    elapsed: f32,
+}
+
+// 主机端：直播开着时才有 socket
+pub struct Caster {
+    sock: UdpSocket,
+    target: String,
+    seq: u64,
+    last_sent: f64,
+}
+
+// 观战端
+pub struct Viewer {
+    sock: UdpSocket,
+    frame: Option<Frame>,
+    last_heard: f64,
+}
+
+#[derive(Default)]
+pub struct Spectate {
+    caster: Option<Caster>,
+    viewer: Option<Viewer>,
+    pub msg: Option<String>,
+}
+
+fn now() -> f64 { macroquad::miniquad::date::now() }
+
+impl Caster {
+    fn open() -> Result<Self, String> {
+        let sock = UdpSocket::bind(("0.0.0.0", 0)).map_err(|e| e.to_string())?;
+        sock.set_broadcast(true).map_err(|e| e.to_string())?;
+        let target = std::env::var(ADDR_ENV).unwrap_or_else(|_| format!("255.255.255.255:{}", PORT));
+        Ok(Self { sock, target, seq: 0, last_sent: 0.0 })
+    }
+}
+
+fn phase_of(game: &Game) -> Phase {
+    match game.mode {
+        GameMode::Playing => Phase::Playing,
+        GameMode::Paused | GameMode::Feedback => Phase::Paused,
+        GameMode::Killcam | GameMode::GameOver => Phase::Over,
+        _ => Phase::Idle,
+    }
+}
+
+// 每个渲染帧调用：设置里开了直播就发一帧
+pub fn broadcast(game: &mut Game) {
+    if !game.settings.broadcast {
+        game.spectate.caster = None;
+        return;
+    }
+    if game.spectate.caster.is_none() {
+        match Caster::open() {
+            Ok(c) => game.spectate.caster = Some(c),
+            Err(e) => {
+                crate::logfile::warn(&format!("观战直播打不开：{}", e));
+                game.settings.broadcast = false;
+                return;
+            }
+        }
+    }
+    let phase = phase_of(game);
+    let Some(c) = game.spectate.caster.as_ref() else { return };
+    if phase == Phase::Idle && now() - c.last_sent < IDLE_EVERY { return; }
+    let frame = Frame {
+        seq: c.seq + 1,
+        name: game.player_name.clone(),
+        phase,
+        label: game.mode_label(),
+        field: (game.field.x, game.field.y),
+        player: game.player.clone(),
+        obs: game.obs.live.iter().take(OBS_MAX).map(|o| [o.rect.x, o.rect.y, o.rect.w, o.rect.h]).collect(),
+        pus: game.pus.live.iter().map(|p| (p.rect.x, p.rect.y, p.kind)).collect(),
+        score: game.score,
+        lives: game.lives,
+        shield: game.shield,
+        invuln: game.invuln,
+        elapsed: game.elapsed,
+    };
+    let Some(c) = game.spectate.caster.as_mut() else { return };
+    let _ = c.sock.send_to(&serde_json::to_vec(&frame).unwrap(), &c.target);
+    c.seq = frame.seq;
+    c.last_sent = now();
+}
+
+// 菜单 [V]：开始收直播
+pub fn enter(game: &mut Game) {
+    game.spectate.msg = None;
+    match UdpSocket::bind(("0.0.0.0", PORT)).and_then(|s| s.set_nonblocking(true).map(|_| s)) {
+        Ok(sock) => game.spectate.viewer = Some(Viewer { sock, frame: None, last_heard: 0.0 }),
+        Err(e) => game.spectate.msg = Some(trf("端口 {} 打不开：{}", &[&PORT, &e])),
+    }
+    game.obs.clear_all();
+    game.pus.live.clear();
+    game.effects = Effects::new(0);
+    game.mode = GameMode::Spectate;
+}
+
+// 每帧：收完积压的包只留最新的一帧，套进本地状态给绘制用；[ESC] 返回菜单
+pub fn update(game: &mut Game) {
+    if is_key_pressed(KeyCode::Escape) {
+        game.spectate.viewer = None;
+        game.obs.clear_all();
+        game.pus.live.clear();
+        game.mode = GameMode::Menu;
+        return;
+    }
+    let Some(v) = game.spectate.viewer.as_mut() else { return };
+    let mut buf = [0u8; 65536];
+    let mut latest: Option<Frame> = None;
+    while let Ok(n) = v.sock.recv(&mut buf) {
+        let Ok(f) = serde_json::from_slice::<Frame>(&buf[..n]) else { continue };
+        // 同一批里乱序到达的旧帧丢掉
+        if latest.as_ref().is_none_or(|l| f.seq > l.seq) { latest = Some(f); }
+    }
+    let Some(f) = latest else { return };
+    v.last_heard = now();
+    game.field = vec2(f.field.0, f.field.1);
+    game.player = f.player.clone();
+    game.obs.clear_all();
+    for r in &f.obs { game.obs.spawn(Rect::new(r[0], r[1], r[2], r[3]), 0.0, 0.0); }
+    game.pus.live.clear();
+    for &(x, y, kind) in &f.pus { game.pus.spawn(x, y, kind); }
+    game.score = f.score;
+    game.lives = f.lives;
+    game.shield = f.shield;
+    game.invuln = f.invuln;
+    game.elapsed = f.elapsed;
+    v.frame = Some(f);
+}
+
+pub fn draw(game: &Game, res: &Resources) {
+    let font = &res.font;
+    let ts = game.settings.text_scale();
+    let w = game.field.x;
+    let live = game.spectate.viewer.as_ref().and_then(|v| v.frame.as_ref().filter(|_| now() - v.last_heard < SILENT));
+    let Some(f) = live.filter(|f| f.phase != Phase::Idle) else {
+        draw_text_center(font, w, tr("观战"), 90.0, 40.0 * ts, SKYBLUE);
+        let status = match (&game.spectate.msg, live) {
+            (Some(e), _) => e.clone(),
+            (None, Some(f)) => trf("{} 在菜单里，等待开局…", &[&f.name]),
+            (None, None) => trf("正在局域网里找直播（端口 {}）…  主机开局前按 [B] 打开直播", &[&PORT]),
+        };
+        draw_text_center(font, w, &status, 250.0, 22.0 * ts, LIGHTGRAY);
+        draw_text_center(font, w, tr("[ESC] 返回"), game.field.y - 40.0, 20.0 * ts, ORANGE);
+        return;
+    };
+    draw_player(game, res);
+    draw_obstacles(game, res);
+    draw_powerups(game, res);
+    draw_rectangle(0.0, 0.0, w, 46.0 * ts, Color::from_rgba(20, 24, 32, 220));
+    let t = f.elapsed as u32;
+    let hud = trf("观战：{}（{}）   SCORE {}   ♥{}   盾{}   {:02}:{:02}", &[&f.name, &f.label, &f.score, &f.lives, &f.shield, &(t / 60), &(t % 60)]);
+    draw_text_ex(&hud, 16.0, 30.0 * ts, TextParams { font: Some(font), font_size: (22.0 * ts) as u16, color: YELLOW, ..Default::default() });
+    match f.phase {
+        Phase::Paused => draw_text_center(font, w, tr("主机暂停中"), 300.0, 28.0 * ts, YELLOW),
+        Phase::Over => draw_text_center(font, w, &trf("游戏结束：{} 分", &[&f.score]), 280.0, 36.0 * ts, RED),
+        _ => {}
+    }
+    draw_text_center(font, w, tr("[ESC] 退出观战"), game.field.y - 16.0, 18.0 * ts, GRAY);
+}
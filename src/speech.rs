@@ -0,0 +1,80 @@
+use tts::Tts;
+
+use crate::i18n::trf;
+use crate::settings::Lang;
+use crate::{main_items, options, pause_items, play_items, Game, GameMode};
+
+// ===== 朗读（tts 特性）=====
+// 无障碍里打开“朗读菜单”后，用系统的语音把菜单里当前选中的项、结算成绩和底部提示条念出来，看不清屏幕也能走菜单、听到分数。
+// 经 tts 库走各平台的朗读接口：Windows 有读屏软件在跑就交给它（Tolk），否则用 WinRT 语音；macOS 用 AVFoundation；Linux 用 Speech Dispatcher。
+// 嗓音跟着界面语言挑（先找语言和地区都对的，再找语言对的），英文界面不会用中文嗓音去念；读屏软件用它自己的设置。
+// 初始化失败就安静地不念（日志里记一条）。新的一句会打断还没念完的上一句，菜单上快速翻动时只听到停下的那项。
+// 每帧在处理完输入后看一眼要念的内容有没有变，变了才念；局内（除了结算）不念，免得干扰。
+
+#[derive(Default)]
+pub struct Speech {
+    tts: Option<Tts>, // 第一次要念时才初始化；失败就一直是 None
+    tried: bool,
+    voice_for: Option<Lang>, // 当前嗓音是按哪种语言挑的
+    focus: Option<String>, // 上次念过的选中项 / 结算
+    toast: Option<String>,
+}
+
+impl Speech {
+    fn say(&mut self, text: &str, lang: Lang) {
+        if !self.tried {
+            self.tried = true;
+            match Tts::default() {
+                Ok(t) => self.tts = Some(t),
+                Err(e) => crate::logfile::warn(&format!("系统朗读不可用，朗读已停用：{}", e)),
+            }
+        }
+        let Some(tts) = self.tts.as_mut() else { return };
+        if self.voice_for != Some(lang) {
+            self.voice_for = Some(lang);
+            pick_voice(tts, lang);
+        }
+        if let Err(e) = tts.speak(text, true) { crate::logfile::warn(&format!("朗读失败：{}", e)); }
+    }
+}
+
+// 没有合适的嗓音、或后端不让选（比如交给读屏软件时）就用系统默认的
+fn pick_voice(tts: &mut Tts, lang: Lang) {
+    let Ok(voices) = tts.voices() else { return };
+    let code = lang.code();
+    let primary = code.split('-').next().unwrap_or(code);
+    let exact = voices.iter().find(|v| v.language().as_str().eq_ignore_ascii_case(code));
+    let Some(voice) = exact.or_else(|| voices.iter().find(|v| v.language().primary_language().eq_ignore_ascii_case(primary))) else {
+        crate::logfile::warn(&format!("没有 {} 的朗读嗓音，用系统默认的", code));
+        return;
+    };
+    let _ = tts.set_voice(voice);
+}
+
+// 当前界面上该念的“焦点”：选中的菜单项、设置行，或结算成绩
+fn focus(game: &Game) -> Option<String> {
+    match game.mode {
+        GameMode::Menu if game.play_menu => play_items().get(game.play_list.cursor).map(|it| it.label.to_string()),
+        GameMode::Menu => main_items().get(game.main_menu.cursor).map(|it| it.label.to_string()),
+        GameMode::Paused => pause_items(game).get(game.pause_menu.cursor).map(|it| trf("已暂停，{}", &[&it.label])),
+        GameMode::Settings => Some(options::focus(game)),
+        GameMode::GameOver => Some(trf("游戏结束，得分 {}，最高 {}，躲过 {}", &[&game.score, &game.best(), &game.dodged])),
+        _ => None,
+    }
+}
+
+// 每帧调用
+pub fn tick(game: &mut Game) {
+    if !game.settings.narrate { return; }
+    let lang = game.settings.lang;
+    let toast = game.toast.text().map(str::to_string);
+    if toast.is_some() && toast != game.speech.toast {
+        game.speech.say(toast.as_deref().unwrap_or_default(), lang);
+    }
+    game.speech.toast = toast;
+    let now = focus(game);
+    if now.is_some() && now != game.speech.focus {
+        game.speech.say(now.as_deref().unwrap_or_default(), lang);
+    }
+    game.speech.focus = now;
+}
@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+
+use macroquad::prelude::*;
+use serde::Deserialize;
+
+use crate::anim::AnimDef;
+
+// ===== 贴图集 =====
+// 可选：assets/sprites/atlas.json 列出若干张图集页（PNG）和每个贴图在哪一页、哪个矩形，例如
+//   { "pages": ["page0.png"], "sprites": { "player": { "page": 0, "rect": [0, 0, 96, 24] } } }
+// 页的路径相对 assets/sprites。贴图按灰度画，画的时候乘上皮肤 / 主题 / 道具的颜色，换色不用另出图。
+// 帧动画也写在这个文件里，见 anim.rs。
+// 缺文件、缺页、缺某个贴图都不算错：缺的那样东西照旧用矩形和圆画。高对比度模式一律用纯色图形。
+// 一组东西（比如全部障碍）先收进 Batch，按页排好再画：同一页的贴图挨着提交，macroquad 会并成一批。
+pub const ATLAS_PATH: &str = "assets/sprites/atlas.json";
+
+#[derive(Deserialize)]
+struct Manifest {
+    pages: Vec<String>,
+    sprites: HashMap<String, SpriteDef>,
+    #[serde(default)]
+    animations: HashMap<String, AnimDef>,
+}
+
+#[derive(Deserialize)]
+struct SpriteDef {
+    page: usize,
+    rect: [f32; 4], // 页内像素 x, y, w, h
+}
+
+#[derive(Clone, Copy)]
+struct Sprite { page: usize, src: Rect }
+
+#[derive(Default)]
+pub struct Atlas {
+    pages: Vec<Texture2D>,
+    sprites: HashMap<String, Sprite>,
+    anims: HashMap<String, AnimDef>,
+}
+
+impl Atlas {
+    pub fn load(path: &str) -> Self {
+        let Ok(text) = crate::storage::read_asset_string(path) else { return Self::default() };
+        let m = match serde_json::from_str::<Manifest>(&text) {
+            Ok(m) => m,
+            Err(e) => {
+                crate::logfile::warn(&format!("贴图集 {} 解析失败：{}", path, e));
+                return Self::default();
+            }
+        };
+        let dir = std::path::Path::new(path).parent().unwrap_or(std::path::Path::new("."));
+        let mut pages = Vec::new();
+        for p in &m.pages {
+            let Ok(bytes) = crate::storage::read_asset(dir.join(p)) else {
+                crate::logfile::warn(&format!("贴图集缺少图集页 {}，改用图形", p));
+                return Self::default();
+            };
+            let tex = Texture2D::from_file_with_format(&bytes, None);
+            tex.set_filter(FilterMode::Linear);
+            pages.push(tex);
+        }
+        let sprites = m.sprites.into_iter()
+            .filter(|(_, d)| d.page < pages.len())
+            .map(|(k, d)| (k, Sprite { page: d.page, src: Rect::new(d.rect[0], d.rect[1], d.rect[2], d.rect[3]) }))
+            .collect();
+        Self { pages, sprites, anims: m.animations }
+    }
+
+    pub fn has(&self, name: &str) -> bool { self.sprites.contains_key(name) || self.anims.contains_key(name) }
+    pub fn anim(&self, name: &str) -> Option<&AnimDef> { self.anims.get(name) }
+
+    // 有同名动画时取它在 t 秒时的帧，否则就是这张贴图本身
+    pub fn frame<'a>(&'a self, name: &'a str, t: f32) -> &'a str {
+        self.anim(name).and_then(|d| d.frame_at(t)).unwrap_or(name)
+    }
+
+    pub fn batch(&self) -> Batch<'_> { Batch { atlas: self, items: Vec::new() } }
+}
+
+pub struct Batch<'a> {
+    atlas: &'a Atlas,
+    items: Vec<(Sprite, Rect, Color)>,
+}
+
+impl Batch<'_> {
+    // 收下一个贴图；图集里没有时返回 false，调用方自己画图形
+    pub fn push(&mut self, name: &str, dest: Rect, tint: Color) -> bool {
+        let Some(s) = self.atlas.sprites.get(name) else { return false };
+        self.items.push((*s, dest, tint));
+        true
+    }
+
+    // 按页排好一次画完（排序稳定，同一页内保持放进来的先后）
+    pub fn flush(mut self) {
+        self.items.sort_by_key(|(s, _, _)| s.page);
+        for (s, dest, tint) in &self.items {
+            let params = DrawTextureParams { dest_size: Some(dest.size()), source: Some(s.src), ..Default::default() };
+            draw_texture_ex(&self.atlas.pages[s.page], dest.x, dest.y, *tint, params);
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use macroquad::prelude::Color;
+use serde::{Deserialize, Serialize};
+
+// ===== 关卡阶段表（assets/stages.json）=====
+// 每隔 stage_length 秒进入下一阶段；超出表尾后停留在最后一个阶段，编号继续增长。
+pub const STAGES_PATH: &str = "assets/stages.json";
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Hazard {
+    Block,   // 普通方块
+    Drifter, // 斜向漂移
+    Dart,    // 小而快
+    Slab,    // 宽而扁
+    Well,    // 引力井（稀有，见 gravity.rs）
+}
+
+fn one() -> f32 { 1.0 }
+fn default_pu_chance() -> f32 { 0.30 }
+fn default_length() -> f32 { 30.0 }
+
+#[derive(Clone, Deserialize)]
+pub struct StageDef {
+    pub name: String,
+    pub background: [u8; 3],
+    pub obstacle: [u8; 3],
+    #[serde(default = "one")]
+    pub fall_mul: f32,
+    #[serde(default = "one")]
+    pub spawn_mul: f32,
+    #[serde(default = "default_pu_chance")]
+    pub powerup_chance: f32,
+    #[serde(default)]
+    pub unlock: Option<Hazard>,
+}
+
+impl StageDef {
+    pub fn bg_color(&self) -> Color { rgb(self.background) }
+    pub fn obstacle_color(&self) -> Color { rgb(self.obstacle) }
+}
+
+fn rgb(c: [u8; 3]) -> Color { Color::from_rgba(c[0], c[1], c[2], 255) }
+
+#[derive(Clone, Deserialize)]
+pub struct StageTable {
+    #[serde(default = "default_length")]
+    pub stage_length: f32,
+    pub stages: Vec<StageDef>,
+}
+
+impl Default for StageTable {
+    // 找不到配置时的单阶段兜底，保持原本的配色
+    fn default() -> Self {
+        Self {
+            stage_length: default_length(),
+            stages: vec![StageDef {
+                name: "夜空".to_string(),
+                background: [14, 17, 22],
+                obstacle: [255, 100, 100],
+                fall_mul: 1.0,
+                spawn_mul: 1.0,
+                powerup_chance: default_pu_chance(),
+                unlock: None,
+            }],
+        }
+    }
+}
+
+impl StageTable {
+    pub fn load(path: &str) -> Self {
+        let Ok(text) = crate::storage::read_asset_string(path) else { return Self::default() };
+        match serde_json::from_str::<StageTable>(&text) {
+            Ok(t) if !t.stages.is_empty() && t.stage_length > 0.0 => t,
+            Ok(_) => Self::default(),
+            Err(e) => {
+                crate::logfile::warn(&format!("阶段表 {} 解析失败：{}", path, e));
+                Self::default()
+            }
+        }
+    }
+    // 第几阶段（从 0 开始，不封顶）
+    pub fn stage_at(&self, elapsed: f32) -> usize { (elapsed / self.stage_length) as usize }
+    pub fn def(&self, stage: usize) -> &StageDef { &self.stages[stage.min(self.stages.len() - 1)] }
+    // 到该阶段为止解锁的所有危险类型（普通方块总是可用）
+    pub fn hazards(&self, stage: usize, out: &mut Vec<Hazard>) {
+        out.clear();
+        out.push(Hazard::Block);
+        for s in self.stages.iter().take(stage + 1) {
+            if let Some(h) = s.unlock {
+                if !out.contains(&h) { out.push(h); }
+            }
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{PowerUpKind, PU_KINDS};
+
+// ===== 本局统计 + 结算界面用的“历史最佳” =====
+// 局内随事件累加；结束时与存档里的历史最佳比较，刷新的项在结算界面高亮。
+#[derive(Clone, Copy, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct RunStats {
+    pub elapsed: f32,            // 结束时填入
+    pub dodged: u32,             // 结束时填入
+    pub near_misses: u32,
+    pub pickups: [u32; PU_KINDS], // 按道具种类计数（下标为 PowerUpKind as usize）
+    pub shields_used: u32,
+    pub max_combo: u32,
+    pub deaths: u32, // 掉命次数（含最后一条）
+}
+
+impl RunStats {
+    pub fn picked(&mut self, kind: PowerUpKind) { self.pickups[kind as usize] += 1; }
+    pub fn total_pickups(&self) -> u32 { self.pickups.iter().sum() }
+}
+
+// 生涯累计：每局结束时并入，存进存档
+#[derive(Clone, Copy, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Lifetime {
+    pub playtime: f64,     // 秒
+    pub runs: u32,
+    pub total_score: i64,
+    pub deaths: u32,
+    pub pickups: [u32; PU_KINDS],
+    pub longest_run: f32,
+}
+
+impl Lifetime {
+    pub fn add(&mut self, s: &RunStats, score: i32) {
+        self.playtime += s.elapsed as f64;
+        self.runs += 1;
+        self.total_score += score.max(0) as i64;
+        self.deaths += s.deaths;
+        for (t, n) in self.pickups.iter_mut().zip(s.pickups) { *t += n; }
+        self.longest_run = self.longest_run.max(s.elapsed);
+    }
+}
+
+// 各项统计的历史最佳（只记普通模式、未续关的局）
+#[derive(Clone, Copy, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct StatBests {
+    pub elapsed: f32,
+    pub dodged: u32,
+    pub near_misses: u32,
+    pub max_combo: u32,
+}
+
+// 本局刷新了哪些最佳
+#[derive(Clone, Copy, Default)]
+pub struct NewBests {
+    pub score: bool,
+    pub elapsed: bool,
+    pub dodged: bool,
+    pub near_misses: bool,
+    pub max_combo: bool,
+}
+
+impl NewBests {
+    pub fn any(&self) -> bool { self.score || self.elapsed || self.dodged || self.near_misses || self.max_combo }
+}
+
+impl StatBests {
+    // 用本局成绩刷新历史最佳，返回刷新了哪些项
+    pub fn record(&mut self, s: &RunStats) -> NewBests {
+        let nb = NewBests {
+            score: false,
+            elapsed: s.elapsed > self.elapsed,
+            dodged: s.dodged > self.dodged,
+            near_misses: s.near_misses > self.near_misses,
+            max_combo: s.max_combo > self.max_combo,
+        };
+        self.elapsed = self.elapsed.max(s.elapsed);
+        self.dodged = self.dodged.max(s.dodged);
+        self.near_misses = self.near_misses.max(s.near_misses);
+        self.max_combo = self.max_combo.max(s.max_combo);
+        nb
+    }
+}
+
+// ===== 死亡位置记录（跨局累计，存进存档）=====
+// 每次游戏结束记一条：玩家位置、存活时间和当时的障碍情况。统计界面据此画热力条、按屏幕三等分求平均存活时间。
+const DEATH_LOG_MAX: usize = 500; // 只保留最近这么多条
+pub const HEAT_BINS: usize = 24;
+
+#[derive(Clone, Copy, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct DeathRecord {
+    pub x: f32,        // 玩家中心 / 场地宽度（0..1）
+    pub elapsed: f32,  // 存活时间
+    pub on_screen: u32, // 场上障碍数
+    pub killer_x: f32, // 撞上的障碍中心（0..1）
+    pub killer_w: f32, // 撞上的障碍宽度 / 场地宽度
+}
+
+#[derive(Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct DeathLog {
+    pub deaths: Vec<DeathRecord>,
+}
+
+impl DeathLog {
+    pub fn push(&mut self, d: DeathRecord) {
+        if self.deaths.len() >= DEATH_LOG_MAX { self.deaths.remove(0); }
+        self.deaths.push(d);
+    }
+
+    fn bin(x: f32, n: usize) -> usize { ((x.clamp(0.0, 1.0) * n as f32) as usize).min(n - 1) }
+
+    // 按横向位置分桶的死亡次数
+    pub fn heat(&self) -> [u32; HEAT_BINS] {
+        let mut h = [0; HEAT_BINS];
+        for d in &self.deaths { h[Self::bin(d.x, HEAT_BINS)] += 1; }
+        h
+    }
+
+    // 左/中/右三段的平均存活时间（没有记录的段为 None）
+    pub fn avg_by_third(&self) -> [Option<f32>; 3] {
+        let (mut sum, mut n) = ([0.0; 3], [0u32; 3]);
+        for d in &self.deaths {
+            let b = Self::bin(d.x, 3);
+            sum[b] += d.elapsed;
+            n[b] += 1;
+        }
+        std::array::from_fn(|i| (n[i] > 0).then(|| sum[i] / n[i] as f32))
+    }
+
+    pub fn avg_on_screen(&self) -> f32 {
+        if self.deaths.is_empty() { return 0.0; }
+        self.deaths.iter().map(|d| d.on_screen as f32).sum::<f32>() / self.deaths.len() as f32
+    }
+}
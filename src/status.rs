@@ -0,0 +1,88 @@
+use macroquad::prelude::*;
+
+use crate::hud::{Anchor, Hud};
+use crate::{score_mul, Game, PowerUpKind, FREEZE_DURATION, MULT_DURATION, SLOW_DURATION};
+
+// ===== 道具状态栏 =====
+// 顶栏右侧一排图标，生效中的才出现，从右往左排，新效果往里加一项就行。
+// 有时限的效果外面一圈随剩余时间缩短（叠加延长超过一次的时长时这圈先保持满），快到期时闪烁；角上的数字是层数。
+const RADIUS: f32 = 13.0;
+const SPACING: f32 = 36.0;
+const BLINK_BELOW: f32 = 1.5; // 剩这么多秒开始闪
+
+#[derive(Clone, Copy)]
+enum Icon { Shield, Clock, Star, Snow, Rewind }
+
+struct Status {
+    icon: Icon,
+    color: Color,
+    badge: Option<String>,
+    left: Option<(f32, f32)>, // (剩余秒数, 满额秒数)
+}
+
+fn collect(game: &Game) -> Vec<Status> {
+    let mut v = Vec::new();
+    let color = |kind| crate::powerup_color(game, kind);
+    if game.shield > 0 { v.push(Status { icon: Icon::Shield, color: color(PowerUpKind::Shield), badge: Some(game.shield.to_string()), left: None }); }
+    if game.slow.active() {
+        let badge = (game.slow.stacks > 1).then(|| format!("×{}", game.slow.stacks));
+        v.push(Status { icon: Icon::Clock, color: color(PowerUpKind::Slow), badge, left: Some((game.slow.remaining, SLOW_DURATION)) });
+    }
+    if game.mult.active() { v.push(Status { icon: Icon::Star, color: color(PowerUpKind::Multiplier), badge: Some(format!("x{}", score_mul(game))), left: Some((game.mult.remaining, MULT_DURATION)) }); }
+    if game.freeze.active() { v.push(Status { icon: Icon::Snow, color: color(PowerUpKind::Freeze), badge: None, left: Some((game.freeze.remaining, FREEZE_DURATION)) }); }
+    if game.rewind { v.push(Status { icon: Icon::Rewind, color: color(PowerUpKind::Rewind), badge: None, left: None }); }
+    v
+}
+
+fn draw_icon(icon: Icon, x: f32, y: f32, r: f32, color: Color) {
+    match icon {
+        Icon::Shield => {
+            draw_rectangle(x - r * 0.55, y - r * 0.6, r * 1.1, r * 0.6, color);
+            draw_triangle(vec2(x - r * 0.55, y), vec2(x + r * 0.55, y), vec2(x, y + r * 0.7), color);
+        }
+        Icon::Clock => {
+            draw_circle_lines(x, y, r * 0.6, 2.0, color);
+            draw_line(x, y, x, y - r * 0.45, 2.0, color);
+            draw_line(x, y, x + r * 0.35, y, 2.0, color);
+        }
+        Icon::Star => {
+            draw_poly(x, y, 3, r * 0.65, -90.0, color);
+            draw_poly(x, y, 3, r * 0.65, 90.0, color);
+        }
+        Icon::Snow => {
+            for a in [0.0f32, 60.0, 120.0] {
+                let d = vec2(a.to_radians().cos(), a.to_radians().sin()) * r * 0.65;
+                draw_line(x - d.x, y - d.y, x + d.x, y + d.y, 2.0, color);
+            }
+        }
+        Icon::Rewind => {
+            draw_triangle(vec2(x - r * 0.65, y), vec2(x, y - r * 0.45), vec2(x, y + r * 0.45), color);
+            draw_triangle(vec2(x, y), vec2(x + r * 0.65, y - r * 0.45), vec2(x + r * 0.65, y + r * 0.45), color);
+        }
+    }
+}
+
+pub fn draw_tray(font: &Font, game: &Game, hud: &Hud) {
+    let r = hud.px(RADIUS);
+    for (i, s) in collect(game).iter().enumerate() {
+        let Vec2 { x, y } = hud.at(Anchor::TopRight, vec2(16.0 + RADIUS + i as f32 * SPACING, 23.0));
+        // 快到期时闪烁；减少动态效果时改成缓慢的明暗呼吸
+        let expiring = s.left.is_some_and(|(t, _)| t < BLINK_BELOW);
+        let color = match (expiring, crate::motion::calm(game)) {
+            (true, false) if (get_time() * 6.0).sin() < 0.0 => Color { a: 0.35, ..s.color },
+            (true, true) => Color { a: 0.45 + 0.55 * crate::motion::pulse(get_time() as f32), ..s.color },
+            _ => s.color,
+        };
+        draw_circle(x, y, r, Color::new(0.0, 0.0, 0.0, 0.45));
+        draw_circle_lines(x, y, r, 2.0, Color { a: 0.25, ..s.color });
+        if let Some((t, full)) = s.left {
+            // 从正上方起顺时针，剩多少画多少
+            draw_arc(x, y, 32, r, -90.0, 2.5, 360.0 * (t / full).clamp(0.0, 1.0), color);
+        }
+        draw_icon(s.icon, x, y, r, color);
+        if let Some(b) = &s.badge {
+            let size = hud.px(14.0) as u16;
+            draw_text_ex(b, x + r * 0.45, y + r + hud.px(2.0), TextParams { font: Some(font), font_size: size, color: WHITE, ..Default::default() });
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+use std::ffi::{c_char, c_void, CString};
+
+use libloading::Library;
+
+// ===== Steamworks（steam 特性）=====
+// 运行时加载 Steam 自带的 steam_api 动态库，走 flat C 接口，不需要在编译时链接 SDK。
+// 对应关系（都要先在 Steamworks 后台建好同名项）：
+//   成就        assets/achievements.json 的 key → 同名 Steam 成就
+//   最高分      普通模式各难度里的最高分 → 统计项 best_score
+//   每日挑战    当天的分数 → 排行榜 daily_<天数>（不存在就建，降序，只留最好成绩）
+// 回调用手动派发：每帧取一次，异步调用的结果（找排行榜）和覆盖层开关都从这里来。
+// 覆盖层：渲染循环本来就每帧交换缓冲、从不阻塞，覆盖层能正常叠加；打开时局内自动暂停，
+// 免得玩家在覆盖层里操作时被撞死。
+// 启动时 Steam 没开、缺 steam_appid.txt 或找不到库都只记一条日志，游戏照常跑。
+#[cfg(windows)]
+const LIB_NAME: &str = "steam_api64.dll";
+#[cfg(target_os = "macos")]
+const LIB_NAME: &str = "libsteam_api.dylib";
+#[cfg(all(unix, not(target_os = "macos")))]
+const LIB_NAME: &str = "libsteam_api.so";
+
+const BEST_STAT: &str = "best_score";
+// 回调编号（isteamfriends.h / isteamutils.h / isteamuserstats.h）
+const OVERLAY_ACTIVATED: i32 = 331;
+const CALL_COMPLETED: i32 = 703;
+const LEADERBOARD_FOUND: i32 = 1104;
+// 排行榜枚举
+const SORT_DESCENDING: i32 = 2;
+const DISPLAY_NUMERIC: i32 = 1;
+const UPLOAD_KEEP_BEST: i32 = 1;
+
+type Pipe = i32;
+type ApiCall = u64;
+type Board = u64;
+
+#[repr(C)]
+struct CallbackMsg {
+    user: i32,
+    callback: i32,
+    param: *mut u8,
+    size: i32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct CallCompleted {
+    call: ApiCall,
+    callback: i32,
+    size: u32,
+}
+
+// Steam 的回调结构在 Windows 上按 8 字节对齐打包，其他平台按 4
+#[cfg_attr(windows, repr(C, packed(8)))]
+#[cfg_attr(not(windows), repr(C, packed(4)))]
+#[derive(Clone, Copy, Default)]
+struct LeaderboardFound {
+    board: Board,
+    found: u8,
+}
+
+struct Api {
+    run_frame: unsafe extern "C" fn(Pipe),
+    next_callback: unsafe extern "C" fn(Pipe, *mut CallbackMsg) -> bool,
+    free_callback: unsafe extern "C" fn(Pipe),
+    call_result: unsafe extern "C" fn(Pipe, ApiCall, *mut c_void, i32, i32, *mut bool) -> bool,
+    set_achievement: unsafe extern "C" fn(*mut c_void, *const c_char) -> bool,
+    set_stat: unsafe extern "C" fn(*mut c_void, *const c_char, i32) -> bool,
+    store_stats: unsafe extern "C" fn(*mut c_void) -> bool,
+    find_board: unsafe extern "C" fn(*mut c_void, *const c_char, i32, i32) -> ApiCall,
+    upload_score: unsafe extern "C" fn(*mut c_void, Board, i32, i32, *const i32, i32) -> ApiCall,
+    shutdown: unsafe extern "C" fn(),
+}
+
+pub struct Steam {
+    _lib: Library, // 函数指针都指向它，得一直留着
+    api: Api,
+    pipe: Pipe,
+    stats: *mut c_void,
+    boards: HashMap<String, Board>,
+    finding: Vec<(ApiCall, String, i32)>, // 等排行榜找到后再上传的分数
+    dirty: bool,                          // 有改动还没 StoreStats
+    pub overlay: bool,                    // 覆盖层开着
+}
+
+unsafe fn sym<T: Copy>(lib: &Library, name: &[u8]) -> Result<T, String> {
+    lib.get::<T>(name).map(|s| *s).map_err(|e| e.to_string())
+}
+
+unsafe fn load() -> Result<Steam, String> {
+    let lib = Library::new(LIB_NAME).map_err(|e| format!("找不到 {}：{}", LIB_NAME, e))?;
+    // 新版 SDK 导出 InitFlat（0 为成功），老版本只有 SteamAPI_Init
+    let ok = match sym::<unsafe extern "C" fn(*mut [c_char; 1024]) -> i32>(&lib, b"SteamAPI_InitFlat\0") {
+        Ok(init) => init(&mut [0; 1024]) == 0,
+        Err(_) => sym::<unsafe extern "C" fn() -> bool>(&lib, b"SteamAPI_Init\0")?(),
+    };
+    if !ok { return Err("SteamAPI 初始化失败（Steam 没开，或缺少 steam_appid.txt）".to_string()); }
+    sym::<unsafe extern "C" fn()>(&lib, b"SteamAPI_ManualDispatch_Init\0")?();
+    let pipe = sym::<unsafe extern "C" fn() -> Pipe>(&lib, b"SteamAPI_GetHSteamPipe\0")?();
+    let user_stats = sym::<unsafe extern "C" fn() -> *mut c_void>(&lib, b"SteamAPI_SteamUserStats_v013\0")
+        .or_else(|_| sym(&lib, b"SteamAPI_SteamUserStats_v012\0"))?;
+    let stats = user_stats();
+    if stats.is_null() { return Err("拿不到 ISteamUserStats".to_string()); }
+    let api = Api {
+        run_frame: sym(&lib, b"SteamAPI_ManualDispatch_RunFrame\0")?,
+        next_callback: sym(&lib, b"SteamAPI_ManualDispatch_GetNextCallback\0")?,
+        free_callback: sym(&lib, b"SteamAPI_ManualDispatch_FreeLastCallback\0")?,
+        call_result: sym(&lib, b"SteamAPI_ManualDispatch_GetAPICallResult\0")?,
+        set_achievement: sym(&lib, b"SteamAPI_ISteamUserStats_SetAchievement\0")?,
+        set_stat: sym(&lib, b"SteamAPI_ISteamUserStats_SetStatInt32\0")?,
+        store_stats: sym(&lib, b"SteamAPI_ISteamUserStats_StoreStats\0")?,
+        find_board: sym(&lib, b"SteamAPI_ISteamUserStats_FindOrCreateLeaderboard\0")?,
+        upload_score: sym(&lib, b"SteamAPI_ISteamUserStats_UploadLeaderboardScore\0")?,
+        shutdown: sym(&lib, b"SteamAPI_Shutdown\0")?,
+    };
+    Ok(Steam { _lib: lib, api, pipe, stats, boards: HashMap::new(), finding: Vec::new(), dirty: false, overlay: false })
+}
+
+// 启动时调用；unlocked 是存档里已有的成就，补报给 Steam（离线时解锁的也能同步上去）
+pub fn init<'a>(unlocked: impl Iterator<Item = &'a String>) -> Option<Steam> {
+    match unsafe { load() } {
+        Ok(mut s) => {
+            for key in unlocked { s.achievement(key); }
+            Some(s)
+        }
+        Err(e) => {
+            crate::logfile::warn(&format!("Steam 未启用：{}", e));
+            None
+        }
+    }
+}
+
+impl Steam {
+    pub fn achievement(&mut self, key: &str) {
+        let Ok(k) = CString::new(key) else { return };
+        unsafe { (self.api.set_achievement)(self.stats, k.as_ptr()); }
+        self.dirty = true;
+    }
+
+    pub fn best_score(&mut self, score: i32) {
+        let name = CString::new(BEST_STAT).unwrap();
+        unsafe { (self.api.set_stat)(self.stats, name.as_ptr(), score); }
+        self.dirty = true;
+    }
+
+    pub fn daily_score(&mut self, day: u32, score: i32) {
+        let name = format!("daily_{}", day);
+        if let Some(&b) = self.boards.get(&name) { return self.upload(b, score); }
+        let Ok(c) = CString::new(name.as_str()) else { return };
+        let call = unsafe { (self.api.find_board)(self.stats, c.as_ptr(), SORT_DESCENDING, DISPLAY_NUMERIC) };
+        if call != 0 { self.finding.push((call, name, score)); }
+    }
+
+    fn upload(&self, board: Board, score: i32) {
+        unsafe { (self.api.upload_score)(self.stats, board, UPLOAD_KEEP_BEST, score, std::ptr::null(), 0); }
+    }
+
+    // 找排行榜的异步调用完成了：取结果，上传排队的分数
+    fn board_found(&mut self, call: ApiCall) {
+        let Some(i) = self.finding.iter().position(|f| f.0 == call) else { return };
+        let (_, name, score) = self.finding.remove(i);
+        let mut r = LeaderboardFound::default();
+        let mut failed = false;
+        let size = std::mem::size_of::<LeaderboardFound>() as i32;
+        let ok = unsafe { (self.api.call_result)(self.pipe, call, &mut r as *mut _ as *mut c_void, size, LEADERBOARD_FOUND, &mut failed) };
+        let (board, found) = (r.board, r.found);
+        if !ok || failed || found == 0 { return crate::logfile::warn(&format!("Steam 排行榜 {} 不可用", name)); }
+        self.boards.insert(name, board);
+        self.upload(board, score);
+    }
+
+    // 每帧调用：提交改动，处理回调
+    pub fn frame(&mut self) {
+        if self.dirty {
+            unsafe { (self.api.store_stats)(self.stats); }
+            self.dirty = false;
+        }
+        unsafe { (self.api.run_frame)(self.pipe); }
+        let mut msg = CallbackMsg { user: 0, callback: 0, param: std::ptr::null_mut(), size: 0 };
+        while unsafe { (self.api.next_callback)(self.pipe, &mut msg) } {
+            match msg.callback {
+                OVERLAY_ACTIVATED if msg.size >= 1 => self.overlay = unsafe { *msg.param } != 0,
+                CALL_COMPLETED if msg.size as usize >= std::mem::size_of::<CallCompleted>() => {
+                    let done = unsafe { std::ptr::read_unaligned(msg.param as *const CallCompleted) };
+                    if done.callback == LEADERBOARD_FOUND { self.board_found(done.call); }
+                }
+                _ => {}
+            }
+            unsafe { (self.api.free_callback)(self.pipe); }
+        }
+    }
+
+    pub fn shutdown(self) {
+        if self.dirty { unsafe { (self.api.store_stats)(self.stats); } }
+        unsafe { (self.api.shutdown)(); }
+    }
+}
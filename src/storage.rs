@@ -0,0 +1,200 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+// ===== 存储后端 =====
+// 玩家数据（存档、设置、挂起、历史、回放、幽灵、档案、日志）的读写都走这里，不直接碰 std::fs。
+// 桌面版就是文件系统（Disk）；网页版（wasm32）没有文件系统，存进浏览器的 localStorage（Browser），
+// 键是“dodge-rush:”加上数据目录下的相对路径，目录只是键里的前缀。网页上要配合 web/storage.js。
+// 导出、截图、反馈包这类给人拿走的文件仍直接写盘，网页版上写不了，照常报错。
+// assets/ 下随游戏发布的只读文件另走 read_asset / list_assets：桌面版照常读盘；
+// 网页版开局前 preload 按 assets/manifest.txt 把它们都取到内存（macroquad 的 load_file，网页上就是 fetch），之后照样同步读。
+// 安卓版同理：assets/ 打包在 APK 里，std::fs 读不到，也按清单用 load_file（走 AssetManager）预先取出来。
+// 安卓的玩家数据就是 Disk，写在 paths 定下的应用内部存储里。
+#[cfg(any(target_arch = "wasm32", target_os = "android"))]
+pub const MANIFEST: &str = "assets/manifest.txt";
+
+pub trait Storage {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>>;
+    // 上级目录不存在时自动建
+    fn write(&self, path: &Path, bytes: &[u8]) -> io::Result<()>;
+    fn remove(&self, path: &Path) -> io::Result<()>;
+    // dir 下直接放着的文件，按名字排好
+    fn list(&self, dir: &Path) -> Vec<PathBuf>;
+    fn exists(&self, path: &Path) -> bool { self.read(path).is_ok() }
+    fn append(&self, path: &Path, bytes: &[u8]) -> io::Result<()> {
+        let mut all = self.read(path).unwrap_or_default();
+        all.extend_from_slice(bytes);
+        self.write(path, &all)
+    }
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        self.write(to, &self.read(from)?)?;
+        self.remove(from)
+    }
+    // 连同子目录整个删掉
+    fn remove_dir(&self, dir: &Path) -> io::Result<()>;
+}
+
+// —— 文件系统（桌面版）——
+pub struct Disk;
+
+impl Storage for Disk {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> { std::fs::read(path) }
+    // 写完落盘再返回，write_atomic 靠它保证临时文件是完整的
+    fn write(&self, path: &Path, bytes: &[u8]) -> io::Result<()> {
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) { std::fs::create_dir_all(dir)?; }
+        let mut f = std::fs::File::create(path)?;
+        io::Write::write_all(&mut f, bytes)?;
+        f.sync_all()
+    }
+    fn remove(&self, path: &Path) -> io::Result<()> { std::fs::remove_file(path) }
+    fn list(&self, dir: &Path) -> Vec<PathBuf> { list_dir(dir) }
+    fn exists(&self, path: &Path) -> bool { path.exists() }
+    fn append(&self, path: &Path, bytes: &[u8]) -> io::Result<()> {
+        let mut f = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+        io::Write::write_all(&mut f, bytes)
+    }
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> { std::fs::rename(from, to) }
+    fn remove_dir(&self, dir: &Path) -> io::Result<()> { std::fs::remove_dir_all(dir) }
+}
+
+fn list_dir(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else { return Vec::new() };
+    let mut files: Vec<PathBuf> = entries.flatten().map(|e| e.path()).filter(|p| p.is_file()).collect();
+    files.sort();
+    files
+}
+
+// —— 浏览器 localStorage（网页版）——
+// localStorage 只能存字符串：JS 那边把每个字节存成一个码位 0~255 的字符，原样进出
+#[cfg(target_arch = "wasm32")]
+pub struct Browser;
+
+#[cfg(target_arch = "wasm32")]
+mod js {
+    // 在 web/storage.js 里实现。get / keys 先把结果放在 JS 那边并返回字节数（没有为 -1），再用 take 拷进来
+    extern "C" {
+        pub fn dr_storage_get(key: *const u8, key_len: usize) -> i32;
+        pub fn dr_storage_keys(prefix: *const u8, prefix_len: usize) -> i32;
+        pub fn dr_storage_take(out: *mut u8);
+        pub fn dr_storage_set(key: *const u8, key_len: usize, val: *const u8, val_len: usize) -> i32;
+        pub fn dr_storage_remove(key: *const u8, key_len: usize);
+    }
+
+    pub fn take(len: i32) -> Option<Vec<u8>> {
+        let mut buf = vec![0u8; usize::try_from(len).ok()?];
+        unsafe { dr_storage_take(buf.as_mut_ptr()) };
+        Some(buf)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+const KEY_PREFIX: &str = "dodge-rush:";
+
+#[cfg(target_arch = "wasm32")]
+fn key(path: &Path) -> String { format!("{}{}", KEY_PREFIX, path.to_string_lossy().replace('\\', "/")) }
+
+#[cfg(target_arch = "wasm32")]
+impl Browser {
+    // 以 prefix 开头的所有键（去掉 KEY_PREFIX 之后的相对路径）
+    fn keys(&self, prefix: &str) -> Vec<String> {
+        let p = format!("{}{}", KEY_PREFIX, prefix);
+        let bytes = js::take(unsafe { js::dr_storage_keys(p.as_ptr(), p.len()) }).unwrap_or_default();
+        String::from_utf8_lossy(&bytes).lines().filter_map(|k| k.strip_prefix(KEY_PREFIX)).map(str::to_string).collect()
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Storage for Browser {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        let k = key(path);
+        js::take(unsafe { js::dr_storage_get(k.as_ptr(), k.len()) }).ok_or_else(|| io::ErrorKind::NotFound.into())
+    }
+    fn write(&self, path: &Path, bytes: &[u8]) -> io::Result<()> {
+        let k = key(path);
+        match unsafe { js::dr_storage_set(k.as_ptr(), k.len(), bytes.as_ptr(), bytes.len()) } {
+            0 => Ok(()),
+            _ => Err(io::Error::other("浏览器存储空间已满")),
+        }
+    }
+    fn remove(&self, path: &Path) -> io::Result<()> {
+        let k = key(path);
+        unsafe { js::dr_storage_remove(k.as_ptr(), k.len()) };
+        Ok(())
+    }
+    fn list(&self, dir: &Path) -> Vec<PathBuf> {
+        let prefix = format!("{}/", dir.to_string_lossy().replace('\\', "/").trim_end_matches('/'));
+        let prefix = if prefix == "/" { String::new() } else { prefix };
+        let mut files: Vec<PathBuf> = self.keys(&prefix).into_iter().filter(|k| !k[prefix.len()..].contains('/')).map(PathBuf::from).collect();
+        files.sort();
+        files
+    }
+    fn remove_dir(&self, dir: &Path) -> io::Result<()> {
+        let prefix = format!("{}/", dir.to_string_lossy().replace('\\', "/").trim_end_matches('/'));
+        for k in self.keys(&prefix) { self.remove(Path::new(&k))?; }
+        Ok(())
+    }
+}
+
+pub fn backend() -> &'static dyn Storage {
+    #[cfg(target_arch = "wasm32")]
+    return &Browser;
+    #[cfg(not(target_arch = "wasm32"))]
+    &Disk
+}
+
+// 和 std::fs 同名同用法，调用处直接换个前缀
+pub fn read(path: impl AsRef<Path>) -> io::Result<Vec<u8>> { backend().read(path.as_ref()) }
+pub fn read_to_string(path: impl AsRef<Path>) -> io::Result<String> { utf8(read(path)?) }
+pub fn write(path: impl AsRef<Path>, bytes: impl AsRef<[u8]>) -> io::Result<()> { backend().write(path.as_ref(), bytes.as_ref()) }
+pub fn append(path: impl AsRef<Path>, bytes: impl AsRef<[u8]>) -> io::Result<()> { backend().append(path.as_ref(), bytes.as_ref()) }
+pub fn remove_file(path: impl AsRef<Path>) -> io::Result<()> { backend().remove(path.as_ref()) }
+pub fn remove_dir_all(dir: impl AsRef<Path>) -> io::Result<()> { backend().remove_dir(dir.as_ref()) }
+pub fn rename(from: impl AsRef<Path>, to: impl AsRef<Path>) -> io::Result<()> { backend().rename(from.as_ref(), to.as_ref()) }
+pub fn exists(path: impl AsRef<Path>) -> bool { backend().exists(path.as_ref()) }
+pub fn list(dir: impl AsRef<Path>) -> Vec<PathBuf> { backend().list(dir.as_ref()) }
+
+fn utf8(bytes: Vec<u8>) -> io::Result<String> { String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)) }
+
+// —— 随游戏发布的只读文件 ——
+#[cfg(any(target_arch = "wasm32", target_os = "android"))]
+thread_local! {
+    static ASSETS: std::cell::RefCell<std::collections::HashMap<PathBuf, Vec<u8>>> = std::cell::RefCell::new(std::collections::HashMap::new());
+}
+
+// 网页版和安卓版开局前调用一次；清单里列的文件逐个取回来放进内存，取不到的记一条警告。桌面版什么也不做
+pub async fn preload() {
+    #[cfg(any(target_arch = "wasm32", target_os = "android"))]
+    {
+        let list = macroquad::file::load_string(MANIFEST).await.unwrap_or_else(|e| {
+            crate::logfile::warn(&format!("读不到资源清单 {}：{}", MANIFEST, e));
+            String::new()
+        });
+        for name in list.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+            match macroquad::file::load_file(name).await {
+                Ok(bytes) => { ASSETS.with_borrow_mut(|a| a.insert(PathBuf::from(name), bytes)); }
+                Err(e) => crate::logfile::warn(&format!("资源 {} 下载失败：{}", name, e)),
+            }
+        }
+    }
+}
+
+pub fn read_asset(path: impl AsRef<Path>) -> io::Result<Vec<u8>> {
+    #[cfg(any(target_arch = "wasm32", target_os = "android"))]
+    return ASSETS.with_borrow(|a| a.get(path.as_ref()).cloned()).ok_or_else(|| io::ErrorKind::NotFound.into());
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android")))]
+    std::fs::read(path)
+}
+
+pub fn read_asset_string(path: impl AsRef<Path>) -> io::Result<String> { utf8(read_asset(path)?) }
+
+// dir 下直接放着的资源文件，按名字排好
+pub fn list_assets(dir: impl AsRef<Path>) -> Vec<PathBuf> {
+    #[cfg(any(target_arch = "wasm32", target_os = "android"))]
+    return ASSETS.with_borrow(|a| {
+        let mut files: Vec<PathBuf> = a.keys().filter(|p| p.parent() == Some(dir.as_ref())).cloned().collect();
+        files.sort();
+        files
+    });
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android")))]
+    list_dir(dir.as_ref())
+}
@@ -0,0 +1,313 @@
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::background::Backdrop;
+use crate::difficulty::Difficulty;
+use crate::director::Director;
+use crate::effects::Effects;
+use crate::events::EventScheduler;
+use crate::movement::Mover;
+use crate::powerups::EffectSlot;
+use crate::shooting::Projectile;
+use crate::mutators::Mutators;
+use crate::{paths, storage};
+use crate::stages::Hazard;
+use crate::stats::RunStats;
+use crate::waves::WaveRunner;
+use crate::wind::Wind;
+use crate::gravity::Well;
+use crate::{Game, GameMode, Obstacle, PlayMode, Player, PowerUp};
+
+// ===== 整局快照：挂起 / 恢复，以及时光倒流 =====
+// 中途按 ESC 离开或直接关窗时，把整局状态写进 suspend.json，菜单里可以原样接着玩。
+// 只挂起普通/禅模式的非挑战局；恢复后文件即删除，同一局不能恢复两次。
+// 全局随机数读不出内部状态：快照时抽一个新种子重新播种并记下，恢复时用它播种，之后的序列完全一致。
+pub const SUSPEND_PATH: &str = "suspend.json";
+
+// macroquad 的 Rect 不带 serde，这里给它一份远程定义
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Rect")]
+pub struct RectDef { x: f32, y: f32, w: f32, h: f32 }
+
+#[derive(Serialize, Deserialize)]
+pub struct Snapshot {
+    zen: bool,
+    // —— 本局规则 ——
+    difficulty: Difficulty,
+    director: Director,
+    mutators: Mutators,
+    start_lives: u32,
+    #[serde(default)]
+    loadout: i32,
+    #[serde(default)]
+    character: usize,
+    // —— 实体 ——
+    mover: Mover,
+    player: Player,
+    obstacles: Vec<Obstacle>,
+    powerups: Vec<PowerUp>,
+    field: (f32, f32),
+    // —— 随机数 ——
+    seed: u64,
+    rng_seed: u64,
+    // —— 计时与计分 ——
+    time_tick: f32,
+    elapsed: f32,
+    score: i32,
+    dodged: u32,
+    spawn_timer: f32,
+    spawn_interval: f32,
+    breather: bool,
+    fall_speed: f32,
+    shield: u32,
+    lives: u32,
+    invuln: f32,
+    continued: bool,
+    #[serde(default)]
+    teleport: bool,
+    #[serde(default)]
+    shooting: bool,
+    #[serde(default)]
+    graze: u32,
+    #[serde(default)]
+    stored_bomb: bool,
+    #[serde(default)]
+    ult: f32,
+    #[serde(default)]
+    overdrive: f32,
+    #[serde(default)]
+    mult: EffectSlot,
+    #[serde(default = "one")]
+    combo: u32,
+    #[serde(default)]
+    combo_timer: f32,
+    #[serde(default)]
+    freeze: EffectSlot,
+    #[serde(default)]
+    reverse: f32,
+    #[serde(default)]
+    haste: f32,
+    #[serde(default)]
+    blackout: f32,
+    #[serde(default)]
+    ammo: u32,
+    #[serde(default)]
+    shots: Vec<Projectile>,
+    slow: EffectSlot,
+    pu_spawn_timer: f32,
+    #[serde(default)]
+    pity: f32,
+    wave: WaveRunner,
+    events: EventScheduler,
+    #[serde(default = "Wind::new")]
+    wind: Wind,
+    #[serde(default)]
+    wells: Vec<Well>,
+    stage: usize,
+    hazards: Vec<Hazard>,
+    #[serde(default)]
+    next_milestone: i32,
+    #[serde(default)]
+    milestone_pu: Option<f32>,
+    #[serde(default)]
+    stats: RunStats,
+}
+
+fn one() -> u32 { 1 }
+
+pub fn exists() -> bool { storage::exists(paths::profile(SUSPEND_PATH)) }
+
+pub fn can_suspend(game: &Game) -> bool {
+    matches!(game.mode, GameMode::Playing | GameMode::Paused)
+        && game.challenge.is_none()
+        && matches!(game.play, PlayMode::Standard | PlayMode::Zen)
+        && !game.replay.watching()
+}
+
+// 拍一张快照（会重新播种全局随机数）
+pub fn capture(game: &Game) -> Snapshot {
+    let rng_seed = ((rand::rand() as u64) << 32) | rand::rand() as u64;
+    rand::srand(rng_seed);
+    snapshot(game, rng_seed)
+}
+
+fn snapshot(game: &Game, rng_seed: u64) -> Snapshot {
+    Snapshot {
+        zen: game.play == PlayMode::Zen,
+        difficulty: game.difficulty,
+        director: game.director.clone(),
+        mutators: game.mutators,
+        start_lives: game.start_lives,
+        loadout: game.loadout,
+        character: game.character,
+        mover: game.mover.clone(),
+        player: game.player.clone(),
+        obstacles: game.obs.live.clone(),
+        powerups: game.pus.live.clone(),
+        field: (game.field.x, game.field.y),
+        seed: game.seed,
+        rng_seed,
+        time_tick: game.time_tick,
+        elapsed: game.elapsed,
+        score: game.score,
+        dodged: game.dodged,
+        spawn_timer: game.spawn_timer,
+        spawn_interval: game.spawn_interval,
+        breather: game.breather,
+        fall_speed: game.fall_speed,
+        shield: game.shield,
+        lives: game.lives,
+        invuln: game.invuln,
+        continued: game.continued,
+        teleport: game.teleport,
+        shooting: game.shooting,
+        graze: game.graze,
+        stored_bomb: game.stored_bomb,
+        ult: game.ult,
+        overdrive: game.overdrive,
+        mult: game.mult,
+        combo: game.combo,
+        combo_timer: game.combo_timer,
+        freeze: game.freeze,
+        reverse: game.reverse,
+        haste: game.haste,
+        blackout: game.blackout,
+        ammo: game.ammo,
+        shots: game.shots.live.clone(),
+        slow: game.slow,
+        pu_spawn_timer: game.pu_spawn_timer,
+        pity: game.pity,
+        wave: game.wave.clone(),
+        events: game.events.clone(),
+        wind: game.wind.clone(),
+        wells: game.wells.clone(),
+        stage: game.stage,
+        hazards: game.hazards.clone(),
+        next_milestone: game.next_milestone,
+        milestone_pu: game.milestone_pu,
+        stats: game.stats,
+    }
+}
+
+pub fn suspend(game: &mut Game) {
+    let snap = capture(game);
+    if let Ok(json) = serde_json::to_string(&snap) {
+        game.suspended = storage::write(paths::profile(SUSPEND_PATH), json).is_ok();
+    }
+}
+
+// —— 安卓：切到后台后被系统杀掉 ——
+// 切后台时拿不到任何通知，进程随后可能直接被杀，关窗挂起那条路走不到。所以局内每隔几秒悄悄写一份挂起快照，
+// 进暂停时再补一份；被杀了下次打开菜单里照样 [R] 接着玩。这一局正常结束（没有走挂起）就把它删掉。
+// 这里不能像 capture 那样重新播种：会改掉本局之后的随机序列，录像就对不上了。恢复时改用种子和局内时间拼出的新种子，
+// 接着玩的那段和原来不一样，但恢复的局本来就不录像。
+#[cfg(target_os = "android")]
+const CHECKPOINT_SECS: f32 = 5.0;
+
+#[cfg(target_os = "android")]
+thread_local! {
+    // (距上次写快照的局内时间, 磁盘上那份是不是这一局写的)
+    static CHECKPOINT: std::cell::Cell<(f32, bool)> = const { std::cell::Cell::new((0.0, false)) };
+}
+
+// 每帧
+#[cfg(target_os = "android")]
+pub fn checkpoint(game: &mut Game, dt: f32) {
+    let (mut timer, mut ours) = CHECKPOINT.get();
+    if can_suspend(game) {
+        if game.mode == GameMode::Playing { timer += dt; }
+        let due = if game.mode == GameMode::Playing { timer >= CHECKPOINT_SECS } else { timer > 0.0 };
+        if due {
+            let snap = snapshot(game, game.seed ^ ((game.time_tick.to_bits() as u64) << 32) ^ game.dodged as u64);
+            if let Ok(json) = serde_json::to_string(&snap) {
+                ours = storage::write(paths::profile(SUSPEND_PATH), json).is_ok();
+                game.suspended = false; // 之前挂起的那局已被覆盖
+            }
+            timer = 0.0;
+        }
+    } else if ours {
+        // 走了 suspend() 的（回菜单、关窗）留着，其余是这局结束了
+        if !game.suspended { let _ = storage::remove_file(paths::profile(SUSPEND_PATH)); }
+        (timer, ours) = (0.0, false);
+    }
+    CHECKPOINT.set((timer, ours));
+}
+
+// 恢复成功后停在暂停界面，按 [P] 继续
+pub fn resume(game: &mut Game) {
+    let snap = storage::read_to_string(paths::profile(SUSPEND_PATH)).ok().and_then(|s| serde_json::from_str::<Snapshot>(&s).ok());
+    let _ = storage::remove_file(paths::profile(SUSPEND_PATH));
+    game.suspended = false;
+    let Some(s) = snap else { return };
+
+    // 规则沿用挂起时的设置，玩家自己的选择先收起来，回菜单时还原
+    game.use_fixed_rules();
+    game.play = if s.zen { PlayMode::Zen } else { PlayMode::Standard };
+    game.difficulty = s.difficulty;
+    game.director = s.director.clone();
+    game.mutators = s.mutators;
+    game.start_lives = s.start_lives;
+    game.loadout = s.loadout;
+    game.character = s.character.min(crate::characters::CHARACTERS.len() - 1);
+    game.seed = s.seed;
+    game.backdrop = Backdrop::generate(s.seed);
+    game.effects = Effects::new(s.seed);
+    game.field = vec2(s.field.0, s.field.1);
+    game.field_changed = false;
+    game.shake.clear();
+    restore_state(game, s);
+    game.mode = GameMode::Paused;
+}
+
+// 把实体、计时、计分和随机数状态换成快照里的（规则与外观不动）
+pub fn restore_state(game: &mut Game, s: Snapshot) {
+    rand::srand(s.rng_seed);
+    game.mover = s.mover;
+    game.player = s.player;
+    game.obs.live = s.obstacles;
+    game.obs.dead.clear();
+    game.pus.live = s.powerups;
+    game.pus.dead.clear();
+    game.time_tick = s.time_tick;
+    game.elapsed = s.elapsed;
+    game.score = s.score;
+    game.dodged = s.dodged;
+    game.spawn_timer = s.spawn_timer;
+    game.spawn_interval = s.spawn_interval;
+    game.breather = s.breather;
+    game.fall_speed = s.fall_speed;
+    game.shield = s.shield;
+    game.lives = s.lives;
+    game.invuln = s.invuln;
+    game.continued = s.continued;
+    game.teleport = s.teleport;
+    game.shooting = s.shooting;
+    game.graze = s.graze;
+    game.stored_bomb = s.stored_bomb;
+    game.ult = s.ult;
+    game.overdrive = s.overdrive;
+    game.mult = s.mult;
+    game.combo = s.combo;
+    game.combo_timer = s.combo_timer;
+    game.freeze = s.freeze;
+    game.reverse = s.reverse;
+    game.haste = s.haste;
+    game.blackout = s.blackout;
+    game.ammo = s.ammo;
+    game.shots.clear();
+    game.shots.live = s.shots;
+    game.slow = s.slow;
+    game.pu_spawn_timer = s.pu_spawn_timer;
+    game.pity = s.pity;
+    game.wave = s.wave;
+    game.events = s.events;
+    game.wind = s.wind;
+    game.wells = s.wells;
+    game.stage = s.stage;
+    game.hazards = s.hazards;
+    game.stage_fade = 0.0;
+    // 旧快照没有里程碑进度：从当前分数之后的下一个开始
+    game.next_milestone = if s.next_milestone > 0 { s.next_milestone } else { (game.score / crate::MILESTONE_EVERY + 1) * crate::MILESTONE_EVERY };
+    game.milestone_pu = s.milestone_pu;
+    game.stats = s.stats;
+}
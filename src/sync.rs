@@ -0,0 +1,191 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{tr, trf};
+use crate::{paths, storage, SAVE_PATH};
+
+// ===== 存档同步 =====
+// 存档的读写抽象成 SaveBackend：默认是本地文件；打开 cloud-sync 特性并设置 DODGE_SYNC_URL 后，
+// 再接一个 HTTP/WebDAV 远端（GET 读、PUT 写，地址里可带 user:pass@ 做 Basic 认证，这时必须是 https://）。
+// 时间戳用存档里的 saved_at（写盘时填），两边比较不依赖文件系统或服务器时间。
+// 启动（和切换档案）时对账：上次同步之后只有一边变了就跟着那边走，两边都变了就让玩家选。
+#[cfg(feature = "cloud-sync")]
+const SYNC_URL_ENV: &str = "DODGE_SYNC_URL";
+const SYNC_STATE_PATH: &str = "sync.json";
+
+pub trait SaveBackend {
+    fn name(&self) -> &'static str;
+    // 没有存档时返回 Ok(None)
+    fn load(&self) -> Result<Option<String>, String>;
+    fn store(&self, text: &str) -> Result<(), String>;
+    // 存档里记的保存时间（Unix 秒）；没有 saved_at 的老存档算 0
+    fn last_modified(&self) -> Result<Option<u64>, String> {
+        Ok(self.load()?.map(|t| saved_at(&t).unwrap_or(0)))
+    }
+}
+
+pub fn saved_at(text: &str) -> Option<u64> {
+    serde_json::from_str::<serde_json::Value>(text).ok()?.get("saved_at")?.as_u64()
+}
+
+// —— 本地文件（默认）——
+pub struct LocalFile {
+    path: PathBuf,
+}
+
+impl LocalFile {
+    pub fn current() -> Self { Self { path: paths::profile(SAVE_PATH) } }
+}
+
+impl SaveBackend for LocalFile {
+    fn name(&self) -> &'static str { tr("本机") }
+    fn load(&self) -> Result<Option<String>, String> {
+        match storage::read_to_string(&self.path) {
+            Ok(s) => Ok(Some(s)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+    fn store(&self, text: &str) -> Result<(), String> { paths::write_atomic(&self.path, text).map_err(|e| e.to_string()) }
+}
+
+// —— HTTP / WebDAV 远端 ——
+#[cfg(feature = "cloud-sync")]
+pub struct WebDav {
+    url: String, // 本档案的存档地址
+}
+
+#[cfg(feature = "cloud-sync")]
+impl SaveBackend for WebDav {
+    fn name(&self) -> &'static str { tr("云端") }
+    fn load(&self) -> Result<Option<String>, String> {
+        let (status, body) = crate::http::request("GET", &self.url, None)?;
+        match status {
+            200..=299 => Ok(Some(String::from_utf8_lossy(&body).into_owned())),
+            404 => Ok(None),
+            s => Err(format!("HTTP {}", s)),
+        }
+    }
+    fn store(&self, text: &str) -> Result<(), String> {
+        match crate::http::request("PUT", &self.url, Some(text.as_bytes()))?.0 {
+            200..=299 => Ok(()),
+            s => Err(format!("HTTP {}", s)),
+        }
+    }
+}
+
+// 远端按档案分文件：<DODGE_SYNC_URL>/<档案 id>-save.json
+#[cfg(feature = "cloud-sync")]
+fn remote(profile_id: &str) -> Option<Box<dyn SaveBackend>> {
+    let base = std::env::var(SYNC_URL_ENV).ok().filter(|s| !s.trim().is_empty())?;
+    Some(Box::new(WebDav { url: format!("{}/{}-save.json", base.trim_end_matches('/'), profile_id) }))
+}
+#[cfg(not(feature = "cloud-sync"))]
+fn remote(_: &str) -> Option<Box<dyn SaveBackend>> { None }
+
+// 上次同步成功时两边一致的 saved_at，存在档案目录里
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+struct SyncState {
+    last_sync: u64,
+}
+
+impl SyncState {
+    fn load() -> Self {
+        storage::read_to_string(paths::profile(SYNC_STATE_PATH)).ok().and_then(|s| serde_json::from_str(&s).ok()).unwrap_or_default()
+    }
+    fn save(&self) { let _ = storage::write(paths::profile(SYNC_STATE_PATH), serde_json::to_string(self).unwrap()); }
+}
+
+pub struct CloudSync {
+    remote: Box<dyn SaveBackend>,
+    pub conflict: Option<Conflict>,
+    pub status: Option<String>, // 最近一次同步的结果（菜单上显示）
+}
+
+// 两边都改过：等玩家选
+pub struct Conflict {
+    pub local: u64,
+    pub remote: u64,
+}
+
+pub enum Outcome { Unchanged, Pushed, Pulled, Conflict }
+
+// 没开特性或没配地址时为 None，一切照旧只用本地文件
+pub fn connect(profile_id: &str) -> Option<CloudSync> {
+    remote(profile_id).map(|remote| CloudSync { remote, conflict: None, status: None })
+}
+
+impl CloudSync {
+    pub fn reconcile(&mut self) -> Outcome {
+        match self.try_reconcile() {
+            Ok(o) => o,
+            Err(e) => {
+                self.status = Some(trf("同步失败：{}", &[&e]));
+                Outcome::Unchanged
+            }
+        }
+    }
+
+    fn try_reconcile(&mut self) -> Result<Outcome, String> {
+        let local = LocalFile::current();
+        let (l_at, r_at) = (local.last_modified()?, self.remote.last_modified()?);
+        let last = SyncState::load().last_sync;
+        let outcome = match (l_at, r_at) {
+            (None, None) => return Ok(Outcome::Unchanged),
+            (Some(l), Some(r)) if l == r => Outcome::Unchanged,
+            (Some(_), None) => self.push_to_remote(&local)?,
+            (None, Some(_)) => self.pull_to_local(&local)?,
+            (Some(_), Some(r)) if r == last => self.push_to_remote(&local)?,
+            (Some(l), Some(_)) if l == last => self.pull_to_local(&local)?,
+            (Some(l), Some(r)) => {
+                self.conflict = Some(Conflict { local: l, remote: r });
+                return Ok(Outcome::Conflict);
+            }
+        };
+        SyncState { last_sync: l_at.max(r_at).unwrap_or(0) }.save();
+        self.status = Some(match outcome {
+            Outcome::Pushed => trf("已上传到{}", &[&self.remote.name()]),
+            Outcome::Pulled => trf("已从{}下载", &[&self.remote.name()]),
+            _ => tr("已同步").to_string(),
+        });
+        Ok(outcome)
+    }
+
+    fn push_to_remote(&self, local: &LocalFile) -> Result<Outcome, String> {
+        self.remote.store(&local.load()?.ok_or(tr("本机存档不见了"))?)?;
+        Ok(Outcome::Pushed)
+    }
+    fn pull_to_local(&self, local: &LocalFile) -> Result<Outcome, String> {
+        local.store(&self.remote.load()?.ok_or(tr("云端存档不见了"))?)?;
+        Ok(Outcome::Pulled)
+    }
+
+    // 冲突时玩家的选择：use_remote 为真则用远端覆盖本地（之后需要重新读档）
+    pub fn resolve(&mut self, use_remote: bool) -> Result<(), String> {
+        let Some(c) = self.conflict.take() else { return Ok(()) };
+        let local = LocalFile::current();
+        let at = if use_remote {
+            self.pull_to_local(&local)?;
+            c.remote
+        } else {
+            self.push_to_remote(&local)?;
+            c.local
+        };
+        SyncState { last_sync: at }.save();
+        self.status = Some(tr("已同步").to_string());
+        Ok(())
+    }
+
+    // 退出前把本地存档推上去（有未解决的冲突时不动）
+    pub fn push(&mut self) {
+        if self.conflict.is_some() { return; }
+        let local = LocalFile::current();
+        let Ok(Some(text)) = local.load() else { return };
+        match self.remote.store(&text) {
+            Ok(()) => SyncState { last_sync: saved_at(&text).unwrap_or(0) }.save(),
+            Err(e) => crate::logfile::warn(&format!("上传存档失败：{}", e)),
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+
+use crate::audio::Sfx;
+use crate::rng::{Rng, Stream};
+
+// ===== 合成音效 =====
+// assets/audio/ 里缺哪个音效文件，开局就按 assets/sfxr.json 里的参数现场合成一个顶上（缺文件用下面的默认值），光秃秃的仓库也有声音。
+// 参数仿 jsfxr：波形、起始音高、滑音、一次跳音（琶音）、方波占空比、起音 / 持续 / 衰减三段包络和持续段开头的冲击。
+// 合成出来就是普通的 WAV 字节，和读文件走同一条加载路径；听觉预警（cues.rs）的提示音也用这里的 wav 打包。
+pub const SFXR_PATH: &str = "assets/sfxr.json";
+pub const RATE: u32 = 44100;
+const NOISE_SEED: u64 = 0x5F3A;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Wave { Square, Saw, Sine, Noise }
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Voice {
+    pub wave: Wave,
+    pub freq: f32,    // 起始音高（Hz）；噪声是换随机值的频率，越低越闷
+    pub slide: f32,   // 音高每秒变化多少个八度，负数往下滑
+    pub jump: f32,    // 到 jump_at 秒时音高再乘这个，1 为不跳
+    pub jump_at: f32,
+    pub duty: f32,    // 方波占空比（0–1）
+    pub attack: f32,  // 三段包络（秒）
+    pub sustain: f32,
+    pub decay: f32,
+    pub punch: f32,   // 持续段开头多响多少（0–1），之后线性回落
+    pub volume: f32,
+}
+
+impl Default for Voice {
+    fn default() -> Self {
+        Self { wave: Wave::Square, freq: 440.0, slide: 0.0, jump: 1.0, jump_at: 0.0, duty: 0.5, attack: 0.0, sustain: 0.05, decay: 0.15, punch: 0.0, volume: 0.5 }
+    }
+}
+
+// 每种音效一段参数，键名同 assets/audio/ 下的文件名
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Voices {
+    pub pickup: Voice,
+    pub shield: Voice,
+    pub bomb: Voice,
+    pub death: Voice,
+    pub menu_move: Voice,
+    pub menu_select: Voice,
+}
+
+impl Default for Voices {
+    fn default() -> Self {
+        let v = Voice::default();
+        Self {
+            pickup: Voice { freq: 880.0, jump: 1.5, jump_at: 0.06, sustain: 0.06, decay: 0.18, punch: 0.4, ..v },
+            shield: Voice { wave: Wave::Sine, freq: 300.0, slide: 1.5, sustain: 0.12, decay: 0.25, ..v },
+            bomb: Voice { wave: Wave::Noise, freq: 2400.0, slide: -1.2, sustain: 0.15, decay: 0.6, punch: 0.6, volume: 0.7, ..v },
+            death: Voice { wave: Wave::Saw, freq: 440.0, slide: -2.5, sustain: 0.25, decay: 0.5, volume: 0.55, ..v },
+            menu_move: Voice { freq: 660.0, duty: 0.25, sustain: 0.015, decay: 0.04, volume: 0.3, ..v },
+            menu_select: Voice { freq: 520.0, jump: 1.5, jump_at: 0.04, sustain: 0.04, decay: 0.08, volume: 0.35, ..v },
+        }
+    }
+}
+
+impl Voices {
+    pub fn load(path: &str) -> Self {
+        crate::storage::read_asset_string(path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, kind: Sfx) -> Voice {
+        match kind {
+            Sfx::Pickup => self.pickup,
+            Sfx::Shield => self.shield,
+            Sfx::Bomb => self.bomb,
+            Sfx::Death => self.death,
+            Sfx::MenuMove => self.menu_move,
+            Sfx::MenuSelect => self.menu_select,
+        }
+    }
+}
+
+impl Voice {
+    fn envelope(&self, t: f32) -> f32 {
+        if t < self.attack { return t / self.attack; }
+        let t = t - self.attack;
+        if t < self.sustain { return 1.0 + self.punch * (1.0 - t / self.sustain); }
+        let t = t - self.sustain;
+        if t < self.decay { 1.0 - t / self.decay } else { 0.0 }
+    }
+
+    // 单声道采样（-1..1），长度是三段包络之和
+    fn samples(&self) -> Vec<f32> {
+        let n = ((self.attack + self.sustain + self.decay) * RATE as f32) as usize;
+        let mut rng = Rng::stream(NOISE_SEED, Stream::Cosmetic);
+        let (mut phase, mut noise) = (0.0f32, rng.range(-1.0, 1.0));
+        (0..n).map(|i| {
+            let t = i as f32 / RATE as f32;
+            let jump = if self.jump_at > 0.0 && t >= self.jump_at { self.jump } else { 1.0 };
+            let freq = (self.freq * 2f32.powf(self.slide * t) * jump).clamp(20.0, RATE as f32 * 0.5);
+            phase += freq / RATE as f32;
+            if phase >= 1.0 {
+                phase -= 1.0;
+                noise = rng.range(-1.0, 1.0);
+            }
+            let s = match self.wave {
+                Wave::Square => if phase < self.duty { 1.0 } else { -1.0 },
+                Wave::Saw => phase * 2.0 - 1.0,
+                Wave::Sine => (phase * std::f32::consts::TAU).sin(),
+                Wave::Noise => noise,
+            };
+            s * self.envelope(t) * self.volume
+        })
+        .collect()
+    }
+
+    pub fn render(&self) -> Vec<u8> { wav(self.samples().into_iter().map(|s| (s, s))) }
+}
+
+// 把立体声采样（左, 右）打包成 16 位 PCM 的 WAV
+pub fn wav(frames: impl ExactSizeIterator<Item = (f32, f32)>) -> Vec<u8> {
+    let data = frames.len() as u32 * 4;
+    let mut out = Vec::with_capacity(44 + data as usize);
+    out.extend_from_slice(b"RIFF");
+    out.extend_from_slice(&(36 + data).to_le_bytes());
+    out.extend_from_slice(b"WAVEfmt ");
+    out.extend_from_slice(&16u32.to_le_bytes());
+    out.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    out.extend_from_slice(&2u16.to_le_bytes()); // 声道数
+    out.extend_from_slice(&RATE.to_le_bytes());
+    out.extend_from_slice(&(RATE * 4).to_le_bytes());
+    out.extend_from_slice(&4u16.to_le_bytes());
+    out.extend_from_slice(&16u16.to_le_bytes());
+    out.extend_from_slice(b"data");
+    out.extend_from_slice(&data.to_le_bytes());
+    for (l, r) in frames {
+        for s in [l, r] { out.extend_from_slice(&((s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16).to_le_bytes()); }
+    }
+    out
+}
@@ -0,0 +1,191 @@
+use macroquad::prelude::*;
+
+use crate::i18n::{tr, trf};
+use crate::trace::{get_char_pressed, is_key_pressed};
+
+// ===== 单行文本框 =====
+// 排行榜名字、档案名、挑战署名和开局种子共用。打的字插在光标处，[←/→] [HOME/END] 移光标，[BACKSPACE/DEL] 删字，长度按字符数算。
+// 系统输入法上屏的汉字就是普通的字符事件，和字母一样收。有的平台（比如 X11）窗口收不到输入法的字，
+// 这时按 [TAB] 打开内置的拼音选字：打拼音字母，[1~9] 或 [SPACE] 选字，[-/=] 翻页，[TAB/ESC] 收起。
+// 字库只收了起名常用的字，不求全。
+// 安卓上打开文本框时顺便弹出系统软键盘，文本框丢掉（输完或取消）时收起。
+const PAGE: usize = 9;
+const SPELL_MAX: usize = 6;
+
+pub enum Event { Submit, Cancel }
+
+pub struct TextField {
+    chars: Vec<char>,
+    cursor: usize, // 光标前有几个字符
+    max: usize,
+    accept: fn(char) -> bool,
+    spell: Option<String>, // 拼音选字打开时，正在拼的字母
+    page: usize,
+}
+
+impl TextField {
+    pub fn new(text: &str, max: usize) -> Self {
+        while get_char_pressed().is_some() {} // 丢弃积压的字符（包括唤出输入框的那个键）
+        let chars: Vec<char> = text.chars().take(max).collect();
+        #[cfg(target_os = "android")]
+        macroquad::miniquad::window::show_keyboard(true);
+        Self { cursor: chars.len(), chars, max, accept: |c| !c.is_control(), spell: None, page: 0 }
+    }
+    // 只收数字，没有拼音选字
+    pub fn digits(text: &str, max: usize) -> Self {
+        let mut f = Self::new(text, max);
+        f.accept = |c| c.is_ascii_digit();
+        f
+    }
+
+    pub fn text(&self) -> String { self.chars.iter().collect() }
+    pub fn is_blank(&self) -> bool { self.chars.iter().all(|c| c.is_whitespace()) }
+    pub fn at_end(&self) -> bool { self.cursor == self.chars.len() }
+    pub fn picking(&self) -> bool { self.spell.is_some() }
+
+    pub fn insert(&mut self, c: char) {
+        if !(self.accept)(c) || self.chars.len() >= self.max { return; }
+        self.chars.insert(self.cursor, c);
+        self.cursor += 1;
+    }
+    pub fn backspace(&mut self) {
+        if self.cursor == 0 { return; }
+        self.cursor -= 1;
+        self.chars.remove(self.cursor);
+    }
+    // 街机式换字：光标前的字符换成 set 里的前一个 / 后一个，不在 set 里的从头开始；光标在最前面就插一个
+    pub fn cycle(&mut self, set: &[u8], step: i32) {
+        let Some(i) = self.cursor.checked_sub(1) else { return self.insert(set[0] as char) };
+        let n = set.len() as i32;
+        let at = set.iter().position(|b| *b as char == self.chars[i].to_ascii_uppercase());
+        self.chars[i] = match at { Some(k) => set[(k as i32 + step).rem_euclid(n) as usize] as char, None => set[0] as char };
+    }
+
+    // 本帧的按键；[ENTER] 提交、[ESC] 取消交给调用方处理。拼音选字打开时这两个键归选字用
+    pub fn update(&mut self) -> Option<Event> {
+        if self.spell.is_some() { return self.update_spell(); }
+        while let Some(c) = get_char_pressed() { self.insert(c); }
+        if is_key_pressed(KeyCode::Backspace) { self.backspace(); }
+        if is_key_pressed(KeyCode::Delete) && !self.at_end() { self.chars.remove(self.cursor); }
+        if is_key_pressed(KeyCode::Left) { self.cursor = self.cursor.saturating_sub(1); }
+        if is_key_pressed(KeyCode::Right) { self.cursor = (self.cursor + 1).min(self.chars.len()); }
+        if is_key_pressed(KeyCode::Home) { self.cursor = 0; }
+        if is_key_pressed(KeyCode::End) { self.cursor = self.chars.len(); }
+        if is_key_pressed(KeyCode::Tab) && (self.accept)('中') { self.spell = Some(String::new()); }
+        if is_key_pressed(KeyCode::Enter) { return Some(Event::Submit); }
+        is_key_pressed(KeyCode::Escape).then_some(Event::Cancel)
+    }
+
+    fn update_spell(&mut self) -> Option<Event> {
+        while let Some(c) = get_char_pressed() {
+            let Some(spell) = self.spell.as_mut() else { break };
+            match c {
+                'a'..='z' | 'A'..='Z' if spell.len() < SPELL_MAX => {
+                    spell.push(c.to_ascii_lowercase());
+                    self.page = 0;
+                }
+                '1'..='9' => self.pick(c as usize - '1' as usize),
+                ' ' => self.pick(0),
+                '-' => self.page = self.page.saturating_sub(1),
+                '=' if (self.page + 1) * PAGE < self.candidates().len() => self.page += 1,
+                _ => {}
+            }
+        }
+        let empty = self.spell.as_ref().is_none_or(|s| s.is_empty());
+        if is_key_pressed(KeyCode::Backspace) {
+            if empty { self.backspace(); } else if let Some(s) = self.spell.as_mut() { s.pop(); self.page = 0; }
+        }
+        if is_key_pressed(KeyCode::Tab) || is_key_pressed(KeyCode::Escape) { self.spell = None; }
+        // 没在拼的时候 [ENTER] 直接收起并提交
+        if is_key_pressed(KeyCode::Enter) {
+            if !empty { self.pick(0); return None; }
+            self.spell = None;
+            return Some(Event::Submit);
+        }
+        None
+    }
+
+    fn candidates(&self) -> Vec<char> { candidates(self.spell.as_deref().unwrap_or("")) }
+
+    fn pick(&mut self, i: usize) {
+        let Some(c) = self.candidates().get(self.page * PAGE + i).copied() else { return };
+        self.insert(c);
+        self.spell = Some(String::new());
+        self.page = 0;
+    }
+
+    // 以 x 为中心画「label + 文字」，光标处一条闪烁的竖线
+    pub fn draw(&self, font: &Font, x: f32, y: f32, size: f32, label: &str, color: Color) {
+        let full = format!("{}{}", label, self.text());
+        let left = x - measure_text(&full, Some(font), size as u16, 1.0).width * 0.5;
+        self.draw_left(font, left, y, size, label, color);
+    }
+
+    // 同上，左边对齐到 left
+    pub fn draw_left(&self, font: &Font, left: f32, y: f32, size: f32, label: &str, color: Color) {
+        let head: String = self.chars[..self.cursor].iter().collect();
+        let full = format!("{}{}", label, self.text());
+        let width = |s: &str| measure_text(s, Some(font), size as u16, 1.0).width;
+        draw_text_ex(&full, left, y, TextParams { font: Some(font), font_size: size as u16, color, ..Default::default() });
+        if get_time().fract() < 0.5 {
+            let cx = left + width(&format!("{}{}", label, head)) + 1.0;
+            draw_line(cx, y - size * 0.8, cx, y + size * 0.15, 2.0, color);
+        }
+    }
+
+    // 拼音选字打开时的候选行（和提示），没打开时不画；位置由调用方给
+    pub fn draw_picker(&self, font: &Font, x: f32, y: f32, size: f32) {
+        let Some(spell) = &self.spell else { return };
+        let shown: Vec<String> = self.candidates().iter().skip(self.page * PAGE).take(PAGE).enumerate().map(|(i, c)| format!("{}.{}", i + 1, c)).collect();
+        let line = trf("拼音：{}_   {}", &[spell, &shown.join(" ")]);
+        let params = |size: f32, color| TextParams { font: Some(font), font_size: size as u16, color, ..Default::default() };
+        let center = |s: &str, size: f32| x - measure_text(s, Some(font), size as u16, 1.0).width * 0.5;
+        draw_text_ex(&line, center(&line, size), y, params(size, SKYBLUE));
+        let hint = tr("[1~9/SPACE] 选字   [-/=] 翻页   [TAB] 收起");
+        draw_text_ex(hint, center(hint, size * 0.8), y + size * 1.2, params(size * 0.8, GRAY));
+    }
+}
+
+#[cfg(target_os = "android")]
+impl Drop for TextField {
+    fn drop(&mut self) { macroquad::miniquad::window::show_keyboard(false); }
+}
+
+// 拼音对应的候选字：整个音节对上的排前面，其余按前缀匹配
+pub fn candidates(spell: &str) -> Vec<char> {
+    if spell.is_empty() { return Vec::new(); }
+    let exact = PINYIN.iter().filter(|(s, _)| *s == spell);
+    let prefix = PINYIN.iter().filter(|(s, _)| *s != spell && s.starts_with(spell));
+    let mut out: Vec<char> = Vec::new();
+    for c in exact.chain(prefix).flat_map(|(_, cs)| cs.chars()) {
+        if !out.contains(&c) { out.push(c); }
+    }
+    out
+}
+
+const PINYIN: &[(&str, &str)] = &[
+    ("a", "阿啊"), ("ai", "爱艾"), ("an", "安"), ("ao", "奥傲"),
+    ("ba", "八巴霸"), ("bai", "白百柏"), ("bao", "宝豹包"), ("bei", "北贝"), ("ben", "本奔"), ("bing", "冰兵"), ("bo", "波博"),
+    ("cai", "才彩"), ("chen", "陈晨辰"), ("cheng", "成城程"), ("chu", "楚初"), ("chuan", "川"), ("chun", "春纯"),
+    ("da", "大达"), ("dan", "丹"), ("dao", "刀道"), ("de", "德"), ("di", "帝地迪"), ("dong", "东冬"),
+    ("er", "二儿"), ("fa", "法发"), ("fan", "凡帆范"), ("fei", "飞菲"), ("feng", "风峰凤锋"), ("fu", "福富"),
+    ("gang", "刚钢"), ("gao", "高"), ("ge", "哥歌"), ("guang", "光广"), ("guo", "国郭果"),
+    ("hai", "海"), ("han", "寒韩涵汉"), ("hao", "浩豪好"), ("he", "何和河鹤"), ("hong", "红宏鸿虹"), ("hu", "虎胡湖"),
+    ("hua", "华花"), ("huang", "黄皇"), ("hui", "辉慧"), ("huo", "火"),
+    ("ji", "吉极纪"), ("jia", "佳家嘉"), ("jian", "剑建健"), ("jiang", "江姜将"), ("jie", "杰洁"), ("jin", "金锦晋"),
+    ("jing", "静晶京"), ("jun", "军君俊"), ("kai", "凯开"), ("ke", "可克柯"), ("kong", "空孔"),
+    ("lan", "兰蓝岚"), ("lang", "狼浪朗"), ("lei", "雷蕾"), ("li", "李丽力黎"), ("lin", "林琳霖"), ("ling", "玲灵凌"),
+    ("liu", "刘流柳"), ("long", "龙"), ("lu", "路陆鹿"), ("ma", "马"), ("mei", "美梅"), ("meng", "梦萌猛"),
+    ("ming", "明鸣"), ("mo", "墨莫魔"), ("mu", "木沐"), ("na", "娜"), ("ning", "宁凝"), ("niu", "牛"),
+    ("pei", "佩"), ("peng", "鹏彭"), ("qi", "七琪奇齐"), ("qian", "千倩"), ("qiang", "强"), ("qing", "青清晴"),
+    ("qiu", "秋球"), ("ran", "然冉"), ("ren", "人仁"), ("rong", "荣蓉"), ("rui", "瑞锐"),
+    ("san", "三"), ("sha", "沙"), ("shan", "山珊闪"), ("shen", "神深"), ("sheng", "胜圣"), ("shi", "石诗士"),
+    ("shu", "书舒"), ("shuang", "双霜"), ("shui", "水"), ("si", "思丝"), ("song", "松宋"),
+    ("tao", "涛桃"), ("tian", "天田甜"), ("ting", "婷"), ("wang", "王望"), ("wei", "伟威薇魏"), ("wen", "文雯"),
+    ("wu", "吴武舞"), ("xi", "西希曦"), ("xia", "夏霞侠"), ("xiao", "小晓肖"), ("xin", "新心鑫欣"), ("xing", "星兴"),
+    ("xiong", "熊雄"), ("xu", "徐旭"), ("xuan", "轩萱"), ("xue", "雪学"),
+    ("ya", "雅亚"), ("yan", "燕岩言颜"), ("yang", "杨阳洋"), ("ye", "叶夜"), ("yi", "一艺怡依"), ("yin", "银音"),
+    ("ying", "英莹鹰影"), ("yong", "勇永"), ("you", "优友"), ("yu", "宇雨玉鱼羽"), ("yuan", "元圆远"), ("yue", "月悦岳"),
+    ("yun", "云芸"), ("ze", "泽"), ("zhang", "张章"), ("zhao", "赵照"), ("zhe", "哲"), ("zhen", "真珍"),
+    ("zheng", "正郑"), ("zhi", "之志智"), ("zhong", "中钟"), ("zhou", "周舟"), ("zhu", "朱竹珠"), ("zi", "子紫"),
+];
@@ -0,0 +1,80 @@
+use macroquad::prelude::*;
+
+use crate::cosmetics::{rgb, Theme};
+
+// ===== 障碍的危险等级 =====
+// 障碍按下落速度（相对当前基准落速）分成慢 / 普通 / 快三档，颜色各不相同，一眼就能看出哪个先到。
+// 配色来自当前主题：主题可以在 cosmetics.json 里用 "threat": [[慢], [普通], [快]] 直接给三种颜色（色弱友好主题就是这样做的），
+// 没给时从主题的障碍色推出来：慢的偏冷偏灰、快的偏亮偏黄。
+// 光靠颜色不够（高对比度模式下一律纯红），所以再加记号：快的底部有向下的箭头，横飘的在前进方向一侧有小三角。
+// 等级只在画的时候从速度算，不存进障碍，模拟和回放都不受影响。
+const SLOW_BELOW: f32 = 1.0;
+const FAST_FROM: f32 = 1.2;
+const COOL: Color = Color::new(0.55, 0.62, 0.78, 1.0);
+const HOT: Color = Color::new(1.0, 0.95, 0.4, 1.0);
+const DRIFT_MIN: f32 = 1.0; // 横向速度超过这个才算横飘
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Tier { Slow, Normal, Fast }
+
+impl Tier {
+    pub const ALL: [Tier; 3] = [Tier::Slow, Tier::Normal, Tier::Fast];
+
+    // vy 为 0（观战画面只传位置）或基准落速还没定时当普通
+    pub fn of(vy: f32, fall_speed: f32) -> Tier {
+        if vy == 0.0 || fall_speed <= 0.0 { return Tier::Normal; }
+        let k = vy.abs() / fall_speed;
+        if k < SLOW_BELOW { Tier::Slow } else if k < FAST_FROM { Tier::Normal } else { Tier::Fast }
+    }
+
+    pub fn label(self) -> &'static str {
+        crate::i18n::tr(match self {
+            Tier::Slow => "慢",
+            Tier::Normal => "普通",
+            Tier::Fast => "快",
+        })
+    }
+}
+
+fn mix(a: Color, b: Color, t: f32) -> Color {
+    Color::new(a.r + (b.r - a.r) * t, a.g + (b.g - a.g) * t, a.b + (b.b - a.b) * t, a.a)
+}
+
+// 三档的颜色，下标同 Tier::ALL；base 是这一局原本的障碍色
+pub fn palette(theme: &Theme, base: Color) -> [Color; 3] {
+    match theme.threat {
+        Some(c) => c.map(rgb),
+        None => [mix(base, COOL, 0.45), base, mix(base, HOT, 0.5)],
+    }
+}
+
+pub fn color(palette: &[Color; 3], tier: Tier) -> Color { palette[tier as usize] }
+
+// 形状记号：快的在底部画向下的箭头，横飘的在前进方向一侧画小三角
+pub fn draw_marks(r: Rect, tier: Tier, vx: f32, color: Color) {
+    let s = (r.w.min(r.h) * 0.3).clamp(3.0, 9.0);
+    if tier == Tier::Fast {
+        let (cx, y) = (r.center().x, r.bottom() - s * 0.4);
+        draw_line(cx - s, y - s, cx, y, 2.0, color);
+        draw_line(cx + s, y - s, cx, y, 2.0, color);
+    }
+    if vx.abs() > DRIFT_MIN {
+        let (x, dir) = if vx > 0.0 { (r.right() - 2.0, 1.0) } else { (r.x + 2.0, -1.0) };
+        let cy = r.center().y;
+        draw_triangle(vec2(x, cy), vec2(x - dir * s, cy - s * 0.7), vec2(x - dir * s, cy + s * 0.7), color);
+    }
+}
+
+// 图例：三档色块加记号，最后一个是横飘；(x, y) 是左上角
+pub fn draw_legend(font: &Font, x: f32, y: f32, palette: &[Color; 3], ts: f32) {
+    let size = 26.0;
+    let row = 34.0 * ts;
+    let items = Tier::ALL.iter().map(|&t| (t, 0.0, t.label())).chain([(Tier::Normal, 1.0, crate::i18n::tr("横飘"))]);
+    draw_rectangle(x - 10.0, y - 10.0, 130.0 * ts, row * 4.0 + 12.0, Color::new(0.0, 0.0, 0.0, 0.45));
+    for (i, (tier, vx, label)) in items.enumerate() {
+        let r = Rect::new(x, y + i as f32 * row, size, size);
+        draw_rectangle(r.x, r.y, r.w, r.h, color(palette, tier));
+        draw_marks(r, tier, vx, WHITE);
+        draw_text_ex(label, r.right() + 12.0, r.y + size * 0.78, TextParams { font: Some(font), font_size: (20.0 * ts) as u16, color: WHITE, ..Default::default() });
+    }
+}
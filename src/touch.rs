@@ -0,0 +1,128 @@
+use std::cell::RefCell;
+
+use macroquad::prelude::*;
+
+use crate::settings::TouchMode;
+use crate::{field_camera, steer, view_size, Game, GameMode, PlayMode};
+
+// ===== 触屏 =====
+// 手机 / 网页版用。局内一根手指按住拖动挡板；拖着的时候第二根手指点一下，放出攒好的炸弹（没有炸弹就开超载）。
+// 跟手方式见 settings.touch：直接 = 挡板跑向手指正下方；相对 = 像触控板，只看手指从按下起挪了多少，手指不会挡住挡板。
+// 菜单等界面底部画几个大按钮，点下去等于按了对应的键：和手柄一样在 trace.rs 里并进键盘输入，界面代码不用知道触屏。
+// 最近一次操作来自触屏时才画按钮，一按键盘就收起来；安卓版一开始就画，按键（多半是返回键）也不收。
+const BUTTON_H: f32 = 64.0;
+const BUTTON_W_MAX: f32 = 220.0;
+const BUTTON_GAP: f32 = 12.0;
+const PAUSE_SIZE: f32 = 52.0;
+
+// 拖动挡板的那根手指
+struct Drag {
+    id: u64,
+    x: f32,             // 手指现在的场地 x
+    anchor: (f32, f32), // 按下时手指和挡板中心的场地 x（相对模式用）
+}
+
+#[derive(Default)]
+struct State {
+    drag: Option<Drag>,
+    pressed: Vec<KeyCode>, // 本帧点到的按钮
+    second_tap: bool,
+    active: bool, // 最近一次操作来自触屏
+}
+
+thread_local! {
+    static STATE: RefCell<State> = RefCell::new(State { active: cfg!(target_os = "android"), ..State::default() });
+}
+
+// 各界面的按钮：(文字, 等于按下的键)；重开跟着改键走
+fn buttons(game: &Game) -> Vec<(&'static str, KeyCode)> {
+    let restart = game.settings.keys.restart.primary();
+    match game.mode {
+        GameMode::Menu | GameMode::Paused => Vec::new(), // 菜单本身就能点
+        GameMode::PreRun => vec![("出发", KeyCode::Space), ("返回", KeyCode::Escape)],
+        GameMode::Playing => vec![("II", KeyCode::Pause)],
+        GameMode::Killcam => vec![("跳过", KeyCode::Space)],
+        GameMode::GameOver if game.play == PlayMode::Hotseat => vec![("继续", KeyCode::Space)],
+        GameMode::GameOver => vec![("再来", restart), ("菜单", KeyCode::Escape)],
+        _ => vec![("返回", KeyCode::Escape)],
+    }
+}
+
+// 按钮位置（场地坐标）：局内只在右上角放一个暂停，其余界面在底部排成一行
+fn layout(game: &Game) -> Vec<(Rect, &'static str, KeyCode)> {
+    let view = view_size(game);
+    let list = buttons(game);
+    if game.mode == GameMode::Playing {
+        return list.into_iter().map(|(label, k)| (Rect::new(view.x - PAUSE_SIZE - 10.0, 10.0, PAUSE_SIZE, PAUSE_SIZE), label, k)).collect();
+    }
+    let n = list.len() as f32;
+    let bw = ((view.x - BUTTON_GAP * (n + 1.0)) / n).min(BUTTON_W_MAX);
+    let x0 = (view.x - bw * n - BUTTON_GAP * (n - 1.0)) * 0.5;
+    let y = view.y - BUTTON_H - 70.0; // 让开底部的按键提示
+    list.into_iter().enumerate().map(|(i, (label, k))| (Rect::new(x0 + i as f32 * (bw + BUTTON_GAP), y, bw, BUTTON_H), label, k)).collect()
+}
+
+// 每帧开头调用一次：新按下的手指先看是不是点在按钮上，否则第一根拿来拖动、之后的算第二指点按
+pub fn poll(game: &Game) {
+    let touches = crate::trace::touches();
+    let cam = field_camera(view_size(game), Vec2::ZERO);
+    let buttons = layout(game);
+    let paddle = game.player.rect().center().x;
+    STATE.with_borrow_mut(|s| {
+        s.pressed.clear();
+        s.second_tap = false;
+        for t in &touches {
+            let at = cam.screen_to_world(t.position);
+            match t.phase {
+                TouchPhase::Started => {
+                    s.active = true;
+                    if let Some(b) = buttons.iter().find(|b| b.0.contains(at)) {
+                        s.pressed.push(b.2);
+                    } else if s.drag.is_none() {
+                        s.drag = Some(Drag { id: t.id, x: at.x, anchor: (at.x, paddle) });
+                    } else {
+                        s.second_tap = true;
+                    }
+                }
+                TouchPhase::Ended | TouchPhase::Cancelled => {
+                    if s.drag.as_ref().is_some_and(|d| d.id == t.id) { s.drag = None; }
+                }
+                TouchPhase::Moved | TouchPhase::Stationary => {
+                    if let Some(d) = s.drag.as_mut().filter(|d| d.id == t.id) { d.x = at.x; }
+                }
+            }
+        }
+        if !cfg!(target_os = "android") && !get_keys_pressed().is_empty() { s.active = false; }
+    });
+}
+
+// 触屏给出的移动方向（-1 / 0 / 1），没有手指在拖时为 0
+pub fn axis(game: &Game) -> f32 {
+    STATE.with_borrow(|s| {
+        let Some(d) = &s.drag else { return 0.0 };
+        let target = match game.settings.touch {
+            TouchMode::Direct => d.x,
+            TouchMode::Relative => d.anchor.1 + (d.x - d.anchor.0),
+        };
+        steer(game, target)
+    })
+}
+
+pub fn second_tap() -> bool { STATE.with_borrow(|s| s.second_tap) }
+pub fn key_pressed(k: KeyCode) -> bool { STATE.with_borrow(|s| s.pressed.contains(&k)) }
+
+// 按钮画在最上层，只在触屏是当前设备时画
+pub fn draw(font: &Font, game: &Game) {
+    if !STATE.with_borrow(|s| s.active) { return; }
+    set_camera(&field_camera(view_size(game), Vec2::ZERO));
+    let size = (26.0 * game.settings.text_scale()) as u16;
+    for (r, label, _) in layout(game) {
+        draw_rectangle(r.x, r.y, r.w, r.h, Color::new(0.1, 0.12, 0.18, 0.6));
+        draw_rectangle_lines(r.x, r.y, r.w, r.h, 2.0, Color::new(1.0, 1.0, 1.0, 0.5));
+        let label = crate::i18n::tr(label);
+        let dim = measure_text(label, Some(font), size, 1.0);
+        let (x, y) = (r.x + (r.w - dim.width) * 0.5, r.y + (r.h + dim.offset_y) * 0.5);
+        draw_text_ex(label, x, y, TextParams { font: Some(font), font_size: size, color: WHITE, ..Default::default() });
+    }
+    set_default_camera();
+}
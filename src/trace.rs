@@ -0,0 +1,202 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use macroquad::prelude as mq;
+use macroquad::prelude::{vec2, KeyCode, MouseButton, Touch, TouchPhase};
+use serde::{Deserialize, Serialize};
+
+use crate::settings::SETTINGS_PATH;
+use crate::{paths, storage, SAVE_PATH};
+
+// ===== 输入轨迹（排查 bug 用）=====
+// --record trace.bin 把每一帧游戏向 macroquad 问到的输入（按键、字符、鼠标、帧时间、窗口尺寸）和开局种子
+// 按提问顺序原样记下；--play trace.bin 按同样的顺序把答案喂回去，逐帧重现，可以挂调试器慢慢看。
+// 和回放（replay.rs）无关：回放只录局内的固定步输入，这里连菜单操作和帧时间都录。
+// 开录时当前档案的存档和设置也一起存进去，重放时放进临时目录，不碰本机的存档。
+// 游戏代码里的输入函数都从这里导入（同名，遮住 macroquad 的），不录不放时直接转给 macroquad。
+// 开了 gamepad 特性时手柄翻译出的按键、触屏按钮点出的按键也在这里并进来，轨迹里记的是合并后的结果。
+// 文件格式：MAGIC、u32 头长度、头（JSON），然后是一串小端记录：标签 u8 + 按标签而定的内容。
+// 重放时问题对不上（标签或按键不同）说明行为已经分叉，直接 panic 报出帧号。
+const MAGIC: &[u8; 8] = b"DRTRACE1";
+// 头（版本、存档、设置）的长度上限；文件里写的长度超过它就当坏文件，不先照着分配内存
+const HEADER_MAX: usize = 4 << 20;
+
+#[derive(Serialize, Deserialize)]
+struct Header {
+    game: String,
+    save: Option<String>,
+    settings: Option<String>,
+}
+
+enum Mode {
+    Off,
+    Record(BufWriter<File>),
+    Play { src: BufReader<File>, frame: u64 },
+}
+
+static MODE: Mutex<Mode> = Mutex::new(Mode::Off);
+
+// —— 开始录 / 放 ——
+pub fn record(path: &str) -> Result<(), String> {
+    let header = Header {
+        game: env!("CARGO_PKG_VERSION").to_string(),
+        save: storage::read_to_string(paths::profile(SAVE_PATH)).ok(),
+        settings: storage::read_to_string(paths::profile(SETTINGS_PATH)).ok(),
+    };
+    let json = serde_json::to_vec(&header).unwrap();
+    let mut out = BufWriter::new(File::create(path).map_err(|e| e.to_string())?);
+    out.write_all(MAGIC).and_then(|_| out.write_all(&(json.len() as u32).to_le_bytes())).and_then(|_| out.write_all(&json)).map_err(|e| e.to_string())?;
+    *MODE.lock().unwrap() = Mode::Record(out);
+    Ok(())
+}
+
+// 读头、把存档放进临时数据目录（代替 paths::init），之后按轨迹喂输入
+pub fn play(path: &str) -> Result<(), String> {
+    let mut src = BufReader::new(File::open(path).map_err(|e| e.to_string())?);
+    let mut magic = [0u8; 8];
+    src.read_exact(&mut magic).map_err(|e| e.to_string())?;
+    if &magic != MAGIC { return Err("不是输入轨迹文件".to_string()); }
+    let mut len = [0u8; 4];
+    src.read_exact(&mut len).map_err(|e| e.to_string())?;
+    let len = u32::from_le_bytes(len) as usize;
+    if len > HEADER_MAX { return Err(format!("轨迹头长度 {} 不对，文件可能损坏", len)); }
+    let mut json = vec![0u8; len];
+    src.read_exact(&mut json).map_err(|e| e.to_string())?;
+    let header: Header = serde_json::from_slice(&json).map_err(|e| e.to_string())?;
+    if header.game != env!("CARGO_PKG_VERSION") {
+        crate::logfile::warn(&format!("轨迹来自版本 {}，当前 {}，可能很快就会分叉", header.game, env!("CARGO_PKG_VERSION")));
+    }
+    let dir: PathBuf = std::env::temp_dir().join(format!("dodge-trace-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    if let Some(s) = &header.save { std::fs::write(dir.join(SAVE_PATH), s).map_err(|e| e.to_string())?; }
+    if let Some(s) = &header.settings { std::fs::write(dir.join(SETTINGS_PATH), s).map_err(|e| e.to_string())?; }
+    paths::init_scratch(dir);
+    *MODE.lock().unwrap() = Mode::Play { src, frame: 0 };
+    Ok(())
+}
+
+// 每帧开头调用：写 / 读一个帧分隔
+pub fn frame() {
+    let mut mode = MODE.lock().unwrap();
+    match &mut *mode {
+        Mode::Off => {}
+        Mode::Record(out) => {
+            let _ = out.flush(); // 每帧落盘，崩溃时轨迹也是完整的
+            let _ = out.write_all(b"F");
+        }
+        Mode::Play { src, frame } => {
+            let mut tag = [0u8; 1];
+            if src.read_exact(&mut tag).is_err() {
+                crate::logfile::warn(&format!("输入轨迹放完了（共 {} 帧），之后交还键盘鼠标", frame));
+                *mode = Mode::Off;
+                return;
+            }
+            if tag[0] != b'F' { panic!("输入轨迹在第 {} 帧分叉：这一帧少问了输入", frame); }
+            *frame += 1;
+        }
+    }
+}
+
+// 一次提问：录的时候问 macroquad 并记下答案，放的时候从文件里读答案
+fn ask<const N: usize>(tag: u8, id: u16, live: impl FnOnce() -> [u8; N]) -> [u8; N] {
+    let mut mode = MODE.lock().unwrap();
+    match &mut *mode {
+        Mode::Off => live(),
+        Mode::Record(out) => {
+            let v = live();
+            let _ = out.write_all(&[tag]).and_then(|_| out.write_all(&id.to_le_bytes())).and_then(|_| out.write_all(&v));
+            v
+        }
+        Mode::Play { src, frame } => {
+            let mut head = [0u8; 3];
+            let mut v = [0u8; N];
+            if src.read_exact(&mut head).and_then(|_| src.read_exact(&mut v)).is_err() {
+                panic!("输入轨迹在第 {} 帧中途结束", frame);
+            }
+            let got = (head[0], u16::from_le_bytes([head[1], head[2]]));
+            if got != (tag, id) {
+                panic!("输入轨迹在第 {} 帧分叉：问的是 {}#{}，轨迹里是 {}#{}", frame, tag as char, id, got.0 as char, got.1);
+            }
+            v
+        }
+    }
+}
+
+fn flag(tag: u8, id: u16, live: impl FnOnce() -> bool) -> bool { ask(tag, id, || [live() as u8])[0] != 0 }
+
+fn pair(tag: u8, live: impl FnOnce() -> (f32, f32)) -> (f32, f32) {
+    let v = ask(tag, 0, || {
+        let (a, b) = live();
+        let mut v = [0u8; 8];
+        v[..4].copy_from_slice(&a.to_le_bytes());
+        v[4..].copy_from_slice(&b.to_le_bytes());
+        v
+    });
+    (f32::from_le_bytes([v[0], v[1], v[2], v[3]]), f32::from_le_bytes([v[4], v[5], v[6], v[7]]))
+}
+
+#[cfg(feature = "gamepad")]
+use crate::pad::{key_down as pad_down, key_pressed as pad_pressed, stick_x as pad_stick_x};
+#[cfg(not(feature = "gamepad"))]
+fn pad_down(_: KeyCode) -> bool { false }
+#[cfg(not(feature = "gamepad"))]
+fn pad_pressed(_: KeyCode) -> bool { false }
+#[cfg(not(feature = "gamepad"))]
+fn pad_stick_x() -> f32 { 0.0 }
+
+const PHASES: [TouchPhase; 5] = [TouchPhase::Started, TouchPhase::Stationary, TouchPhase::Moved, TouchPhase::Ended, TouchPhase::Cancelled];
+
+fn alt_down() -> bool { mq::is_key_down(KeyCode::LeftAlt) || mq::is_key_down(KeyCode::RightAlt) }
+
+fn button_id(b: MouseButton) -> u16 {
+    match b {
+        MouseButton::Left => 0,
+        MouseButton::Right => 1,
+        MouseButton::Middle => 2,
+        MouseButton::Unknown => 3,
+    }
+}
+
+// —— 给游戏代码用的输入函数（和 macroquad 同名）——
+// [ALT+ENTER] 是切全屏（display.rs），里面的 ENTER 不再算给界面
+pub fn is_key_pressed(k: KeyCode) -> bool { flag(b'K', k as u16, || (mq::is_key_pressed(k) && !(k == KeyCode::Enter && alt_down())) || pad_pressed(k) || crate::touch::key_pressed(k)) }
+pub fn is_key_down(k: KeyCode) -> bool { flag(b'k', k as u16, || mq::is_key_down(k) || pad_down(k)) }
+pub fn alt_enter() -> bool { flag(b'A', 0, || mq::is_key_pressed(KeyCode::Enter) && alt_down()) }
+pub fn is_mouse_button_pressed(b: MouseButton) -> bool { flag(b'B', button_id(b), || mq::is_mouse_button_pressed(b)) }
+pub fn is_mouse_button_down(b: MouseButton) -> bool { flag(b'b', button_id(b), || mq::is_mouse_button_down(b)) }
+pub fn mouse_position() -> (f32, f32) { pair(b'M', mq::mouse_position) }
+pub fn stick_x() -> f32 { f32::from_le_bytes(ask(b'S', 0, || pad_stick_x().to_le_bytes())) }
+pub fn get_frame_time() -> f32 { f32::from_le_bytes(ask(b'D', 0, || mq::get_frame_time().to_le_bytes())) }
+pub fn screen_width() -> f32 { f32::from_le_bytes(ask(b'W', 0, || mq::screen_width().to_le_bytes())) }
+pub fn screen_height() -> f32 { f32::from_le_bytes(ask(b'H', 0, || mq::screen_height().to_le_bytes())) }
+
+// 触摸点：先问有几个，再逐个问 id、阶段和位置
+pub fn touches() -> Vec<Touch> {
+    let live = mq::touches();
+    let n = u16::from_le_bytes(ask(b'n', 0, || (live.len() as u16).to_le_bytes()));
+    (0..n)
+        .map(|i| {
+            let v: [u8; 17] = ask(b'P', i, || {
+                let t = &live[i as usize];
+                let mut v = [0u8; 17];
+                v[..8].copy_from_slice(&t.id.to_le_bytes());
+                v[8] = PHASES.iter().position(|p| *p == t.phase).unwrap_or(0) as u8;
+                v[9..13].copy_from_slice(&t.position.x.to_le_bytes());
+                v[13..].copy_from_slice(&t.position.y.to_le_bytes());
+                v
+            });
+            let f = |j: usize| f32::from_le_bytes([v[j], v[j + 1], v[j + 2], v[j + 3]]);
+            Touch { id: u64::from_le_bytes(v[..8].try_into().unwrap()), phase: PHASES[v[8] as usize % PHASES.len()], position: vec2(f(9), f(13)) }
+        })
+        .collect()
+}
+
+pub fn get_char_pressed() -> Option<char> {
+    let v = u32::from_le_bytes(ask(b'C', 0, || mq::get_char_pressed().map_or(u32::MAX, |c| c as u32).to_le_bytes()));
+    char::from_u32(v)
+}
+
+// 开局种子用的时间（毫秒）
+pub fn now_ms() -> u64 { u64::from_le_bytes(ask(b'T', 0, || ((macroquad::miniquad::date::now() * 1000.0) as u64).to_le_bytes())) }
@@ -0,0 +1,130 @@
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::export::EXPORT_DIR;
+use crate::i18n::tr;
+use crate::{parse_save, paths, storage, Save};
+
+// ===== 档案导出 / 导入 =====
+// 整份存档（带签名）连同档案名压成一个 .drp 文件，同时转成 base64 分享串（DRP1: 开头）放进剪贴板。
+// 导入是合并：最高分、前十名、挑战成绩取高的，解锁和成就取并集，生涯统计留局数多的一边。
+// 签名照常校验：改过的存档导进来，当前档案也会被标成改动过。
+pub const SHARE_PREFIX: &str = "DRP1:";
+pub const EXT: &str = "drp";
+const ENTRY: &str = "profile.json";
+
+#[derive(Serialize, Deserialize)]
+struct Packet {
+    name: String,
+    save: serde_json::Value, // 原样的存档文件内容（含 sig）
+}
+
+// 单个文件的压缩包（档案和幽灵共用）
+pub fn zip_one(entry: &str, text: &str) -> Result<Vec<u8>, String> {
+    let mut zw = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let opts = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    zw.start_file(entry, opts).map_err(|e| e.to_string())?;
+    zw.write_all(text.as_bytes()).map_err(|e| e.to_string())?;
+    Ok(zw.finish().map_err(|e| e.to_string())?.into_inner())
+}
+
+pub fn unzip_one(entry: &str, bytes: &[u8]) -> Option<String> {
+    let mut zip = zip::ZipArchive::new(std::io::Cursor::new(bytes)).ok()?;
+    let mut text = String::new();
+    zip.by_name(entry).ok()?.read_to_string(&mut text).ok()?;
+    Some(text)
+}
+
+fn pack(name: &str, save_text: &str) -> Result<Vec<u8>, String> {
+    let save = serde_json::from_str(save_text).map_err(|e| e.to_string())?;
+    zip_one(ENTRY, &serde_json::to_string(&Packet { name: name.to_string(), save }).map_err(|e| e.to_string())?)
+}
+
+// 返回档案名和读出来的存档（签名、迁移都按正常读档走）
+pub fn unpack(bytes: &[u8]) -> Result<(String, Save), String> {
+    let json = unzip_one(ENTRY, bytes).ok_or(tr("不是档案文件"))?;
+    let p: Packet = serde_json::from_str(&json).map_err(|_| tr("档案内容损坏").to_string())?;
+    let save = parse_save(&p.save.to_string()).ok_or(tr("档案内容损坏"))?;
+    Ok((p.name, save))
+}
+
+// 写出 exports/<档案 id>-<时间>.drp，返回文件路径和分享串
+pub fn export(id: &str, name: &str, save_file: &Path) -> Result<(PathBuf, String), String> {
+    let text = storage::read_to_string(save_file).map_err(|_| tr("这个档案还没有存档").to_string())?;
+    let bytes = pack(name, &text)?;
+    let dir = paths::data(EXPORT_DIR);
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let path = dir.join(format!("{}-{}.{}", id, macroquad::miniquad::date::now() as u64, EXT));
+    std::fs::write(&path, &bytes).map_err(|e| e.to_string())?;
+    Ok((path, format!("{}{}", SHARE_PREFIX, base64(&bytes))))
+}
+
+pub fn decode_share(text: &str) -> Option<Vec<u8>> { unbase64(text.trim().strip_prefix(SHARE_PREFIX)?) }
+
+// 把导入的存档并进 into
+pub fn merge(into: &mut Save, from: Save) {
+    into.records.merge(&from.records);
+    into.top.merge(&from.top);
+    for d in from.daily {
+        match into.daily.iter_mut().find(|x| x.day == d.day) {
+            Some(x) => if d.score > x.score { *x = d },
+            None => into.daily.push(d),
+        }
+    }
+    into.daily.sort_by_key(|d| d.day);
+    for (week, score) in from.weekly {
+        let e = into.weekly.entry(week).or_insert(score);
+        *e = (*e).max(score);
+    }
+    into.xp = into.xp.max(from.xp);
+    into.wallet.coins = into.wallet.coins.max(from.wallet.coins);
+    for k in from.wallet.owned {
+        if !into.wallet.owns(&k) { into.wallet.owned.push(k); }
+    }
+    // 同一个成就留较早的解锁时间
+    for (k, t) in from.achievements {
+        let e = into.achievements.entry(k).or_insert(t);
+        *e = (*e).min(t);
+    }
+    let (a, b) = (&mut into.stat_bests, from.stat_bests);
+    a.elapsed = a.elapsed.max(b.elapsed);
+    a.dodged = a.dodged.max(b.dodged);
+    a.near_misses = a.near_misses.max(b.near_misses);
+    a.max_combo = a.max_combo.max(b.max_combo);
+    // 累计数据两边可能有重叠，相加会重复计算，只留玩得多的一边
+    if from.lifetime.runs > into.lifetime.runs {
+        into.lifetime = from.lifetime;
+        into.deaths = from.deaths;
+    }
+    into.tutorial_done |= from.tutorial_done;
+    into.tampered |= from.tampered;
+}
+
+// —— base64（标准字母表，带补位）——
+const B64: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+pub fn base64(data: &[u8]) -> String {
+    let mut out = String::new();
+    for c in data.chunks(3) {
+        let n = (c[0] as u32) << 16 | (*c.get(1).unwrap_or(&0) as u32) << 8 | *c.get(2).unwrap_or(&0) as u32;
+        for i in 0..4 {
+            out.push(if i <= c.len() { B64[(n >> (18 - 6 * i) & 63) as usize] as char } else { '=' });
+        }
+    }
+    out
+}
+
+pub fn unbase64(text: &str) -> Option<Vec<u8>> {
+    let digits: Vec<u32> = text.bytes().filter(|b| !b.is_ascii_whitespace() && *b != b'=')
+        .map(|b| B64.iter().position(|x| *x == b).map(|i| i as u32))
+        .collect::<Option<_>>()?;
+    let mut out = Vec::new();
+    for c in digits.chunks(4) {
+        if c.len() < 2 { return None; }
+        let n = c.iter().enumerate().fold(0, |n, (i, d)| n | d << (18 - 6 * i));
+        out.extend_from_slice(&n.to_be_bytes()[1..c.len()]);
+    }
+    Some(out)
+}
@@ -0,0 +1,164 @@
+use macroquad::prelude::*;
+
+use crate::i18n::tr;
+use crate::waves::SpawnKind;
+use crate::{spawn_kind, spawn_powerup, Game, PowerUpKind, PU_SIZE};
+
+// ===== 新手教程：叠在 Playing 上的脚本序列 =====
+// 每一步先给提示，等玩家真正做到了才进入下一步；教程内碰撞不会结束游戏（有护盾时照常消耗）。
+const FALL_SPEED: f32 = 180.0;
+const STEP_PAUSE: f32 = 1.2;   // 完成一步后的停顿
+const DODGE_COUNT: u32 = 3;
+const DODGE_EVERY: f32 = 1.3;
+const RESPAWN_AFTER: f32 = 1.0; // 道具漏接后重新投放的等待
+const SPEED_SEEN: f32 = 150.0;  // 认为“确实朝该方向移动过”的速度
+// 认危险等级那一步依次落下的障碍：(相对落速, 横向速度)，慢 / 普通 / 快各一个，再来一个横飘的
+const THREAT_DEMO: [(f32, f32); 4] = [(0.85, 0.0), (1.1, 0.0), (1.4, 0.0), (1.0, -90.0)];
+
+#[derive(Clone, Copy, PartialEq)]
+enum Step { Move, Dodge, Threat, ShieldPickup, ShieldHit, Slow, Bomb, Done }
+
+pub struct Tutorial {
+    step: Step,
+    timer: f32,          // 本步已进行的时间
+    done_at: Option<f32>, // 本步完成的时刻，停顿后进入下一步
+    went_left: bool,
+    went_right: bool,
+    spawned: u32,
+    dodged_start: u32,
+    picked: Option<PowerUpKind>,
+}
+
+impl Tutorial {
+    pub fn new() -> Self {
+        Self { step: Step::Move, timer: 0.0, done_at: None, went_left: false, went_right: false, spawned: 0, dodged_start: 0, picked: None }
+    }
+    pub fn on_pickup(&mut self, kind: PowerUpKind) { self.picked = Some(kind); }
+    // 认危险等级那一步在旁边画图例
+    pub fn shows_legend(&self) -> bool { self.step == Step::Threat }
+    pub fn finished(&self) -> bool { self.step == Step::Done && self.timer > STEP_PAUSE * 2.0 }
+
+    pub fn prompt(&self) -> &'static str {
+        if self.done_at.is_some() && self.step != Step::Done { return tr("很好！"); }
+        tr(match self.step {
+            Step::Move => "用 ←/→（或 A/D）左右移动",
+            Step::Dodge => "躲开落下的方块",
+            Step::Threat => "颜色和记号表示障碍有多快，对照左边的图例",
+            Step::ShieldPickup => "接住蓝色的护盾道具",
+            Step::ShieldHit => "这一下躲不开——护盾会替你挡住",
+            Step::Slow => "接住绿色的减速道具：障碍会变慢",
+            Step::Bomb => "接住橙色的炸弹：冲击波会炸掉身边的障碍",
+            Step::Done => "教程完成！祝你好运",
+        })
+    }
+
+    fn complete(&mut self) { if self.done_at.is_none() { self.done_at = Some(self.timer); } }
+
+    fn advance(&mut self, next: Step) {
+        *self = Self { step: next, ..Self::new() };
+    }
+}
+
+fn next_step(s: Step) -> Step {
+    match s {
+        Step::Move => Step::Dodge,
+        Step::Dodge => Step::Threat,
+        Step::Threat => Step::ShieldPickup,
+        Step::ShieldPickup => Step::ShieldHit,
+        Step::ShieldHit => Step::Slow,
+        Step::Slow => Step::Bomb,
+        Step::Bomb | Step::Done => Step::Done,
+    }
+}
+
+// 在玩家正上方投放道具；漏接了就再来一次
+fn offer_powerup(game: &mut Game, kind: PowerUpKind) {
+    let t = &game.tutorial;
+    let due = if t.spawned == 0 { t.timer > 0.3 } else { game.pus.live.is_empty() && t.timer > RESPAWN_AFTER };
+    if due && game.pus.live.is_empty() {
+        let x = (game.player.x + game.player.w * 0.5 - PU_SIZE * 0.5).clamp(0.0, game.field.x - PU_SIZE);
+        spawn_powerup(game, x, kind);
+        game.tutorial.spawned += 1;
+        game.tutorial.timer = 0.0;
+    }
+}
+
+pub fn tick(game: &mut Game, dt: f32) {
+    game.fall_speed = FALL_SPEED;
+    game.elapsed += dt;
+    game.tutorial.timer += dt;
+
+    if let Some(at) = game.tutorial.done_at {
+        if game.tutorial.timer - at >= STEP_PAUSE && game.tutorial.step != Step::Done {
+            let next = next_step(game.tutorial.step);
+            game.tutorial.advance(next);
+            if matches!(next, Step::Dodge | Step::Threat) { game.tutorial.dodged_start = game.dodged; }
+        }
+        return;
+    }
+
+    let picked = game.tutorial.picked.take();
+    match game.tutorial.step {
+        Step::Move => {
+            let t = &mut game.tutorial;
+            if game.player.vx < -SPEED_SEEN { t.went_left = true; }
+            if game.player.vx > SPEED_SEEN { t.went_right = true; }
+            if t.went_left && t.went_right { t.complete(); }
+        }
+        Step::Dodge => {
+            // 对准玩家当前位置落下
+            if game.tutorial.spawned < DODGE_COUNT && game.tutorial.timer >= DODGE_EVERY * game.tutorial.spawned as f32 + 0.5 {
+                let size = 44.0;
+                let x = game.player.x + game.player.w * 0.5 - size * 0.5;
+                spawn_kind(game, SpawnKind::Block, x, size, 1.0, 0.0);
+                game.tutorial.spawned += 1;
+            }
+            if game.dodged >= game.tutorial.dodged_start + DODGE_COUNT { game.tutorial.complete(); }
+        }
+        Step::Threat => {
+            let n = game.tutorial.spawned as usize;
+            if n < THREAT_DEMO.len() && game.tutorial.timer >= DODGE_EVERY * n as f32 + 0.5 {
+                let (speed, vx) = THREAT_DEMO[n];
+                let x = game.field.x * (n as f32 + 0.5) / THREAT_DEMO.len() as f32 - 20.0;
+                spawn_kind(game, SpawnKind::Block, x, 40.0, speed, vx);
+                game.tutorial.spawned += 1;
+            }
+            if game.dodged >= game.tutorial.dodged_start + THREAT_DEMO.len() as u32 { game.tutorial.complete(); }
+        }
+        Step::ShieldPickup => {
+            offer_powerup(game, PowerUpKind::Shield);
+            if picked == Some(PowerUpKind::Shield) { game.tutorial.complete(); }
+        }
+        Step::ShieldHit => {
+            // 横贯全场的长条，必然撞上
+            if game.tutorial.spawned == 0 && game.tutorial.timer > 0.5 {
+                game.obs.spawn(Rect::new(0.0, -30.0, game.field.x, 18.0), 0.0, game.fall_speed);
+                game.tutorial.spawned = 1;
+            }
+            if game.tutorial.spawned == 1 && game.shield == 0 { game.tutorial.complete(); }
+        }
+        Step::Slow => {
+            offer_powerup(game, PowerUpKind::Slow);
+            if picked == Some(PowerUpKind::Slow) { game.tutorial.complete(); }
+        }
+        Step::Bomb => {
+            // 先撒一排方块，再投放炸弹
+            if game.tutorial.spawned == 0 {
+                let n = 6;
+                for i in 0..n {
+                    let x = game.field.x * (i as f32 + 0.5) / n as f32 - 16.0;
+                    spawn_kind(game, SpawnKind::Block, x, 32.0, 0.6, 0.0);
+                }
+                game.tutorial.spawned = 1;
+                game.tutorial.timer = 0.0;
+            }
+            if game.pus.live.is_empty() && game.tutorial.timer > 0.8 {
+                let x = (game.player.x + game.player.w * 0.5 - PU_SIZE * 0.5).clamp(0.0, game.field.x - PU_SIZE);
+                spawn_powerup(game, x, PowerUpKind::Bomb);
+                game.tutorial.timer = 0.0;
+            }
+            if picked == Some(PowerUpKind::Bomb) { game.tutorial.complete(); }
+        }
+        Step::Done => {}
+    }
+}
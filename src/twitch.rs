@@ -0,0 +1,167 @@
+use std::collections::HashSet;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+use macroquad::prelude::*;
+
+use crate::events::WorldEventKind;
+use crate::i18n::{tr, trf};
+use crate::replay::ReplayState;
+use crate::{draw_text_center, spawn_powerup, Game, GameMode, PlayMode, PowerUpKind, PU_SIZE, SLOW_DURATION};
+
+// ===== Twitch 聊天互动（twitch 特性）=====
+// settings.json 里填了 twitch_channel 和 twitch_token（oauth:…）就在启动后连上频道聊天室。
+// 普通模式局内轮流开投票：观众发 !meteor / !slow / !gift 投票，窗口结束时票最多的生效，之后冷却一段再开下一轮。
+// 每人每轮一票。被观众干预过的一局和自适应难度一样不计入最高分。
+// 读聊天在后台线程里做，结果经通道送回，每帧 poll 一次。走明文 IRC 端口（本项目没有 TLS）。
+const SERVER: &str = "irc.chat.twitch.tv:6667";
+const VOTE_WINDOW: f32 = 20.0;
+const COOLDOWN: f32 = 30.0;
+const BANNER_TIME: f32 = 2.5;
+
+pub struct ChatCommand {
+    pub word: &'static str,
+    pub label: &'static str,
+    apply: fn(&mut Game),
+}
+
+pub const COMMANDS: [ChatCommand; 3] = [
+    ChatCommand { word: "!meteor", label: "流星雨", apply: |g| g.events.force(WorldEventKind::MeteorShower) },
+    ChatCommand { word: "!slow", label: "减速", apply: |g| { g.slow.apply(g.pu_tuning.stacking.slow, SLOW_DURATION); } },
+    ChatCommand { word: "!gift", label: "礼物", apply: gift },
+];
+
+// 礼物：玩家头顶掉一个护盾
+fn gift(game: &mut Game) {
+    let x = game.player.x + game.player.w * 0.5 - PU_SIZE * 0.5;
+    spawn_powerup(game, x, PowerUpKind::Shield);
+}
+
+enum Line {
+    Joined,
+    Vote { user: String, choice: usize },
+    Failed(String),
+}
+
+#[derive(Clone, PartialEq)]
+pub enum Status { Connecting, Live, Failed(String) }
+
+struct Vote {
+    remaining: f32,
+    tally: [u32; COMMANDS.len()],
+    voters: HashSet<String>,
+}
+
+#[derive(Default)]
+pub struct Twitch {
+    rx: Option<Receiver<Line>>,
+    tried: bool,
+    pub status: Option<Status>, // 没配频道时为 None
+    vote: Option<Vote>,
+    cooldown: f32,
+    banner: Option<(usize, f32)>, // 刚生效的命令和横幅剩余时间
+}
+
+fn connect(channel_name: &str, token: &str, tx: Sender<Line>) {
+    let run = || -> Result<(), String> {
+        let stream = TcpStream::connect(SERVER).map_err(|e| e.to_string())?;
+        let mut out = stream.try_clone().map_err(|e| e.to_string())?;
+        let token = if token.starts_with("oauth:") { token.to_string() } else { format!("oauth:{}", token) };
+        write!(out, "PASS {}\r\nNICK {}\r\nJOIN #{}\r\n", token, channel_name, channel_name).map_err(|e| e.to_string())?;
+        for line in BufReader::new(stream).lines() {
+            let line = line.map_err(|e| e.to_string())?;
+            if let Some(rest) = line.strip_prefix("PING") {
+                write!(out, "PONG{}\r\n", rest).map_err(|e| e.to_string())?;
+            } else if line.contains("Login authentication failed") || line.contains("Improperly formatted auth") {
+                return Err(tr("登录失败，检查 twitch_token").to_string());
+            } else if line.contains(" 001 ") {
+                if tx.send(Line::Joined).is_err() { return Ok(()); }
+            } else if let Some((user, text)) = parse_privmsg(&line) {
+                let Some(choice) = COMMANDS.iter().position(|c| text.trim().eq_ignore_ascii_case(c.word)) else { continue };
+                if tx.send(Line::Vote { user, choice }).is_err() { return Ok(()); }
+            }
+        }
+        Err(tr("聊天室断开了").to_string())
+    };
+    if let Err(e) = run() { let _ = tx.send(Line::Failed(e)); }
+}
+
+// ":name!name@name.tmi.twitch.tv PRIVMSG #chan :text" → (name, text)
+fn parse_privmsg(line: &str) -> Option<(String, &str)> {
+    let rest = line.strip_prefix(':')?;
+    let (prefix, rest) = rest.split_once(' ')?;
+    let rest = rest.strip_prefix("PRIVMSG ")?;
+    let (_, text) = rest.split_once(" :")?;
+    Some((prefix.split('!').next()?.to_lowercase(), text))
+}
+
+// 只在能计分的普通局里投票
+fn votable(game: &Game) -> bool {
+    game.mode == GameMode::Playing && game.play == PlayMode::Standard && game.challenge.is_none() && !game.replay.watching()
+}
+
+// 每帧：收聊天，推进投票；第一次调用时按设置连上频道
+pub fn poll(game: &mut Game, dt: f32) {
+    let t = &mut game.twitch;
+    if !t.tried {
+        t.tried = true;
+        let (chan, token) = (game.settings.twitch_channel.trim().to_lowercase(), game.settings.twitch_token.trim().to_string());
+        if !chan.is_empty() && !token.is_empty() {
+            let (tx, rx) = channel();
+            std::thread::spawn(move || connect(&chan, &token, tx));
+            t.rx = Some(rx);
+            t.status = Some(Status::Connecting);
+        }
+    }
+    if let Some((_, left)) = &mut t.banner {
+        *left -= dt;
+        if *left <= 0.0 { t.banner = None; }
+    }
+    let Some(rx) = &t.rx else { return };
+    let mut ballots = Vec::new();
+    while let Ok(line) = rx.try_recv() {
+        match line {
+            Line::Joined => t.status = Some(Status::Live),
+            Line::Failed(e) => {
+                crate::logfile::warn(&format!("Twitch 聊天：{}", e));
+                t.status = Some(Status::Failed(e));
+            }
+            Line::Vote { user, choice } => ballots.push((user, choice)),
+        }
+    }
+    if !votable(game) { return; }
+    let t = &mut game.twitch;
+    if t.status != Some(Status::Live) { return; }
+    let Some(vote) = &mut t.vote else {
+        t.cooldown -= dt;
+        if t.cooldown <= 0.0 { t.vote = Some(Vote { remaining: VOTE_WINDOW, tally: [0; COMMANDS.len()], voters: HashSet::new() }); }
+        return;
+    };
+    for (user, choice) in ballots {
+        if vote.voters.insert(user) { vote.tally[choice] += 1; }
+    }
+    vote.remaining -= dt;
+    if vote.remaining > 0.0 { return; }
+    let tally = vote.tally;
+    t.vote = None;
+    t.cooldown = COOLDOWN;
+    let Some(best) = tally.iter().enumerate().filter(|(_, n)| **n > 0).max_by_key(|(_, n)| **n).map(|(i, _)| i) else { return };
+    t.banner = Some((best, BANNER_TIME));
+    (COMMANDS[best].apply)(game);
+    // 回放重现不了聊天，干预之后不再录
+    game.crowd = true;
+    game.replay = ReplayState::Off;
+}
+
+// 局内 HUD：底部一行投票进度，刚生效时屏幕中间一行横幅
+pub fn draw_hud(font: &Font, game: &Game, ts: f32) {
+    let t = &game.twitch;
+    if let Some((i, _)) = t.banner {
+        draw_text_center(font, game.field.x, &trf("观众投票：{}！", &[&tr(COMMANDS[i].label)]), game.field.y * 0.4, 30.0 * ts, MAGENTA);
+    }
+    let Some(vote) = &t.vote else { return };
+    let opts: Vec<String> = COMMANDS.iter().zip(vote.tally).map(|(c, n)| format!("{} {} {}", c.word, tr(c.label), n)).collect();
+    let txt = trf("投票 {:.0}s   {}", &[&vote.remaining.max(0.0).ceil(), &opts.join("   ")]);
+    draw_text_center(font, game.field.x, &txt, game.field.y - 34.0 * ts, 18.0 * ts, VIOLET);
+}
@@ -0,0 +1,446 @@
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{tr, trf};
+use crate::rng::{Rng, Stream};
+use crate::trace::{get_char_pressed, is_key_pressed};
+use crate::{draw_text_center, save_game, Game, GameMode, PlayMode, OB_MAX_SIZE, OB_MIN_SIZE, PLAYER_H, PLAYER_Y};
+
+// ===== 联机对战（UDP）=====
+// 两人用同一个种子、同样的固定规则各玩各的。网络层是状态同步而不是锁步：
+// 自己的场地只在本机模拟，每 SEND_EVERY 步把玩家、障碍和分数打成快照发给对方，画在右半屏。
+// 擦边攒“垃圾”，累计数随快照一起发出（丢包也丢不了），对方按差值往自己的障碍流里补方块。
+// 握手核对协议和程序版本；对局中核对回合、种子、垃圾账目和步数差，对不上就判不同步、本局作废。
+// 没有中继服务器：房主开端口，对方输入地址直连。
+pub const PORT: u16 = 47800;
+pub const FIELD: (f32, f32) = (400.0, 600.0); // 两边一样大的场地，左右并排正好一个 800x600 窗口
+const PROTOCOL: u32 = 1;
+const SEND_EVERY: u32 = 4;          // 30Hz
+const HEARTBEAT: f64 = 0.25;        // 房间里（不在局内）的发送间隔，秒
+const HELLO_EVERY: f64 = 0.5;
+const TIMEOUT: f64 = 5.0;           // 这么久收不到对方的包算掉线
+const MAX_DRIFT: u32 = 120 * 3;     // 两边都活着时步数差超过 3 秒算不同步
+const OBS_MAX: usize = 48;          // 快照里最多带多少个障碍（包控制在 2KB 以内）
+const GARBAGE_GAP: u32 = 15;        // 收到的垃圾每隔这么多步落一个
+
+#[derive(Serialize, Deserialize)]
+enum Msg {
+    Hello { protocol: u32, game: String, name: String },
+    Welcome { name: String },
+    Reject { reason: String },
+    State(Snapshot),
+    Bye,
+}
+
+#[derive(Clone, Serialize, Deserialize, Default)]
+pub struct Snapshot {
+    pub round: u32,
+    pub seed: u64,
+    pub step: u32,
+    pub died_at: Option<u32>,
+    pub x: f32,
+    pub w: f32,
+    pub score: i32,
+    pub obs: Vec<[f32; 4]>,
+    pub garbage_out: u32, // 累计送出
+    pub garbage_in: u32,  // 累计收下
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Outcome { Win, Lose, Draw, Void(String) }
+
+// 一条连接和当前回合
+pub struct Link {
+    sock: UdpSocket,
+    host: bool,
+    peer: Option<SocketAddr>,
+    pub peer_name: Option<String>, // 握手完成后才有
+    pub error: Option<String>,     // 断开的原因；有值时这条连接作废
+    last_heard: f64,
+    last_sent: f64,
+    round: u32,
+    seed: u64,
+    mine: Snapshot,
+    pub remote: Option<Snapshot>,
+    garbage_in: u32,
+    garbage_rng: Rng,
+    pub outcome: Option<Outcome>,
+    pub delta: Option<i32>,        // 本局排位分的变化
+}
+
+// 房间界面的状态（game.versus）
+#[derive(Default)]
+pub struct Versus {
+    pub link: Option<Link>,
+    pub typing: Option<String>, // 正在输入对方地址
+    pub msg: Option<String>,
+    last_addr: String,
+}
+
+fn now() -> f64 { macroquad::miniquad::date::now() }
+
+impl Link {
+    fn new(sock: UdpSocket, host: bool, peer: Option<SocketAddr>) -> Self {
+        Self {
+            sock, host, peer, peer_name: None, error: None, last_heard: now(), last_sent: 0.0,
+            round: 0, seed: 0, mine: Snapshot::default(), remote: None, garbage_in: 0, garbage_rng: Rng::stream(0, Stream::Versus), outcome: None, delta: None,
+        }
+    }
+
+    fn host() -> Result<Self, String> {
+        let sock = UdpSocket::bind(("0.0.0.0", PORT)).map_err(|e| trf("端口 {} 打不开：{}", &[&PORT, &e]))?;
+        sock.set_nonblocking(true).map_err(|e| e.to_string())?;
+        Ok(Self::new(sock, true, None))
+    }
+
+    fn join(addr: &str) -> Result<Self, String> {
+        let addr = if addr.contains(':') { addr.to_string() } else { format!("{}:{}", addr, PORT) };
+        let peer = addr.to_socket_addrs().map_err(|e| e.to_string())?.next().ok_or(tr("无法解析地址"))?;
+        let sock = UdpSocket::bind(("0.0.0.0", 0)).map_err(|e| e.to_string())?;
+        sock.set_nonblocking(true).map_err(|e| e.to_string())?;
+        Ok(Self::new(sock, false, Some(peer)))
+    }
+
+    fn send(&self, msg: &Msg) {
+        if let Some(p) = self.peer { let _ = self.sock.send_to(&serde_json::to_vec(msg).unwrap(), p); }
+    }
+
+    // 收完本帧所有包；握手、拒绝、掉线都在这里处理，返回对方最新的快照
+    fn pump(&mut self, name: &str) -> Option<Snapshot> {
+        if self.error.is_some() { return None; }
+        let mut latest = None;
+        let mut buf = [0u8; 65536];
+        while let Ok((n, from)) = self.sock.recv_from(&mut buf) {
+            let Ok(msg) = serde_json::from_slice::<Msg>(&buf[..n]) else { continue };
+            if self.peer.is_some_and(|p| p != from) {
+                // 房间已经有人了
+                if matches!(msg, Msg::Hello { .. }) {
+                    let _ = self.sock.send_to(&serde_json::to_vec(&Msg::Reject { reason: tr("房间已满").to_string() }).unwrap(), from);
+                }
+                continue;
+            }
+            self.last_heard = now();
+            match msg {
+                Msg::Hello { protocol, game, name: peer_name } if self.host => {
+                    if protocol != PROTOCOL || game != env!("CARGO_PKG_VERSION") {
+                        let reason = trf("版本不一致（房主 {}，你 {}）", &[&env!("CARGO_PKG_VERSION"), &game]);
+                        let _ = self.sock.send_to(&serde_json::to_vec(&Msg::Reject { reason }).unwrap(), from);
+                        continue;
+                    }
+                    self.peer = Some(from);
+                    self.peer_name = Some(peer_name);
+                    self.send(&Msg::Welcome { name: name.to_string() });
+                }
+                Msg::Welcome { name } => self.peer_name = Some(name),
+                Msg::Reject { reason } => self.error = Some(reason),
+                Msg::State(s) if self.peer_name.is_some() && s.round >= self.round => latest = Some(s),
+                Msg::Bye => self.error = Some(tr("对手离开了").to_string()),
+                _ => {}
+            }
+        }
+        // 加入方一直重发 Hello 直到收到 Welcome
+        if !self.host && self.peer_name.is_none() && now() - self.last_sent > HELLO_EVERY {
+            self.send(&Msg::Hello { protocol: PROTOCOL, game: env!("CARGO_PKG_VERSION").to_string(), name: name.to_string() });
+            self.last_sent = now();
+        }
+        if self.peer.is_some() && self.error.is_none() && now() - self.last_heard > TIMEOUT {
+            self.error = Some(if self.peer_name.is_some() { tr("对手掉线了") } else { tr("连不上对方") }.to_string());
+        }
+        latest
+    }
+
+    fn begin(&mut self, round: u32, seed: u64) {
+        self.round = round;
+        self.seed = seed;
+        self.remote = None;
+        self.garbage_in = 0;
+        self.garbage_rng = Rng::stream(seed, Stream::Versus);
+        self.outcome = None;
+        self.delta = None;
+        self.mine = Snapshot { round, seed, ..Snapshot::default() };
+    }
+
+    // 对方的快照和本机对不上：返回原因
+    fn desync(&self, s: &Snapshot) -> Option<String> {
+        if s.seed != self.seed { return Some(tr("种子不同").to_string()); }
+        if s.garbage_in > self.mine.garbage_out { return Some(tr("垃圾账目对不上").to_string()); }
+        let both_alive = s.died_at.is_none() && self.mine.died_at.is_none() && self.outcome.is_none();
+        if both_alive && s.step.abs_diff(self.mine.step) > MAX_DRIFT { return Some(tr("两边进度相差太多").to_string()); }
+        None
+    }
+}
+
+// 谁先死谁输；对方已经活过了我死的那一步才能判我输（包可能还在路上）
+pub fn judge(my_step: u32, my_death: Option<u32>, remote: &Snapshot) -> Option<Outcome> {
+    match (my_death, remote.died_at) {
+        (Some(a), Some(b)) => Some(if a < b { Outcome::Lose } else if a > b { Outcome::Win } else { Outcome::Draw }),
+        (Some(a), None) if remote.step > a => Some(Outcome::Lose),
+        (None, Some(b)) if my_step > b => Some(Outcome::Win),
+        _ => None,
+    }
+}
+
+// 开一局：固定规则（同挑战）、同一个种子、固定大小的场地
+fn start_round(game: &mut Game, round: u32, seed: u64) {
+    let Some(link) = game.versus.link.as_mut() else { return };
+    link.begin(round, seed);
+    game.use_fixed_rules();
+    game.play = PlayMode::Versus;
+    game.forced_seed = Some(seed);
+    game.reset_round();
+}
+
+fn end_round(game: &mut Game, outcome: Outcome) {
+    settle(game, outcome);
+    game.mode = GameMode::GameOver;
+}
+
+// 定下本局胜负（只定一次），分出胜负的记进排位
+fn settle(game: &mut Game, outcome: Outcome) {
+    let Some(link) = game.versus.link.as_mut() else { return };
+    if link.outcome.is_some() { return; }
+    let got = link.remote.as_ref().map_or(0, |s| s.garbage_out);
+    let name = link.peer_name.clone().unwrap_or_default();
+    let delta = game.ladder.record(&name, &outcome, link.mine.garbage_out, got);
+    link.outcome = Some(outcome);
+    link.delta = delta;
+    if delta.is_some() { save_game(game); }
+}
+
+// 收包并处理：对方开了新一局就跟上；同一局的快照用来判胜负、查不同步
+fn receive(game: &mut Game) {
+    let name = game.player_name.clone();
+    let Some(link) = game.versus.link.as_mut() else { return };
+    let latest = link.pump(&name);
+    let in_round = matches!(game.mode, GameMode::Playing | GameMode::GameOver);
+    if link.error.is_some() && link.round > 0 && link.outcome.is_none() && in_round {
+        let e = link.error.clone().unwrap_or_default();
+        return end_round(game, Outcome::Void(e));
+    }
+    let Some(s) = latest else { return };
+    if s.round > link.round {
+        if link.host { return; } // 只有房主能开局
+        start_round(game, s.round, s.seed);
+    }
+    let Some(link) = game.versus.link.as_mut() else { return };
+    if let Some(why) = link.desync(&s) {
+        link.remote = Some(s);
+        if link.outcome.is_none() && in_round { end_round(game, Outcome::Void(trf("不同步：{}", &[&why]))); }
+        return;
+    }
+    link.remote = Some(s);
+}
+
+// 局内每个固定步（Playing 和 GameOver）：更新自己的快照、落垃圾、判胜负、定时发送
+pub fn tick(game: &mut Game) {
+    receive(game);
+    let playing = game.mode == GameMode::Playing;
+    let (steps, field_x, fall) = (game.run_steps, game.field.x, game.fall_speed);
+    let Some(link) = game.versus.link.as_mut() else { return };
+    if link.round == 0 { return; }
+    let m = &mut link.mine;
+    if playing { m.step = steps; }
+    if !playing && m.died_at.is_none() && link.outcome.is_none() { m.died_at = Some(steps); }
+    m.x = game.player.x;
+    m.w = game.player.w;
+    m.score = game.score;
+    m.obs = game.obs.live.iter().take(OBS_MAX).map(|o| [o.rect.x, o.rect.y, o.rect.w, o.rect.h]).collect();
+    m.garbage_out = game.stats.near_misses;
+    m.garbage_in = link.garbage_in;
+    // 局内按步数发；结束画面里步数不再走，改按时间发
+    let due = if playing { steps.is_multiple_of(SEND_EVERY) } else { now() - link.last_sent > HEARTBEAT };
+    if due {
+        link.send(&Msg::State(link.mine.clone()));
+        link.last_sent = now();
+    }
+    let Some(remote) = link.remote.clone() else { return };
+    // 对方送来的垃圾：一个一个落下
+    if playing && remote.garbage_out > link.garbage_in && steps.is_multiple_of(GARBAGE_GAP) {
+        link.garbage_in += 1;
+        let size = link.garbage_rng.range(OB_MIN_SIZE, OB_MAX_SIZE);
+        let x = link.garbage_rng.range(0.0, (field_x - size).max(0.0));
+        game.obs.spawn(Rect::new(x, -size - 10.0, size, size), 0.0, fall * 1.2);
+    }
+    let Some(link) = game.versus.link.as_ref() else { return };
+    if link.outcome.is_some() { return; }
+    if let Some(o) = judge(link.mine.step, link.mine.died_at, &remote) {
+        if playing { end_round(game, o); } else { settle(game, o); }
+    }
+}
+
+// —— 房间界面 ——
+// [H] 建房，[J] 加入（输入 IP[:端口]），连上后房主按 [SPACE] 开局，[ESC] 离开
+pub fn update_lobby(game: &mut Game) {
+    if let Some(text) = &mut game.versus.typing {
+        while let Some(c) = get_char_pressed() {
+            if !c.is_control() && !c.is_whitespace() && text.len() < 64 { text.push(c); }
+        }
+        if is_key_pressed(KeyCode::Backspace) { text.pop(); }
+        if is_key_pressed(KeyCode::Escape) { game.versus.typing = None; }
+        if is_key_pressed(KeyCode::Enter) {
+            let addr = game.versus.typing.take().unwrap_or_default();
+            game.versus.last_addr = addr.clone();
+            match Link::join(&addr) {
+                Ok(l) => {
+                    game.versus.link = Some(l);
+                    game.versus.msg = None;
+                }
+                Err(e) => game.versus.msg = Some(e),
+            }
+        }
+        return;
+    }
+    receive(game);
+    if game.mode != GameMode::Lobby { return; } // 房主开了新一局
+    let Some(link) = game.versus.link.as_mut() else {
+        if is_key_pressed(KeyCode::H) {
+            match Link::host() {
+                Ok(l) => game.versus.link = Some(l),
+                Err(e) => game.versus.msg = Some(e),
+            }
+        }
+        if is_key_pressed(KeyCode::J) {
+            while get_char_pressed().is_some() {}
+            game.versus.typing = Some(game.versus.last_addr.clone());
+        }
+        if is_key_pressed(KeyCode::R) { game.mode = GameMode::Ranked; }
+        if is_key_pressed(KeyCode::Escape) { leave(game); }
+        return;
+    };
+    // 不在局内也要定时发快照，当心跳用（刚死的一局也要让对方知道）
+    if link.peer_name.is_some() && now() - link.last_sent > HEARTBEAT {
+        link.send(&Msg::State(link.mine.clone()));
+        link.last_sent = now();
+    }
+    if link.error.is_some() {
+        game.versus.msg = link.error.take();
+        game.versus.link = None;
+        return;
+    }
+    if link.host && link.peer_name.is_some() && (is_key_pressed(KeyCode::Space) || is_key_pressed(KeyCode::Enter)) {
+        let (round, seed) = (link.round + 1, (now() * 1000.0) as u64);
+        return start_round(game, round, seed);
+    }
+    if is_key_pressed(KeyCode::Escape) {
+        link.send(&Msg::Bye);
+        game.versus.link = None;
+    }
+}
+
+// 回到房间（连接留着，可以再来一局）
+pub fn back_to_lobby(game: &mut Game) {
+    game.flush_history();
+    game.mode = GameMode::Lobby;
+}
+
+// 离开房间回菜单，还原玩家自己的规则
+fn leave(game: &mut Game) {
+    close(game);
+    game.versus.msg = None;
+    game.leave_run();
+    game.play = PlayMode::Standard;
+    game.mode = GameMode::Menu;
+}
+
+// 关窗或离开时通知对方
+pub fn close(game: &mut Game) {
+    if let Some(link) = game.versus.link.take() { link.send(&Msg::Bye); }
+}
+
+// 本机在局域网里的地址（给房主报给对方用）；只是选路由，不会真的发包
+fn local_ip() -> Option<String> {
+    let s = UdpSocket::bind(("0.0.0.0", 0)).ok()?;
+    s.connect(("8.8.8.8", 80)).ok()?;
+    Some(s.local_addr().ok()?.ip().to_string())
+}
+
+fn centered(font: &Font, cx: f32, text: &str, y: f32, size: f32, color: Color) {
+    let w = measure_text(text, Some(font), size as u16, 1.0).width;
+    draw_text_ex(text, cx - w * 0.5, y, TextParams { font: Some(font), font_size: size as u16, color, ..Default::default() });
+}
+
+pub fn draw_lobby(font: &Font, game: &Game) {
+    let ts = game.settings.text_scale();
+    let w = game.field.x;
+    draw_text_center(font, w, tr("联机对战"), 90.0, 40.0 * ts, SKYBLUE);
+    draw_text_center(font, w, tr("同一个种子各自躲避，擦边会把障碍送进对手的场地，先倒下的输"), 140.0, 20.0 * ts, LIGHTGRAY);
+    let v = &game.versus;
+    if let Some(text) = &v.typing {
+        draw_text_center(font, w, tr("对方地址（IP 或 IP:端口）："), 230.0, 24.0 * ts, WHITE);
+        draw_text_center(font, w, &format!("{}_", text), 270.0, 28.0 * ts, GOLD);
+        draw_text_center(font, w, tr("[ENTER] 连接   [ESC] 取消"), game.field.y - 40.0, 20.0 * ts, ORANGE);
+        return;
+    }
+    let keys = match &v.link {
+        None => {
+            draw_text_center(font, w, tr("[H] 建房   [J] 加入   [R] 排位记录"), 250.0, 28.0 * ts, WHITE);
+            tr("[ESC] 返回")
+        }
+        Some(l) => {
+            let status = match (&l.peer_name, l.host) {
+                (Some(n), _) => match game.ladder.get(n) {
+                    Some(o) => trf("对手：{}   排位 {}（{}胜 {}负 {}平）", &[&n, &(o.rating.round() as i32), &o.wins, &o.losses, &o.draws]),
+                    None => trf("对手：{}   初次交手", &[&n]),
+                },
+                (None, true) => trf("等待对手加入…  本机 {}:{}", &[&local_ip().unwrap_or_else(|| "?".to_string()), &PORT]),
+                (None, false) => tr("正在连接…").to_string(),
+            };
+            draw_text_center(font, w, &status, 250.0, 26.0 * ts, WHITE);
+            if let Some(o) = &l.outcome {
+                draw_text_center(font, w, &trf("上一局：{}", &[&outcome_label(o)]), 290.0, 22.0 * ts, GRAY);
+            }
+            match (&l.peer_name, l.host) {
+                (Some(_), true) => tr("[SPACE] 开始   [ESC] 解散房间"),
+                (Some(_), false) => tr("等待房主开始   [ESC] 离开"),
+                _ => tr("[ESC] 取消"),
+            }
+        }
+    };
+    if let Some(msg) = &v.msg {
+        draw_text_center(font, w, msg, game.field.y - 75.0, 20.0 * ts, ORANGE);
+    }
+    draw_text_center(font, w, keys, game.field.y - 40.0, 20.0 * ts, ORANGE);
+}
+
+fn outcome_label(o: &Outcome) -> String {
+    match o {
+        Outcome::Win => tr("你赢了！").to_string(),
+        Outcome::Lose => tr("你输了").to_string(),
+        Outcome::Draw => tr("平局").to_string(),
+        Outcome::Void(why) => trf("本局作废（{}）", &[&why]),
+    }
+}
+
+// 局内：右半屏画对手的快照，结束后在自己这边标出胜负
+pub fn draw(game: &Game, font: &Font) {
+    let Some(link) = &game.versus.link else { return };
+    let ts = game.settings.text_scale();
+    let (w, h) = (game.field.x, game.field.y);
+    draw_rectangle(w, 0.0, w, h, Color::new(0.0, 0.0, 0.0, 0.35));
+    draw_line(w, 0.0, w, h, 2.0, GRAY);
+    let name = link.peer_name.as_deref().unwrap_or(tr("对手"));
+    match &link.remote {
+        Some(s) => {
+            for o in &s.obs { draw_rectangle(w + o[0], o[1], o[2], o[3], Color::new(0.85, 0.35, 0.35, 0.85)); }
+            let body = if s.died_at.is_some() { GRAY } else { SKYBLUE };
+            draw_rectangle(w + s.x, PLAYER_Y, s.w, PLAYER_H, body);
+            centered(font, w * 1.5, &format!("{}  {}", name, s.score), 30.0, 22.0 * ts, WHITE);
+        }
+        None => centered(font, w * 1.5, tr("等待对手的画面…"), h * 0.5, 22.0 * ts, GRAY),
+    }
+    let sent = link.mine.garbage_out;
+    let got = link.remote.as_ref().map_or(0, |s| s.garbage_out);
+    draw_text_center(font, w, &trf("送出垃圾 {}   收到 {}", &[&sent, &got]), h - 12.0, 16.0 * ts, LIGHTGRAY);
+    if game.mode != GameMode::GameOver { return; }
+    match &link.outcome {
+        Some(o) => {
+            let color = match o { Outcome::Win => GOLD, Outcome::Lose => RED, _ => LIGHTGRAY };
+            draw_text_center(font, w, &outcome_label(o), 250.0, 40.0 * ts, color);
+            if let Some(d) = link.delta { draw_text_center(font, w, &trf("排位 {:+}", &[&d]), 285.0, 22.0 * ts, color); }
+            draw_text_center(font, w, tr("[ESC] 回到房间"), 320.0, 22.0 * ts, ORANGE);
+        }
+        None => draw_text_center(font, w, tr("等对手分出胜负…"), 250.0, 26.0 * ts, LIGHTGRAY),
+    }
+}
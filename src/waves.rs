@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+
+// ===== 波次/阵型定义（assets/waves/*.json）=====
+// 每个文件描述一个阵型：若干个生成点，x 为屏幕宽度比例（0~1），
+// delay 为相对阵型开始的秒数，speed 为相对当前下落速度的倍率，
+// vx 为可选的水平速度（像素/秒）。
+pub const WAVES_DIR: &str = "assets/waves";
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SpawnKind {
+    #[default]
+    Block,
+    Shield,
+    Slow,
+    Bomb,
+    Heart,
+    Teleport,
+    Ammo,
+    Rewind,
+    Multiplier,
+    Freeze,
+    Reverse,
+    Haste,
+    Blackout,
+}
+
+fn one() -> f32 { 1.0 }
+
+#[derive(Clone, Deserialize)]
+pub struct WaveSpawn {
+    pub x: f32,
+    pub size: f32,
+    #[serde(default)]
+    pub delay: f32,
+    #[serde(default = "one")]
+    pub speed: f32,
+    #[serde(default)]
+    pub vx: f32, // 水平速度（像素/秒），用于斜向移动的障碍
+    #[serde(default)]
+    pub kind: SpawnKind,
+}
+
+#[derive(Clone, Deserialize)]
+pub struct WavePattern {
+    pub name: String,
+    #[serde(default = "one")]
+    pub weight: f32,
+    pub spawns: Vec<WaveSpawn>,
+}
+
+// 读取目录下所有 .json 阵型；单个文件解析失败只跳过该文件
+pub fn load_waves(dir: &str) -> Vec<WavePattern> {
+    crate::storage::list_assets(dir)
+        .iter()
+        .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|p| {
+            let text = crate::storage::read_asset_string(p).ok()?;
+            match serde_json::from_str::<WavePattern>(&text) {
+                Ok(w) if w.spawns.is_empty() => {
+                    crate::logfile::warn(&format!("阵型 {} 没有生成点，已跳过", w.name));
+                    None
+                }
+                Ok(w) => Some(w),
+                Err(e) => {
+                    crate::logfile::warn(&format!("跳过阵型文件 {}：{}", p.display(), e));
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+// 局内每活过这么多秒，多解锁一个更难的阵型
+pub const UNLOCK_SECS: f32 = 20.0;
+
+// 已解锁的阵型：开局只有最容易的一个（列表已由 rating::sort_by_difficulty 排好）
+pub fn unlocked(waves: &[WavePattern], elapsed: f32) -> &[WavePattern] {
+    let n = 1 + (elapsed.max(0.0) / UNLOCK_SECS) as usize;
+    &waves[..n.min(waves.len())]
+}
+
+// 按权重随机挑一个阵型
+pub fn pick_wave(waves: &[WavePattern], roll: f32) -> Option<&WavePattern> {
+    let total: f32 = waves.iter().map(|w| w.weight.max(0.0)).sum();
+    if total <= 0.0 { return None; }
+    let mut t = roll * total;
+    for w in waves {
+        t -= w.weight.max(0.0);
+        if t <= 0.0 { return Some(w); }
+    }
+    waves.last()
+}
+
+// ===== 正在进行的阵型：待生成队列 =====
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct PendingSpawn {
+    pub at: f32,
+    pub spawn: SpawnKind,
+    pub x: f32,
+    pub size: f32,
+    pub speed: f32,
+    pub vx: f32,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WaveRunner {
+    pub clock: f32,
+    pub queue: Vec<PendingSpawn>,
+}
+
+impl WaveRunner {
+    pub fn new() -> Self { Self { clock: 0.0, queue: Vec::new() } }
+    pub fn active(&self) -> bool { !self.queue.is_empty() }
+    pub fn start(&mut self, w: &WavePattern) {
+        self.clock = 0.0;
+        self.queue = w
+            .spawns
+            .iter()
+            .map(|s| PendingSpawn { at: s.delay, spawn: s.kind, x: s.x, size: s.size, speed: s.speed, vx: s.vx })
+            .collect();
+        // 按时间倒序，便于从尾部弹出
+        self.queue.sort_by(|a, b| b.at.total_cmp(&a.at));
+    }
+    pub fn clear(&mut self) { self.clock = 0.0; self.queue.clear(); }
+    // 推进时钟，返回本步到期的生成点
+    pub fn tick(&mut self, dt: f32, out: &mut Vec<PendingSpawn>) {
+        self.clock += dt;
+        while let Some(s) = self.queue.last() {
+            if s.at > self.clock { break; }
+            out.push(self.queue.pop().unwrap());
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use macroquad::prelude::*;
+
+use crate::trace::{is_key_pressed, is_mouse_button_pressed};
+
+// ===== 可选菜单 =====
+// 一组按钮：[↑/↓] 移动（到头绕回，跳过不可选的），[ENTER] / [SPACE] 选中；手柄的十字键和 A 已经翻译成这些键。
+// 鼠标移到哪项就高亮哪项，左键点一下选中（触屏点按由 macroquad 模拟成鼠标点击）。
+// 位置由调用方给（场地坐标，一般用 column 排一列）。暂停菜单和主菜单共用。
+
+pub struct Item<'a> {
+    pub label: &'a str,
+    pub enabled: bool,
+}
+
+#[derive(Default)]
+pub struct ListMenu {
+    pub cursor: usize,
+    last_mouse: Vec2,
+}
+
+impl ListMenu {
+    pub fn at(cursor: usize) -> Self { Self { cursor, ..Default::default() } }
+
+    // 本帧的操作；mouse 是场地坐标下的鼠标位置。返回被选中的项
+    pub fn update(&mut self, items: &[Item], rects: &[Rect], mouse: Vec2) -> Option<usize> {
+        let n = items.len();
+        if n == 0 || !items.iter().any(|it| it.enabled) { return None; }
+        self.cursor = self.cursor.min(n - 1);
+        let step = is_key_pressed(KeyCode::Down) as i32 - is_key_pressed(KeyCode::Up) as i32;
+        if step != 0 || !items[self.cursor].enabled {
+            let step = if step == 0 { 1 } else { step };
+            let mut i = self.cursor as i32;
+            loop {
+                i = (i + step).rem_euclid(n as i32);
+                if items[i as usize].enabled { break; }
+            }
+            self.cursor = i as usize;
+        }
+        // 鼠标动了才跟着高亮，免得停在按钮上的鼠标抢走键盘选中的项
+        let hover = rects.iter().position(|r| r.contains(mouse)).filter(|i| items[*i].enabled);
+        if mouse != self.last_mouse {
+            self.last_mouse = mouse;
+            if let Some(i) = hover { self.cursor = i; }
+        }
+        if is_mouse_button_pressed(MouseButton::Left) { return hover; }
+        (is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::Space)).then_some(self.cursor)
+    }
+
+    pub fn draw(&self, font: &Font, items: &[Item], rects: &[Rect], size: f32) {
+        for (i, (it, r)) in items.iter().zip(rects).enumerate() {
+            let selected = i == self.cursor;
+            let (bg, fg) = match (it.enabled, selected) {
+                (false, _) => (Color::new(0.1, 0.1, 0.12, 0.6), DARKGRAY),
+                (true, true) => (Color::new(0.95, 0.75, 0.2, 0.9), BLACK),
+                (true, false) => (Color::new(0.1, 0.12, 0.18, 0.8), WHITE),
+            };
+            draw_rectangle(r.x, r.y, r.w, r.h, bg);
+            if selected { draw_rectangle_lines(r.x, r.y, r.w, r.h, 2.0, WHITE); }
+            let dim = measure_text(it.label, Some(font), size as u16, 1.0);
+            draw_text_ex(it.label, r.x + (r.w - dim.width) * 0.5, r.y + (r.h + dim.offset_y) * 0.5, TextParams { font: Some(font), font_size: size as u16, color: fg, ..Default::default() });
+        }
+    }
+}
+
+// 以 cx 为中线、从 y 起竖排 n 个按钮
+pub fn column(n: usize, cx: f32, y: f32, size: Vec2, gap: f32) -> Vec<Rect> {
+    (0..n).map(|i| Rect::new(cx - size.x * 0.5, y + i as f32 * (size.y + gap), size.x, size.y)).collect()
+}
@@ -0,0 +1,99 @@
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::Game;
+
+// ===== 阵风区 =====
+// 每隔一段时间，场地某一段竖条里刮起横向阵风：先闪烁预警，再持续几秒。
+// 玩家在风区里会受到横向推力（叠加在移动积分里，车道模式不受影响），道具经过时也会被吹偏一点。
+const FIRST_GUST_AFTER: f32 = 35.0;
+const GUST_GAP_MIN: f32 = 20.0;
+const GUST_GAP_MAX: f32 = 40.0;
+const WARN_TIME: f32 = 1.5;      // 预警时长（不生效）
+const GUST_TIME: f32 = 4.0;      // 生效时长
+const ZONE_MIN_W: f32 = 0.25;    // 风区宽度占场地的比例
+const ZONE_MAX_W: f32 = 0.45;
+const PUSH: f32 = 900.0;         // 玩家受到的横向加速度（px/s²）
+const PU_DRIFT: f32 = 45.0;      // 道具的横向漂移速度（px/s）
+const STREAKS: usize = 14;       // 风区内的风线条数
+const STREAK_LEN: f32 = 60.0;
+const STREAK_SPEED: f32 = 520.0;
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct Gust {
+    pub x: f32,
+    pub w: f32,
+    pub dir: f32,   // -1 向左 / 1 向右
+    warn: f32,      // 预警剩余时间
+    remaining: f32, // 生效剩余时间
+    age: f32,       // 风线动画用
+}
+
+impl Gust {
+    pub fn blowing(&self) -> bool { self.warn <= 0.0 && self.remaining > 0.0 }
+    fn covers(&self, r: Rect) -> bool { r.x + r.w > self.x && r.x < self.x + self.w }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Wind {
+    next_in: f32,
+    pub gust: Option<Gust>,
+}
+
+impl Wind {
+    pub fn new() -> Self { Self { next_in: FIRST_GUST_AFTER, gust: None } }
+    pub fn reset(&mut self) { *self = Self::new(); }
+
+    // roll 给出 0~1 随机数，只在新阵风开始时才抽，平时不消耗随机数
+    pub fn tick(&mut self, dt: f32, field_w: f32, mut roll: impl FnMut() -> f32) {
+        if let Some(g) = &mut self.gust {
+            g.age += dt;
+            if g.warn > 0.0 { g.warn -= dt; } else { g.remaining -= dt; }
+            if g.remaining <= 0.0 { self.gust = None; }
+            return;
+        }
+        self.next_in -= dt;
+        if self.next_in > 0.0 { return; }
+        self.next_in = GUST_GAP_MIN + roll() * (GUST_GAP_MAX - GUST_GAP_MIN);
+        let w = field_w * (ZONE_MIN_W + roll() * (ZONE_MAX_W - ZONE_MIN_W));
+        let x = roll() * (field_w - w);
+        let dir = if roll() < 0.5 { -1.0 } else { 1.0 };
+        self.gust = Some(Gust { x, w, dir, warn: WARN_TIME, remaining: GUST_TIME, age: 0.0 });
+    }
+
+    fn blowing_over(&self, r: Rect) -> Option<f32> {
+        self.gust.filter(|g| g.blowing() && g.covers(r)).map(|g| g.dir)
+    }
+    // 玩家在风区里受到的横向加速度
+    pub fn push_at(&self, r: Rect) -> f32 { self.blowing_over(r).map_or(0.0, |d| d * PUSH) }
+    // 道具的横向漂移速度
+    pub fn drift_at(&self, r: Rect) -> f32 { self.blowing_over(r).map_or(0.0, |d| d * PU_DRIFT) }
+}
+
+// 预警：风区边框闪烁并画出风向箭头；生效：半透明底色上飞过的风线
+pub fn draw(game: &Game) {
+    let Some(g) = game.wind.gust else { return };
+    let h = game.field.y;
+    if !g.blowing() {
+        if crate::motion::blink(game, g.age, 3.0) {
+            let c = Color::new(0.75, 0.9, 1.0, 0.5);
+            draw_rectangle_lines(g.x, 0.0, g.w, h, 2.0, c);
+            let (cx, cy) = (g.x + g.w * 0.5, h * 0.45);
+            draw_line(cx - g.dir * 30.0, cy, cx + g.dir * 30.0, cy, 4.0, c);
+            draw_triangle(vec2(cx + g.dir * 42.0, cy), vec2(cx + g.dir * 24.0, cy - 12.0), vec2(cx + g.dir * 24.0, cy + 12.0), c);
+        }
+        return;
+    }
+    draw_rectangle(g.x, 0.0, g.w, h, Color::new(0.7, 0.85, 1.0, 0.06));
+    let t = if crate::motion::calm(game) { 0.0 } else { g.age };
+    for i in 0..STREAKS {
+        // 每条风线的高度与起点由序号决定，不消耗随机数
+        let y = h * ((i as f32 * 0.618_034).fract() * 0.9 + 0.05);
+        let offset = (i as f32 * 0.381_966).fract() * (g.w + STREAK_LEN);
+        let travel = (offset + t * STREAK_SPEED).rem_euclid(g.w + STREAK_LEN);
+        let head = if g.dir > 0.0 { g.x + travel - STREAK_LEN } else { g.x + g.w - travel + STREAK_LEN };
+        let tail = head - g.dir * STREAK_LEN;
+        let (a, b) = (head.clamp(g.x, g.x + g.w), tail.clamp(g.x, g.x + g.w));
+        if (a - b).abs() > 1.0 { draw_line(a, y, b, y, 2.0, Color::new(0.85, 0.95, 1.0, 0.45)); }
+    }
+}
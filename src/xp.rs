@@ -0,0 +1,50 @@
+use crate::stats::RunStats;
+
+// ===== 账号经验与等级 =====
+// 普通模式每局结束按分数和统计给经验；等级由累计经验推出，存档只存经验。
+// 达到等级解锁角色、变异规则和外观（外观的等级条件写在 assets/cosmetics.json 里）。
+const LEVEL_BASE: u32 = 100; // 1 → 2 级所需经验
+const LEVEL_STEP: u32 = 50;  // 之后每级多要的经验
+pub const LEVEL_MAX: u32 = 50;
+
+// 本局获得的经验
+pub fn run_xp(score: i32, s: &RunStats) -> u32 {
+    score.max(0) as u32 / 5 + s.dodged / 2 + s.near_misses * 2 + s.max_combo.saturating_sub(1) * 5 + s.elapsed as u32 / 10
+}
+
+fn need(level: u32) -> u32 { LEVEL_BASE + LEVEL_STEP * (level - 1) }
+
+// (等级, 本级已有经验, 升到下一级所需经验)
+pub fn level_of(xp: u32) -> (u32, u32, u32) {
+    let (mut level, mut left) = (1, xp);
+    while level < LEVEL_MAX && left >= need(level) {
+        left -= need(level);
+        level += 1;
+    }
+    (level, left, need(level))
+}
+
+// —— 按等级解锁的内容（不在表里的一开始就可用）——
+#[derive(Clone, Copy, PartialEq)]
+pub enum Reward {
+    Character(&'static str), // 角色 key
+    Mutator(&'static str),   // 变异规则 key
+}
+
+pub const UNLOCKS: [(u32, Reward); 6] = [
+    (2, Reward::Character("tank")),
+    (3, Reward::Mutator("ice")),
+    (4, Reward::Character("sprint")),
+    (5, Reward::Mutator("double")),
+    (6, Reward::Mutator("giant")),
+    (8, Reward::Character("gamble")),
+];
+
+pub fn unlocked(reward: Reward, level: u32) -> bool {
+    UNLOCKS.iter().all(|(lv, r)| *r != reward || level >= *lv)
+}
+
+// 解锁它需要的等级（不需要时为 None）
+pub fn required_level(reward: Reward) -> Option<u32> {
+    UNLOCKS.iter().find(|(_, r)| *r == reward).map(|(lv, _)| *lv)
+}
@@ -0,0 +1,20 @@
+#!/bin/sh
+# 网页版构建，产物在 web/dist/，放到任意静态文件服务器上打开 index.html 就能玩
+# （直接双击打不开：浏览器不让 file:// 页面 fetch 资源）。第一次先装目标：
+#   rustup target add wasm32-unknown-unknown
+#   web/build.sh && python3 -m http.server -d web/dist
+# 只带 audio 特性（默认的 tls 也关掉，ring 编不了 wasm）；手柄、脚本、Discord、Steam、语音播报这些网页上用不了，联网功能也没有 TCP/UDP 可用。
+set -e
+cd "$(dirname "$0")/.."
+cargo build --release --target wasm32-unknown-unknown --no-default-features --features audio
+out=web/dist
+rm -rf "$out"
+mkdir -p "$out"
+cp target/wasm32-unknown-unknown/release/rust-dodge-rush.wasm web/index.html web/storage.js "$out"/
+# gl.js 和声音插件用 Cargo.lock 里那个版本的 macroquad 自带的打包文件，版本对不上会链接失败
+ver=$(grep -A1 '^name = "macroquad"$' Cargo.lock | sed -n 's/^version = "\(.*\)"$/\1/p')
+cp "${CARGO_HOME:-$HOME/.cargo}"/registry/src/*/macroquad-"$ver"/js/mq_js_bundle.js "$out"/
+# assets/ 整个拷过去，再列一份清单给 storage::preload 逐个下载（字体由 load_ttf_font 自己取，不进清单）
+cp -r assets "$out"/
+(cd "$out" && find assets -type f ! -name '*.otf' ! -name manifest.txt | sort > assets/manifest.txt)
+echo "已生成 $out/"
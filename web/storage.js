@@ -0,0 +1,55 @@
+// 网页版存档：把 src/storage.rs 里的 Browser 接到 localStorage 上。
+// localStorage 只能存字符串，每个字节存成一个码位 0~255 的字符，原样进出。
+// 读的时候分两步：get / keys 先把结果放在 pending 里并返回字节数（没有为 -1），Rust 那边按长度备好缓冲区再调 take 拷走。
+"use strict";
+(function () {
+    var pending = new Uint8Array(0);
+
+    function text(ptr, len) {
+        return new TextDecoder().decode(new Uint8Array(wasm_memory.buffer, ptr, len));
+    }
+
+    function hold(bytes) {
+        pending = bytes;
+        return pending.length;
+    }
+
+    function register_plugin(importObject) {
+        importObject.env.dr_storage_get = function (key, key_len) {
+            var v = localStorage.getItem(text(key, key_len));
+            if (v === null) return -1;
+            var bytes = new Uint8Array(v.length);
+            for (var i = 0; i < v.length; i++) bytes[i] = v.charCodeAt(i);
+            return hold(bytes);
+        };
+        importObject.env.dr_storage_keys = function (prefix, prefix_len) {
+            var p = text(prefix, prefix_len), keys = [];
+            for (var i = 0; i < localStorage.length; i++) {
+                var k = localStorage.key(i);
+                if (k.startsWith(p)) keys.push(k);
+            }
+            return hold(new TextEncoder().encode(keys.join("\n")));
+        };
+        importObject.env.dr_storage_take = function (out) {
+            new Uint8Array(wasm_memory.buffer, out, pending.length).set(pending);
+            pending = new Uint8Array(0);
+        };
+        importObject.env.dr_storage_set = function (key, key_len, val, val_len) {
+            var bytes = new Uint8Array(wasm_memory.buffer, val, val_len), s = "";
+            // 分段转，免得大文件把 apply 的参数个数撑爆
+            for (var i = 0; i < bytes.length; i += 0x8000) s += String.fromCharCode.apply(null, bytes.subarray(i, i + 0x8000));
+            try {
+                localStorage.setItem(text(key, key_len), s);
+                return 0;
+            } catch (e) {
+                console.warn("localStorage 写入失败", e);
+                return -1;
+            }
+        };
+        importObject.env.dr_storage_remove = function (key, key_len) {
+            localStorage.removeItem(text(key, key_len));
+        };
+    }
+
+    miniquad_add_plugin({ register_plugin: register_plugin, name: "dodge_storage", version: 1 });
+})();